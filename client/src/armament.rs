@@ -29,9 +29,8 @@ impl Mk48Game {
         let mut best_armament: Option<(usize, f32)> = None;
 
         if let Some(armament_selection) = armament_selection {
-            for i in 0..player_contact.data().armaments.len() {
-                let armament = &player_contact.data().armaments[i];
-
+            let armaments = player_contact.armaments();
+            for (i, armament) in armaments.iter().enumerate() {
                 if armament.entity_type != armament_selection {
                     // Wrong type; cannot fire.
                     continue;
@@ -61,9 +60,11 @@ impl Mk48Game {
                 }
 
                 let transform = *player_contact.transform()
-                    + player_contact
-                        .data()
-                        .armament_transform(player_contact.turrets(), i);
+                    + player_contact.data().armament_transform(
+                        player_contact.loadout(),
+                        player_contact.turrets(),
+                        i,
+                    );
 
                 let armament_direction_target = Angle::from(mouse_position - transform.position);
 
@@ -230,32 +231,46 @@ pub struct Group {
     pub entity_type: EntityType,
     pub total: u8,
     pub ready: u8,
+    /// Rounds remaining in the group's magazines, or None if unlimited.
+    pub rounds: Option<u32>,
 }
 
-pub fn group_armaments(armaments: &[Armament], armament_consumption: &[bool]) -> Vec<Group> {
+/// Magazines default to full if unknown.
+pub fn group_armaments(
+    armaments: &[Armament],
+    armament_consumption: &[bool],
+    magazines: &[u8],
+) -> Vec<Group> {
     let mut groups = Vec::<Group>::with_capacity(armaments.len().min(5));
     for (i, armament) in armaments.iter().enumerate() {
         let ready = armament_consumption.get(i).cloned().unwrap_or(true) as u8;
+        let rounds = armament
+            .magazine
+            .map(|capacity| magazines.get(i).cloned().unwrap_or(capacity) as u32);
         if let Some(group) = groups
             .iter_mut()
             .find(|g| g.entity_type == armament.entity_type)
         {
             group.total += 1;
             group.ready += ready;
+            if let Some((group_rounds, rounds)) = group.rounds.as_mut().zip(rounds) {
+                *group_rounds += rounds;
+            }
         } else {
             groups.push(Group {
                 entity_type: armament.entity_type,
                 total: 1,
                 ready,
+                rounds,
             });
         }
     }
     groups
 }
 
-pub fn update(entity_type: Option<EntityType>, armament: &mut Option<EntityType>) {
-    if let Some(entity_type) = entity_type {
-        let armaments = &entity_type.data().armaments;
+/// Keeps the armament selection valid for the player's armaments, if alive.
+pub fn update(armaments: Option<&[Armament]>, armament: &mut Option<EntityType>) {
+    if let Some(armaments) = armaments {
         if !armaments.iter().any(|a| Some(a.entity_type) == *armament) {
             let best = (*armament)
                 .and_then(|selection| {
@@ -272,3 +287,79 @@ pub fn update(entity_type: Option<EntityType>, armament: &mut Option<EntityType>
         *armament = None;
     }
 }
+
+/// Whether the fire control assist applies to an armament, i.e. it travels roughly in a straight
+/// line at its nominal speed.
+pub fn has_fire_control(armament: EntityType) -> bool {
+    let data = armament.data();
+    data.kind == EntityKind::Weapon
+        && matches!(
+            data.sub_kind,
+            EntitySubKind::Shell | EntitySubKind::Torpedo | EntitySubKind::Rocket
+        )
+}
+
+/// Finds where a projectile fired from `origin` at `speed` would intercept a target at `position`
+/// moving at `velocity`. Returns None if it can't within `range`.
+pub fn intercept(
+    origin: Vec2,
+    speed: f32,
+    range: f32,
+    position: Vec2,
+    velocity: Vec2,
+) -> Option<Vec2> {
+    // Solve |delta + velocity * t| = speed * t for the smallest positive t.
+    let delta = position - origin;
+    let a = velocity.length_squared() - speed * speed;
+    let b = 2.0 * delta.dot(velocity);
+    let c = delta.length_squared();
+
+    let t = if a.abs() < 0.001 {
+        // Target is as fast as the projectile.
+        (b < 0.0).then(|| -c / b)?
+    } else {
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        let (t1, t2) = ((-b - root) / (2.0 * a), (-b + root) / (2.0 * a));
+        match (t1 >= 0.0, t2 >= 0.0) {
+            (true, true) => t1.min(t2),
+            (true, false) => t1,
+            (false, true) => t2,
+            (false, false) => return None,
+        }
+    };
+
+    (speed * t <= range).then(|| position + velocity * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::armament::intercept;
+    use glam::Vec2;
+
+    #[test]
+    fn intercept_stationary() {
+        let target = Vec2::new(300.0, 400.0);
+        let point = intercept(Vec2::ZERO, 50.0, 1000.0, target, Vec2::ZERO).unwrap();
+        assert!(point.abs_diff_eq(target, 0.001));
+        assert_eq!(intercept(Vec2::ZERO, 50.0, 400.0, target, Vec2::ZERO), None);
+    }
+
+    #[test]
+    fn intercept_moving() {
+        let (position, velocity) = (Vec2::new(0.0, 500.0), Vec2::new(30.0, 0.0));
+        let point = intercept(Vec2::ZERO, 50.0, 10000.0, position, velocity).unwrap();
+        // Projectile and target arrive at the same time.
+        let t = point.length() / 50.0;
+        assert!(point.abs_diff_eq(position + velocity * t, 0.01));
+
+        // Target is too fast to catch from behind.
+        assert_eq!(
+            intercept(Vec2::ZERO, 10.0, 10000.0, position, Vec2::new(0.0, 20.0)),
+            None
+        );
+    }
+}
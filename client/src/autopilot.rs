@@ -0,0 +1,159 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use client_util::rate_limiter::RateLimiter;
+use common::angle::Angle;
+use common::contact::{Contact, ContactTrait};
+use common::guidance::Guidance;
+use common::navigation::{PathSearch, PathStatus};
+use common::terrain::Terrain;
+use common::velocity::Velocity;
+use common_util::range::map_ranges;
+use glam::Vec2;
+use std::collections::VecDeque;
+
+/// Steers the player's boat through waypoints queued by the player, around terrain.
+pub struct Autopilot {
+    /// Destinations, in order.
+    waypoints: VecDeque<Vec2>,
+    /// Path to the first waypoint (around terrain), which is the last element of the path.
+    path: VecDeque<Vec2>,
+    /// Terrain is explored (and modified) while sailing, so the path is periodically re-planned.
+    replan_rate_limiter: RateLimiter,
+    /// Planning in progress, spread over multiple frames. The current path, if any, is followed in
+    /// the meantime.
+    search: Option<PathSearch>,
+    /// Seconds remaining to tell the player that a waypoint was skipped for being unreachable.
+    unreachable: f32,
+}
+
+impl Default for Autopilot {
+    fn default() -> Self {
+        Self {
+            waypoints: VecDeque::new(),
+            path: VecDeque::new(),
+            replan_rate_limiter: RateLimiter::new(2.0),
+            search: None,
+            unreachable: 0.0,
+        }
+    }
+}
+
+impl Autopilot {
+    /// Maximum cells expanded while planning, per frame.
+    const EXPANSIONS_PER_FRAME: usize = 256;
+    /// How long to tell the player that a waypoint was unreachable.
+    const UNREACHABLE_SECONDS: f32 = 4.0;
+
+    /// Queues a waypoint, after any existing ones.
+    pub fn push(&mut self, waypoint: Vec2) {
+        self.waypoints.push_back(waypoint);
+    }
+
+    /// Forgets all waypoints (e.g. because the player took manual control).
+    pub fn clear(&mut self) {
+        self.waypoints.clear();
+        self.path.clear();
+        self.search = None;
+    }
+
+    /// Whether a waypoint was recently skipped for being unreachable.
+    pub fn unreachable(&self) -> bool {
+        self.unreachable > 0.0
+    }
+
+    /// Iterates the points the boat will sail through, in order.
+    pub fn route(&self) -> impl Iterator<Item = Vec2> + '_ {
+        self.path
+            .iter()
+            .chain(self.waypoints.iter().skip(1))
+            .copied()
+    }
+
+    /// Returns guidance towards the next waypoint, if any.
+    pub fn update(
+        &mut self,
+        terrain: &Terrain,
+        contact: &Contact,
+        elapsed_seconds: f32,
+    ) -> Option<Guidance> {
+        let replan = self.replan_rate_limiter.update_ready(elapsed_seconds);
+        self.unreachable = (self.unreachable - elapsed_seconds).max(0.0);
+        let &waypoint = self.waypoints.front()?;
+        let entity_type = contact.entity_type()?;
+        let data = entity_type.data();
+        let transform = contact.transform();
+        let arrival_radius = data.length.max(50.0);
+
+        // The path ends at the waypoint, unless it was clamped to a strict area border.
+        let destination = self.path.back().copied().unwrap_or(waypoint);
+        if transform.position.distance(destination) < arrival_radius {
+            self.waypoints.pop_front();
+            self.path.clear();
+            self.search = None;
+            if self.waypoints.is_empty() {
+                // Arrived at the final waypoint.
+                return Some(Guidance {
+                    direction_target: transform.direction,
+                    velocity_target: Velocity::ZERO,
+                });
+            }
+            return self.update(terrain, contact, 0.0);
+        }
+
+        if self.search.is_none() && (self.path.is_empty() || replan) {
+            self.search = Some(PathSearch::new(
+                terrain,
+                entity_type,
+                transform.position,
+                waypoint,
+            ));
+        }
+
+        if let Some(search) = self.search.as_mut() {
+            match search.step(terrain, Self::EXPANSIONS_PER_FRAME) {
+                PathStatus::Searching => {}
+                PathStatus::Found(path) => {
+                    self.path = path.into();
+                    self.search = None;
+                }
+                PathStatus::Unreachable => {
+                    // Skip it, and let the player know.
+                    self.waypoints.pop_front();
+                    self.path.clear();
+                    self.search = None;
+                    self.unreachable = Self::UNREACHABLE_SECONDS;
+                    return self.update(terrain, contact, 0.0);
+                }
+            }
+        }
+
+        if self.path.is_empty() {
+            // Still planning the first path to the waypoint.
+            return None;
+        }
+
+        while self.path.len() > 1 && transform.position.distance(self.path[0]) < arrival_radius {
+            self.path.pop_front();
+        }
+
+        // Slow down when approaching the final waypoint.
+        let target = self.path[0];
+        let remaining = if self.waypoints.len() == 1 && self.path.len() == 1 {
+            transform.position.distance(target)
+        } else {
+            f32::INFINITY
+        };
+        let max_speed = data.speed.to_mps();
+
+        Some(Guidance {
+            direction_target: Angle::from(target - transform.position),
+            velocity_target: Velocity::from_mps(map_ranges(
+                remaining,
+                arrival_radius..arrival_radius * 5.0,
+                max_speed * 0.25..max_speed,
+                true,
+            )),
+        })
+    }
+}
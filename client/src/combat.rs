@@ -0,0 +1,154 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::ui::UiKill;
+use common::protocol::{Damage, Kill};
+use common::ticks::Ticks;
+use common_util::range::map_ranges;
+use core_protocol::name::PlayerAlias;
+use std::collections::VecDeque;
+
+/// Recent kills anywhere in the world, which fade out after a while.
+#[derive(Default)]
+pub struct KillFeed {
+    /// Oldest first, with the time each kill was received.
+    kills: VecDeque<(Kill, f32)>,
+}
+
+impl KillFeed {
+    /// How long a kill is shown for, in seconds.
+    const DURATION: f32 = 8.0;
+    /// How long a kill takes to fade out, at the end of its duration, in seconds.
+    const FADE: f32 = 2.0;
+    /// Older kills are dropped to make room for newer ones.
+    const MAX_KILLS: usize = 5;
+
+    pub fn push(&mut self, kill: Kill, time_seconds: f32) {
+        if self.kills.len() >= Self::MAX_KILLS {
+            self.kills.pop_front();
+        }
+        self.kills.push_back((kill, time_seconds));
+    }
+
+    /// Forgets expired kills and returns the rest, newest first.
+    pub fn update(&mut self, time_seconds: f32) -> Vec<UiKill> {
+        self.kills
+            .retain(|&(_, time)| time_seconds - time < Self::DURATION);
+        self.kills
+            .iter()
+            .rev()
+            .map(|&(kill, time)| UiKill {
+                kill,
+                opacity: map_ranges(
+                    time_seconds - time,
+                    Self::DURATION - Self::FADE..Self::DURATION,
+                    1.0..0.0,
+                    true,
+                ),
+            })
+            .collect()
+    }
+}
+
+/// Engagements with other players, since the player's boat spawned.
+#[derive(Default)]
+pub struct CombatLog {
+    /// In order of first contact.
+    engagements: Vec<Engagement>,
+}
+
+/// Damage exchanged with one other player, without a long pause.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Engagement {
+    pub player: PlayerAlias,
+    pub dealt: Ticks,
+    pub received: Ticks,
+    /// When damage was last exchanged, in seconds.
+    last_seconds: f32,
+}
+
+impl CombatLog {
+    /// Damage exchanged with the same player after a pause this long, in seconds, starts a new
+    /// engagement.
+    const PAUSE: f32 = 30.0;
+
+    pub fn push(&mut self, damage: &Damage, time_seconds: f32) {
+        let index =
+            if let Some(index) = self.engagements.iter().rposition(|e| {
+                e.player == damage.player && time_seconds - e.last_seconds < Self::PAUSE
+            }) {
+                index
+            } else {
+                self.engagements.push(Engagement {
+                    player: damage.player,
+                    dealt: Ticks::ZERO,
+                    received: Ticks::ZERO,
+                    last_seconds: time_seconds,
+                });
+                self.engagements.len() - 1
+            };
+
+        let engagement = &mut self.engagements[index];
+        engagement.last_seconds = time_seconds;
+        if damage.dealt {
+            engagement.dealt = engagement.dealt.saturating_add(damage.amount);
+        } else {
+            engagement.received = engagement.received.saturating_add(damage.amount);
+        }
+    }
+
+    /// Forgets all engagements (e.g. because the player spawned a new boat).
+    pub fn clear(&mut self) {
+        self.engagements.clear();
+    }
+
+    pub fn engagements(&self) -> &[Engagement] {
+        &self.engagements
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::combat::CombatLog;
+    use common::entity::EntityType;
+    use common::protocol::Damage;
+    use common::ticks::Ticks;
+    use core_protocol::name::PlayerAlias;
+
+    fn damage(player: &str, amount: u16, dealt: bool) -> Damage {
+        Damage {
+            player: PlayerAlias::new_unsanitized(player),
+            entity_type: EntityType::G5,
+            amount: Ticks::from_repr(amount),
+            dealt,
+        }
+    }
+
+    #[test]
+    fn engagements() {
+        let mut log = CombatLog::default();
+        log.push(&damage("a", 10, true), 0.0);
+        log.push(&damage("b", 5, false), 1.0);
+        log.push(&damage("a", 20, false), 20.0);
+        log.push(&damage("a", 1, true), 45.0);
+
+        let engagements = log.engagements();
+        assert_eq!(engagements.len(), 2);
+        assert_eq!(engagements[0].player.as_str(), "a");
+        assert_eq!(engagements[0].dealt, Ticks::from_repr(11));
+        assert_eq!(engagements[0].received, Ticks::from_repr(20));
+        assert_eq!(engagements[1].player.as_str(), "b");
+        assert_eq!(engagements[1].received, Ticks::from_repr(5));
+
+        // A long pause starts a new engagement with the same player.
+        log.push(&damage("b", 7, true), 60.0);
+        let engagements = log.engagements();
+        assert_eq!(engagements.len(), 3);
+        assert_eq!(engagements[1].dealt, Ticks::ZERO);
+        assert_eq!(engagements[2].player.as_str(), "b");
+        assert_eq!(engagements[2].dealt, Ticks::from_repr(7));
+
+        log.clear();
+        assert!(log.engagements().is_empty());
+    }
+}
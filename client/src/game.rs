@@ -1,41 +1,54 @@
 // SPDX-FileCopyrightText: 2021 Softbear, Inc.
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
-use crate::armament::{group_armaments, FireRateLimiter, Group};
+use crate::armament::{group_armaments, has_fire_control, intercept, FireRateLimiter, Group};
 use crate::audio::Audio;
+use crate::autopilot::Autopilot;
 use crate::background::{Mk48BackgroundLayer, Mk48OverlayLayer};
 use crate::camera::Mk48Camera;
+use crate::combat::{CombatLog, KillFeed};
 use crate::interpolated::Interpolated;
 use crate::interpolated_contact::InterpolatedContact;
+use crate::key_bindings::{Action, Binding};
+use crate::killcam::Killcam;
+use crate::minimap::{event_color, ping_color, Minimap};
 use crate::particle::{Mk48Particle, Mk48ParticleLayer};
 use crate::settings::{Mk48Settings, ShadowSetting};
 use crate::sortable_sprite::SortableSprite;
+use crate::spectator::Spectator;
 use crate::sprite::SpriteLayer;
 use crate::state::Mk48State;
 use crate::trail::TrailLayer;
 use crate::ui::{
-    InstructionStatus, UiEvent, UiProps, UiState, UiStatus, UiStatusPlaying, UiStatusRespawning,
+    InstructionStatus, ReplayControl, UiEvent, UiProps, UiReplay, UiState, UiStatus,
+    UiStatusPlaying, UiStatusRespawning, UiStatusSpectating,
 };
 use crate::weather::Weather;
 use client_util::context::Context;
 use client_util::fps_monitor::FpsMonitor;
 use client_util::game_client::GameClient;
+use client_util::gamepad::{GamepadButton, GamepadEvent};
 use client_util::joystick::Joystick;
-use client_util::keyboard::{Key, KeyboardEvent};
-use client_util::mouse::{MouseButton, MouseEvent, MouseState};
+use client_util::keyboard::KeyboardEvent;
+use client_util::mouse::MouseEvent;
 use client_util::rate_limiter::RateLimiter;
 use common::altitude::Altitude;
 use common::angle::Angle;
 use common::contact::{Contact, ContactTrait};
 use common::entity::{EntityData, EntityId, EntityKind, EntitySubKind, EntityType};
 use common::guidance::Guidance;
-use common::protocol::{Command, Control, Fire, Hint, Pay, Spawn, Update, Upgrade};
+use common::protocol::{
+    Command, Control, Depth, Fire, Hint, Pay, Port, Spawn, SpectateTarget, Update, Upgrade,
+    WorldEvent,
+};
 use common::ticks::Ticks;
 use common::transform::Transform;
 use common::velocity::Velocity;
 use common::world::strict_area_border;
 use common_util::range::{gen_radius, lerp, map_ranges};
+use core_protocol::dto::PingKind;
 use core_protocol::id::{GameId, TeamId};
+use core_protocol::rpc::{Request, TeamRequest};
 use glam::{Mat2, UVec2, Vec2, Vec3, Vec4Swizzles};
 use rand::{thread_rng, Rng};
 use renderer::{gray_a, rgb_array, rgba, DefaultRender, Layer, RenderChain};
@@ -81,6 +94,18 @@ pub struct Mk48Game {
     pub fire_rate_limiter: FireRateLimiter,
     /// FPS counter
     pub fps_counter: FpsMonitor,
+    /// Terrain image for the minimap.
+    minimap: Minimap,
+    /// Steers through waypoints queued by the player.
+    autopilot: Autopilot,
+    /// Recent kills anywhere in the world.
+    pub(crate) kill_feed: KillFeed,
+    /// Damage exchanged since spawning, shown after sinking.
+    combat_log: CombatLog,
+    /// Follows other players, or moves the camera freely, while not alive.
+    spectator: Spectator,
+    /// Replays the player's boat sinking, if open.
+    killcam: Option<Killcam>,
     ui_state: UiState,
 }
 
@@ -117,8 +142,6 @@ impl std::ops::Deref for Mk48Params {
 
 /// Back 75 degrees is reverse angle.
 const REVERSE_ANGLE: f32 = PI * 3.0 / 8.0;
-pub const SURFACE_KEY: Key = Key::R;
-pub const ACTIVE_KEY: Key = Key::Z;
 
 impl Mk48Game {
     // Don't reverse early on, when the player doesn't have a great idea of their orientation.
@@ -132,9 +155,11 @@ impl Mk48Game {
         player_contact.entity_type().unwrap().data().level > 1
     }
 
-    // Right button down or left button down and time has passed.
-    fn is_holding_control(mouse: &MouseState, time: f32) -> bool {
-        mouse.is_down(MouseButton::Right) || mouse.is_down_not_click(MouseButton::Left, time)
+    /// The contact the camera follows: the player's boat, or else the spectated player's boat.
+    fn camera_contact<'a>(spectator: &Spectator, state: &'a Mk48State) -> Option<&'a Contact> {
+        state
+            .player_contact()
+            .or_else(|| spectator.followed(&state.contacts))
     }
 
     fn create_render_chain(context: &Context<Self>) -> Result<RenderChain<FullLayer>, String> {
@@ -206,6 +231,12 @@ impl GameClient for Mk48Game {
             peek_update_sound_counter: 0,
             fire_rate_limiter: FireRateLimiter::new(),
             fps_counter: FpsMonitor::new(1.0),
+            minimap: Minimap::default(),
+            autopilot: Autopilot::default(),
+            kill_feed: KillFeed::default(),
+            combat_log: CombatLog::default(),
+            spectator: Spectator::default(),
+            killcam: None,
             ui_state: UiState::default(),
         })
     }
@@ -257,6 +288,8 @@ impl GameClient for Mk48Game {
                 if contact.player_id() == context.state.core.player_id && contact.is_boat() {
                     context.state.game.entity_id = Some(contact.id());
                     // Just spawned so reset these.
+                    self.combat_log.clear();
+                    self.spectator.clear();
                     self.first_control = true;
                     self.first_zoom = true;
                     self.interpolated_altitude.reset();
@@ -269,6 +302,14 @@ impl GameClient for Mk48Game {
             }
         }
 
+        // Logged after spawning, which resets the combat log.
+        for damage in &update.damage {
+            self.combat_log.push(damage, context.client.time_seconds);
+        }
+        for &kill in &update.kills {
+            self.kill_feed.push(kill, context.client.time_seconds);
+        }
+
         // Contacts absent in the update are currently considered lost.
         // Borrow entity_id early to avoid use of self in closure.
         let entity_id = &mut context.state.game.entity_id;
@@ -401,51 +442,35 @@ impl GameClient for Mk48Game {
 
     fn peek_keyboard(&mut self, event: &KeyboardEvent, context: &mut Context<Self>) {
         if event.down {
-            if let Some(contact) = context.state.game.player_contact() {
-                let entity_type = contact.entity_type().unwrap();
-                let consumptions: Vec<bool> = contact.reloads().iter().map(|b| *b).collect();
-                let groups = group_armaments(&entity_type.data().armaments, &consumptions);
-                match event.key {
-                    SURFACE_KEY => {
-                        self.set_submerge(!self.ui_state.submerge, &*context);
-                    }
-                    ACTIVE_KEY => {
-                        self.set_active(!self.ui_state.active, &*context);
-                    }
-                    Key::Tab => {
-                        self.ui_state.armament = groups
-                            .get(
-                                self.ui_state
-                                    .armament
-                                    .and_then(|current| {
-                                        groups.iter().position(|Group { entity_type, .. }| {
-                                            *entity_type == current
-                                        })
-                                    })
-                                    .map(|idx| (groups.len() + idx + 1) % groups.len())
-                                    .unwrap_or(0),
-                            )
-                            .map(|Group { entity_type, .. }| *entity_type);
-                    }
-                    _ => {
-                        if let Some(digit) = event.key.digit_with_ten() {
-                            if let Some(armament) = groups
-                                .get((digit.get() - 1) as usize)
-                                .map(|Group { entity_type, .. }| *entity_type)
-                            {
-                                self.ui_state.armament = Some(armament);
-                            }
-                        }
-                    }
-                }
+            self.peek_binding(Binding::Key(event.key), context);
+        }
+    }
+
+    fn peek_mouse(&mut self, event: &MouseEvent, context: &mut Context<Self>) {
+        match *event {
+            MouseEvent::Wheel(delta) => {
+                self.mk48_camera.zoom(delta);
+                self.first_zoom = false;
             }
+            MouseEvent::Button {
+                button, down: true, ..
+            } => self.peek_binding(Binding::Mouse(button), context),
+            _ => {}
         }
     }
 
-    fn peek_mouse(&mut self, event: &MouseEvent, _context: &mut Context<Self>) {
-        if let MouseEvent::Wheel(delta) = event {
-            self.mk48_camera.zoom(*delta);
-            self.first_zoom = false;
+    fn peek_gamepad(&mut self, event: &GamepadEvent, context: &mut Context<Self>) {
+        if let &GamepadEvent::Button {
+            button, down: true, ..
+        } = event
+        {
+            match button {
+                GamepadButton::LeftBumper => self.cycle_armament(false, context),
+                GamepadButton::RightBumper => self.cycle_armament(true, context),
+                GamepadButton::South => self.set_depth(self.ui_state.depth.cycle(), context),
+                GamepadButton::North => self.set_active(!self.ui_state.active, context),
+                _ => {}
+            }
         }
     }
 
@@ -461,15 +486,50 @@ impl GameClient for Mk48Game {
         // filtering.
         let mut team_proximity: HashMap<TeamId, f32> = HashMap::new();
 
+        // Spectating moves the camera, so it goes first.
+        let bindings = &context.settings.key_bindings;
+        let down = |action| {
+            if bindings
+                .state(action, &context.keyboard, &context.mouse)
+                .is_down()
+            {
+                1.0
+            } else {
+                0.0
+            }
+        };
+        let direction = Vec2::new(
+            down(Action::Right) - down(Action::Left),
+            down(Action::Forward) - down(Action::Backward),
+        ) + context.gamepad.left_stick * Vec2::new(1.0, -1.0);
+        if let Some(command) = self.spectator.update(
+            &context.state.core,
+            direction,
+            context.state.game.world_radius,
+            elapsed_seconds,
+        ) {
+            context.send_to_game(command);
+        }
+        if let Some(SpectateTarget::Free(position)) = self.spectator.target() {
+            self.mk48_camera.saved_camera = Some((position, SpectateTarget::FREE_RANGE));
+        }
+        if let Some(killcam) = &mut self.killcam {
+            killcam.update(elapsed_seconds);
+            if let Some(saved_camera) = &mut self.mk48_camera.saved_camera {
+                saved_camera.0 = killcam.camera_position();
+            }
+        }
+
         // Temporary (will be recalculated after moving ships).
+        let camera_contact = Self::camera_contact(&self.spectator, &context.state.game);
         self.mk48_camera.update(
-            context.state.game.player_contact(),
+            camera_contact,
             elapsed_seconds,
             layer.background.cache_frame,
         );
         let (camera, _) = self
             .mk48_camera
-            .camera(context.state.game.player_contact(), renderer.aspect_ratio());
+            .camera(camera_contact, renderer.aspect_ratio());
 
         // Update audio volume.
         if Self::maybe_contact_mut(
@@ -478,6 +538,7 @@ impl GameClient for Mk48Game {
         )
         .is_some()
             || context.state.game.death_reason.is_some()
+            || self.spectator.target().is_some()
         {
             context.audio.set_muted_by_game(false);
             if !context.audio.is_playing(Audio::Ocean) {
@@ -529,9 +590,10 @@ impl GameClient for Mk48Game {
         }
 
         // May have changed due to the above.
+        let camera_contact = Self::camera_contact(&self.spectator, &context.state.game);
         let (camera, zoom) = self
             .mk48_camera
-            .camera(context.state.game.player_contact(), renderer.aspect_ratio());
+            .camera(camera_contact, renderer.aspect_ratio());
 
         // Set camera before update layers so they don't get last frame's camera.
         // TODO decouple update and render.
@@ -568,7 +630,7 @@ impl GameClient for Mk48Game {
             &mut context.state.game.terrain,
             terrain_reset,
             context.settings.shadows.is_some(),
-            &*renderer,
+            renderer,
         ));
         shadow_layer.camera = layer.background.shadow_camera(
             renderer,
@@ -607,7 +669,19 @@ impl GameClient for Mk48Game {
         // Update trails.
         layer.trails.set_time(context.client.time_seconds);
 
-        for InterpolatedContact { view: contact, .. } in context.state.game.contacts.values() {
+        // The replay, if open, is shown instead of current contacts.
+        let replay_contacts: Option<Vec<Contact>> = self
+            .killcam
+            .as_ref()
+            .map(|killcam| killcam.contacts().collect());
+        let contacts: Box<dyn Iterator<Item = &Contact>> =
+            if let Some(replay_contacts) = &replay_contacts {
+                Box::new(replay_contacts.iter())
+            } else {
+                Box::new(context.state.game.contacts.values().map(|c| &c.view))
+            };
+
+        for contact in contacts {
             let friendly = context.state.core.is_friendly(contact.player_id());
 
             let color_bytes = if friendly {
@@ -665,13 +739,16 @@ impl GameClient for Mk48Game {
                         );
                     }
 
-                    sortable_sprites.push(SortableSprite::new_entity(
-                        entity_id,
-                        entity_type,
-                        transform,
-                        altitude,
-                        alpha,
-                    ));
+                    // Smoke and chaff decoys are drawn as their clouds instead.
+                    if data.cloud_radius() == 0.0 {
+                        sortable_sprites.push(SortableSprite::new_entity(
+                            entity_id,
+                            entity_type,
+                            transform,
+                            altitude,
+                            alpha,
+                        ));
+                    }
                 }
 
                 if contact.is_boat()
@@ -688,8 +765,8 @@ impl GameClient for Mk48Game {
                 }
 
                 if contact.is_boat() {
-                    for i in 0..data.armaments.len() {
-                        let armament = &data.armaments[i];
+                    let armaments = contact.armaments();
+                    for (i, armament) in armaments.iter().enumerate() {
                         if armament.hidden
                             || armament.vertical
                             || !(armament.external || (friendly && !context.settings.cinematic))
@@ -707,7 +784,8 @@ impl GameClient for Mk48Game {
                             entity_id,
                             entity_type,
                             armament_type,
-                            *contact.transform() + data.armament_transform(contact.turrets(), i),
+                            *contact.transform()
+                                + data.armament_transform(contact.loadout(), contact.turrets(), i),
                             altitude + 0.02,
                             alpha * if reloaded { 1.0 } else { 0.5 },
                         ));
@@ -746,6 +824,7 @@ impl GameClient for Mk48Game {
                             // Is this player's own boat?
                             if context.state.core.player_id.is_some()
                                 && contact.player_id() == context.state.core.player_id
+                                && self.killcam.is_none()
                             {
                                 // Radii
                                 let hud_color = gray_a(255, 50);
@@ -808,7 +887,8 @@ impl GameClient for Mk48Game {
 
                                 // Target bearing line.
                                 if context.settings.circle_hud
-                                    || Self::is_holding_control(
+                                    || context.settings.key_bindings.is_steering(
+                                        &context.keyboard,
                                         &context.mouse,
                                         context.client.time_seconds,
                                     )
@@ -933,7 +1013,7 @@ impl GameClient for Mk48Game {
                                         .zip(Some(mouse_pos))
                                     })
                                 {
-                                    let armament = &data.armaments[i];
+                                    let armament = &contact.armaments()[i];
                                     if armament.entity_type != EntityType::Depositor {
                                         let transform = *contact.transform();
                                         let direction = contact.transform().direction;
@@ -1282,6 +1362,38 @@ impl GameClient for Mk48Game {
                         }
                     }
                 }
+
+                // Smoke and chaff clouds.
+                let cloud_radius = data.cloud_radius();
+                if cloud_radius > 0.0 {
+                    let smoke = data.sub_kind == EntitySubKind::Smoke;
+                    let per_second = if smoke { 60.0 } else { 120.0 };
+                    let count =
+                        ((elapsed_seconds * per_second + rng.gen::<f32>()) as usize).min(100);
+                    for _ in 0..count {
+                        let position =
+                            contact.transform().position + gen_radius(&mut rng, cloud_radius);
+                        layer.airborne_particles.add(if smoke {
+                            // Billowing grey smoke.
+                            Mk48Particle {
+                                position,
+                                velocity: gen_radius(&mut rng, 3.0),
+                                radius: 8.0,
+                                color: 0.75,
+                                smoothness: 1.0,
+                            }
+                        } else {
+                            // Glinting strips of foil.
+                            Mk48Particle {
+                                position,
+                                velocity: gen_radius(&mut rng, 1.0),
+                                radius: 0.5,
+                                color: 1.0,
+                                smoothness: 0.0,
+                            }
+                        });
+                    }
+                }
             } else {
                 layer.sprites.draw(
                     "contact",
@@ -1356,6 +1468,180 @@ impl GameClient for Mk48Game {
             );
         }
 
+        // Fire control assist, which leads the enemy boat nearest to the mouse.
+        if let (true, Some(player_contact), Some(armament), Some(mouse_position)) = (
+            context.settings.fire_control && !context.settings.cinematic,
+            context.state.game.player_contact(),
+            self.ui_state.armament.filter(|&a| has_fire_control(a)),
+            context
+                .mouse
+                .view_position
+                .map(|p| self.camera.to_world_position(p)),
+        ) {
+            let core = &context.state.core;
+            let target = context
+                .state
+                .game
+                .contacts
+                .values()
+                .map(|c| &c.view)
+                .filter(|c| {
+                    c.entity_type()
+                        .map_or(false, |e| e.data().kind == EntityKind::Boat)
+                        && !core.is_friendly(c.player_id())
+                })
+                .map(|c| (c.transform().position.distance(mouse_position), c))
+                .filter(|(distance, c)| *distance < c.data().length.max(0.05 * zoom))
+                .min_by(|(a, _), (b, _)| a.partial_cmp(b).unwrap())
+                .map(|(_, c)| c);
+
+            if let Some(target) = target {
+                let armament_data = armament.data();
+                let transform = target.transform();
+                if let Some(lead) = intercept(
+                    player_contact.transform().position,
+                    armament_data.speed.to_mps(),
+                    armament_data.range,
+                    transform.position,
+                    transform.direction.to_vec() * transform.velocity.to_mps(),
+                ) {
+                    let color = rgba(255, 220, 80, 150);
+                    // Predicted track of the target.
+                    layer
+                        .graphics
+                        .draw_line(transform.position, lead, 0.002 * zoom, color);
+                    layer
+                        .graphics
+                        .draw_circle(lead, 0.012 * zoom, 0.003 * zoom, color);
+                    layer.graphics.draw_filled_circle(lead, 0.003 * zoom, color);
+                }
+            }
+        }
+
+        // Autopilot route.
+        if let Some(player_contact) = context.state.game.player_contact() {
+            let color = rgba(255, 255, 255, 100);
+            let mut from = player_contact.transform().position;
+            for to in self.autopilot.route() {
+                layer.graphics.draw_line(from, to, 0.003 * zoom, color);
+                layer
+                    .graphics
+                    .draw_circle(to, 0.008 * zoom, 0.003 * zoom, color);
+                from = to;
+            }
+        }
+
+        // Path of whatever sank the player, in the replay.
+        if let Some(killcam) = &self.killcam {
+            let color = rgba(255, 60, 60, 200);
+            let mut last = None;
+            for position in killcam.killer_path() {
+                if let Some(from) = last {
+                    layer
+                        .graphics
+                        .draw_line(from, position, 0.004 * zoom, color);
+                }
+                last = Some(position);
+            }
+            if let Some(position) = last {
+                layer
+                    .graphics
+                    .draw_circle(position, 0.02 * zoom, 0.004 * zoom, color);
+            }
+        }
+
+        // Ports, with a line along the berth (boats may dock facing either way).
+        let port_color = gray_a(224, 96);
+        for port in &context.state.game.ports {
+            let berth = port.direction.to_vec() * (Port::RADIUS * 0.5);
+            layer
+                .graphics
+                .draw_circle(port.position, Port::RADIUS, 0.004 * zoom, port_color);
+            layer.graphics.draw_line(
+                port.position - berth,
+                port.position + berth,
+                0.004 * zoom,
+                port_color,
+            );
+        }
+
+        // World events. Like team pings, those that are off-screen are shown as arrows.
+        for event in &context.state.game.events {
+            let position = event.position();
+            let [r, g, b] = event_color(event);
+            let color = rgb_array([r, g, b]).extend(0.8);
+            let view_position = self.camera.to_view_position(position);
+            let extent = view_position.abs().max_element();
+
+            if let WorldEvent::Storm { radius, .. } = *event {
+                layer
+                    .graphics
+                    .draw_circle(position, radius, 0.006 * zoom, color);
+            }
+
+            if extent <= 1.0 {
+                let label = match event {
+                    WorldEvent::SupplyDrop { .. } => String::from("Supply Drop"),
+                    WorldEvent::Bounty { alias, reward, .. } => {
+                        format!("Bounty: {} ({})", alias, reward)
+                    }
+                    WorldEvent::Storm { .. } => String::from("Storm"),
+                };
+                layer
+                    .graphics
+                    .draw_circle(position, 0.03 * zoom, 0.004 * zoom, color);
+                layer.text.draw(
+                    &label,
+                    position + Vec2::new(0.0, 0.06 * zoom),
+                    0.03 * zoom,
+                    [r, g, b, 255],
+                );
+            } else {
+                let edge = self
+                    .camera
+                    .to_world_position(view_position * (0.92 / extent));
+                let direction = position - edge;
+                layer.graphics.draw_triangle(
+                    edge,
+                    Vec2::splat(0.025 * zoom),
+                    direction.y.atan2(direction.x) - PI * 0.5,
+                    color,
+                );
+            }
+        }
+
+        // Team pings. Those that are off-screen are shown as arrows at the edge of the screen.
+        for (ping, age) in context.state.core.pings() {
+            let position = Vec2::new(ping.x, ping.y);
+            let color = rgb_array(ping_color(ping.kind))
+                .extend(1.0 - age * (1.0 / PingKind::DURATION_SECONDS));
+            let view_position = self.camera.to_view_position(position);
+            let extent = view_position.abs().max_element();
+            if extent <= 1.0 {
+                let pulse = (age * 1.5).fract();
+                layer.graphics.draw_circle(
+                    position,
+                    (0.01 + 0.03 * pulse) * zoom,
+                    0.004 * zoom,
+                    color,
+                );
+                layer
+                    .graphics
+                    .draw_filled_circle(position, 0.006 * zoom, color);
+            } else {
+                let edge = self
+                    .camera
+                    .to_world_position(view_position * (0.92 / extent));
+                let direction = position - edge;
+                layer.graphics.draw_triangle(
+                    edge,
+                    Vec2::splat(0.025 * zoom),
+                    direction.y.atan2(direction.x) - PI * 0.5,
+                    color,
+                );
+            }
+        }
+
         // For hinting to server.
         let aspect_ratio = renderer.aspect_ratio();
         frame.end(&Mk48Params {
@@ -1364,11 +1650,20 @@ impl GameClient for Mk48Game {
         });
 
         // After the above line, mouse world position state may be out-of-date. Recalculate it here.
-        let aim_target = context
+        let mut aim_target = context
             .mouse
             .view_position
             .map(|p| self.camera.to_world_position(p));
 
+        // The right stick of a gamepad aims relative to the boat, overriding the mouse.
+        if context.gamepad.right_stick != Vec2::ZERO {
+            if let Some(player_contact) = context.state.game.player_contact() {
+                // Browser reports positive y as down.
+                let offset = context.gamepad.right_stick * Vec2::new(1.0, -1.0) * visual_range;
+                aim_target = Some(player_contact.transform().position + offset);
+            }
+        }
+
         // Send command later, when lifetimes allow.
         let mut control: Option<Command> = None;
 
@@ -1378,7 +1673,10 @@ impl GameClient for Mk48Game {
         );
 
         crate::armament::update(
-            player_contact.as_ref().and_then(|c| c.model.entity_type()),
+            player_contact
+                .as_ref()
+                .filter(|c| c.model.entity_type().is_some())
+                .map(|c| c.model.armaments()),
             &mut self.ui_state.armament,
         );
 
@@ -1389,10 +1687,17 @@ impl GameClient for Mk48Game {
                 let player_contact = &player_contact.view;
                 let max_speed = player_contact.data().speed.to_mps();
 
-                let joystick = Joystick::try_from_keyboard_state(
+                let bindings = &context.settings.key_bindings;
+                let state = |action| bindings.state(action, &context.keyboard, &context.mouse);
+                let joystick = Joystick::try_from_key_states(
                     context.client.time_seconds,
-                    &context.keyboard,
-                );
+                    state(Action::Forward),
+                    state(Action::Backward),
+                    state(Action::Left),
+                    state(Action::Right),
+                    state(Action::Stop).is_down(),
+                )
+                .or_else(|| Joystick::try_from_gamepad_state(&context.gamepad));
                 let stop = joystick.as_ref().map(|j| j.stop).unwrap_or(false);
 
                 if let Some(joystick) = joystick {
@@ -1411,7 +1716,12 @@ impl GameClient for Mk48Game {
                     self.first_control = false; // First control was joystick.
                 }
 
-                if Self::is_holding_control(&context.mouse, context.client.time_seconds) {
+                // By default, right button down or left button down and time has passed.
+                if bindings.is_steering(
+                    &context.keyboard,
+                    &context.mouse,
+                    context.client.time_seconds,
+                ) {
                     let current_dir = player_contact.transform().direction;
                     let mut direction_target = Angle::from(
                         aim_target.unwrap_or_default() - player_contact.transform().position,
@@ -1468,6 +1778,17 @@ impl GameClient for Mk48Game {
                 }
             }
 
+            // Manual control overrides the autopilot.
+            if guidance.is_some() {
+                self.autopilot.clear();
+            } else {
+                guidance = self.autopilot.update(
+                    &context.state.game.terrain,
+                    &player_contact.view,
+                    elapsed_seconds,
+                );
+            }
+
             if let Some(guidance) = guidance.as_ref() {
                 player_contact.model.predict_guidance(guidance);
                 player_contact.view.predict_guidance(guidance);
@@ -1476,13 +1797,22 @@ impl GameClient for Mk48Game {
             // Re-borrow as immutable.
             let player_contact = context.state.game.player_contact().unwrap();
 
+            // The server forces submarines with an exhausted battery to surface.
+            if player_contact.battery() == Some(0.0) && self.ui_state.depth.drains_battery() {
+                self.ui_state.depth = Depth::Surface;
+            }
+
             let status = UiStatus::Playing(UiStatusPlaying {
                 entity_type: player_contact.entity_type().unwrap(),
-                position: player_contact.transform().position.into(),
+                loadout: player_contact.loadout(),
+                position: player_contact.transform().position,
                 direction: player_contact.transform().direction,
                 velocity: player_contact.transform().velocity,
                 altitude: player_contact.altitude(),
-                submerge: self.ui_state.submerge,
+                depth: self.ui_state.depth,
+                battery: player_contact.battery(),
+                docked: context.state.game.docked,
+                waypoint_unreachable: self.autopilot.unreachable(),
                 active: self.ui_state.active,
                 instruction_status: if player_contact.data().level <= 3 {
                     InstructionStatus {
@@ -1495,11 +1825,52 @@ impl GameClient for Mk48Game {
                 },
                 armament: self.ui_state.armament,
                 armament_consumption: player_contact.reloads().iter().map(|b| *b).collect(),
+                magazines: context.state.game.magazines.clone(),
+                air_wing: context.state.game.air_wing.clone(),
                 team_proximity,
+                minimap: context.settings.minimap_shown.then(|| {
+                    let core = &context.state.core;
+                    let teammates = context
+                        .state
+                        .game
+                        .contacts
+                        .values()
+                        .filter(|c| {
+                            c.view
+                                .entity_type()
+                                .map(|e| e.data().kind == EntityKind::Boat)
+                                .unwrap_or(false)
+                                && c.view.player_id().map_or(false, |id| {
+                                    Some(id) != core.player_id && core.members.contains(&id)
+                                })
+                        })
+                        .map(|c| c.view.transform().position)
+                        .collect();
+                    let pings = core
+                        .pings()
+                        .map(|(ping, _)| (Vec2::new(ping.x, ping.y), ping.kind))
+                        .collect();
+                    let route = self.autopilot.route().collect();
+                    self.minimap.update(
+                        &context.state.game.terrain,
+                        context.state.game.world_radius,
+                        player_contact.transform().position,
+                        context.settings.minimap_zoom,
+                        teammates,
+                        pings,
+                        route,
+                        context.state.game.events.clone(),
+                        context.state.game.ports.clone(),
+                        elapsed_seconds,
+                    )
+                }),
             });
 
             if self.control_rate_limiter.update_ready(elapsed_seconds) {
-                let left_click = context.mouse.take_click(MouseButton::Left);
+                let bindings = &context.settings.key_bindings;
+                let fire = bindings.take_fire(&context.keyboard, &mut context.mouse)
+                    || context.gamepad.is_down(GamepadButton::LeftTrigger)
+                    || context.gamepad.is_down(GamepadButton::RightTrigger);
 
                 // Get hint before borrow of player_contact().
                 let hint = Some(Hint {
@@ -1508,17 +1879,14 @@ impl GameClient for Mk48Game {
 
                 let current_control = Control {
                     guidance: Some(*player_contact.guidance()), // TODO don't send if hasn't changed.
-                    submerge: self.ui_state.submerge,
+                    depth: self.ui_state.depth,
                     aim_target,
                     active: self.ui_state.active,
-                    pay: context.keyboard.is_down(Key::C).then_some(Pay),
-                    fire: if left_click
-                        || context
-                            .keyboard
-                            .state(Key::Space)
-                            .combined(context.keyboard.state(Key::E))
-                            .is_down()
-                    {
+                    pay: bindings
+                        .state(Action::Pay, &context.keyboard, &context.mouse)
+                        .is_down()
+                        .then_some(Pay),
+                    fire: if fire {
                         Self::find_best_armament(
                             &self.fire_rate_limiter,
                             player_contact,
@@ -1561,6 +1929,22 @@ impl GameClient for Mk48Game {
             self.respawn_overridden = false;
 
             status
+        } else if self.spectator.target().is_some() || self.spectator.spawn_delay() > 0 {
+            UiStatus::Spectating(UiStatusSpectating {
+                following: match self.spectator.target() {
+                    Some(SpectateTarget::Player(player_id)) => Some(
+                        context
+                            .state
+                            .core
+                            .player_or_bot(player_id)
+                            .map(|player| player.alias)
+                            .unwrap_or_default(),
+                    ),
+                    _ => None,
+                },
+                active: self.spectator.target().is_some(),
+                spawn_delay: self.spectator.spawn_delay(),
+            })
         } else if let Some(death_reason) = context
             .state
             .game
@@ -1569,11 +1953,29 @@ impl GameClient for Mk48Game {
             .filter(|_| !self.respawn_overridden)
             .cloned()
         {
-            UiStatus::Respawning(UiStatusRespawning { death_reason })
+            let replay = if let Some(killcam) = &self.killcam {
+                Some(killcam.ui())
+            } else {
+                (!context.state.game.replay.is_empty()).then_some(UiReplay {
+                    open: false,
+                    paused: true,
+                    progress: 0.0,
+                    speed: 1.0,
+                })
+            };
+            UiStatus::Respawning(UiStatusRespawning {
+                death_reason,
+                combat_log: self.combat_log.engagements().to_vec(),
+                replay,
+            })
         } else {
             UiStatus::Spawning
         };
 
+        if !matches!(status, UiStatus::Respawning(_)) {
+            self.killcam = None;
+        }
+
         if let Some(control) = control {
             context.send_to_game(control);
         }
@@ -1594,31 +1996,172 @@ impl GameClient for Mk48Game {
             UiEvent::Armament(armament) => {
                 self.ui_state.armament = armament;
             }
+            UiEvent::Depth(depth) => {
+                self.set_depth(depth, &*context);
+            }
             UiEvent::GraphicsSettingsChanged => {
                 self.render_chain = Self::create_render_chain(context).unwrap();
             }
             UiEvent::OverrideRespawn => {
                 self.respawn_overridden = true;
             }
-            UiEvent::Respawn(entity_type) => {
-                context.send_to_game(Command::Spawn(Spawn { entity_type }));
+            UiEvent::SpectateCycle(forward) => {
+                self.spectator.cycle(&context.state.core, forward);
             }
-            UiEvent::Spawn { alias, entity_type } => {
-                context.send_set_alias(alias);
-                context.send_to_game(Command::Spawn(Spawn { entity_type }));
+            UiEvent::SpectateFree => {
+                self.spectator.free(self.camera.center);
             }
-            UiEvent::Submerge(submerge) => {
-                self.set_submerge(submerge, &*context);
+            UiEvent::SpectateStop => {
+                self.spectator.stop();
             }
-            UiEvent::Upgrade(entity_type) => {
+            UiEvent::Replay(control) => match control {
+                ReplayControl::Open => {
+                    if let Some(death_reason) = context.state.game.death_reason.as_ref() {
+                        let frames = context.state.game.replay.iter().cloned().collect();
+                        self.killcam = Killcam::new(frames, death_reason);
+                    }
+                }
+                ReplayControl::Close => self.killcam = None,
+                ReplayControl::Paused(paused) => {
+                    if let Some(killcam) = &mut self.killcam {
+                        killcam.set_paused(paused);
+                    }
+                }
+                ReplayControl::Seek(progress) => {
+                    if let Some(killcam) = &mut self.killcam {
+                        killcam.seek(progress);
+                    }
+                }
+                ReplayControl::Speed(speed) => {
+                    if let Some(killcam) = &mut self.killcam {
+                        killcam.set_speed(speed);
+                    }
+                }
+            },
+            UiEvent::Respawn(entity_type, loadout) => {
+                self.killcam = None;
+                self.autopilot.clear();
+                context.send_to_game(Command::Spawn(Spawn {
+                    entity_type,
+                    loadout,
+                }));
+            }
+            UiEvent::Spawn {
+                alias,
+                entity_type,
+                loadout,
+            } => {
+                context.send_set_alias(alias);
+                self.autopilot.clear();
+                context.send_to_game(Command::Spawn(Spawn {
+                    entity_type,
+                    loadout,
+                }));
+            }
+            UiEvent::Upgrade(entity_type, loadout) => {
                 context.audio.play(Audio::Upgrade);
-                context.send_to_game(Command::Upgrade(Upgrade { entity_type }));
+                context.send_to_game(Command::Upgrade(Upgrade {
+                    entity_type,
+                    loadout,
+                }));
             }
         }
     }
 }
 
 impl Mk48Game {
+    /// Performs discrete actions (as opposed to held actions, like movement) bound to a key or
+    /// mouse button that was just pressed.
+    fn peek_binding(&mut self, binding: Binding, context: &mut Context<Self>) {
+        if let Some(contact) = context.state.game.player_contact() {
+            let consumptions: Vec<bool> = contact.reloads().iter().map(|b| *b).collect();
+            let groups = group_armaments(
+                contact.armaments(),
+                &consumptions,
+                &context.state.game.magazines,
+            );
+            // Collected, since pinging requires mutable access to the context.
+            let actions: Vec<Action> = context.settings.key_bindings.actions(binding).collect();
+            for action in actions {
+                match action {
+                    Action::Surface => {
+                        self.set_depth(self.ui_state.depth.cycle(), context);
+                    }
+                    Action::Active => {
+                        self.set_active(!self.ui_state.active, context);
+                    }
+                    Action::CycleArmament => {
+                        self.cycle_armament(true, context);
+                    }
+                    Action::Waypoint => {
+                        if let Some(position) = context
+                            .mouse
+                            .view_position
+                            .map(|p| self.camera.to_world_position(p))
+                        {
+                            self.autopilot.push(position);
+                        }
+                    }
+                    _ => {
+                        if let Some(kind) = action.ping() {
+                            self.ping(kind, context);
+                        } else if let Some(armament) = action.armament().and_then(|i| {
+                            groups.get(i).map(|Group { entity_type, .. }| *entity_type)
+                        }) {
+                            self.ui_state.armament = Some(armament);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Selects the next (or previous) armament group, wrapping around.
+    fn cycle_armament(&mut self, forward: bool, context: &Context<Self>) {
+        if let Some(contact) = context.state.game.player_contact() {
+            let consumptions: Vec<bool> = contact.reloads().iter().map(|b| *b).collect();
+            let groups = group_armaments(
+                contact.armaments(),
+                &consumptions,
+                &context.state.game.magazines,
+            );
+            self.ui_state.armament = groups
+                .get(
+                    self.ui_state
+                        .armament
+                        .and_then(|current| {
+                            groups
+                                .iter()
+                                .position(|Group { entity_type, .. }| *entity_type == current)
+                        })
+                        .map(|idx| {
+                            let offset = if forward { 1 } else { groups.len() - 1 };
+                            (idx + offset) % groups.len()
+                        })
+                        .unwrap_or(0),
+                )
+                .map(|Group { entity_type, .. }| *entity_type);
+        }
+    }
+
+    /// Pings the mouse position for the rest of the team.
+    fn ping(&self, kind: PingKind, context: &mut Context<Self>) {
+        if context.state.core.team_id().is_none() {
+            return;
+        }
+        if let Some(position) = context
+            .mouse
+            .view_position
+            .map(|p| self.camera.to_world_position(p))
+        {
+            context.send_to_server(Request::Team(TeamRequest::Ping {
+                kind,
+                x: position.x,
+                y: position.y,
+            }));
+        }
+    }
+
     fn set_active(&mut self, active: bool, context: &Context<Self>) {
         if let Some(contact) = context.state.game.player_contact() {
             if active && contact.data().sensors.sonar.range >= 0.0 {
@@ -1628,16 +2171,16 @@ impl Mk48Game {
         self.ui_state.active = active;
     }
 
-    fn set_submerge(&mut self, submerge: bool, context: &Context<Self>) {
+    fn set_depth(&mut self, depth: Depth, context: &Context<Self>) {
         if let Some(contact) = context.state.game.player_contact() {
             if contact.data().sub_kind == EntitySubKind::Submarine {
-                if !self.ui_state.submerge && submerge {
+                if !self.ui_state.depth.is_submerged() && depth.is_submerged() {
                     context.audio.play(Audio::Dive);
-                } else if self.ui_state.submerge && !submerge {
+                } else if self.ui_state.depth.is_submerged() && !depth.is_submerged() {
                     context.audio.play(Audio::Surface);
                 }
             }
         }
-        self.ui_state.submerge = submerge;
+        self.ui_state.depth = depth;
     }
 }
@@ -103,7 +103,7 @@ impl InterpolatedContact {
                         continue;
                     }
 
-                    let armament = &data.armaments[i];
+                    let armament = &self.view.armaments()[i];
                     let armament_entity_data = armament.entity_type.data();
 
                     if !matches!(
@@ -120,8 +120,8 @@ impl InterpolatedContact {
                     let boat_velocity = self.view.transform().direction.to_vec()
                         * self.view.transform().velocity.to_mps();
 
-                    let armament_transform =
-                        *self.view.transform() + data.armament_transform(self.view.turrets(), i);
+                    let armament_transform = *self.view.transform()
+                        + data.armament_transform(self.view.loadout(), self.view.turrets(), i);
 
                     let direction_vector: Vec2 = if armament.vertical {
                         // Straight up.
@@ -319,11 +319,18 @@ impl Mk48Game {
                         audio_layer.play_with_volume(Audio::AlarmSlow, 0.1 * volume.max(0.5));
                     }
                 }
-                EntityKind::Decoy => {
-                    if data.sub_kind == EntitySubKind::Sonar {
+                EntityKind::Decoy => match data.sub_kind {
+                    EntitySubKind::Sonar => {
                         audio_layer.play_with_volume(Audio::Sonar3, volume);
                     }
-                }
+                    EntitySubKind::Chaff => {
+                        audio_layer.play_with_volume(Audio::Rocket, volume);
+                    }
+                    EntitySubKind::Smoke => {
+                        audio_layer.play_with_volume(Audio::Splash, volume);
+                    }
+                    _ => {}
+                },
                 _ => {}
             }
         }
@@ -0,0 +1,477 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use client_util::keyboard::{Key, KeyState, KeyboardState};
+use client_util::mouse::{MouseButton, MouseButtonState, MouseState};
+use core_protocol::dto::PingKind;
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+/// A key or mouse button that can be bound to an [`Action`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Binding {
+    Key(Key),
+    Mouse(MouseButton),
+}
+
+impl Binding {
+    /// The state of the binding, as if it were a key.
+    pub fn state(self, keyboard: &KeyboardState, mouse: &MouseState) -> KeyState {
+        match self {
+            Self::Key(key) => *keyboard.state(key),
+            Self::Mouse(button) => match *mouse.state(button) {
+                MouseButtonState::Down(time) => KeyState::Down(time),
+                _ => KeyState::Up,
+            },
+        }
+    }
+}
+
+impl Display for Binding {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Key(key) => write!(f, "{}", key),
+            Self::Mouse(button) => write!(f, "Mouse{}", button),
+        }
+    }
+}
+
+impl FromStr for Binding {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(button) = s.strip_prefix("Mouse") {
+            MouseButton::from_str(button).map(Self::Mouse)
+        } else {
+            Key::from_str(s).map(Self::Key)
+        }
+        .map_err(|_| ())
+    }
+}
+
+/// Something the player can do with a [`Binding`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Action {
+    Forward,
+    Backward,
+    Left,
+    Right,
+    Stop,
+    /// Move towards the mouse while held.
+    Steer,
+    Fire,
+    Pay,
+    Surface,
+    Active,
+    CycleArmament,
+    /// Queue an autopilot waypoint at the mouse position.
+    Waypoint,
+    /// Ping the mouse position for the team.
+    PingAttack,
+    PingDanger,
+    PingRegroup,
+    Armament1,
+    Armament2,
+    Armament3,
+    Armament4,
+    Armament5,
+    Armament6,
+    Armament7,
+    Armament8,
+    Armament9,
+    Armament10,
+}
+
+impl Action {
+    pub const ALL: [Self; 25] = [
+        Self::Forward,
+        Self::Backward,
+        Self::Left,
+        Self::Right,
+        Self::Stop,
+        Self::Steer,
+        Self::Fire,
+        Self::Pay,
+        Self::Surface,
+        Self::Active,
+        Self::CycleArmament,
+        Self::Waypoint,
+        Self::PingAttack,
+        Self::PingDanger,
+        Self::PingRegroup,
+        Self::Armament1,
+        Self::Armament2,
+        Self::Armament3,
+        Self::Armament4,
+        Self::Armament5,
+        Self::Armament6,
+        Self::Armament7,
+        Self::Armament8,
+        Self::Armament9,
+        Self::Armament10,
+    ];
+
+    /// Name used for storage.
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Forward => "forward",
+            Self::Backward => "backward",
+            Self::Left => "left",
+            Self::Right => "right",
+            Self::Stop => "stop",
+            Self::Steer => "steer",
+            Self::Fire => "fire",
+            Self::Pay => "pay",
+            Self::Surface => "surface",
+            Self::Active => "active",
+            Self::CycleArmament => "cycleArmament",
+            Self::Waypoint => "waypoint",
+            Self::PingAttack => "pingAttack",
+            Self::PingDanger => "pingDanger",
+            Self::PingRegroup => "pingRegroup",
+            Self::Armament1 => "armament1",
+            Self::Armament2 => "armament2",
+            Self::Armament3 => "armament3",
+            Self::Armament4 => "armament4",
+            Self::Armament5 => "armament5",
+            Self::Armament6 => "armament6",
+            Self::Armament7 => "armament7",
+            Self::Armament8 => "armament8",
+            Self::Armament9 => "armament9",
+            Self::Armament10 => "armament10",
+        }
+    }
+
+    /// Human readable name.
+    pub fn label(self) -> String {
+        match self {
+            Self::Forward => "Forward",
+            Self::Backward => "Backward",
+            Self::Left => "Turn Left",
+            Self::Right => "Turn Right",
+            Self::Stop => "Stop",
+            Self::Steer => "Steer (towards mouse)",
+            Self::Fire => "Fire",
+            Self::Pay => "Pay",
+            Self::Surface => "Cycle Depth",
+            Self::Active => "Active Sensors",
+            Self::CycleArmament => "Next Weapon",
+            Self::Waypoint => "Add Waypoint",
+            Self::PingAttack => "Ping Attack",
+            Self::PingDanger => "Ping Danger",
+            Self::PingRegroup => "Ping Regroup",
+            _ => return format!("Weapon {}", self.armament().unwrap() + 1),
+        }
+        .to_owned()
+    }
+
+    /// Index of the armament group selected by this action, if any.
+    pub fn armament(self) -> Option<usize> {
+        Some(match self {
+            Self::Armament1 => 0,
+            Self::Armament2 => 1,
+            Self::Armament3 => 2,
+            Self::Armament4 => 3,
+            Self::Armament5 => 4,
+            Self::Armament6 => 5,
+            Self::Armament7 => 6,
+            Self::Armament8 => 7,
+            Self::Armament9 => 8,
+            Self::Armament10 => 9,
+            _ => return None,
+        })
+    }
+
+    /// Kind of team ping placed by this action, if any.
+    pub fn ping(self) -> Option<PingKind> {
+        Some(match self {
+            Self::PingAttack => PingKind::Attack,
+            Self::PingDanger => PingKind::Danger,
+            Self::PingRegroup => PingKind::Regroup,
+            _ => return None,
+        })
+    }
+
+    /// Whether it is fine for a binding to trigger both actions. Mouse buttons can fire on click
+    /// and steer when held.
+    fn compatible(self, other: Self, binding: Binding) -> bool {
+        matches!(binding, Binding::Mouse(_))
+            && matches!(
+                (self, other),
+                (Self::Fire, Self::Steer) | (Self::Steer, Self::Fire)
+            )
+    }
+}
+
+/// Keyboard layouts with preset bindings, so that movement keys stay in the same place.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum KeyboardLayout {
+    Qwerty,
+    Qwertz,
+    Azerty,
+}
+
+impl KeyboardLayout {
+    pub const ALL: [Self; 3] = [Self::Qwerty, Self::Qwertz, Self::Azerty];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Qwerty => "QWERTY",
+            Self::Qwertz => "QWERTZ",
+            Self::Azerty => "AZERTY",
+        }
+    }
+}
+
+/// Up to [`Self::SLOTS`] bindings per [`Action`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyBindings {
+    bindings: [[Option<Binding>; Self::SLOTS]; Action::ALL.len()],
+}
+
+impl KeyBindings {
+    pub const SLOTS: usize = 3;
+
+    /// Bindings that suit a particular keyboard layout.
+    pub fn preset(layout: KeyboardLayout) -> Self {
+        use Binding::{Key as K, Mouse as M};
+
+        let mut ret = Self {
+            bindings: [[None; Self::SLOTS]; Action::ALL.len()],
+        };
+        let mut bind = |action: Action, bindings: &[Binding]| {
+            for (slot, &binding) in bindings.iter().enumerate() {
+                ret.set(action, slot, Some(binding));
+            }
+        };
+
+        let (forward, left, active) = match layout {
+            KeyboardLayout::Qwerty => (Key::W, Key::A, Key::Z),
+            KeyboardLayout::Qwertz => (Key::W, Key::A, Key::Y),
+            KeyboardLayout::Azerty => (Key::Z, Key::Q, Key::W),
+        };
+
+        bind(Action::Forward, &[K(forward), K(Key::Up)]);
+        bind(Action::Backward, &[K(Key::S), K(Key::Down)]);
+        bind(Action::Left, &[K(left), K(Key::Left)]);
+        bind(Action::Right, &[K(Key::D), K(Key::Right)]);
+        bind(Action::Stop, &[K(Key::X)]);
+        bind(
+            Action::Steer,
+            &[M(MouseButton::Right), M(MouseButton::Left)],
+        );
+        bind(
+            Action::Fire,
+            &[M(MouseButton::Left), K(Key::Space), K(Key::E)],
+        );
+        bind(Action::Pay, &[K(Key::C)]);
+        bind(Action::Surface, &[K(Key::R)]);
+        bind(Action::Active, &[K(active)]);
+        bind(Action::CycleArmament, &[K(Key::Tab)]);
+        bind(Action::Waypoint, &[K(Key::F)]);
+        bind(Action::PingAttack, &[K(Key::G)]);
+        bind(Action::PingDanger, &[K(Key::H)]);
+        bind(Action::PingRegroup, &[K(Key::J)]);
+        let digits = [
+            Key::One,
+            Key::Two,
+            Key::Three,
+            Key::Four,
+            Key::Five,
+            Key::Six,
+            Key::Seven,
+            Key::Eight,
+            Key::Nine,
+            Key::Zero,
+        ];
+        for action in Action::ALL {
+            if let Some(i) = action.armament() {
+                bind(action, &[K(digits[i])]);
+            }
+        }
+
+        ret
+    }
+
+    /// Bindings of an action (some slots may be empty).
+    pub fn get(&self, action: Action) -> &[Option<Binding>; Self::SLOTS] {
+        &self.bindings[action as usize]
+    }
+
+    /// Sets (or clears) one binding of an action.
+    pub fn set(&mut self, action: Action, slot: usize, binding: Option<Binding>) {
+        self.bindings[action as usize][slot] = binding;
+    }
+
+    /// Iterates the bindings of an action.
+    pub fn iter(&self, action: Action) -> impl Iterator<Item = Binding> + '_ {
+        self.get(action).iter().filter_map(|b| *b)
+    }
+
+    /// Whether the binding triggers the action.
+    pub fn is_bound(&self, action: Action, binding: Binding) -> bool {
+        self.iter(action).any(|b| b == binding)
+    }
+
+    /// Actions that the binding triggers.
+    pub fn actions(&self, binding: Binding) -> impl Iterator<Item = Action> + '_ {
+        Action::ALL
+            .into_iter()
+            .filter(move |&action| self.is_bound(action, binding))
+    }
+
+    /// Name of the first binding of an action, for hints.
+    pub fn hint(&self, action: Action) -> String {
+        self.iter(action)
+            .next()
+            .map(|b| b.to_string())
+            .unwrap_or_else(|| String::from("-"))
+    }
+
+    /// Combined state of all the bindings of an action (see [`KeyState::combined`]).
+    pub fn state(&self, action: Action, keyboard: &KeyboardState, mouse: &MouseState) -> KeyState {
+        self.iter(action).fold(KeyState::Up, |state, binding| {
+            state.combined(&binding.state(keyboard, mouse))
+        })
+    }
+
+    /// Whether to steer towards the mouse. Mouse buttons that also fire must be held for longer
+    /// than a click.
+    pub fn is_steering(&self, keyboard: &KeyboardState, mouse: &MouseState, time: f32) -> bool {
+        self.iter(Action::Steer).any(|binding| match binding {
+            Binding::Key(key) => keyboard.is_down(key),
+            Binding::Mouse(button) if self.is_bound(Action::Fire, binding) => {
+                mouse.is_down_not_click(button, time)
+            }
+            Binding::Mouse(button) => mouse.is_down(button),
+        })
+    }
+
+    /// Whether to fire. Consumes mouse clicks. Mouse buttons that also steer only fire on click.
+    pub fn take_fire(&self, keyboard: &KeyboardState, mouse: &mut MouseState) -> bool {
+        let mut fire = false;
+        for binding in self.iter(Action::Fire) {
+            fire |= match binding {
+                Binding::Key(key) => keyboard.is_down(key),
+                Binding::Mouse(button) => {
+                    mouse.take_click(button)
+                        || (!self.is_bound(Action::Steer, binding) && mouse.is_down(button))
+                }
+            };
+        }
+        fire
+    }
+
+    /// Bindings that trigger more than one action at once, and which actions.
+    pub fn conflicts(&self) -> Vec<(Binding, Action, Action)> {
+        let mut conflicts = Vec::new();
+        for (i, &a) in Action::ALL.iter().enumerate() {
+            for &b in &Action::ALL[i + 1..] {
+                for binding in self.iter(a) {
+                    if self.is_bound(b, binding) && !a.compatible(b, binding) {
+                        conflicts.push((binding, a, b));
+                    }
+                }
+            }
+        }
+        conflicts
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        Self::preset(KeyboardLayout::Qwerty)
+    }
+}
+
+/// Stored as, for example, "forward=W+Up,backward=S+Down,...", with empty slots left blank (e.g.
+/// "pay=+P"). Actions missing from storage (e.g. added in an update) keep their default bindings.
+impl ToString for KeyBindings {
+    fn to_string(&self) -> String {
+        Action::ALL
+            .iter()
+            .map(|&action| {
+                let bindings = self
+                    .get(action)
+                    .iter()
+                    .map(|b| b.map(|b| b.to_string()).unwrap_or_default())
+                    .collect::<Vec<_>>()
+                    .join("+");
+                format!("{}={}", action.as_str(), bindings)
+            })
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+}
+
+impl FromStr for KeyBindings {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut ret = Self::default();
+        for pair in s.split(',') {
+            let (name, bindings) = pair.split_once('=').ok_or(())?;
+            let action = if let Some(action) = Action::ALL.into_iter().find(|a| a.as_str() == name)
+            {
+                action
+            } else {
+                continue;
+            };
+            ret.bindings[action as usize] = [None; Self::SLOTS];
+            for (slot, binding) in bindings.split('+').take(Self::SLOTS).enumerate() {
+                ret.set(action, slot, Binding::from_str(binding).ok());
+            }
+        }
+        Ok(ret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::key_bindings::{Action, Binding, KeyBindings, KeyboardLayout};
+    use client_util::keyboard::Key;
+    use client_util::mouse::MouseButton;
+    use std::str::FromStr;
+
+    #[test]
+    fn round_trip() {
+        for layout in KeyboardLayout::ALL {
+            let mut bindings = KeyBindings::preset(layout);
+            bindings.set(Action::Pay, 0, None);
+            bindings.set(
+                Action::Surface,
+                2,
+                Some(Binding::Mouse(MouseButton::Middle)),
+            );
+            assert_eq!(KeyBindings::from_str(&bindings.to_string()), Ok(bindings));
+        }
+    }
+
+    #[test]
+    fn missing_actions_keep_defaults() {
+        let bindings = KeyBindings::from_str("stop=Q,bogus=W").unwrap();
+        assert!(bindings.is_bound(Action::Stop, Binding::Key(Key::Q)));
+        assert!(!bindings.is_bound(Action::Stop, Binding::Key(Key::X)));
+        assert_eq!(
+            bindings.get(Action::Forward),
+            KeyBindings::default().get(Action::Forward)
+        );
+    }
+
+    #[test]
+    fn presets_have_no_conflicts() {
+        for layout in KeyboardLayout::ALL {
+            assert_eq!(KeyBindings::preset(layout).conflicts(), vec![]);
+        }
+    }
+
+    #[test]
+    fn conflicts() {
+        let mut bindings = KeyBindings::default();
+        bindings.set(Action::Surface, 0, Some(Binding::Key(Key::W)));
+        assert_eq!(
+            bindings.conflicts(),
+            vec![(Binding::Key(Key::W), Action::Forward, Action::Surface)]
+        );
+    }
+}
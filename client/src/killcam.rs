@@ -0,0 +1,181 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::ui::UiReplay;
+use common::contact::{Contact, ContactTrait};
+use common::death_reason::DeathReason;
+use common::entity::EntityId;
+use common::ticks::Ticks;
+use glam::Vec2;
+
+/// Contacts known after an update was applied, while the player had a boat.
+#[derive(Clone)]
+pub struct ReplayFrame {
+    pub contacts: Vec<Contact>,
+    /// The player's boat.
+    pub entity_id: EntityId,
+}
+
+impl ReplayFrame {
+    /// About 10 seconds, as updates are sent every tick.
+    pub const MAX_FRAMES: usize = 100;
+
+    fn own_contact(&self) -> Option<&Contact> {
+        self.contacts.iter().find(|c| c.id() == self.entity_id)
+    }
+}
+
+/// Plays back the updates leading up to the player's boat sinking.
+pub struct Killcam {
+    /// Never empty.
+    frames: Vec<ReplayFrame>,
+    /// Playback position, in frames.
+    position: f32,
+    paused: bool,
+    /// Playback speed multiplier.
+    speed: f32,
+    /// Where the player's boat was last seen.
+    death_position: Vec2,
+    /// Positions of whatever sank the player's boat, with the index of the frame of each.
+    killer_path: Vec<(usize, Vec2)>,
+}
+
+impl Killcam {
+    /// Returns None if the player's boat wasn't seen in any frame.
+    pub fn new(frames: Vec<ReplayFrame>, death_reason: &DeathReason) -> Option<Self> {
+        let (last, own) = frames
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(i, frame)| frame.own_contact().map(|c| (i, c)))?;
+        let death_position = own.transform().position;
+
+        let could_be_killer = |contact: &Contact| -> bool {
+            if contact.id() == own.id() {
+                return false;
+            }
+            match death_reason {
+                DeathReason::Weapon(_, entity_type) | DeathReason::Obstacle(entity_type) => {
+                    contact.entity_type() == Some(*entity_type)
+                }
+                DeathReason::Boat(_) | DeathReason::Ram(_) => contact.is_boat(),
+                _ => false,
+            }
+        };
+
+        // Whatever could have done it and was last seen closest to the player's boat.
+        let killer = frames[..=last].iter().rev().find_map(|frame| {
+            frame
+                .contacts
+                .iter()
+                .filter(|c| could_be_killer(c))
+                .min_by(|a, b| {
+                    let a = a.transform().position.distance_squared(death_position);
+                    let b = b.transform().position.distance_squared(death_position);
+                    a.partial_cmp(&b).unwrap()
+                })
+                .map(|c| c.id())
+        });
+
+        let killer_path = killer
+            .map(|id| {
+                frames
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(i, frame)| {
+                        frame
+                            .contacts
+                            .iter()
+                            .find(|c| c.id() == id)
+                            .map(|c| (i, c.transform().position))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(Self {
+            frames,
+            position: 0.0,
+            paused: false,
+            speed: 1.0,
+            death_position,
+            killer_path,
+        })
+    }
+
+    /// Advances playback as if `elapsed_seconds` passed, pausing at the end.
+    pub fn update(&mut self, elapsed_seconds: f32) {
+        if self.paused {
+            return;
+        }
+        self.position += elapsed_seconds * self.speed / Ticks::ONE.to_secs();
+        if self.position >= self.end() {
+            self.position = self.end();
+            self.paused = true;
+        }
+    }
+
+    fn end(&self) -> f32 {
+        (self.frames.len() - 1) as f32
+    }
+
+    /// Unpausing at the end restarts playback.
+    pub fn set_paused(&mut self, paused: bool) {
+        if !paused && self.position >= self.end() {
+            self.position = 0.0;
+        }
+        self.paused = paused;
+    }
+
+    /// Seeks to `progress`, from 0 to 1.
+    pub fn seek(&mut self, progress: f32) {
+        self.position = progress.clamp(0.0, 1.0) * self.end();
+    }
+
+    pub fn set_speed(&mut self, speed: f32) {
+        self.speed = speed.clamp(0.1, 4.0);
+    }
+
+    fn frame(&self) -> &ReplayFrame {
+        &self.frames[self.position as usize]
+    }
+
+    /// Contacts at the current playback position, simulated forward from the latest frame.
+    pub fn contacts(&self) -> impl Iterator<Item = Contact> + '_ {
+        let delta_seconds = self.position.fract() * Ticks::ONE.to_secs();
+        self.frame().contacts.iter().map(move |contact| {
+            let mut contact = contact.clone();
+            contact.simulate(delta_seconds);
+            contact
+        })
+    }
+
+    /// Position of the player's boat at the current playback position.
+    pub fn camera_position(&self) -> Vec2 {
+        self.frame()
+            .own_contact()
+            .map(|c| c.transform().position)
+            .unwrap_or(self.death_position)
+    }
+
+    /// Where whatever sank the player's boat has been, up to the current playback position.
+    pub fn killer_path(&self) -> impl Iterator<Item = Vec2> + '_ {
+        self.killer_path
+            .iter()
+            .take_while(|&&(i, _)| i as f32 <= self.position)
+            .map(|&(_, position)| position)
+    }
+
+    pub fn ui(&self) -> UiReplay {
+        UiReplay {
+            open: true,
+            paused: self.paused,
+            progress: if self.end() > 0.0 {
+                self.position / self.end()
+            } else {
+                1.0
+            },
+            speed: self.speed,
+        }
+    }
+}
@@ -1,5 +1,5 @@
 /// Generated by `engine/target/debug/licensing --binary client --binary server --format rust`
-pub const LICENSES: &'static [(&'static str, &'static [&'static str])] = &[
+pub const LICENSES: &[(&str, &[&str])] = &[
     ("(Apache-2.0 OR MIT) AND BSD-3-Clause", &["encoding_rs"]),
     ("0BSD", &["enum-iterator", "enum-iterator-derive"]),
     ("0BSD OR Apache-2.0 OR MIT", &["adler"]),
@@ -215,7 +215,6 @@ pub const LICENSES: &'static [(&'static str, &'static [&'static str])] = &[
             "rawpointer",
             "rayon",
             "rayon-core",
-            "rc_borrow_mut",
             "rcgen",
             "regex",
             "regex-syntax",
@@ -17,15 +17,21 @@ use crate::ui::{Mk48Route, Mk48Ui};
 mod animation;
 mod armament;
 mod audio;
+mod autopilot;
 mod background;
 mod camera;
+mod combat;
 mod game;
 mod interpolated;
 mod interpolated_contact;
+mod key_bindings;
+mod killcam;
 mod licenses;
+mod minimap;
 mod particle;
 mod settings;
 mod sortable_sprite;
+mod spectator;
 mod sprite;
 mod state;
 mod tessellation;
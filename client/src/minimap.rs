@@ -0,0 +1,134 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::ui::UiMinimap;
+use client_util::rate_limiter::RateLimiter;
+use common::altitude::Altitude;
+use common::protocol::{Port, WorldEvent};
+use common::terrain::{Terrain, GRASS_LEVEL, SAND_LEVEL};
+use common_util::range::lerp;
+use core_protocol::dto::PingKind;
+use glam::Vec2;
+use std::rc::Rc;
+
+/// Renders explored terrain into a small image for the minimap. Sampling terrain for every pixel is
+/// relatively expensive, so the image is only re-rendered periodically or when the zoom changes.
+pub struct Minimap {
+    terrain: Rc<[u8]>,
+    terrain_center: Vec2,
+    radius: f32,
+    rate_limiter: RateLimiter,
+}
+
+impl Default for Minimap {
+    fn default() -> Self {
+        Self {
+            terrain: Rc::from(Vec::new()),
+            terrain_center: Vec2::ZERO,
+            radius: 0.0,
+            rate_limiter: RateLimiter::new(1.0),
+        }
+    }
+}
+
+impl Minimap {
+    /// Width and height of the terrain image, in pixels.
+    pub const RESOLUTION: usize = 96;
+    /// Number of zoom levels. The first shows the entire world, and each subsequent one shows half
+    /// as much, centered on the boat.
+    pub const ZOOM_LEVELS: u8 = 4;
+
+    /// Gets the minimap, re-rendering the terrain image if it is stale.
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        &mut self,
+        terrain: &Terrain,
+        world_radius: f32,
+        position: Vec2,
+        zoom: u8,
+        teammates: Vec<Vec2>,
+        pings: Vec<(Vec2, PingKind)>,
+        route: Vec<Vec2>,
+        events: Vec<WorldEvent>,
+        ports: Vec<Port>,
+        elapsed_seconds: f32,
+    ) -> UiMinimap {
+        let (center, radius) = if zoom == 0 {
+            (Vec2::ZERO, world_radius)
+        } else {
+            let zoom = zoom.min(Self::ZOOM_LEVELS - 1);
+            (position, world_radius / (1u32 << zoom) as f32)
+        };
+
+        if self.rate_limiter.update_ready(elapsed_seconds) || radius != self.radius {
+            self.terrain = Self::render(terrain, center, radius);
+            self.terrain_center = center;
+            self.radius = radius;
+        }
+
+        UiMinimap {
+            center,
+            radius,
+            world_radius,
+            terrain: Rc::clone(&self.terrain),
+            terrain_center: self.terrain_center,
+            teammates,
+            pings,
+            route,
+            events,
+            ports,
+        }
+    }
+
+    /// Renders RGBA pixels, top row first. Unexplored terrain is transparent.
+    fn render(terrain: &Terrain, center: Vec2, radius: f32) -> Rc<[u8]> {
+        let pixel = 2.0 * radius / Self::RESOLUTION as f32;
+        let mut pixels = Vec::with_capacity(Self::RESOLUTION * Self::RESOLUTION * 4);
+        for j in 0..Self::RESOLUTION {
+            for i in 0..Self::RESOLUTION {
+                let pos = center
+                    + Vec2::new(
+                        (i as f32 + 0.5) * pixel - radius,
+                        radius - (j as f32 + 0.5) * pixel,
+                    );
+                pixels.extend_from_slice(&Self::color(terrain.sample_loaded(pos)));
+            }
+        }
+        pixels.into()
+    }
+
+    fn color(altitude: Option<Altitude>) -> [u8; 4] {
+        match altitude {
+            None => [0, 0, 0, 0],
+            Some(altitude) if altitude < SAND_LEVEL => {
+                let depth = altitude.0 as f32 / i8::MIN as f32;
+                [
+                    lerp(50.0, 20.0, depth) as u8,
+                    lerp(110.0, 50.0, depth) as u8,
+                    lerp(170.0, 110.0, depth) as u8,
+                    255,
+                ]
+            }
+            Some(altitude) if altitude < GRASS_LEVEL => [194, 178, 128, 255],
+            Some(_) => [96, 128, 56, 255],
+        }
+    }
+}
+
+/// Color of a team ping, in both the world and the minimap.
+pub fn ping_color(kind: PingKind) -> [u8; 3] {
+    match kind {
+        PingKind::Attack => [255, 75, 75],
+        PingKind::Danger => [255, 200, 40],
+        PingKind::Regroup => [60, 160, 255],
+    }
+}
+
+/// Color of a world event marker, in both the world and the minimap.
+pub fn event_color(event: &WorldEvent) -> [u8; 3] {
+    match event {
+        WorldEvent::SupplyDrop { .. } => [255, 215, 80],
+        WorldEvent::Bounty { .. } => [230, 90, 255],
+        WorldEvent::Storm { .. } => [170, 185, 205],
+    }
+}
@@ -1,24 +1,49 @@
 // SPDX-FileCopyrightText: 2021 Softbear, Inc.
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::key_bindings::KeyBindings;
 use client_util::browser_storage::BrowserStorages;
 use client_util::js_util::is_mobile;
 use client_util::setting::Settings;
 use std::str::FromStr;
 
 /// Settings can be set via Javascript (see util/settings.js and page/Settings.svelte).
-#[derive(Clone, Default, PartialEq, Settings)]
+#[derive(Clone, PartialEq, Eq, Settings)]
 pub struct Mk48Settings {
     pub animations: bool,
     #[setting(no_store)]
     pub cinematic: bool,
     pub circle_hud: bool,
     pub dynamic_waves: bool,
+    /// Show where to aim to hit a moving target.
+    pub fire_control: bool,
     pub fps_shown: bool,
+    pub key_bindings: KeyBindings,
+    pub minimap_shown: bool,
+    /// Index of zoom level, see `Minimap::ZOOM_LEVELS`.
+    #[setting(range = "0..3")]
+    pub minimap_zoom: u8,
     pub shadows: ShadowSetting,
 }
 
-#[derive(Copy, Clone, Debug, PartialEq)]
+impl Default for Mk48Settings {
+    fn default() -> Self {
+        Self {
+            animations: false,
+            cinematic: false,
+            circle_hud: false,
+            dynamic_waves: false,
+            fire_control: true,
+            fps_shown: false,
+            key_bindings: KeyBindings::default(),
+            minimap_shown: true,
+            minimap_zoom: 0,
+            shadows: ShadowSetting::default(),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum ShadowSetting {
     None,
     Hard,
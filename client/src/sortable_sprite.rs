@@ -102,6 +102,7 @@ impl SortableSprite {
             mast * 0.25
         } else {
             let mut height = data.width.min(data.length) * 0.33;
+            #[allow(clippy::if_same_then_else)]
             if data.kind == EntityKind::Aircraft {
                 // Aircraft have wings/blades that are wide compared to the height of the aircraft.
                 height *= 0.66;
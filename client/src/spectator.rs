@@ -0,0 +1,177 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::interpolated_contact::InterpolatedContact;
+use client_util::context::CoreState;
+use client_util::rate_limiter::RateLimiter;
+use common::contact::{Contact, ContactTrait};
+use common::entity::EntityId;
+use common::protocol::{Command, Spectate, SpectateTarget};
+use core_protocol::id::PlayerId;
+use glam::Vec2;
+use std::collections::HashMap;
+
+/// Watches the game without a boat, by following other players or moving the camera freely.
+pub struct Spectator {
+    /// None if not spectating.
+    target: Option<SpectateTarget>,
+    /// Seconds until the server allows spawning, since watching beyond one's own team delays it
+    /// (see `SpectateTarget::SPAWN_DELAY`).
+    spawn_delay: f32,
+    /// Whether the server has yet to be told about the current target.
+    dirty: bool,
+    /// Free camera movement would otherwise be sent every frame.
+    rate_limiter: RateLimiter,
+}
+
+impl Default for Spectator {
+    fn default() -> Self {
+        Self {
+            target: None,
+            spawn_delay: 0.0,
+            dirty: false,
+            rate_limiter: RateLimiter::new(0.25),
+        }
+    }
+}
+
+impl Spectator {
+    /// How fast the free camera moves, in multiples of its range per second.
+    const FREE_SPEED: f32 = 0.75;
+
+    pub fn target(&self) -> Option<SpectateTarget> {
+        self.target
+    }
+
+    /// Whole seconds until spawning is allowed.
+    pub fn spawn_delay(&self) -> u32 {
+        self.spawn_delay.ceil() as u32
+    }
+
+    /// Returns true if watching `target` sees beyond the player's own team, which the server
+    /// penalizes by delaying spawning.
+    fn watches_beyond_team(core: &CoreState, target: SpectateTarget) -> bool {
+        match target {
+            SpectateTarget::Player(player_id) => {
+                let team_id = core.team_id();
+                team_id.is_none()
+                    || core.player_or_bot(player_id).and_then(|p| p.team_id) != team_id
+            }
+            SpectateTarget::Free(_) => true,
+        }
+    }
+
+    /// Follows the next (or previous) player on the liveboard, relative to the one currently
+    /// followed, if any.
+    pub fn cycle(&mut self, core: &CoreState, forward: bool) {
+        let players: Vec<PlayerId> = core
+            .liveboard
+            .iter()
+            .map(|dto| dto.player_id)
+            .filter(|&player_id| Some(player_id) != core.player_id)
+            .collect();
+        if players.is_empty() {
+            if matches!(self.target, Some(SpectateTarget::Player(_))) {
+                self.stop();
+            }
+            return;
+        }
+
+        let current = if let Some(SpectateTarget::Player(player_id)) = self.target {
+            players.iter().position(|&p| p == player_id)
+        } else {
+            None
+        };
+        let n = players.len();
+        let index = match current {
+            Some(i) if forward => (i + 1) % n,
+            Some(i) => (i + n - 1) % n,
+            None if forward => 0,
+            None => n - 1,
+        };
+        self.set(Some(SpectateTarget::Player(players[index])));
+    }
+
+    /// Moves the camera freely, starting at `position`.
+    pub fn free(&mut self, position: Vec2) {
+        self.set(Some(SpectateTarget::Free(position)));
+    }
+
+    pub fn stop(&mut self) {
+        self.set(None);
+    }
+
+    /// Forgets the target without telling the server, which stops spectating upon spawning.
+    pub fn clear(&mut self) {
+        self.target = None;
+        self.spawn_delay = 0.0;
+        self.dirty = false;
+    }
+
+    fn set(&mut self, target: Option<SpectateTarget>) {
+        self.target = target;
+        self.dirty = true;
+    }
+
+    /// Gets the boat of the followed player, if it is visible.
+    pub fn followed<'a>(
+        &self,
+        contacts: &'a HashMap<EntityId, InterpolatedContact>,
+    ) -> Option<&'a Contact> {
+        if let Some(SpectateTarget::Player(player_id)) = self.target {
+            contacts
+                .values()
+                .map(|c| &c.view)
+                .find(|c| c.is_boat() && c.player_id() == Some(player_id))
+        } else {
+            None
+        }
+    }
+
+    /// Moves the free camera in `direction` (with a length of at most 1), moves on from followed
+    /// players that left the liveboard, counts down the spawn delay, and returns a command if the
+    /// server should be updated.
+    pub fn update(
+        &mut self,
+        core: &CoreState,
+        direction: Vec2,
+        world_radius: f32,
+        elapsed_seconds: f32,
+    ) -> Option<Command> {
+        match &mut self.target {
+            Some(SpectateTarget::Player(player_id)) => {
+                let player_id = *player_id;
+                if !core.liveboard.iter().any(|dto| dto.player_id == player_id) {
+                    self.cycle(core, true);
+                }
+            }
+            Some(SpectateTarget::Free(position)) => {
+                if direction != Vec2::ZERO {
+                    *position += direction.clamp_length_max(1.0)
+                        * (SpectateTarget::FREE_RANGE * Self::FREE_SPEED * elapsed_seconds);
+                    *position = position.clamp_length_max(world_radius);
+                    self.dirty = true;
+                }
+            }
+            None => {}
+        }
+
+        if self
+            .target
+            .map_or(false, |target| Self::watches_beyond_team(core, target))
+        {
+            self.spawn_delay = SpectateTarget::SPAWN_DELAY.to_secs();
+        } else {
+            self.spawn_delay = (self.spawn_delay - elapsed_seconds).max(0.0);
+        }
+
+        if self.rate_limiter.update_ready(elapsed_seconds) && self.dirty {
+            self.dirty = false;
+            Some(Command::Spectate(Spectate {
+                target: self.target,
+            }))
+        } else {
+            None
+        }
+    }
+}
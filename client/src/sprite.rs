@@ -79,6 +79,7 @@ impl SpriteLayer {
     }
 
     /// Draws a sprite. `angle` is in radians.
+    #[allow(clippy::too_many_arguments)]
     pub fn draw(
         &mut self,
         sprite: &str,
@@ -111,6 +112,7 @@ impl SpriteLayer {
     }
 
     /// Draws a sprite or a shadow depending on if the alpha > 0.0.
+    #[allow(clippy::too_many_arguments)]
     fn draw_inner(
         &mut self,
         sprite: &str,
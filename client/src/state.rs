@@ -3,13 +3,15 @@
 
 use crate::animation::Animation;
 use crate::interpolated_contact::InterpolatedContact;
+use crate::killcam::ReplayFrame;
 use client_util::apply::Apply;
 use common::contact::Contact;
 use common::death_reason::DeathReason;
 use common::entity::EntityId;
-use common::protocol::Update;
+use common::protocol::{Port, Sortie, Update, WorldEvent};
 use common::terrain::Terrain;
-use std::collections::HashMap;
+use common::ticks::Ticks;
+use std::collections::{HashMap, VecDeque};
 
 /// State associated with game server connection. Reset when connection is reset.
 pub struct Mk48State {
@@ -17,6 +19,18 @@ pub struct Mk48State {
     pub contacts: HashMap<EntityId, InterpolatedContact>,
     pub death_reason: Option<DeathReason>,
     pub entity_id: Option<EntityId>,
+    /// Rounds remaining in each of the player's boat's armaments.
+    pub magazines: Box<[u8]>,
+    /// Aircraft launched from the player's boat.
+    pub air_wing: Vec<Sortie>,
+    /// Supply drops, bounties and storms in progress.
+    pub events: Vec<WorldEvent>,
+    /// Ports in terrain that has been loaded.
+    pub ports: Vec<Port>,
+    /// Remaining immunity to damage, if the player's boat is docked at a port.
+    pub docked: Option<Ticks>,
+    /// Recent frames of the player's current (or last) boat, for replaying its sinking.
+    pub replay: VecDeque<ReplayFrame>,
     pub score: u32,
     pub terrain: Terrain,
     pub world_radius: f32,
@@ -30,6 +44,12 @@ impl Default for Mk48State {
             contacts: HashMap::new(),
             death_reason: None,
             entity_id: None,
+            magazines: Default::default(),
+            air_wing: Vec::new(),
+            events: Vec::new(),
+            ports: Vec::new(),
+            docked: None,
+            replay: VecDeque::new(),
             score: 0,
             terrain: Terrain::default(),
             // Keep border off splash screen by assuming radius.
@@ -72,6 +92,35 @@ impl Apply<Update> for Mk48State {
 
         self.world_radius = update.world_radius;
         self.score = update.score;
+        self.magazines = update.magazines;
+        self.air_wing = update.air_wing;
+        self.events = update.events;
+        self.docked = update.docked;
+        for port in update.ports {
+            // Resent whenever their chunk is reloaded.
+            if !self.ports.contains(&port) {
+                self.ports.push(port);
+            }
+        }
+
+        // Contacts were already updated, by peek.
+        if let Some(entity_id) = self.entity_id {
+            if self
+                .replay
+                .back()
+                .map_or(false, |frame| frame.entity_id != entity_id)
+            {
+                // New boat.
+                self.replay.clear();
+            }
+            if self.replay.len() >= ReplayFrame::MAX_FRAMES {
+                self.replay.pop_front();
+            }
+            self.replay.push_back(ReplayFrame {
+                contacts: self.contacts.values().map(|c| c.model.clone()).collect(),
+                entity_id,
+            });
+        }
     }
 
     fn reset(&mut self) {
@@ -1,7 +1,6 @@
 // SPDX-FileCopyrightText: 2021 Softbear, Inc.
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
-use crate::game::{ACTIVE_KEY, SURFACE_KEY};
 use common::death_reason::DeathReason;
 use common::entity::{EntityKind, EntitySubKind, EntityType};
 use core_protocol::id::LanguageId;
@@ -16,7 +15,9 @@ pub trait Mk48Translation: Sized {
     fn death_reason_boat(self, alias: PlayerAlias) -> String {
         self.death_reason_collision(&alias)
     }
+    s!(death_reason_admin);
     s!(death_reason_border);
+    s!(death_reason_crush);
     fn death_reason_collision(self, thing: impl Display) -> String;
     fn death_reason_obstacle(self, entity_type: EntityType) -> String {
         self.death_reason_collision(&entity_type.data().label)
@@ -45,6 +46,8 @@ pub trait Mk48Translation: Sized {
             (EntityKind::Boat, EntitySubKind::Ram) => self.entity_boat_ram_name(),
             (EntityKind::Boat, EntitySubKind::Submarine) => self.entity_boat_submarine_name(),
             (EntityKind::Boat, EntitySubKind::Tanker) => self.entity_boat_tanker_name(),
+            (EntityKind::Decoy, EntitySubKind::Chaff) => self.entity_decoy_chaff_name(),
+            (EntityKind::Decoy, EntitySubKind::Smoke) => self.entity_decoy_smoke_name(),
             (EntityKind::Decoy, EntitySubKind::Sonar) => self.entity_decoy_sonar_name(),
             (EntityKind::Obstacle, EntitySubKind::Structure) => {
                 self.entity_obstacle_structure_name()
@@ -126,6 +129,8 @@ pub trait Mk48Translation: Sized {
     s!(entity_boat_submarine_name);
     s!(entity_boat_tanker_hint);
     s!(entity_boat_tanker_name);
+    s!(entity_decoy_chaff_name);
+    s!(entity_decoy_smoke_name);
     s!(entity_decoy_sonar_name);
     s!(entity_obstacle_structure_name);
     s!(entity_weapon_depositor_name);
@@ -144,12 +149,21 @@ pub trait Mk48Translation: Sized {
     s!(instruction_zoom_touch);
 
     s!(sensor_active_label);
-    fn sensor_active_hint(self, sensors: &str) -> String;
+    fn sensor_active_hint(self, sensors: &str, key: &str) -> String;
     s!(sensor_radar_label);
     s!(sensor_sonar_label);
 
+    s!(ship_battery_label);
+    s!(ship_battery_hint);
+    s!(ship_docked_label);
+    s!(ship_docked_hint);
+    s!(ship_refit_label);
+    s!(ship_depth_deep_label);
+    s!(ship_depth_periscope_label);
+    s!(ship_depth_shallow_label);
     s!(ship_surface_label);
-    fn ship_surface_hint(self) -> String;
+    fn ship_surface_hint(self, key: &str) -> String;
+    s!(ship_waypoint_unreachable_label);
 
     s!(team_fleet_label);
     s!(team_fleet_name_placeholder);
@@ -207,8 +221,10 @@ impl Mk48Translation for LanguageId {
 
     fn death_reason(self, death_reason: &DeathReason) -> String {
         match death_reason {
+            DeathReason::Admin => self.death_reason_admin().to_owned(),
             &DeathReason::Boat(alias) => self.death_reason_boat(alias),
             DeathReason::Border => self.death_reason_border().to_owned(),
+            DeathReason::Crush => self.death_reason_crush().to_owned(),
             &DeathReason::Obstacle(entity_type) => self.death_reason_obstacle(entity_type),
             &DeathReason::Ram(alias) => self.death_reason_ram(alias),
             DeathReason::Terrain => self.death_reason_terrain().to_owned(),
@@ -222,6 +238,23 @@ impl Mk48Translation for LanguageId {
         }
     }
 
+    fn death_reason_admin(self) -> &'static str {
+        match self {
+            Arabic => "أغرقه مشرف!",
+            Bork => "Borked by the borkmin!",
+            English => "Sunk by an admin!",
+            French => "Coulé par un administrateur!",
+            German => "Von einem Admin versenkt!",
+            Hindi => "एक व्यवस्थापक द्वारा डुबोया गया!",
+            Italian => "Affondato da un amministratore!",
+            Japanese => "管理者に撃沈された!",
+            Russian => "Потоплен администратором!",
+            SimplifiedChinese => "被管理员击沉!",
+            Spanish => "¡Hundido por un administrador!",
+            Vietnamese => "Bị quản trị viên đánh chìm!",
+        }
+    }
+
     fn death_reason_border(self) -> &'static str {
         match self {
             Arabic => "تحطمت في الحدود!",
@@ -273,6 +306,23 @@ impl Mk48Translation for LanguageId {
         }
     }
 
+    fn death_reason_crush(self) -> &'static str {
+        match self {
+            Arabic => "سحقها ضغط الماء!",
+            Bork => "Borked by the deep!",
+            English => "Crushed by water pressure!",
+            French => "Écrasé par la pression de l'eau!",
+            German => "Vom Wasserdruck zerquetscht!",
+            Hindi => "पानी के दबाव से कुचल गया!",
+            Italian => "Schiacciato dalla pressione dell'acqua!",
+            Japanese => "水圧で圧壊!",
+            Russian => "Раздавлен давлением воды!",
+            SimplifiedChinese => "被水压压碎!",
+            Spanish => "¡Aplastado por la presión del agua!",
+            Vietnamese => "Bị áp lực nước nghiền nát!",
+        }
+    }
+
     fn death_reason_terrain(self) -> &'static str {
         match self {
             Arabic => "تحطمت في الأرض!",
@@ -881,6 +931,40 @@ impl Mk48Translation for LanguageId {
         }
     }
 
+    fn entity_decoy_chaff_name(self) -> &'static str {
+        match self {
+            Arabic => "قشر الرادار",
+            Bork => "radar bork confuser",
+            English => "chaff",
+            French => "paillettes",
+            German => "Düppel",
+            Hindi => "रडार भूसा",
+            Italian => "chaff",
+            Japanese => "チャフ",
+            Russian => "дипольные отражатели",
+            SimplifiedChinese => "箔条",
+            Spanish => "chaff",
+            Vietnamese => "nhiễu radar",
+        }
+    }
+
+    fn entity_decoy_smoke_name(self) -> &'static str {
+        match self {
+            Arabic => "ستار دخان",
+            Bork => "smoky bork hider",
+            English => "smoke screen",
+            French => "écran de fumée",
+            German => "Nebelwand",
+            Hindi => "धुआँ परदा",
+            Italian => "cortina fumogena",
+            Japanese => "煙幕",
+            Russian => "дымовая завеса",
+            SimplifiedChinese => "烟幕",
+            Spanish => "cortina de humo",
+            Vietnamese => "màn khói",
+        }
+    }
+
     fn entity_decoy_sonar_name(self) -> &'static str {
         match self {
             Arabic => "شرك السونار",
@@ -1153,8 +1237,7 @@ impl Mk48Translation for LanguageId {
         }
     }
 
-    fn sensor_active_hint(self, sensors: &str) -> String {
-        let key = ACTIVE_KEY;
+    fn sensor_active_hint(self, sensors: &str, key: &str) -> String {
         match self {
             English => format!("({key}) Active {sensors} helps you see more, but may also give away your position"),
             Spanish => format!("({key}) Los {sensors} activos te ayudan a ver más, pero también pueden revelar tu posición"),
@@ -1205,6 +1288,142 @@ impl Mk48Translation for LanguageId {
         }
     }
 
+    fn ship_battery_label(self) -> &'static str {
+        match self {
+            Arabic => "البطارية",
+            Bork => "Borkery",
+            English => "Battery",
+            French => "Batterie",
+            German => "Batterie",
+            Hindi => "बैटरी",
+            Italian => "Batteria",
+            Japanese => "バッテリー",
+            Russian => "Батарея",
+            SimplifiedChinese => "电池",
+            Spanish => "Batería",
+            Vietnamese => "Pin",
+        }
+    }
+
+    fn ship_battery_hint(self) -> &'static str {
+        match self {
+            Arabic => "تنفد تحت عمق المنظار. اصعد إلى السطح أو عمق المنظار لإعادة الشحن.",
+            Bork => "Drains below borkiscope depth. Surface or snorkel to rebork.",
+            English => "Drains below periscope depth. Surface or snorkel to recharge.",
+            French => "Se vide sous l'immersion périscopique. Faites surface ou utilisez le schnorchel pour recharger.",
+            German => "Entlädt sich unter Periskoptiefe. Auftauchen oder schnorcheln zum Aufladen.",
+            Hindi => "पेरिस्कोप गहराई से नीचे खत्म होती है। रिचार्ज करने के लिए सतह पर आएं या स्नॉर्कल करें।",
+            Italian => "Si scarica sotto la quota periscopica. Emergi o usa lo snorkel per ricaricare.",
+            Japanese => "潜望鏡深度より深いと消耗します。浮上するかシュノーケルで充電します。",
+            Russian => "Разряжается ниже перископной глубины. Всплывите или используйте шноркель для зарядки.",
+            SimplifiedChinese => "在潜望镜深度以下消耗。浮出水面或使用通气管充电。",
+            Spanish => "Se agota por debajo de la cota periscópica. Emerge o usa el snorkel para recargar.",
+            Vietnamese => "Cạn dần dưới độ sâu kính tiềm vọng. Nổi lên hoặc dùng ống thở để sạc lại.",
+        }
+    }
+
+    fn ship_docked_label(self) -> &'static str {
+        match self {
+            Arabic => "راسية",
+            Bork => "Borked",
+            English => "Docked",
+            French => "À quai",
+            German => "Angedockt",
+            Hindi => "बंदरगाह पर",
+            Italian => "Attraccata",
+            Japanese => "停泊中",
+            Russian => "У причала",
+            SimplifiedChinese => "已停泊",
+            Spanish => "Atracado",
+            Vietnamese => "Đã cập cảng",
+        }
+    }
+
+    fn ship_docked_hint(self) -> &'static str {
+        match self {
+            Arabic => "محمية من الضرر حتى ينفد الوقت أو تطلق النار. تصلح أسرع ويمكن تجهيزها بسفينة أخرى من نفس المستوى.",
+            Bork => "Immune to borks until time runs out or you bork. Repairs faster and can rebork as another ship of the same level.",
+            English => "Immune to damage until time runs out or you fire. Repairs faster and can refit to another ship of the same level.",
+            French => "Insensible aux dégâts jusqu'à la fin du temps ou jusqu'à ce que vous tiriez. Se répare plus vite et peut être réaménagé en un autre navire du même niveau.",
+            German => "Immun gegen Schaden, bis die Zeit abläuft oder du feuerst. Repariert schneller und kann zu einem anderen Schiff gleicher Stufe umgerüstet werden.",
+            Hindi => "समय समाप्त होने या फायर करने तक क्षति से सुरक्षित। तेजी से मरम्मत होती है और उसी स्तर के दूसरे जहाज में बदला जा सकता है।",
+            Italian => "Immune ai danni finché il tempo non scade o non spari. Si ripara più velocemente e può essere convertita in un'altra nave dello stesso livello.",
+            Japanese => "時間切れになるか発砲するまでダメージを受けません。修理が速くなり、同じレベルの別の艦に改装できます。",
+            Russian => "Неуязвим, пока не истечёт время или вы не откроете огонь. Ремонтируется быстрее и может переоснаститься в другой корабль того же уровня.",
+            SimplifiedChinese => "在时间耗尽或开火之前免疫伤害。修理更快,并可改装为同级别的其他舰船。",
+            Spanish => "Inmune al daño hasta que se acabe el tiempo o dispares. Se repara más rápido y puede reacondicionarse como otro barco del mismo nivel.",
+            Vietnamese => "Miễn nhiễm sát thương cho đến khi hết thời gian hoặc bạn khai hỏa. Sửa chữa nhanh hơn và có thể đổi sang tàu khác cùng cấp.",
+        }
+    }
+
+    fn ship_refit_label(self) -> &'static str {
+        match self {
+            Arabic => "إعادة التجهيز",
+            Bork => "Rebork",
+            English => "Refit",
+            French => "Réaménager",
+            German => "Umrüsten",
+            Hindi => "पुनः सज्जित करें",
+            Italian => "Riallestire",
+            Japanese => "改装",
+            Russian => "Переоснащение",
+            SimplifiedChinese => "改装",
+            Spanish => "Reacondicionar",
+            Vietnamese => "Tái trang bị",
+        }
+    }
+
+    fn ship_depth_deep_label(self) -> &'static str {
+        match self {
+            Arabic => "عميق",
+            Bork => "Deep bork",
+            English => "Deep",
+            French => "Profond",
+            German => "Tief",
+            Hindi => "गहरा",
+            Italian => "Profondo",
+            Japanese => "深深度",
+            Russian => "Глубоко",
+            SimplifiedChinese => "深潜",
+            Spanish => "Profundo",
+            Vietnamese => "Sâu",
+        }
+    }
+
+    fn ship_depth_periscope_label(self) -> &'static str {
+        match self {
+            Arabic => "المنظار",
+            Bork => "Borkiscope",
+            English => "Periscope",
+            French => "Périscope",
+            German => "Periskop",
+            Hindi => "पेरिस्कोप",
+            Italian => "Periscopio",
+            Japanese => "潜望鏡深度",
+            Russian => "Перископ",
+            SimplifiedChinese => "潜望镜",
+            Spanish => "Periscopio",
+            Vietnamese => "Kính tiềm vọng",
+        }
+    }
+
+    fn ship_depth_shallow_label(self) -> &'static str {
+        match self {
+            Arabic => "ضحل",
+            Bork => "Shallow bork",
+            English => "Shallow",
+            French => "Peu profond",
+            German => "Flach",
+            Hindi => "उथला",
+            Italian => "Basso",
+            Japanese => "浅深度",
+            Russian => "Мелко",
+            SimplifiedChinese => "浅潜",
+            Spanish => "Poco profundo",
+            Vietnamese => "Nông",
+        }
+    }
+
     fn ship_surface_label(self) -> &'static str {
         match self {
             Arabic => "سطح",
@@ -1222,8 +1441,7 @@ impl Mk48Translation for LanguageId {
         }
     }
 
-    fn ship_surface_hint(self) -> String {
-        let key = SURFACE_KEY;
+    fn ship_surface_hint(self, key: &str) -> String {
         match self {
             Arabic => format!("({key}) يمكنك سطح سفينتك وقتما تشاء ، لكن الغوص أحيانًا يكون مقيدًا بعمق الماء"),
             Bork => format!("({key}) Bork can surface wherever bork wants, but diving is sometimes limited by the depth of the water"),
@@ -1240,6 +1458,23 @@ impl Mk48Translation for LanguageId {
         }
     }
 
+    fn ship_waypoint_unreachable_label(self) -> &'static str {
+        match self {
+            Arabic => "نقطة الطريق غير قابلة للوصول",
+            Bork => "Waypoint unborkable",
+            English => "Waypoint unreachable",
+            French => "Point de passage inaccessible",
+            German => "Wegpunkt unerreichbar",
+            Hindi => "वेपॉइंट तक नहीं पहुँचा जा सकता",
+            Italian => "Punto di passaggio irraggiungibile",
+            Japanese => "経由地に到達できません",
+            Russian => "Путевая точка недостижима",
+            SimplifiedChinese => "航点无法到达",
+            Spanish => "Punto de ruta inalcanzable",
+            Vietnamese => "Không thể đến điểm tham chiếu",
+        }
+    }
+
     fn team_fleet_label(self) -> &'static str {
         match self {
             Arabic => "أسطول",
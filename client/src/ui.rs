@@ -1,19 +1,24 @@
 // SPDX-FileCopyrightText: 2021 Softbear, Inc.
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::combat::Engagement;
 use crate::game::Mk48Game;
 use crate::translation::Mk48Translation;
 use crate::ui::about_dialog::AboutDialog;
 use crate::ui::changelog_dialog::ChangelogDialog;
+use crate::ui::controls_dialog::ControlsDialog;
 use crate::ui::help_dialog::HelpDialog;
 use crate::ui::hint::Hint;
 pub use crate::ui::instructions::InstructionStatus;
+use crate::ui::kill_feed::KillFeed;
 use crate::ui::levels_dialog::LevelsDialog;
 use crate::ui::logo::logo;
+use crate::ui::minimap::Minimap;
 use crate::ui::respawn_overlay::RespawnOverlay;
 use crate::ui::settings_dialog::SettingsDialog;
 use crate::ui::ship_controls::ShipControls;
 use crate::ui::ships_dialog::ShipsDialog;
+use crate::ui::spectate_overlay::{SpectateButton, SpectateOverlay};
 use crate::ui::status_overlay::StatusOverlay;
 use crate::ui::upgrade_overlay::UpgradeOverlay;
 use client_util::context::Context;
@@ -21,12 +26,16 @@ use common::altitude::Altitude;
 use common::angle::Angle;
 use common::death_reason::DeathReason;
 use common::entity::EntityType;
+use common::protocol::{Depth, Kill, Port, Sortie, WorldEvent};
+use common::ticks::Ticks;
 use common::velocity::Velocity;
+use core_protocol::dto::PingKind;
 use core_protocol::id::{LanguageId, TeamId};
 use core_protocol::name::PlayerAlias;
 use engine_macros::SmolRoutable;
 use glam::Vec2;
 use std::collections::HashMap;
+use std::rc::Rc;
 use stylist::yew::styled_component;
 use yew::prelude::*;
 use yew_frontend::component::discord_icon::DiscordIcon;
@@ -52,17 +61,23 @@ use yew_frontend::translation::{use_translation, Translation};
 use yew_router::{Routable, Switch};
 
 mod about_dialog;
+mod air_wing;
 mod changelog_dialog;
+mod controls_dialog;
 mod help_dialog;
 mod hint;
 mod instructions;
+mod kill_feed;
 mod levels_dialog;
 mod logo;
+mod minimap;
+mod replay_controls;
 mod respawn_overlay;
 mod settings_dialog;
 mod ship_controls;
 mod ship_menu;
 mod ships_dialog;
+mod spectate_overlay;
 mod sprite;
 mod status_overlay;
 mod upgrade_overlay;
@@ -84,6 +99,7 @@ pub fn mk48_ui(props: &PropertiesWrapper<UiProps>) -> Html {
     let on_play = gctw.send_ui_event_callback.reform(|alias| UiEvent::Spawn {
         alias,
         entity_type: EntityType::G5,
+        loadout: 0,
     });
 
     let margin = "0.75rem";
@@ -165,6 +181,15 @@ pub fn mk48_ui(props: &PropertiesWrapper<UiProps>) -> Html {
                         <SettingsIcon<Mk48Route> route={Mk48Route::Settings}/>
                         <LanguageMenu/>
                     </Positioner>
+                    if let Some(minimap) = playing.minimap.clone() {
+                        <Positioner id="minimap" position={Position::CenterLeft{margin}}>
+                            <Minimap
+                                {minimap}
+                                position={playing.position}
+                                direction={playing.direction}
+                            />
+                        </Positioner>
+                    }
                     <TeamOverlay
                         position={Position::TopLeft{margin}}
                         style="max-width:25%;"
@@ -172,10 +197,12 @@ pub fn mk48_ui(props: &PropertiesWrapper<UiProps>) -> Html {
                         label={LanguageId::team_fleet_label as fn(LanguageId) -> &'static str}
                         name_placeholder={LanguageId::team_fleet_name_placeholder as fn(LanguageId) -> &'static str}
                     />
-                    <LeaderboardOverlay
-                        position={Position::TopRight{margin}}
-                        style="max-width:25%;"
-                    />
+                    <Positioner id="top_right" position={Position::TopRight{margin}} flex={Flex::Column} max_width="25%">
+                        <LeaderboardOverlay/>
+                        if !props.kill_feed.is_empty() {
+                            <KillFeed kills={props.kill_feed.clone()}/>
+                        }
+                    </Positioner>
                     <ChatOverlay
                         position={Position::BottomRight{margin}}
                         style="max-width:25%;"
@@ -186,9 +213,20 @@ pub fn mk48_ui(props: &PropertiesWrapper<UiProps>) -> Html {
                 if !gctw.settings_cache.cinematic {
                     <Hint entity_type={playing.entity_type}/>
                 }
+            } else if let UiStatus::Spectating(spectating) = status {
+                <Positioner id="spectate" position={Position::TopMiddle{margin}}>
+                    <SpectateOverlay status={spectating}/>
+                </Positioner>
+                <Positioner id="top_right" position={Position::TopRight{margin}} flex={Flex::Column} max_width="25%">
+                    <LeaderboardOverlay/>
+                    if !props.kill_feed.is_empty() {
+                        <KillFeed kills={props.kill_feed.clone()}/>
+                    }
+                </Positioner>
             } else if let UiStatus::Respawning(respawning) = status {
                 <RespawnOverlay status={respawning} score={props.score}/>
-                <Positioner position={Position::TopRight{margin}} max_width="25%">
+                <Positioner position={Position::TopRight{margin}} flex={Flex::Row} max_width="25%">
+                    <SpectateButton/>
                     <XButton onclick={gctw.send_ui_event_callback.reform(|_| UiEvent::OverrideRespawn)}/>
                 </Positioner>
             } else {
@@ -196,10 +234,11 @@ pub fn mk48_ui(props: &PropertiesWrapper<UiProps>) -> Html {
                     {logo()}
                 </SpawnOverlay>
                 <Positioner id="back" position={Position::TopRight{margin}} flex={Flex::Row}>
+                    <SpectateButton/>
                     <LanguageMenu/>
                 </Positioner>
             }
-            if !matches!(props.status, UiStatus::Playing(_)) {
+            if !matches!(props.status, UiStatus::Playing(_) | UiStatus::Spectating(_)) {
                 <Positioner id="invite" position={Position::BottomLeft{margin}}>
                     <InvitationLink/>
                 </Positioner>
@@ -233,7 +272,7 @@ pub fn mk48_ui(props: &PropertiesWrapper<UiProps>) -> Html {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, SmolRoutable)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, SmolRoutable)]
 pub enum Mk48Route {
     #[at("/about/")]
     About,
@@ -247,6 +286,8 @@ pub enum Mk48Route {
     Levels,
     #[at("/settings/")]
     Settings,
+    #[at("/controls/")]
+    Controls,
     #[not_found]
     #[at("/")]
     Home,
@@ -255,7 +296,7 @@ pub enum Mk48Route {
 /// State of UI inputs.
 pub struct UiState {
     pub active: bool,
-    pub submerge: bool,
+    pub depth: Depth,
     pub armament: Option<EntityType>,
 }
 
@@ -263,7 +304,7 @@ impl Default for UiState {
     fn default() -> Self {
         Self {
             active: true,
-            submerge: false,
+            depth: Depth::Surface,
             armament: None,
         }
     }
@@ -273,17 +314,38 @@ pub enum UiEvent {
     /// Sensors active.
     Active(bool),
     Armament(Option<EntityType>),
+    /// Submarine depth.
+    Depth(Depth),
     GraphicsSettingsChanged,
     /// Go from respawning to spawning.
     #[allow(unused)]
     OverrideRespawn,
-    Respawn(EntityType),
+    Replay(ReplayControl),
+    /// Respawn as a ship with a loadout.
+    Respawn(EntityType, u8),
+    /// Follow the next player on the liveboard (or the previous one, if false).
+    SpectateCycle(bool),
+    /// Move the camera freely.
+    SpectateFree,
+    /// Stop spectating.
+    SpectateStop,
     Spawn {
         alias: PlayerAlias,
         entity_type: EntityType,
+        loadout: u8,
     },
-    Submerge(bool),
-    Upgrade(EntityType),
+    /// Upgrade (or refit) to a ship with a loadout.
+    Upgrade(EntityType, u8),
+}
+
+/// Controls playback of the replay of the player's boat sinking.
+pub enum ReplayControl {
+    Open,
+    Close,
+    Paused(bool),
+    /// From 0 to 1.
+    Seek(f32),
+    Speed(f32),
 }
 
 #[derive(PartialEq, Clone, Default)]
@@ -291,44 +353,118 @@ pub struct UiProps {
     pub fps: f32,
     pub score: u32,
     pub status: UiStatus,
+    /// Recent kills, newest first.
+    pub kill_feed: Vec<UiKill>,
+}
+
+#[derive(PartialEq, Clone)]
+pub struct UiKill {
+    pub kill: Kill,
+    /// Fades out before disappearing.
+    pub opacity: f32,
 }
 
 /// Mutually exclusive statuses.
 #[derive(Default, PartialEq, Clone)]
+#[allow(clippy::large_enum_variant)]
 pub enum UiStatus {
     #[default]
     Spawning,
     Playing(UiStatusPlaying),
     Respawning(UiStatusRespawning),
+    Spectating(UiStatusSpectating),
 }
 
 #[derive(PartialEq, Clone)]
 pub struct UiStatusPlaying {
     pub entity_type: EntityType,
+    /// Index into the entity type's loadouts.
+    pub loadout: u8,
     pub velocity: Velocity,
     pub direction: Angle,
     pub position: Vec2,
     pub altitude: Altitude,
-    pub submerge: bool,
+    pub depth: Depth,
+    /// Fraction of battery remaining, if a submarine.
+    pub battery: Option<f32>,
+    /// Remaining immunity to damage, if docked at a port.
+    pub docked: Option<Ticks>,
+    /// Whether the autopilot recently skipped a waypoint it couldn't reach.
+    pub waypoint_unreachable: bool,
     /// Active sensors.
     pub active: bool,
     pub instruction_status: InstructionStatus,
     pub armament: Option<EntityType>,
     pub armament_consumption: Box<[bool]>,
+    /// Rounds remaining in each armament's magazine.
+    pub magazines: Box<[u8]>,
+    /// Aircraft launched from the player's boat.
+    pub air_wing: Vec<Sortie>,
     pub team_proximity: HashMap<TeamId, f32>,
+    /// None if the minimap is hidden.
+    pub minimap: Option<UiMinimap>,
+}
+
+#[derive(PartialEq, Clone)]
+pub struct UiMinimap {
+    /// Center of the minimap, in world coordinates.
+    pub center: Vec2,
+    /// Half the width of the minimap, in world units.
+    pub radius: f32,
+    pub world_radius: f32,
+    /// RGBA pixels of explored terrain, `Minimap::RESOLUTION` squared.
+    pub terrain: Rc<[u8]>,
+    /// May lag behind `center`, as terrain is rendered less often.
+    pub terrain_center: Vec2,
+    /// Positions of visible team members.
+    pub teammates: Vec<Vec2>,
+    /// Positions of active team pings.
+    pub pings: Vec<(Vec2, PingKind)>,
+    /// Autopilot waypoints, in order.
+    pub route: Vec<Vec2>,
+    /// Supply drops, bounties and storms in progress.
+    pub events: Vec<WorldEvent>,
+    /// Ports in explored terrain.
+    pub ports: Vec<Port>,
+}
+
+#[derive(PartialEq, Eq, Clone)]
+pub struct UiStatusSpectating {
+    /// None if the camera is free (or spectating stopped).
+    pub following: Option<PlayerAlias>,
+    /// False if spectating stopped, but spawning is still delayed.
+    pub active: bool,
+    /// Whole seconds until spawning is allowed, since watching beyond one's own team delays it.
+    pub spawn_delay: u32,
 }
 
 #[derive(PartialEq, Clone)]
 pub struct UiStatusRespawning {
     pub death_reason: DeathReason,
+    /// Damage exchanged with other players before sinking.
+    pub combat_log: Vec<Engagement>,
+    /// None if there is nothing to replay.
+    pub replay: Option<UiReplay>,
+}
+
+#[derive(PartialEq, Clone, Copy)]
+pub struct UiReplay {
+    /// Whether the replay is being shown, as opposed to just available.
+    pub open: bool,
+    pub paused: bool,
+    /// From 0 to 1.
+    pub progress: f32,
+    /// Playback speed multiplier.
+    pub speed: f32,
 }
 
 impl Mk48Game {
-    pub(crate) fn update_ui_props(&self, context: &mut Context<Self>, status: UiStatus) {
+    pub(crate) fn update_ui_props(&mut self, context: &mut Context<Self>, status: UiStatus) {
         let props = UiProps {
             fps: self.fps_counter.last_sample().unwrap_or(0.0),
             score: context.state.game.score,
             status,
+            kill_feed: self.kill_feed.update(context.client.time_seconds),
         };
 
         context.set_ui_props(props);
@@ -355,6 +491,9 @@ fn switch(routes: Mk48Route) -> Html {
         Mk48Route::Settings => html! {
             <SettingsDialog/>
         },
+        Mk48Route::Controls => html! {
+            <ControlsDialog/>
+        },
         Mk48Route::Home => html! {},
     }
 }
@@ -0,0 +1,66 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use common::protocol::{Sortie, SortieStatus};
+use stylist::yew::styled_component;
+use yew::{classes, html, html_nested, Html, Properties};
+
+#[derive(PartialEq, Eq, Properties)]
+pub struct AirWingProps {
+    pub air_wing: Vec<Sortie>,
+}
+
+/// Lists aircraft launched from the player's boat, and what they are doing.
+#[styled_component(AirWing)]
+pub fn air_wing(props: &AirWingProps) -> Html {
+    let container_style = css!(
+        r#"
+        border-top: 1px solid #ffffff40;
+        color: white;
+        font-size: 0.9rem;
+        margin-top: 0.5em;
+        padding-top: 0.25em;
+        user-select: none;
+        "#
+    );
+
+    let row_style = css!(
+        r#"
+        display: flex;
+        gap: 0.5em;
+        justify-content: space-between;
+        white-space: nowrap;
+        "#
+    );
+
+    let low_fuel_style = css!(
+        r#"
+        color: #e74c3c;
+        "#
+    );
+
+    html! {
+        <div class={container_style}>
+            {props.air_wing.iter().map(|Sortie{entity_type, status, fuel}| {
+                let (status, fuel) = match status {
+                    SortieStatus::Airborne => ("Airborne", Some(fuel)),
+                    SortieStatus::Returning => ("Returning", Some(fuel)),
+                    SortieStatus::Rearming => ("Rearming", None),
+                };
+                html_nested!{
+                    <div class={row_style.clone()}>
+                        <span>{entity_type.data().label}</span>
+                        <span>
+                            {status}
+                            if let Some(fuel) = fuel {
+                                <span class={classes!((fuel.to_secs() < 10.0).then(|| low_fuel_style.clone()))}>
+                                    {format!(" ({:.0}s)", fuel.to_secs())}
+                                </span>
+                            }
+                        </span>
+                    </div>
+                }
+            }).collect::<Html>()}
+        </div>
+    }
+}
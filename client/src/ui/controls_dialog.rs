@@ -0,0 +1,243 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::key_bindings::{Action, Binding, KeyBindings, KeyboardLayout};
+use crate::settings::Mk48Settings;
+use crate::Mk48Game;
+use client_util::browser_storage::BrowserStorages;
+use client_util::keyboard::Key;
+use client_util::mouse::MouseButton;
+use client_util::setting::CommonSettings;
+use stylist::yew::styled_component;
+use web_sys::{HtmlInputElement, HtmlSelectElement, InputEvent, KeyboardEvent, MouseEvent};
+use yew::{classes, html, html_nested, use_state, Callback, Html, TargetCast};
+use yew_frontend::dialog::dialog::Dialog;
+use yew_frontend::frontend::{use_ctw, use_gctw};
+
+#[styled_component(ControlsDialog)]
+pub fn controls_dialog() -> Html {
+    let select_style = css! {
+        r#"
+        border-radius: 0.25em;
+        box-sizing: border-box;
+        cursor: pointer;
+        font-size: 1em;
+        font-weight: bold;
+        outline: 0;
+        padding: 0.7em;
+        pointer-events: all;
+        white-space: nowrap;
+        margin-top: 0.25em;
+        border: 0;
+        color: white;
+        background-color: #0075ff;
+        display: block;
+        "#
+    };
+
+    let binding_style = css! {
+        r#"
+        background-color: #00000040;
+        border: 1px solid #ffffff40;
+        border-radius: 0.25em;
+        color: white;
+        cursor: pointer;
+        min-width: 8em;
+        padding: 0.3em;
+        "#
+    };
+
+    let capturing_style = css! {
+        r#"
+        border-color: white;
+        "#
+    };
+
+    let conflict_style = css! {
+        r#"
+        color: #ff8080;
+        "#
+    };
+
+    const BACKSPACE: u32 = 8;
+    const ESCAPE: u32 = 27;
+    const DELETE: u32 = 46;
+
+    let ctw = use_ctw();
+    let gctw = use_gctw::<Mk48Game>();
+    let key_bindings = gctw.settings_cache.key_bindings.clone();
+    // Which action and slot is waiting for a key or mouse button to be pressed.
+    let capturing = use_state::<Option<(Action, usize)>, _>(|| None);
+
+    let set_key_bindings = gctw
+        .change_settings_callback
+        .reform(|key_bindings: KeyBindings| {
+            Box::new(
+                move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                    settings.set_key_bindings(key_bindings, browser_storages);
+                },
+            )
+        });
+
+    let on_select_preset = {
+        let set_key_bindings = set_key_bindings.clone();
+        Callback::from(move |event: InputEvent| {
+            let value = event.target_unchecked_into::<HtmlSelectElement>().value();
+            if let Some(&layout) = KeyboardLayout::ALL.iter().find(|l| l.label() == value) {
+                set_key_bindings.emit(KeyBindings::preset(layout));
+            }
+        })
+    };
+
+    let gamepad_dead_zone = ctw.setting_cache.gamepad_dead_zone;
+    let on_set_gamepad_dead_zone =
+        ctw.change_common_settings_callback
+            .reform(|event: InputEvent| {
+                let value = event
+                    .target_unchecked_into::<HtmlInputElement>()
+                    .value_as_number() as f32;
+                Box::new(
+                    move |settings: &mut CommonSettings, browser_storages: &mut BrowserStorages| {
+                        settings.set_gamepad_dead_zone(value, browser_storages);
+                    },
+                )
+            });
+
+    let gamepad_sensitivity = ctw.setting_cache.gamepad_sensitivity;
+    let on_set_gamepad_sensitivity =
+        ctw.change_common_settings_callback
+            .reform(|event: InputEvent| {
+                let value = event
+                    .target_unchecked_into::<HtmlInputElement>()
+                    .value_as_number() as f32;
+                Box::new(
+                    move |settings: &mut CommonSettings, browser_storages: &mut BrowserStorages| {
+                        settings.set_gamepad_sensitivity(value, browser_storages);
+                    },
+                )
+            });
+
+    let conflicts = key_bindings.conflicts();
+
+    let rows = Action::ALL.into_iter().map(|action| {
+        let slots = (0..KeyBindings::SLOTS).map(|slot| {
+            let this = Some((action, slot));
+            let is_capturing = *capturing == this;
+            let binding = key_bindings.get(action)[slot];
+            let conflicted = binding.map_or(false, |binding| {
+                conflicts.iter().any(|&(b, a1, a2)| b == binding && (a1 == action || a2 == action))
+            });
+
+            let bind = {
+                let capturing = capturing.clone();
+                let key_bindings = key_bindings.clone();
+                let set_key_bindings = set_key_bindings.clone();
+                move |binding: Option<Binding>| {
+                    let mut key_bindings = key_bindings.clone();
+                    key_bindings.set(action, slot, binding);
+                    set_key_bindings.emit(key_bindings);
+                    capturing.set(None);
+                }
+            };
+
+            // The first press starts capturing, the second press is bound.
+            let onmousedown = {
+                let capturing = capturing.clone();
+                let bind = bind.clone();
+                Callback::from(move |event: MouseEvent| {
+                    if !is_capturing {
+                        capturing.set(this);
+                    } else if let Some(button) = MouseButton::try_from_button(event.button()) {
+                        event.prevent_default();
+                        bind(Some(Binding::Mouse(button)));
+                    }
+                })
+            };
+
+            // The game ignores keys pressed while an input is focused.
+            let onkeydown = {
+                let capturing = capturing.clone();
+                Callback::from(move |event: KeyboardEvent| {
+                    if !is_capturing {
+                        return;
+                    }
+                    event.prevent_default();
+                    event.stop_propagation();
+                    match event.key_code() {
+                        ESCAPE => capturing.set(None),
+                        BACKSPACE | DELETE => bind(None),
+                        key_code => {
+                            if let Some(key) = Key::try_from_key_code(key_code) {
+                                bind(Some(Binding::Key(key)));
+                            }
+                        }
+                    }
+                })
+            };
+
+            let value = if is_capturing {
+                String::from("Press a key...")
+            } else {
+                binding.map(|b| b.to_string()).unwrap_or_else(|| String::from("-"))
+            };
+
+            html_nested! {
+                <td>
+                    <input
+                        type="button"
+                        class={classes!(binding_style.clone(), is_capturing.then(|| capturing_style.clone()), conflicted.then(|| conflict_style.clone()))}
+                        {value}
+                        {onmousedown}
+                        {onkeydown}
+                        oncontextmenu={|event: MouseEvent| event.prevent_default()}
+                    />
+                </td>
+            }
+        }).collect::<Html>();
+
+        html_nested! {
+            <tr>
+                <td>{action.label()}</td>
+                {slots}
+            </tr>
+        }
+    }).collect::<Html>();
+
+    html! {
+        <Dialog title={"Controls"}>
+            <p>{"Click a binding, then press a key or mouse button. Press Backspace to clear it, or Escape to cancel."}</p>
+
+            <select
+                oninput={on_select_preset}
+                class={select_style}
+            >
+                <option selected={true} disabled={true}>{"Keyboard Layout Preset"}</option>
+                {KeyboardLayout::ALL.into_iter().map(|layout| html_nested!{
+                    <option value={layout.label()}>{layout.label()}</option>
+                }).collect::<Html>()}
+            </select>
+
+            {conflicts.iter().map(|(binding, a, b)| html_nested!{
+                <p class={conflict_style.clone()}>{format!("{} is bound to both {} and {}", binding, a.label(), b.label())}</p>
+            }).collect::<Html>()}
+
+            <table>
+                {rows}
+            </table>
+
+            <h3>{"Gamepad"}</h3>
+
+            <p>{"Left stick steers, right stick aims, triggers fire, bumpers cycle armaments, A surfaces, Y toggles active sensors, and B stops."}</p>
+
+            <label>
+                {format!("Dead Zone ({}%)", (gamepad_dead_zone * 100.0).round())}
+                <input type="range" min="0" max="0.5" step="0.05" value={gamepad_dead_zone.to_string()} oninput={on_set_gamepad_dead_zone}/>
+            </label>
+
+            <label>
+                {format!("Sensitivity ({:.2}x)", gamepad_sensitivity)}
+                <input type="range" min="0.25" max="2" step="0.05" value={gamepad_sensitivity.to_string()} oninput={on_set_gamepad_sensitivity}/>
+            </label>
+        </Dialog>
+    }
+}
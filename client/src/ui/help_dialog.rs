@@ -35,6 +35,12 @@ pub fn help_dialog() -> Html {
                 <li>{"Use the "}<b>{"WASD"}</b>{" or "}<b>{"arrow"}</b>{" keys to move, and 'x' key to stop"}</li>
             </ol>
 
+            <p>
+                {"All of the keys and mouse buttons mentioned here can be changed in the "}
+                <RouteLink<Mk48Route> route={Mk48Route::Controls}>{"controls"}</RouteLink<Mk48Route>>
+                {" settings."}
+            </p>
+
             <p>
                 {"Once your ship is moving in a direction, it will keep moving, so you can focus on using your weapons."}</p>
 
@@ -43,6 +49,16 @@ pub fn help_dialog() -> Html {
                 {"You can control the speed of your ship by varying the distance between your mouse and your ship. "}
             </p>
 
+            <p>
+                {"Press 'f' to add a waypoint at your mouse, and your ship will steer itself around land to each waypoint in turn. "}
+                {"Steering manually cancels all waypoints."}
+            </p>
+
+            <p>
+                {"When in a team, press 'g', 'h' or 'j' to ping the position of your mouse as a target to attack, "}
+                {"a danger, or a place to regroup. Only your team can see pings, on the map and at the edge of the screen."}
+            </p>
+
             <h2>{"Ships"}</h2>
 
             <p>
@@ -58,7 +74,10 @@ pub fn help_dialog() -> Html {
                 <li><b>{"Battleships"}</b>{" and "}<b>{"Dreadnoughts"}</b>{" are very formidable ships, having extremely
                 powerful main cannons. They may carry a minimal complement of aircraft for submarine defense."}</li>
                 <li><b>{"Submarines"}</b>{" travel underwater, making them immune to certain
-                types of weapons, but must surface to fire certain types of weapons."}</li>
+                types of weapons, but must surface to fire certain types of weapons. Periscope depth is slow,
+                as is the deepest depth, below the thermocline is harder to hear from above, and the more
+                damaged a submarine is, the shallower it can dive without being crushed. Below periscope depth, submarines run on a battery that drains faster at
+                high speed, and must surface or snorkel to recharge it."}</li>
                 <li><b>{"Hovercraft"}</b>{" can travel on land and water."}</li>
                 <li><b>{"Rams"}</b>{" are specially designed to ram other ships."}</li>
                 <li><b>{"Dredgers"}</b>{" have the ability to modify the land. New land
@@ -81,6 +100,24 @@ pub fn help_dialog() -> Html {
                 {" to help you evade detection."}
             </p>
 
+            <p>
+                {"Not every ship has a captain. Escorted "}<b>{"convoys"}</b>{" of tankers sail between ports, and "}
+                <b>{"pirates"}</b>{" prowl for weaker ships. Both drop extra loot when sunk."}
+            </p>
+
+            <p>
+                {"Every few minutes, a world event is announced in chat and marked on your map: a "}
+                <b>{"supply drop"}</b>{" of crates and coins, a "}<b>{"bounty"}</b>
+                {" on the top player, paid to whoever sinks them, or a "}<b>{"storm"}</b>
+                {" that slows ships on the surface and downs aircraft."}
+            </p>
+
+            <p>
+                {"Sheltered "}<b>{"ports"}</b>{" along the coast are marked on your map. Stop inside one, "}
+                {"lined up with the berth, to dock. Docked ships repair faster, can refit to another ship of "}
+                {"the same level, and are immune to damage until they fire or leave for a minute."}
+            </p>
+
             <p>
                 {"Here is a full list of ships: "}
                 <RouteLink<Mk48Route> route={Mk48Route::Ships}>{"Mk48.io Ships"}</RouteLink<Mk48Route>>
@@ -112,7 +149,9 @@ pub fn help_dialog() -> Html {
 
                 <li><b>{"Mines"}</b>{" are like depth charges but last much longer and are more damaging."}</li>
 
-                <li><b>{"Aircraft"}</b>{" fly towards your mouse cursor, and automatically deploy weapons of their own."}</li>
+                <li><b>{"Aircraft"}</b>{" fly towards your mouse cursor, and automatically deploy weapons of their own. Once out of weapons or low on fuel, they return to land and rearm."}</li>
+
+                <li><b>{"Decoys"}</b>{" confuse the enemy. Sonar decoys lure homing torpedoes, smoke screens block line of sight, and chaff jams radar and lures radar-guided missiles."}</li>
 
                 <li><b>{"Depositor"}</b>{" creates new land. We'll let you figure out if this can be used as a weapon."}</li>
             </ol>
@@ -124,6 +163,13 @@ pub fn help_dialog() -> Html {
                 {"Consuming crates speeds up reloading."}
             </p>
 
+            <p>
+                {"Most weapons also have limited "}
+                <b>{"ammunition"}</b>
+                {", shown in parentheses next to each weapon. "}
+                {"Stay near an oil platform, headquarters, or friendly tanker to resupply."}
+            </p>
+
             <h2>{"Sensors"}</h2>
 
             <p>{"All ships have some combination of sensors to identify other ships and obstacles:"}</p>
@@ -134,6 +180,11 @@ pub fn help_dialog() -> Html {
                 <li><b>{"Sonar"}</b>{" tracks underwater targets."}</li>
             </ol>
 
+            <p>
+                {"Visual and radar can't see through land, although taller ships and aircraft can see over low-lying terrain. "}
+                {"Sonar is unaffected."}
+            </p>
+
             <p>
                 {"There are two modes for sensors, "}<b>{"active"}</b>{" and "}<b>{"passive"}</b>
                 {". Passive mode listens for emissions (e.g. sound in the case of sonar) from other entities. "}
@@ -166,6 +217,14 @@ pub fn help_dialog() -> Html {
                 {"Submarines can travel beneath both types of ice sheet."}
             </p>
 
+            <h2>{"Spectating"}</h2>
+
+            <p>
+                {"While not sailing, click "}<b>{"Spectate"}</b>{" to follow other players on the leaderboard and see what they see. "}
+                {"Alternatively, switch to a "}<b>{"free camera"}</b>{" and move it with the movement keys. "}
+                {"Following players outside your team, or using the free camera, delays sailing until a while after you stop."}
+            </p>
+
         </Dialog>
     }
 }
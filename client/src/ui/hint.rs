@@ -8,7 +8,7 @@ use web_sys::HtmlDivElement;
 use yew::{html, use_effect_with_deps, use_node_ref, Html, Properties};
 use yew_frontend::translation::use_translation;
 
-#[derive(PartialEq, Properties)]
+#[derive(PartialEq, Eq, Properties)]
 pub struct HintProps {
     pub entity_type: EntityType,
 }
@@ -4,13 +4,13 @@ use yew::{html, Html, Properties};
 use yew_frontend::component::positioner::Position;
 use yew_frontend::translation::use_translation;
 
-#[derive(Copy, Clone, PartialEq, Properties)]
+#[derive(Copy, Clone, PartialEq, Eq, Properties)]
 pub struct InstructionsProps {
     pub position: Position,
     pub status: InstructionStatus,
 }
 
-#[derive(Copy, Clone, Default, PartialEq)]
+#[derive(Copy, Clone, Default, PartialEq, Eq)]
 pub struct InstructionStatus {
     pub touch: bool,
     pub basics: bool,
@@ -0,0 +1,61 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::ui::UiKill;
+use stylist::yew::styled_component;
+use yew::{html, html_nested, Html, Properties};
+
+#[derive(PartialEq, Properties)]
+pub struct KillFeedProps {
+    /// Newest first.
+    pub kills: Vec<UiKill>,
+}
+
+#[styled_component(KillFeed)]
+pub fn kill_feed(props: &KillFeedProps) -> Html {
+    let container_style = css!(
+        r#"
+        color: white;
+        font-size: 0.9rem;
+        margin-top: 0.5rem;
+        text-align: right;
+        user-select: none;
+        "#
+    );
+
+    let kill_style = css!(
+        r#"
+        background-color: #00000040;
+        border-radius: 0.25rem;
+        margin-bottom: 0.25rem;
+        padding: 0.1rem 0.4rem;
+        white-space: nowrap;
+        overflow: hidden;
+        text-overflow: ellipsis;
+        "#
+    );
+
+    let class_style = css!(
+        r#"
+        color: #ffffffa0;
+        "#
+    );
+
+    html! {
+        <div class={container_style}>
+            {props.kills.iter().map(|UiKill{kill, opacity}| {
+                let killer_class = kill.killer_entity_type.map(|e| format!(" ({})", e.data().label));
+                let weapon = kill.weapon.map_or("Collision", |e| e.data().label);
+                html_nested!{
+                    <div class={kill_style.clone()} style={format!("opacity: {};", opacity)}>
+                        <b>{kill.killer.as_str()}</b>
+                        <span class={class_style.clone()}>{killer_class.unwrap_or_default()}</span>
+                        {format!(" [{}] ", weapon)}
+                        <b>{kill.victim.as_str()}</b>
+                        <span class={class_style.clone()}>{format!(" ({})", kill.victim_entity_type.data().label)}</span>
+                    </div>
+                }
+            }).collect::<Html>()}
+        </div>
+    }
+}
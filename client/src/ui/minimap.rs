@@ -0,0 +1,248 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::minimap::{event_color, ping_color, Minimap as MinimapRenderer};
+use crate::settings::Mk48Settings;
+use crate::ui::UiMinimap;
+use crate::Mk48Game;
+use client_util::browser_storage::BrowserStorages;
+use common::angle::Angle;
+use common::protocol::WorldEvent;
+use glam::Vec2;
+use renderer::rgba_array_to_css;
+use std::f64::consts::PI;
+use stylist::yew::styled_component;
+use wasm_bindgen::{Clamped, JsCast};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, ImageData, MouseEvent};
+use yew::{html, use_effect_with_deps, use_node_ref, Html, NodeRef, Properties};
+use yew_frontend::frontend::use_gctw;
+
+#[derive(PartialEq, Properties)]
+pub struct MinimapProps {
+    pub minimap: UiMinimap,
+    /// Position of own boat.
+    pub position: Vec2,
+    /// Direction of own boat.
+    pub direction: Angle,
+}
+
+/// Resolution of the overlay (border and markers), which is drawn over the terrain image.
+const OVERLAY_SIZE: u32 = 256;
+
+#[styled_component(Minimap)]
+pub fn minimap(props: &MinimapProps) -> Html {
+    let container_style = css!(
+        r#"
+        background-color: #00000060;
+        border-radius: 0.5rem;
+        cursor: pointer;
+        height: 12rem;
+        overflow: hidden;
+        pointer-events: all;
+        position: relative;
+        width: 12rem;
+        "#
+    );
+
+    let canvas_style = css!(
+        r#"
+        height: 100%;
+        image-rendering: pixelated;
+        left: 0;
+        position: absolute;
+        top: 0;
+        width: 100%;
+        "#
+    );
+
+    let gctw = use_gctw::<Mk48Game>();
+    let terrain_ref = use_node_ref();
+    let overlay_ref = use_node_ref();
+
+    let zoom = gctw.settings_cache.minimap_zoom;
+    let on_click = gctw.change_settings_callback.reform(move |_: MouseEvent| {
+        Box::new(
+            move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                settings
+                    .set_minimap_zoom((zoom + 1) % MinimapRenderer::ZOOM_LEVELS, browser_storages);
+            },
+        )
+    });
+
+    {
+        let terrain_ref = terrain_ref.clone();
+        let overlay_ref = overlay_ref.clone();
+        use_effect_with_deps(
+            move |(minimap, position, direction)| {
+                let _ = draw_terrain(&terrain_ref, minimap);
+                let _ = draw_overlay(&overlay_ref, minimap, *position, *direction);
+                || {}
+            },
+            (props.minimap.clone(), props.position, props.direction),
+        );
+    }
+
+    let resolution = MinimapRenderer::RESOLUTION.to_string();
+    let overlay_size = OVERLAY_SIZE.to_string();
+
+    html! {
+        <div class={container_style} onclick={on_click} title="Click to zoom">
+            <canvas
+                ref={terrain_ref}
+                width={resolution.clone()}
+                height={resolution}
+                class={canvas_style.clone()}
+            />
+            <canvas
+                ref={overlay_ref}
+                width={overlay_size.clone()}
+                height={overlay_size}
+                class={canvas_style}
+            />
+        </div>
+    }
+}
+
+fn context_2d(node_ref: &NodeRef) -> Option<CanvasRenderingContext2d> {
+    node_ref
+        .cast::<HtmlCanvasElement>()?
+        .get_context("2d")
+        .ok()??
+        .dyn_into()
+        .ok()
+}
+
+fn draw_terrain(node_ref: &NodeRef, minimap: &UiMinimap) -> Option<()> {
+    let context = context_2d(node_ref)?;
+    let resolution = MinimapRenderer::RESOLUTION as f64;
+    context.clear_rect(0.0, 0.0, resolution, resolution);
+
+    // Terrain image may lag behind the center.
+    let offset = (minimap.terrain_center - minimap.center) / (2.0 * minimap.radius);
+    let image = ImageData::new_with_u8_clamped_array(
+        Clamped(&minimap.terrain[..]),
+        MinimapRenderer::RESOLUTION as u32,
+    )
+    .ok()?;
+    context
+        .put_image_data(
+            &image,
+            (offset.x as f64 * resolution).round(),
+            (-offset.y as f64 * resolution).round(),
+        )
+        .ok()
+}
+
+fn draw_overlay(
+    node_ref: &NodeRef,
+    minimap: &UiMinimap,
+    position: Vec2,
+    direction: Angle,
+) -> Option<()> {
+    let context = context_2d(node_ref)?;
+    let size = OVERLAY_SIZE as f64;
+    let scale = size / (2.0 * minimap.radius as f64);
+    let to_canvas = |pos: Vec2| -> (f64, f64) {
+        let relative = pos - minimap.center;
+        (
+            size * 0.5 + relative.x as f64 * scale,
+            size * 0.5 - relative.y as f64 * scale,
+        )
+    };
+
+    context.clear_rect(0.0, 0.0, size, size);
+
+    // World border.
+    let (x, y) = to_canvas(Vec2::ZERO);
+    context.begin_path();
+    context
+        .arc(x, y, minimap.world_radius as f64 * scale, 0.0, 2.0 * PI)
+        .ok()?;
+    context.set_line_width(2.0);
+    context.set_stroke_style(&"#ff4040".into());
+    context.stroke();
+
+    // Ports.
+    context.set_fill_style(&"#e0e0e0".into());
+    for port in &minimap.ports {
+        let (x, y) = to_canvas(port.position);
+        context.fill_rect(x - 2.5, y - 2.5, 5.0, 5.0);
+    }
+
+    // Team members.
+    context.set_fill_style(&"#40ff80".into());
+    for &teammate in &minimap.teammates {
+        let (x, y) = to_canvas(teammate);
+        context.begin_path();
+        context.arc(x, y, 4.0, 0.0, 2.0 * PI).ok()?;
+        context.fill();
+    }
+
+    // Autopilot route, starting from own boat.
+    if !minimap.route.is_empty() {
+        let (x, y) = to_canvas(position);
+        context.begin_path();
+        context.move_to(x, y);
+        for &waypoint in &minimap.route {
+            let (x, y) = to_canvas(waypoint);
+            context.line_to(x, y);
+        }
+        context.set_line_width(1.5);
+        context.set_stroke_style(&"#ffffffa0".into());
+        context.stroke();
+    }
+
+    // Team pings.
+    context.set_line_width(3.0);
+    for &(ping, kind) in &minimap.pings {
+        let [r, g, b] = ping_color(kind);
+        let (x, y) = to_canvas(ping);
+        context.set_stroke_style(&rgba_array_to_css([r, g, b, 255]).into());
+        context.begin_path();
+        context.arc(x, y, 7.0, 0.0, 2.0 * PI).ok()?;
+        context.stroke();
+    }
+
+    // World events, as diamonds (storms also show their extent).
+    for event in &minimap.events {
+        let [r, g, b] = event_color(event);
+        let (x, y) = to_canvas(event.position());
+        if let WorldEvent::Storm { radius, .. } = *event {
+            context.set_fill_style(&rgba_array_to_css([r, g, b, 64]).into());
+            context.begin_path();
+            context
+                .arc(x, y, (radius as f64 * scale).max(4.0), 0.0, 2.0 * PI)
+                .ok()?;
+            context.fill();
+        }
+        context.set_fill_style(&rgba_array_to_css([r, g, b, 255]).into());
+        context.begin_path();
+        context.move_to(x, y - 6.0);
+        context.line_to(x + 6.0, y);
+        context.line_to(x, y + 6.0);
+        context.line_to(x - 6.0, y);
+        context.close_path();
+        context.fill();
+    }
+
+    // Own boat, as an arrow.
+    let (x, y) = to_canvas(position);
+    let forward = direction.to_vec();
+    let forward = (forward.x as f64, -forward.y as f64);
+    let side = (-forward.1, forward.0);
+    context.set_fill_style(&"white".into());
+    context.begin_path();
+    context.move_to(x + forward.0 * 8.0, y + forward.1 * 8.0);
+    context.line_to(
+        x - forward.0 * 5.0 + side.0 * 5.0,
+        y - forward.1 * 5.0 + side.1 * 5.0,
+    );
+    context.line_to(
+        x - forward.0 * 5.0 - side.0 * 5.0,
+        y - forward.1 * 5.0 - side.1 * 5.0,
+    );
+    context.close_path();
+    context.fill();
+
+    Some(())
+}
@@ -0,0 +1,106 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::ui::{ReplayControl, UiEvent, UiReplay};
+use crate::Mk48Game;
+use stylist::yew::styled_component;
+use web_sys::{HtmlInputElement, InputEvent, MouseEvent};
+use yew::{classes, html, html_nested, Html, Properties, TargetCast};
+use yew_frontend::frontend::use_ui_event_callback;
+
+#[derive(PartialEq, Properties)]
+pub struct ReplayControlsProps {
+    pub replay: UiReplay,
+}
+
+/// Playback controls for the replay of the player's boat sinking.
+#[styled_component(ReplayControls)]
+pub fn replay_controls(props: &ReplayControlsProps) -> Html {
+    let container_style = css!(
+        r#"
+        align-items: center;
+        display: flex;
+        flex-direction: row;
+        gap: 0.5rem;
+        justify-content: center;
+        margin: 0.5rem auto;
+        pointer-events: all;
+        "#
+    );
+
+    let button_style = css!(
+        r#"
+        background-color: #00000040;
+        border: 1px solid #ffffff40;
+        border-radius: 0.25rem;
+        color: white;
+        cursor: pointer;
+        font-size: 1rem;
+        padding: 0.3rem 0.6rem;
+        white-space: nowrap;
+        "#
+    );
+
+    let selected_style = css!(
+        r#"
+        border-color: white;
+        "#
+    );
+
+    let ui_event_callback = use_ui_event_callback::<Mk48Game>();
+    let replay = props.replay;
+
+    if !replay.open {
+        let onclick =
+            ui_event_callback.reform(|_: MouseEvent| UiEvent::Replay(ReplayControl::Open));
+        return html! {
+            <div class={container_style}>
+                <button class={button_style} {onclick}>{"Watch Replay"}</button>
+            </div>
+        };
+    }
+
+    let on_pause = ui_event_callback
+        .reform(move |_: MouseEvent| UiEvent::Replay(ReplayControl::Paused(!replay.paused)));
+    let on_seek = ui_event_callback.reform(|event: InputEvent| {
+        let value = event
+            .target_unchecked_into::<HtmlInputElement>()
+            .value_as_number() as f32;
+        UiEvent::Replay(ReplayControl::Seek(value))
+    });
+    let on_close = ui_event_callback.reform(|_: MouseEvent| UiEvent::Replay(ReplayControl::Close));
+
+    let speeds = [0.25, 0.5, 1.0, 2.0]
+        .into_iter()
+        .map(|speed| {
+            let onclick = ui_event_callback
+                .reform(move |_: MouseEvent| UiEvent::Replay(ReplayControl::Speed(speed)));
+            let class = if speed == replay.speed {
+                classes!(button_style.clone(), selected_style.clone())
+            } else {
+                classes!(button_style.clone())
+            };
+            html_nested! {
+                <button {class} {onclick}>{format!("{}x", speed)}</button>
+            }
+        })
+        .collect::<Html>();
+
+    html! {
+        <div class={container_style}>
+            <button class={button_style.clone()} onclick={on_pause}>
+                {if replay.paused { "Play" } else { "Pause" }}
+            </button>
+            <input
+                type="range"
+                min="0"
+                max="1"
+                step="0.01"
+                value={replay.progress.to_string()}
+                oninput={on_seek}
+            />
+            {speeds}
+            <button class={button_style} onclick={on_close}>{"Close"}</button>
+        </div>
+    }
+}
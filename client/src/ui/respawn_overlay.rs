@@ -2,11 +2,13 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use crate::translation::Mk48Translation;
+use crate::ui::replay_controls::ReplayControls;
 use crate::ui::ship_menu::ShipMenu;
 use crate::ui::{UiEvent, UiStatusRespawning};
 use crate::Mk48Game;
+use common::ticks::to_damage;
 use stylist::yew::styled_component;
-use yew::{html, Html, Properties};
+use yew::{html, html_nested, Html, Properties};
 use yew_frontend::frontend::use_ui_event_callback;
 use yew_frontend::overlay::spawn::use_splash_screen;
 use yew_frontend::translation::use_translation;
@@ -49,17 +51,53 @@ pub fn respawn_overlay(props: &RespawnOverlayProps) -> Html {
         "#
     );
 
+    let combat_log_style = css!(
+        r#"
+        border-collapse: collapse;
+        color: white;
+        margin: 0.5rem auto;
+        user-select: none;
+
+        th, td {
+            padding: 0.1rem 0.75rem;
+        }
+        "#
+    );
+
     let t = use_translation();
     let (_paused, _transitioning, onanimationend) = use_splash_screen();
-    let onclick = use_ui_event_callback::<Mk48Game>().reform(UiEvent::Respawn);
+    let onclick = use_ui_event_callback::<Mk48Game>()
+        .reform(|(entity_type, loadout)| UiEvent::Respawn(entity_type, loadout));
+    let replay_open = props.status.replay.map_or(false, |replay| replay.open);
     html! {
         <div id="death" class={container_style} {onanimationend}>
             <h2 class={reason_style}>{t.death_reason(&props.status.death_reason)}</h2>
-            <ShipMenu
-                score={props.score}
-                {onclick}
-                closable={false}
-            />
+            if let Some(replay) = props.status.replay {
+                <ReplayControls {replay}/>
+            }
+            if !replay_open && !props.status.combat_log.is_empty() {
+                <table class={combat_log_style}>
+                    <tr>
+                        <th>{"Opponent"}</th>
+                        <th>{"Dealt"}</th>
+                        <th>{"Received"}</th>
+                    </tr>
+                    {props.status.combat_log.iter().map(|engagement| html_nested!{
+                        <tr>
+                            <td>{engagement.player.as_str()}</td>
+                            <td>{format!("{:.1}", to_damage(engagement.dealt))}</td>
+                            <td>{format!("{:.1}", to_damage(engagement.received))}</td>
+                        </tr>
+                    }).collect::<Html>()}
+                </table>
+            }
+            if !replay_open {
+                <ShipMenu
+                    score={props.score}
+                    {onclick}
+                    closable={false}
+                />
+            }
             <div id="banner_bottom" style="margin: 5rem auto;"></div>
         </div>
     }
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use crate::settings::{Mk48Settings, ShadowSetting};
-use crate::ui::UiEvent;
+use crate::ui::{Mk48Route, UiEvent};
 use crate::Mk48Game;
 use client_util::browser_storage::BrowserStorages;
 use client_util::setting::CommonSettings;
@@ -12,6 +12,7 @@ use std::str::FromStr;
 use stylist::yew::styled_component;
 use web_sys::{HtmlSelectElement, InputEvent};
 use yew::{html, html_nested, Html, TargetCast};
+use yew_frontend::component::route_link::RouteLink;
 use yew_frontend::dialog::dialog::Dialog;
 use yew_frontend::frontend::{use_core_state, use_ctw, use_gctw};
 use yew_frontend::translation::{use_translation, Translation};
@@ -125,6 +126,15 @@ pub fn settings_dialog() -> Html {
         )
     });
 
+    let minimap_shown = gctw.settings_cache.minimap_shown;
+    let on_toggle_minimap = gctw.change_settings_callback.reform(move |_| {
+        Box::new(
+            move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                settings.set_minimap_shown(!minimap_shown, browser_storages);
+            },
+        )
+    });
+
     let circle_hud = gctw.settings_cache.circle_hud;
     let on_toggle_circle_hud = gctw.change_settings_callback.reform(move |_| {
         Box::new(
@@ -134,6 +144,15 @@ pub fn settings_dialog() -> Html {
         )
     });
 
+    let fire_control = gctw.settings_cache.fire_control;
+    let on_toggle_fire_control = gctw.change_settings_callback.reform(move |_| {
+        Box::new(
+            move |settings: &mut Mk48Settings, browser_storages: &mut BrowserStorages| {
+                settings.set_fire_control(!fire_control, browser_storages);
+            },
+        )
+    });
+
     let high_contrast = ctw.setting_cache.high_contrast;
     let on_toggle_high_contrast = ctw.change_common_settings_callback.reform(move |_| {
         Box::new(
@@ -145,7 +164,7 @@ pub fn settings_dialog() -> Html {
 
     let antialias = ctw.setting_cache.antialias;
     let on_toggle_antialias = {
-        let graphics_callback = graphics_callback.clone();
+        let graphics_callback = graphics_callback;
         ctw.change_common_settings_callback.reform(move |_| {
             let graphics_callback = graphics_callback.clone();
             Box::new(
@@ -179,6 +198,16 @@ pub fn settings_dialog() -> Html {
                 {"Circle HUD"}
             </label>
 
+            <label class={label_style.clone()}>
+                <input type="checkbox" checked={fire_control} disabled={cinematic} oninput={on_toggle_fire_control}/>
+                {"Fire Control Assist"}
+            </label>
+
+            <label class={label_style.clone()}>
+                <input type="checkbox" checked={minimap_shown} oninput={on_toggle_minimap}/>
+                {"Minimap"}
+            </label>
+
             <label class={label_style.clone()}>
                 <input type="checkbox" checked={high_contrast} oninput={on_toggle_high_contrast}/>
                 {"High Contrast"}
@@ -194,6 +223,10 @@ pub fn settings_dialog() -> Html {
                 {"Radio"}
             </label>
 
+            <label class={label_style.clone()}>
+                <RouteLink<Mk48Route> route={Mk48Route::Controls}>{"Controls"}</RouteLink<Mk48Route>>
+            </label>
+
             <select
                 oninput={on_select_server_id}
                 class={select_style.clone()}
@@ -2,12 +2,15 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use crate::armament::{group_armaments, Group};
+use crate::key_bindings::{Action, KeyBindings};
 use crate::translation::Mk48Translation;
+use crate::ui::air_wing::AirWing;
 use crate::ui::sprite::Sprite;
 use crate::ui::{UiEvent, UiStatusPlaying};
 use crate::Mk48Game;
 use common::altitude::Altitude;
 use common::entity::{EntityData, EntitySubKind, EntityType};
+use common::protocol::Depth;
 use core_protocol::id::LanguageId;
 use stylist::yew::styled_component;
 use stylist::{css, StyleSource};
@@ -15,7 +18,7 @@ use web_sys::MouseEvent;
 use yew::{classes, html, html_nested, AttrValue, Callback, Html, Properties};
 use yew_frontend::component::positioner::Position;
 use yew_frontend::component::section::Section;
-use yew_frontend::frontend::use_ui_event_callback;
+use yew_frontend::frontend::{use_gctw, use_ui_event_callback};
 use yew_frontend::translation::use_translation;
 
 #[derive(Properties, PartialEq)]
@@ -66,7 +69,21 @@ pub fn ship_controls(props: &ShipControlsProps) -> Html {
         "#
     );
 
+    let battery_style = css!(
+        r#"
+        background-color: #00000040;
+        border: 1px solid #ffffff40;
+        color: white;
+        font-size: 0.9rem;
+        margin: 0.25em 0.5em;
+        position: relative;
+        text-align: center;
+        user-select: none;
+        "#
+    );
+
     let data: &'static EntityData = props.status.entity_type.data();
+    let armaments = data.loadout_armaments(props.status.loadout);
 
     let ui_event_callback = use_ui_event_callback::<Mk48Game>();
     let select_factory = {
@@ -79,32 +96,57 @@ pub fn ship_controls(props: &ShipControlsProps) -> Html {
     };
 
     let t = use_translation();
+    let gctw = use_gctw::<Mk48Game>();
+    let key_bindings = &gctw.settings_cache.key_bindings;
     let status = &props.status;
     html! {
-        <Section id="controls" name={data.label.clone()} position={props.position} style={props.style.clone()} closable={false}>
-            if status.entity_type.data().armaments.is_empty() {
+        <Section id="controls" name={data.label} position={props.position} style={props.style.clone()} closable={false}>
+            if armaments.is_empty() {
                 <small>{t.entity_kind_hint(status.entity_type.data().kind, status.entity_type.data().sub_kind)}</small>
             } else {
-                {group_armaments(&status.entity_type.data().armaments, &*status.armament_consumption).into_iter().map(|Group{entity_type, total, ready}| {
+                {group_armaments(armaments, &status.armament_consumption, &status.magazines).into_iter().map(|Group{entity_type, total, ready, rounds}| {
                     let onclick = select_factory.clone()(entity_type);
+                    let consumption = if let Some(rounds) = rounds {
+                        format!("{ready}/{total} ({rounds})")
+                    } else {
+                        format!("{ready}/{total}")
+                    };
                     html_nested!{
                         <div class={classes!(button_style.clone(), onclick.is_none().then(|| button_selected_style.clone()))} {onclick}>
                             <Sprite {entity_type} class={classes!((ready == 0).then(|| consumed_style.clone()))}/>
-                            <span class={consumption_style.clone()}>{format!("{ready}/{total}")}</span>
+                            <span class={consumption_style.clone()}>{consumption}</span>
                         </div>
                     }
                 }).collect::<Html>()}
             }
-            {surface_button(t, props.status.entity_type, props.status.submerge, &button_style, &button_selected_style, &ui_event_callback)}
-            {active_sensor_button(t, props.status.entity_type, props.status.active, props.status.altitude, &button_style, &button_selected_style, &ui_event_callback)}
+            {depth_buttons(t, props.status.entity_type, props.status.depth, key_bindings, &button_style, &button_selected_style, &ui_event_callback)}
+            if let Some(battery) = status.battery {
+                <div class={battery_style.clone()} title={t.ship_battery_hint()}>
+                    <div style={format!("background-color: {}; height: 100%; left: 0; position: absolute; top: 0; width: {:.1}%;", if battery < 0.25 { "#e74c3c80" } else { "#2ecc7180" }, battery * 100.0)}/>
+                    <span style="position: relative;">{format!("{} {:.0}%", t.ship_battery_label(), battery * 100.0)}</span>
+                </div>
+            }
+            if status.waypoint_unreachable {
+                <div class={battery_style.clone()}>{t.ship_waypoint_unreachable_label()}</div>
+            }
+            if let Some(immunity) = status.docked {
+                <div class={battery_style} title={t.ship_docked_hint()}>
+                    {format!("{} {:.0}s", t.ship_docked_label(), immunity.to_secs())}
+                </div>
+            }
+            {active_sensor_button(t, props.status.entity_type, props.status.active, props.status.altitude, key_bindings, &button_style, &button_selected_style, &ui_event_callback)}
+            if !status.air_wing.is_empty() {
+                <AirWing air_wing={status.air_wing.clone()}/>
+            }
         </Section>
     }
 }
 
-fn surface_button(
+fn depth_buttons(
     t: LanguageId,
     entity_type: EntityType,
-    submerge: bool,
+    depth: Depth,
+    key_bindings: &KeyBindings,
     button_style: &StyleSource,
     button_selected_style: &StyleSource,
     ui_event_callback: &Callback<UiEvent>,
@@ -112,21 +154,35 @@ fn surface_button(
     if entity_type.data().sub_kind != EntitySubKind::Submarine {
         Html::default()
     } else {
-        let onclick = ui_event_callback.reform(move |_: MouseEvent| UiEvent::Submerge(!submerge));
+        let title = t.ship_surface_hint(&key_bindings.hint(Action::Surface));
+        Depth::ALL
+            .into_iter()
+            .map(|d| {
+                let onclick = ui_event_callback.reform(move |_: MouseEvent| UiEvent::Depth(d));
+                let label = match d {
+                    Depth::Surface => t.ship_surface_label(),
+                    Depth::Periscope => t.ship_depth_periscope_label(),
+                    Depth::Shallow => t.ship_depth_shallow_label(),
+                    Depth::Deep => t.ship_depth_deep_label(),
+                };
 
-        html! {
-            <div class={classes!(button_style.clone(), (!submerge).then(|| button_selected_style.clone()))} {onclick} title={t.ship_surface_hint()}>
-                {t.ship_surface_label()}
-            </div>
-        }
+                html_nested! {
+                    <div class={classes!(button_style.clone(), (d == depth).then(|| button_selected_style.clone()))} {onclick} title={title.clone()}>
+                        {label}
+                    </div>
+                }
+            })
+            .collect()
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 fn active_sensor_button(
     t: LanguageId,
     entity_type: EntityType,
     active: bool,
     altitude: Altitude,
+    key_bindings: &KeyBindings,
     button_style: &StyleSource,
     button_selected_style: &StyleSource,
     ui_event_callback: &Callback<UiEvent>,
@@ -145,7 +201,7 @@ fn active_sensor_button(
             )
             .intersperse(" / ")
             .collect::<String>();
-        let title = t.sensor_active_hint(&sensors);
+        let title = t.sensor_active_hint(&sensors, &key_bindings.hint(Action::Active));
         let onclick = ui_event_callback.reform(move |_: MouseEvent| UiEvent::Active(!active));
 
         html! {
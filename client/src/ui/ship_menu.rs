@@ -1,6 +1,7 @@
 // SPDX-FileCopyrightText: 2021 Softbear, Inc.
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::translation::Mk48Translation;
 use crate::ui::sprite::Sprite;
 use common::entity::{EntitySubKind, EntityType};
 use common::util::score_to_level;
@@ -27,8 +28,16 @@ pub struct ShipMenuProps {
     pub style: Option<AttrValue>,
     /// If some, upgrading. Otherwise, spawning.
     pub entity: Option<(EntityType, Vec2)>,
+    /// If true (only while docked), may also refit to another ship of the current level, or
+    /// another loadout of the current ship.
+    #[prop_or(false)]
+    pub refit: bool,
+    /// Current loadout, if upgrading.
+    #[prop_or(0)]
+    pub loadout: u8,
     pub score: u32,
-    pub onclick: Callback<EntityType>,
+    /// Called with the chosen ship and loadout.
+    pub onclick: Callback<(EntityType, u8)>,
     #[prop_or(true)]
     pub open: bool,
     #[prop_or(true)]
@@ -70,9 +79,44 @@ pub fn ship_menu(props: &ShipMenuProps) -> Html {
     "#
     );
 
+    let loadouts_style = css!(
+        r#"
+        display: flex;
+        flex-wrap: wrap;
+        gap: 0.25rem;
+        justify-content: center;
+        margin-top: 0.25rem;
+    "#
+    );
+
+    let loadout_style = css!(
+        r#"
+        background-color: #00000040;
+        border: 1px solid #ffffff40;
+        border-radius: 0.25rem;
+        color: white;
+        cursor: pointer;
+        font-size: 0.8rem;
+        padding: 0.2rem 0.4rem;
+        white-space: nowrap;
+
+        :disabled {
+            cursor: default;
+            opacity: 0.6;
+        }
+    "#
+    );
+
+    let loadout_selected_style = css!(
+        r#"
+        border-color: white;
+    "#
+    );
+
     let entity_type = props.entity.map(|(entity_type, _)| entity_type);
+    let current = entity_type.map(|entity_type| (entity_type, props.loadout));
     let min_level = entity_type
-        .map(|entity_type| entity_type.data().level + 1)
+        .map(|entity_type| entity_type.data().level + !props.refit as u8)
         .unwrap_or(1);
     let max_level = score_to_level(props.score);
     let level = use_state_eq(|| max_level);
@@ -99,7 +143,7 @@ pub fn ship_menu(props: &ShipMenuProps) -> Html {
             ) {
                 Err(lock_icon)
             } else {
-                Ok(props.onclick.reform(move |_| entity_type))
+                Ok(props.onclick.reform(move |_| (entity_type, 0)))
             }
         };
 
@@ -120,7 +164,19 @@ pub fn ship_menu(props: &ShipMenuProps) -> Html {
         })
     };
 
-    let (id, name, ships) = if let Some(entity_type) = entity_type {
+    let (id, name, ships) = if let Some(entity_type) =
+        entity_type.filter(|entity_type| entity_type.data().level == *level)
+    {
+        (
+            "refit",
+            String::from(t.ship_refit_label()),
+            // The current ship may be refit with another loadout.
+            std::iter::once(entity_type)
+                .filter(|entity_type| !entity_type.data().loadouts.is_empty())
+                .chain(entity_type.refit_options(false))
+                .collect::<Vec<_>>(),
+        )
+    } else if let Some(entity_type) = entity_type {
         (
             "upgrade",
             t.upgrade_to_level_label(*level as u32),
@@ -154,20 +210,42 @@ pub fn ship_menu(props: &ShipMenuProps) -> Html {
                     let mut onclick: Option<Callback<MouseEvent>> = None;
                     let mut icon_title: Option<(IconId, &'static str)> = None;
                     match select_factory(entity_type) {
-                        Ok(s) => onclick = Some(s),
+                        Ok(s) => onclick = Some(s).filter(|_| current != Some((entity_type, 0))),
                         Err(it) => icon_title = Some(it),
                     };
+                    let locked = icon_title.is_some();
+                    let loadouts = entity_type.data().loadouts;
 
-                    html_nested!{
-                        <Sprite
-                            {entity_type}
-                            {onclick}
-                            image_class={classes!(icon_title.is_some().then(|| greyed_out_style.clone()))}
-                            >
-                            if let Some((icon_id, title)) = icon_title {
-                                <Icon {icon_id} {title} onclick={attempt_to_unlock_factory(entity_type)}/>
+                    html!{
+                        <div>
+                            <Sprite
+                                {entity_type}
+                                {onclick}
+                                image_class={classes!(locked.then(|| greyed_out_style.clone()))}
+                                >
+                                if let Some((icon_id, title)) = icon_title {
+                                    <Icon {icon_id} {title} onclick={attempt_to_unlock_factory(entity_type)}/>
+                                }
+                            </Sprite>
+                            if !loadouts.is_empty() {
+                                <div class={loadouts_style.clone()}>
+                                    {loadouts.iter().enumerate().map(|(i, loadout)| {
+                                        let i = i as u8;
+                                        let selected = current == Some((entity_type, i));
+                                        let onclick = (!locked && !selected).then(|| props.onclick.reform(move |_| (entity_type, i)));
+                                        html_nested!{
+                                            <button
+                                                class={classes!(loadout_style.clone(), selected.then(|| loadout_selected_style.clone()))}
+                                                disabled={locked}
+                                                {onclick}
+                                            >
+                                                {loadout.label}
+                                            </button>
+                                        }
+                                    }).collect::<Html>()}
+                                </div>
                             }
-                        </Sprite>
+                        </div>
                     }
                 }).collect::<Html>()}
             </div>
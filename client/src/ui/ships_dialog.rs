@@ -56,7 +56,7 @@ fn entity_card(
             <tr>
                 <td>
                     <h3>
-                        {data.label.clone()}
+                        {data.label}
                         if let Some(count) = count {
                             {format!(" × {count}")}
                         }
@@ -67,7 +67,7 @@ fn entity_card(
                         }
                         {t.entity_kind_name(data.kind, data.sub_kind)}
                     </i>
-                    if let Some(href) = data.link.clone() {
+                    if let Some(href) = data.link {
                         {" ("} <Link {href}>{"Learn more"}</Link>{")"}
                     }
                 </td>
@@ -117,7 +117,7 @@ fn entity_card(
                     <Sprite {entity_type}/>
                 </td>
             </tr>
-            {group_armaments(&data.armaments, &[]).into_iter().map(|Group{entity_type, total, ..}| html_nested!{
+            {group_armaments(data.armaments, &[], &[]).into_iter().map(|Group{entity_type, total, ..}| html_nested!{
                 <tr>
                     <td colspan="2">
                         {entity_card(t, table_style, entity_type, Some(total))}
@@ -0,0 +1,108 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::ui::{UiEvent, UiStatusSpectating};
+use crate::Mk48Game;
+use common::protocol::SpectateTarget;
+use stylist::yew::styled_component;
+use web_sys::MouseEvent;
+use yew::{html, Html, Properties};
+use yew_frontend::frontend::use_ui_event_callback;
+
+#[derive(PartialEq, Eq, Properties)]
+pub struct SpectateOverlayProps {
+    pub status: UiStatusSpectating,
+}
+
+/// Shows who is being spectated, with buttons to switch between players or to a free camera, and
+/// how long spawning is delayed for having watched other teams.
+#[styled_component(SpectateOverlay)]
+pub fn spectate_overlay(props: &SpectateOverlayProps) -> Html {
+    let container_style = css!(
+        r#"
+        align-items: center;
+        color: white;
+        display: flex;
+        flex-direction: column;
+        gap: 0.5rem;
+        user-select: none;
+        "#
+    );
+
+    let buttons_style = css!(
+        r#"
+        display: flex;
+        flex-direction: row;
+        gap: 0.25rem;
+        "#
+    );
+
+    let button_style = css!(
+        r#"
+        background-color: #00000040;
+        border: 1px solid #ffffff40;
+        border-radius: 0.25rem;
+        color: white;
+        cursor: pointer;
+        font-size: 1rem;
+        padding: 0.3rem 0.6rem;
+        pointer-events: all;
+        white-space: nowrap;
+        "#
+    );
+
+    let ui_event_callback = use_ui_event_callback::<Mk48Game>();
+    let on_previous = ui_event_callback.reform(|_: MouseEvent| UiEvent::SpectateCycle(false));
+    let on_next = ui_event_callback.reform(|_: MouseEvent| UiEvent::SpectateCycle(true));
+    let on_free = ui_event_callback.reform(|_: MouseEvent| UiEvent::SpectateFree);
+    let on_stop = ui_event_callback.reform(|_: MouseEvent| UiEvent::SpectateStop);
+
+    let status = &props.status;
+    let title = if !status.active {
+        format!("Ready to sail in {}s", status.spawn_delay)
+    } else if let Some(alias) = status.following {
+        format!("Spectating {}", alias.as_str())
+    } else {
+        String::from("Free Camera")
+    };
+
+    html! {
+        <div class={container_style}>
+            <h2 style="margin: 0;">{title}</h2>
+            <div class={buttons_style}>
+                <button class={button_style.clone()} onclick={on_previous}>{"Previous"}</button>
+                <button class={button_style.clone()} onclick={on_next}>{"Next"}</button>
+                <button class={button_style.clone()} onclick={on_free}>{"Free Camera"}</button>
+                if status.active {
+                    <button class={button_style} onclick={on_stop}>{"Stop"}</button>
+                }
+            </div>
+            if status.active && status.spawn_delay > 0 {
+                <small>{format!("Watching other teams delays sailing by {}s.", SpectateTarget::SPAWN_DELAY.to_whole_secs())}</small>
+            }
+        </div>
+    }
+}
+
+/// Starts spectating the first player on the liveboard.
+#[styled_component(SpectateButton)]
+pub fn spectate_button() -> Html {
+    let button_style = css!(
+        r#"
+        background-color: #00000040;
+        border: 1px solid #ffffff40;
+        border-radius: 0.25rem;
+        color: white;
+        cursor: pointer;
+        font-size: 1rem;
+        padding: 0.3rem 0.6rem;
+        pointer-events: all;
+        white-space: nowrap;
+        "#
+    );
+    let onclick =
+        use_ui_event_callback::<Mk48Game>().reform(|_: MouseEvent| UiEvent::SpectateCycle(true));
+    html! {
+        <button class={button_style} {onclick}>{"Spectate"}</button>
+    }
+}
@@ -57,7 +57,7 @@ pub fn sprite(props: &SpriteProps) -> Html {
     let sprite = SPRITE_SHEET
         .sprites
         .get(props.entity_type.as_str())
-        .expect(&format!("should have sprite for {:?}", props.entity_type));
+        .unwrap_or_else(|| panic!("should have sprite for {:?}", props.entity_type));
     let title = props.title.clone().unwrap_or_else(|| {
         format!(
             "{} ({})",
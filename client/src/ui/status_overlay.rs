@@ -30,6 +30,7 @@ pub fn status_overlay(props: &StatusProps) -> Html {
         0.0..1.0,
         true,
     );
+    let cardinal = format!("[{}]", status.direction.to_cardinal());
     html! {
         <>
             <h2 style="margin-bottom: 0.25rem; font-family: monospace, sans-serif;">
@@ -37,7 +38,7 @@ pub fn status_overlay(props: &StatusProps) -> Html {
                 {" "}
                 {format!("{:\u{00A0}>4.1}kn", status.velocity.to_knots())}
                 {" "}
-                {format!("{:\u{00A0}>3}°\u{00A0}{:\u{00A0}<4}", status.direction.to_bearing(), format!("[{}]", status.direction.to_cardinal()))}
+                {format!("{:\u{00A0}>3}°\u{00A0}{:\u{00A0}<4}", status.direction.to_bearing(), cardinal)}
                 {" "}
                 {fmt_position(status.position)}
                 if let Some(fps) = props.fps {
@@ -18,12 +18,15 @@ pub struct UpgradeOverlayProps {
 
 #[function_component(UpgradeOverlay)]
 pub fn upgrade_overlay(props: &UpgradeOverlayProps) -> Html {
-    let onclick = use_ui_event_callback::<Mk48Game>().reform(UiEvent::Upgrade);
+    let onclick = use_ui_event_callback::<Mk48Game>()
+        .reform(|(entity_type, loadout)| UiEvent::Upgrade(entity_type, loadout));
     html! {
         <ShipMenu
             entity={Some((props.status.entity_type, props.status.position))}
+            refit={props.status.docked.is_some()}
+            loadout={props.status.loadout}
             score={props.score}
-            position={props.position.clone()}
+            position={props.position}
             {onclick}
         >
             <Instructions position={props.position} status={props.status.instruction_status}/>
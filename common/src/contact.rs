@@ -24,6 +24,9 @@ pub type ReloadsStorage = u32;
 pub trait ContactTrait {
     fn altitude(&self) -> Altitude;
 
+    /// Fraction of battery remaining, from 0 to 1, if a submarine whose reloads are known.
+    fn battery(&self) -> Option<f32>;
+
     fn damage(&self) -> Ticks;
 
     fn entity_type(&self) -> Option<EntityType>;
@@ -32,6 +35,9 @@ pub trait ContactTrait {
 
     fn id(&self) -> EntityId;
 
+    /// Index of the boat's loadout (see `EntityData::loadouts`), or zero if not a boat.
+    fn loadout(&self) -> u8;
+
     fn player_id(&self) -> Option<PlayerId>;
 
     fn reloads(&self) -> &BitSlice<ReloadsStorage>;
@@ -52,20 +58,35 @@ pub trait ContactTrait {
             .map_or(false, |t| t.data().kind == EntityKind::Boat)
     }
 
+    #[inline]
+    fn is_submarine(&self) -> bool {
+        self.entity_type()
+            .map_or(false, |t| t.data().sub_kind == EntitySubKind::Submarine)
+    }
+
     #[inline]
     fn data(&self) -> &'static EntityData {
         self.entity_type().unwrap().data()
     }
+
+    /// Armaments of the contact's loadout, indexed like `EntityData::armaments`.
+    #[inline]
+    fn armaments(&self) -> &'static [Armament] {
+        self.data().loadout_armaments(self.loadout())
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct Contact {
     transform: Transform,
     altitude: Altitude,
+    /// Quantized fraction of battery remaining.
+    battery: Option<u8>,
     guidance: Guidance,
     damage: Ticks,
     entity_type: Option<EntityType>,
     id: EntityId,
+    loadout: u8,
     player_id: Option<PlayerId>,
     reloads: Option<BitArray<ReloadsStorage>>,
     turrets: Option<Arc<[Angle]>>,
@@ -75,10 +96,12 @@ impl Default for Contact {
     fn default() -> Self {
         Self {
             altitude: Altitude::default(),
+            battery: None,
             damage: Ticks::default(),
             entity_type: None,
             guidance: Guidance::default(),
             id: EntityId::new(u32::MAX).unwrap(),
+            loadout: 0,
             player_id: None,
             reloads: None,
             transform: Transform::default(),
@@ -92,10 +115,12 @@ impl Contact {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         altitude: Altitude,
+        battery: Option<f32>,
         damage: Ticks,
         entity_type: Option<EntityType>,
         guidance: Guidance,
         id: EntityId,
+        loadout: u8,
         player_id: Option<PlayerId>,
         reloads: Option<BitArray<ReloadsStorage>>,
         transform: Transform,
@@ -103,10 +128,12 @@ impl Contact {
     ) -> Self {
         Self {
             altitude,
+            battery: battery.map(|b| (b.clamp(0.0, 1.0) * u8::MAX as f32) as u8),
             damage,
             entity_type,
             guidance,
             id,
+            loadout,
             player_id,
             reloads,
             transform,
@@ -162,8 +189,10 @@ impl Contact {
 
         self.altitude = self.altitude.lerp(model.altitude, lerp);
         self.damage = model.damage;
+        self.loadout = model.loadout;
         self.player_id = model.player_id;
         self.reloads = model.reloads;
+        self.battery = model.battery;
         if interpolate_guidance {
             self.guidance = model.guidance;
         }
@@ -185,7 +214,7 @@ impl Contact {
         {
             let turrets = make_mut_slice(turrets);
             let data: &'static EntityData = self.entity_type.unwrap().data();
-            let turret_data = &*data.turrets;
+            let turret_data = data.turrets;
             for ((v, m), t) in turrets
                 .iter_mut()
                 .zip(model_turrets.iter())
@@ -205,6 +234,12 @@ impl Contact {
         }
     }
 
+    /// Whether the contact is a boat with a choice of loadouts, in which case its loadout is sent.
+    fn has_loadouts(&self) -> bool {
+        self.entity_type
+            .map_or(false, |t| !t.data().loadouts.is_empty())
+    }
+
     /// Applies a control message to a contact (can be used to predict its outcome).
     pub fn predict_guidance(&mut self, guidance: &Guidance) {
         self.guidance = *guidance;
@@ -225,6 +260,11 @@ impl ContactTrait for Contact {
         self.altitude
     }
 
+    #[inline]
+    fn battery(&self) -> Option<f32> {
+        self.battery.map(|b| b as f32 * (1.0 / u8::MAX as f32))
+    }
+
     #[inline]
     fn damage(&self) -> Ticks {
         self.damage
@@ -245,6 +285,11 @@ impl ContactTrait for Contact {
         self.id
     }
 
+    #[inline]
+    fn loadout(&self) -> u8 {
+        self.loadout
+    }
+
     #[inline]
     fn player_id(&self) -> Option<PlayerId> {
         self.player_id
@@ -335,8 +380,10 @@ impl ContactHeader {
         header
     }
 
-    fn tuple_len(&self) -> usize {
-        12 - self.as_bits().count_zeros() as usize
+    /// Battery is not in the header, since it is only known for submarines with known reloads.
+    /// Neither is loadout, since it is only sent for boats that have alternative loadouts.
+    fn tuple_len(&self, has_battery: bool, has_loadout: bool) -> usize {
+        12 - self.as_bits().count_zeros() as usize + has_battery as usize + has_loadout as usize
     }
 }
 
@@ -365,6 +412,22 @@ impl<'a> ContactSerializer<'a> {
         // Assert that, if reloads are known, so is entity type.
         debug_assert!(!(c.reloads.is_some() && c.entity_type.is_none()), "{:?}", c);
 
+        // Assert that battery is known if and only if a submarine's reloads are known.
+        debug_assert_eq!(
+            c.battery.is_some(),
+            c.reloads.is_some() && c.is_submarine(),
+            "{:?}",
+            c
+        );
+
+        // Assert that loadout is valid.
+        debug_assert!(
+            c.entity_type
+                .map_or(c.loadout == 0, |t| t.data().is_valid_loadout(c.loadout)),
+            "{:?}",
+            c
+        );
+
         let s = Self {
             c,
             h: ContactHeader {
@@ -392,14 +455,17 @@ impl<'a> Serialize for ContactSerializer<'a> {
     where
         S: Serializer,
     {
-        let mut tup = serializer.serialize_tuple(self.h.tuple_len())?;
+        let mut tup = serializer.serialize_tuple(
+            self.h
+                .tuple_len(self.c.battery.is_some(), self.c.has_loadouts()),
+        )?;
 
         // 3 required elements.
         tup.serialize_element(&self.c.id)?;
         tup.serialize_element(&self.c.transform.position)?;
         tup.serialize_element(&self.c.transform.direction)?;
 
-        // 8 optional elements.
+        // 8 optional elements (and battery, after reloads).
         if self.h.has_vel {
             tup.serialize_element(&self.c.transform.velocity)?;
         }
@@ -430,6 +496,9 @@ impl<'a> Serialize for ContactSerializer<'a> {
             } else {
                 tup.serialize_element(&ByteSerializer::new(reloads))?;
             }
+            if self.c.is_submarine() {
+                tup.serialize_element(&self.c.battery.unwrap())?;
+            }
         }
 
         // 1 option or unit element.
@@ -444,6 +513,11 @@ impl<'a> Serialize for ContactSerializer<'a> {
             tup.serialize_element(&())?;
         }
 
+        // Loadout, if there is a choice of them.
+        if self.c.has_loadouts() {
+            tup.serialize_element(&self.c.loadout)?;
+        }
+
         tup.end()
     }
 }
@@ -542,7 +616,9 @@ impl<'de, 'a> DeserializeSeed<'de> for ContactDeserializer<'a> {
     where
         D: Deserializer<'de>,
     {
-        deserializer.deserialize_tuple(self.h.tuple_len(), self)
+        // Entity type isn't known yet, so assume battery if reloads are known, and loadout if
+        // type is known (upper bound).
+        deserializer.deserialize_tuple(self.h.tuple_len(self.h.has_reloads, self.h.has_type), self)
     }
 }
 
@@ -603,6 +679,9 @@ impl<'de, 'a> Visitor<'de> for ContactDeserializer<'a> {
                     .unwrap();
                 self.c.reloads = Some(BitArray::from(ReloadsStorage::from_le_bytes(bytes)));
             }
+            if self.c.is_submarine() {
+                self.c.battery = Some(seq.next_element()?.unwrap());
+            }
         }
 
         // 1 option or unit element.
@@ -622,6 +701,11 @@ impl<'de, 'a> Visitor<'de> for ContactDeserializer<'a> {
             let _: () = seq.next_element()?.unwrap();
         }
 
+        // Must be after type is assigned.
+        if self.c.has_loadouts() {
+            self.c.loadout = seq.next_element()?.unwrap();
+        }
+
         Ok(())
     }
 }
@@ -21,6 +21,10 @@ pub enum DeathReason {
     Obstacle(EntityType),
     Ram(PlayerAlias),
     Weapon(PlayerAlias, EntityType),
+    // Killed by an admin.
+    Admin,
+    // Submarine hull crushed by water pressure.
+    Crush,
     // Allows code to convey a reason for killing an entity that is not necessarily a player's boat.
     // In release mode, Unknown is used instead.
     #[cfg(debug_assertions)]
@@ -45,6 +49,8 @@ impl DeathReason {
             }
             Self::Ram(_) => true,
             Self::Weapon(_, _) => true,
+            Self::Admin => false,
+            Self::Crush => false,
             #[cfg(debug_assertions)]
             Self::Debug(_) => false,
         }
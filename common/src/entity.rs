@@ -8,6 +8,7 @@ mod armament;
 mod data;
 mod exhaust;
 mod kind;
+mod loadout;
 mod sensor;
 mod sub_kind;
 mod turret;
@@ -18,6 +19,7 @@ pub use armament::Armament;
 pub use data::EntityData;
 pub use exhaust::Exhaust;
 pub use kind::EntityKind;
+pub use loadout::Loadout;
 pub use sensor::{Sensor, Sensors};
 pub use sub_kind::EntitySubKind;
 pub use turret::Turret;
@@ -1,6 +1,6 @@
 use crate::altitude::Altitude;
 use crate::entity::{
-    Armament, EntityData, EntityKind, EntitySubKind, Exhaust, Sensor, Sensors, Turret,
+    Armament, EntityData, EntityKind, EntitySubKind, Exhaust, Loadout, Sensor, Sensors, Turret,
 };
 use crate::ticks::Ticks;
 use crate::util::{level_to_score, natural_death_coins};
@@ -44,6 +44,17 @@ impl EntityType {
             && (bot || !upgrade_data.npc)
     }
 
+    /// can_refit_to returns true if a boat may be exchanged for another of the same level (while
+    /// docked at a port).
+    pub fn can_refit_to(self, refit: Self, bot: bool) -> bool {
+        let data = self.data();
+        let refit_data = refit.data();
+        refit != self
+            && refit_data.level == data.level
+            && refit_data.kind == data.kind
+            && (bot || !refit_data.npc)
+    }
+
     /// iter returns an iterator that visits all possible entity types and allows a random choice to
     /// be made.
     pub fn iter() -> impl Iterator<Item = Self> + IteratorRandom {
@@ -75,8 +86,20 @@ impl EntityType {
         .flatten()
     }
 
-    /// iterates all loot types entity should drop. Takes score before death.
-    pub fn loot(self, score: u32, score_to_coins: bool) -> impl Iterator<Item = Self> + 'static {
+    /// refit_options returns an iterator that visits all entity types that may be refit to.
+    #[inline]
+    pub fn refit_options(self, bot: bool) -> impl Iterator<Item = Self> + IteratorRandom {
+        Self::iter().filter(move |t| self.can_refit_to(*t, bot))
+    }
+
+    /// iterates all loot types entity should drop. Takes score before death. NPC boats (convoys
+    /// and pirates not controlled by players) drop twice as much, to make hunting them worthwhile.
+    pub fn loot(
+        self,
+        score: u32,
+        score_to_coins: bool,
+        npc: bool,
+    ) -> impl Iterator<Item = Self> + 'static {
         let data: &EntityData = self.data();
 
         debug_assert_eq!(data.kind, EntityKind::Boat);
@@ -90,7 +113,10 @@ impl EntityType {
         let mut rng = thread_rng();
 
         // Loot is based on the length of the boat.
-        let loot_amount = (data.length * 0.25 * (rng.gen::<f32>() * 0.1 + 0.9)) as u32;
+        let mut loot_amount = (data.length * 0.25 * (rng.gen::<f32>() * 0.1 + 0.9)) as u32;
+        if npc {
+            loot_amount *= 2;
+        }
 
         let mut loot_table = ArrayVec::<Self, 4>::new();
 
@@ -254,9 +280,25 @@ pub enum EntityType {
     #[size(length = 113.3, width = 20.137, draft = 8.14, mast = 8.81)]
     #[props(speed = 18.00556, depth = 480)]
     #[sensors(sonar, visual)]
-    #[armament(Set65, forward = 50.5, side = 1.5, angle = 0, count = 2, symmetrical)]
-    #[armament(Set65, forward = 51, side = 0.6, angle = 0, count = 2, symmetrical)]
-    #[armament(Igla, forward = 4.86495, count = 2, vertical)]
+    #[armament(
+        Set65,
+        forward = 50.5,
+        side = 1.5,
+        angle = 0,
+        count = 2,
+        symmetrical,
+        magazine = 8
+    )]
+    #[armament(
+        Set65,
+        forward = 51,
+        side = 0.6,
+        angle = 0,
+        count = 2,
+        symmetrical,
+        magazine = 8
+    )]
+    #[armament(Igla, forward = 4.86495, count = 2, vertical, magazine = 12)]
     #[armament(Brosok, forward = 52, side = 0.5, angle = 0, symmetrical)]
     Akula,
     #[info(
@@ -274,9 +316,10 @@ pub enum EntityType {
         angle = 0,
         turret = 0,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
-    #[armament(Mark54, forward = 0.25, angle = 0, turret = 0, external)]
+    #[armament(Mark54, forward = 0.25, angle = 0, turret = 0, external, magazine = 8)]
     #[armament(
         Mark54,
         forward = 0.25,
@@ -284,15 +327,28 @@ pub enum EntityType {
         angle = 0,
         turret = 1,
         symmetrical,
-        external
+        external,
+        magazine = 8
+    )]
+    #[armament(Mark54, forward = 0.25, angle = 0, turret = 1, external, magazine = 8)]
+    #[armament(Harpoon, forward = -10.25, side = 5.5, angle = 90, symmetrical, external, magazine = 6)]
+    #[armament(Harpoon, forward = -11, side = 5.5, angle = 90, symmetrical, external, magazine = 6)]
+    #[armament(Harpoon, forward = -10.25, side = 5.5, angle = 90, symmetrical, external, magazine = 6)]
+    #[armament(Harpoon, forward = -11, side = 5.5, angle = 90, symmetrical, external, magazine = 6)]
+    #[armament(
+        Essm,
+        forward = 39.7,
+        side = 1.5,
+        count = 2,
+        symmetrical,
+        vertical,
+        magazine = 12
     )]
-    #[armament(Mark54, forward = 0.25, angle = 0, turret = 1, external)]
-    #[armament(Harpoon, forward = -10.25, side = 5.5, angle = 90, symmetrical, external)]
-    #[armament(Harpoon, forward = -11, side = 5.5, angle = 90, symmetrical, external)]
-    #[armament(Harpoon, forward = -10.25, side = 5.5, angle = 90, symmetrical, external)]
-    #[armament(Harpoon, forward = -11, side = 5.5, angle = 90, symmetrical, external)]
-    #[armament(Essm, forward = 39.7, side = 1.5, count = 2, symmetrical, vertical)]
     #[armament(Seahawk, forward = -62, external)]
+    #[armament(Srboc, forward = 15.4, side = 7, angle = 90, symmetrical, hidden)]
+    #[loadout(label = "Anti-Ship")]
+    #[loadout(label = "Anti-Air", replace(Harpoon, Essm, magazine = 12))]
+    #[loadout(label = "Anti-Submarine", replace(Harpoon, Asroc, magazine = 4))]
     #[turret(forward = -15.25, side = 9.4, medium, azimuth_br = 180)]
     #[turret(forward = -15.25, side = -9.4, medium, azimuth_bl = 180)]
     #[turret(Mark12, forward = 51, fast, azimuth_b = 20)]
@@ -322,8 +378,8 @@ pub enum EntityType {
     #[size(length = 75, width = 11.133, draft = 2.5)]
     #[props(speed = 13.34, stealth = 0.5)]
     #[sensors(radar, sonar, visual)]
-    #[armament(Kalibr, forward = -3, side = 0.32, symmetrical, vertical)]
-    #[armament(Kalibr, forward = -3.8, side = 0.32, symmetrical, vertical)]
+    #[armament(Kalibr, forward = -3, side = 0.32, symmetrical, vertical, magazine = 6)]
+    #[armament(Kalibr, forward = -3.8, side = 0.32, symmetrical, vertical, magazine = 6)]
     #[turret(A190, forward = 20.4954, medium, azimuth_b = 40)]
     #[turret(RatepKomar, forward = 15.6236, fast, azimuth_b = 60)]
     #[turret(RatepKomar, forward = -17.8952, angle = 180, fast, azimuth_b = 40)]
@@ -363,8 +419,22 @@ pub enum EntityType {
     #[size(length = 160.9, width = 25.1406, draft = 9)]
     #[props(speed = 10.8)]
     #[sensors(visual)]
-    #[armament(Mark18, forward = 45.1688, side = 7.4, angle = 90, symmetrical)]
-    #[armament(Mark18, forward = 44.5688, side = 7.5, angle = 90, symmetrical)]
+    #[armament(
+        Mark18,
+        forward = 45.1688,
+        side = 7.4,
+        angle = 90,
+        symmetrical,
+        magazine = 8
+    )]
+    #[armament(
+        Mark18,
+        forward = 44.5688,
+        side = 7.5,
+        angle = 90,
+        symmetrical,
+        magazine = 8
+    )]
     #[turret(MarkBViii, forward = 37.5478, slow, azimuth_b = 50)]
     #[turret(
         MarkBViii,
@@ -434,9 +504,25 @@ pub enum EntityType {
     #[size(length = 35, width = 6.35, draft = 1.45)]
     #[props(speed = 15.9477)]
     #[sensors(visual)]
-    #[armament(Mark18, forward = -7, side = 2.3, angle = 7.5, symmetrical, external)]
-    #[armament(Mark9, forward = 4.5, side = 2.5, angle = 184, symmetrical, external)]
-    #[armament(Mark9, forward = 5, side = 2.55, angle = 184, symmetrical, external)]
+    #[armament(Mark18, forward = -7, side = 2.3, angle = 7.5, symmetrical, external, magazine = 8)]
+    #[armament(
+        Mark9,
+        forward = 4.5,
+        side = 2.5,
+        angle = 184,
+        symmetrical,
+        external,
+        magazine = 8
+    )]
+    #[armament(
+        Mark9,
+        forward = 5,
+        side = 2.55,
+        angle = 184,
+        symmetrical,
+        external,
+        magazine = 8
+    )]
     #[turret(_6Pounder, forward = 8, fast)]
     #[turret(_6Pounder, forward = -11.5, angle = 180, fast)]
     #[exhaust(forward = 0)]
@@ -456,7 +542,8 @@ pub enum EntityType {
         angle = 0,
         turret = 0,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
     #[armament(
         Mark18,
@@ -465,9 +552,10 @@ pub enum EntityType {
         angle = 0,
         turret = 0,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
-    #[armament(Mark18, forward = 0.25, angle = 0, turret = 0, external)]
+    #[armament(Mark18, forward = 0.25, angle = 0, turret = 0, external, magazine = 8)]
     #[armament(
         Mark18,
         forward = 0.25,
@@ -475,7 +563,8 @@ pub enum EntityType {
         angle = 0,
         turret = 1,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
     #[armament(
         Mark18,
@@ -484,13 +573,15 @@ pub enum EntityType {
         angle = 0,
         turret = 1,
         symmetrical,
-        external
-    )]
-    #[armament(Mark18, forward = 0.25, angle = 0, turret = 1, external)]
-    #[armament(Mark9, forward = -55, angle = 180, external)]
-    #[armament(Mark9, forward = -55.5, angle = 180, external)]
-    #[armament(Mark9, forward = -56, angle = 180, external)]
-    #[armament(Mark9, forward = -56.5, angle = 180, external)]
+        external,
+        magazine = 8
+    )]
+    #[armament(Mark18, forward = 0.25, angle = 0, turret = 1, external, magazine = 8)]
+    #[armament(Mark9, forward = -55, angle = 180, external, magazine = 8)]
+    #[armament(Mark9, forward = -55.5, angle = 180, external, magazine = 8)]
+    #[armament(Mark9, forward = -56, angle = 180, external, magazine = 8)]
+    #[armament(Mark9, forward = -56.5, angle = 180, external, magazine = 8)]
+    #[armament(SmokeFloat, forward = -51.66, angle = 180, hidden)]
     #[turret(forward = 2.75, medium, azimuth = 45)]
     #[turret(forward = -13, medium, azimuth = 45)]
     #[turret(Mark12, forward = 37.75, medium, azimuth_b = 20)]
@@ -515,9 +606,10 @@ pub enum EntityType {
         angle = 0,
         turret = 0,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
-    #[armament(Mark18, forward = 0.25, angle = 0, turret = 0, external)]
+    #[armament(Mark18, forward = 0.25, angle = 0, turret = 0, external, magazine = 8)]
     #[armament(
         Mark18,
         forward = 0.25,
@@ -525,10 +617,12 @@ pub enum EntityType {
         angle = 0,
         turret = 1,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
-    #[armament(Mark18, forward = 0.25, angle = 0, turret = 1, external)]
-    #[armament(Mark9, forward = -45.75, side = 1.83, angle = 180, symmetrical, external)]
+    #[armament(Mark18, forward = 0.25, angle = 0, turret = 1, external, magazine = 8)]
+    #[armament(Mark9, forward = -45.75, side = 1.83, angle = 180, symmetrical, external, magazine = 8)]
+    #[armament(SmokeFloat, forward = -43.27, angle = 180, hidden)]
     #[turret(forward = -9.39937, medium, azimuth = 40)]
     #[turret(forward = -21.8755, medium, azimuth = 40)]
     #[turret(Ansaldo, forward = 28.8815, medium, azimuth_b = 30)]
@@ -543,9 +637,10 @@ pub enum EntityType {
     #[size(length = 115, width = 17.5, draft = 3.9)]
     #[props(speed = 24.1789, stealth = 0.5)]
     #[sensors(radar, sonar, visual)]
-    #[armament(Nsm, forward = 26.5436, side = 4.77561, angle = -53.7668, count = 2, symmetrical)]
-    #[armament(Nsm, forward = 27.5111, side = 5.51015, angle = -53.7668, count = 2, symmetrical)]
+    #[armament(Nsm, forward = 26.5436, side = 4.77561, angle = -53.7668, count = 2, symmetrical, magazine = 6)]
+    #[armament(Nsm, forward = 27.5111, side = 5.51015, angle = -53.7668, count = 2, symmetrical, magazine = 6)]
     #[armament(Seahawk, forward = -40, external)]
+    #[armament(Srboc, forward = 11.5, side = 6.12, angle = 90, symmetrical, hidden)]
     #[turret(Bofors57MmMk3, forward = 33, fast, azimuth_b = 35)]
     #[turret(Mark49, forward = -22.5, angle = 180, fast)]
     #[exhaust(forward = 1.4, side = 1.68, symmetrical)]
@@ -558,7 +653,7 @@ pub enum EntityType {
     #[size(length = 18.85, width = 3.5, draft = 0.82)]
     #[props(speed = 27.26557)]
     #[sensors(visual)]
-    #[armament(Type53, forward = -7, side = 0.333, angle = 0, symmetrical, external)]
+    #[armament(Type53, forward = -7, side = 0.333, angle = 0, symmetrical, external, magazine = 8)]
     G5,
     #[info(
         label = "Golf",
@@ -568,9 +663,9 @@ pub enum EntityType {
     #[size(length = 98.4, width = 8.2, draft = 8.5)]
     #[props(speed = 8.7455, depth = 260)]
     #[sensors(sonar, visual)]
-    #[armament(Set65, forward = 41, side = 0.5, angle = 0, symmetrical)]
-    #[armament(Set65, forward = 41, side = 0.5, angle = 0, symmetrical)]
-    #[armament(Set65, forward = 41, side = 0.5, angle = 0, symmetrical)]
+    #[armament(Set65, forward = 41, side = 0.5, angle = 0, symmetrical, magazine = 8)]
+    #[armament(Set65, forward = 41, side = 0.5, angle = 0, symmetrical, magazine = 8)]
+    #[armament(Set65, forward = 41, side = 0.5, angle = 0, symmetrical, magazine = 8)]
     Golf,
     #[info(
         label = "East Indiaman",
@@ -586,7 +681,8 @@ pub enum EntityType {
         side = 4.48329,
         angle = 90,
         symmetrical,
-        external
+        external,
+        magazine = 40
     )]
     #[armament(
         CannonBall,
@@ -594,18 +690,20 @@ pub enum EntityType {
         side = 4.53272,
         angle = 89,
         symmetrical,
-        external
+        external,
+        magazine = 40
     )]
-    #[armament(CannonBall, forward = -1.48315, side = 4.31076, angle = 91, symmetrical, external)]
+    #[armament(CannonBall, forward = -1.48315, side = 4.31076, angle = 91, symmetrical, external, magazine = 40)]
     #[armament(
         CannonBall,
         forward = 11.0811,
         side = 4.33021,
         angle = 88,
         symmetrical,
-        external
+        external,
+        magazine = 40
     )]
-    #[armament(CannonBall, forward = -9.85305, side = 4.31076, angle = 92, symmetrical, external)]
+    #[armament(CannonBall, forward = -9.85305, side = 4.31076, angle = 92, symmetrical, external, magazine = 40)]
     Indiaman,
     #[info(
         label = "Iowa",
@@ -615,10 +713,10 @@ pub enum EntityType {
     #[size(length = 270.4, width = 32.74, draft = 12, mast = 38.9)]
     #[props(speed = 16.977)]
     #[sensors(radar, visual)]
-    #[armament(Tomahawk, forward = -13.45, side = 10.748, angle = -90, count = 2, symmetrical, hidden)]
-    #[armament(Tomahawk, forward = -17.08, side = 10.748, angle = -90, count = 2, symmetrical, hidden)]
-    #[armament(Tomahawk, forward = -41.02, side = 4.45, angle = 30, count = 2, symmetrical, hidden)]
-    #[armament(Tomahawk, forward = -46.9846, side = 4.45086, angle = 30, count = 2, symmetrical, hidden)]
+    #[armament(Tomahawk, forward = -13.45, side = 10.748, angle = -90, count = 2, symmetrical, hidden, magazine = 6)]
+    #[armament(Tomahawk, forward = -17.08, side = 10.748, angle = -90, count = 2, symmetrical, hidden, magazine = 6)]
+    #[armament(Tomahawk, forward = -41.02, side = 4.45, angle = 30, count = 2, symmetrical, hidden, magazine = 6)]
+    #[armament(Tomahawk, forward = -46.9846, side = 4.45086, angle = 30, count = 2, symmetrical, hidden, magazine = 6)]
     #[armament(Seahawk, forward = -121, external)]
     #[armament(Seahawk, forward = -109, side = -8, angle = -15, symmetrical, external)]
     #[turret(Mark7, forward = 59.62, slow, azimuth_b = 20)]
@@ -635,12 +733,28 @@ pub enum EntityType {
     #[size(length = 252, width = 28.793, draft = 9.1, mast = 49.71)]
     #[props(speed = 16.46223)]
     #[sensors(radar, sonar, visual)]
-    #[armament(Set65, forward = -50.5471, side = 10, angle = 90, symmetrical)]
-    #[armament(Set65, forward = -51.0471, side = 10, angle = 90, symmetrical)]
-    #[armament(Set65, forward = -51.5471, side = 10, angle = 90, symmetrical)]
-    #[armament(Set65, forward = -52.0471, side = 10, angle = 90, symmetrical)]
-    #[armament(P700, forward = 41, side = 3.5, count = 4, symmetrical, hidden)]
-    #[armament(S300, forward = 61.2, side = 4.7, count = 3, symmetrical, vertical)]
+    #[armament(Set65, forward = -50.5471, side = 10, angle = 90, symmetrical, magazine = 8)]
+    #[armament(Set65, forward = -51.0471, side = 10, angle = 90, symmetrical, magazine = 8)]
+    #[armament(Set65, forward = -51.5471, side = 10, angle = 90, symmetrical, magazine = 8)]
+    #[armament(Set65, forward = -52.0471, side = 10, angle = 90, symmetrical, magazine = 8)]
+    #[armament(
+        P700,
+        forward = 41,
+        side = 3.5,
+        count = 4,
+        symmetrical,
+        hidden,
+        magazine = 6
+    )]
+    #[armament(
+        S300,
+        forward = 61.2,
+        side = 4.7,
+        count = 3,
+        symmetrical,
+        vertical,
+        magazine = 12
+    )]
     #[armament(Ka25, forward = -112.4, external)]
     #[turret(Ak130, forward = -66.6097, angle = 180, medium, azimuth_b = 30)]
     #[turret(Ak130, forward = -79.1108, angle = 180, medium, azimuth_b = 20)]
@@ -661,7 +775,8 @@ pub enum EntityType {
         angle = 0,
         turret = 0,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
     #[armament(
         Set65,
@@ -670,11 +785,23 @@ pub enum EntityType {
         angle = 0,
         turret = 1,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
-    #[armament(BrahMos, forward = 43.4, side = 1.4, count = 3, symmetrical, vertical)]
-    #[armament(Barak8, forward = 37.5, side = 2, symmetrical, vertical)]
-    #[armament(Barak8, forward = -36.3, side = 1.5, symmetrical, vertical)]
+    #[armament(
+        BrahMos,
+        forward = 43.4,
+        side = 1.4,
+        count = 3,
+        symmetrical,
+        vertical,
+        magazine = 6
+    )]
+    #[armament(Barak8, forward = 37.5, side = 2, symmetrical, vertical, magazine = 12)]
+    #[armament(Barak8, forward = -36.3, side = 1.5, symmetrical, vertical, magazine = 12)]
+    #[armament(Srboc, forward = 16.3, side = 6.09, angle = 90, symmetrical, hidden)]
+    #[loadout(label = "Anti-Ship")]
+    #[loadout(label = "Anti-Air", replace(BrahMos, Barak8, magazine = 12))]
     #[turret(forward = -2.5, side = -2.5, angle = -90, medium, azimuth_b = 155)]
     #[turret(forward = -5.3, side = 2.5, angle = 90, medium, azimuth_b = 155)]
     #[turret(OtoMelara76Mm, forward = 54, fast, azimuth_b = 20)]
@@ -689,9 +816,9 @@ pub enum EntityType {
     #[size(length = 25.4, width = 6.24, draft = 1.24)]
     #[props(speed = 22.6)]
     #[sensors(radar, visual)]
-    #[armament(Type53, forward = -0.5, side = 2.3, angle = 5.2, symmetrical, external)]
-    #[armament(Mark9, forward = -11.5, side = 1.2, angle = 182, symmetrical, external)]
-    #[armament(Mark9, forward = -12, side = 1.2, angle = 182, symmetrical, external)]
+    #[armament(Type53, forward = -0.5, side = 2.3, angle = 5.2, symmetrical, external, magazine = 8)]
+    #[armament(Mark9, forward = -11.5, side = 1.2, angle = 182, symmetrical, external, magazine = 8)]
+    #[armament(Mark9, forward = -12, side = 1.2, angle = 182, symmetrical, external, magazine = 8)]
     #[turret(_2M3M, forward = 3.4, side = 0.8, angle = 0, fast)]
     #[turret(_2M3M, forward = -8.5, angle = 180, fast)]
     Komar,
@@ -710,7 +837,8 @@ pub enum EntityType {
         angle = 0,
         turret = 0,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
     #[armament(
         Mark18,
@@ -719,7 +847,8 @@ pub enum EntityType {
         angle = 0,
         turret = 0,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
     #[armament(
         Mark18,
@@ -728,7 +857,8 @@ pub enum EntityType {
         angle = 0,
         turret = 1,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
     #[armament(
         Mark18,
@@ -737,7 +867,8 @@ pub enum EntityType {
         angle = 0,
         turret = 1,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
     #[turret(forward = -3.41018, side = 6.52922, angle = 180, medium, azimuth_fl = 180)]
     #[turret(forward = -3.41018, side = -6.52922, angle = 180, medium, azimuth_fr = 180)]
@@ -755,11 +886,11 @@ pub enum EntityType {
     #[size(length = 95.8, width = 10.8, draft = 2.38)]
     #[props(speed = 8.5)]
     #[sensors(radar, visual)]
-    #[armament(Wz0839, forward = -38, side = 1.75, symmetrical, external)]
-    #[armament(Wz0839, forward = -39, side = 1.75, symmetrical, external)]
-    #[armament(Wz0839, forward = -40, side = 1.75, symmetrical, external)]
-    #[armament(Wz0839, forward = -41, side = 1.75, symmetrical, external)]
-    #[armament(Wz0839, forward = -42, side = 1.75, symmetrical, external)]
+    #[armament(Wz0839, forward = -38, side = 1.75, symmetrical, external, magazine = 6)]
+    #[armament(Wz0839, forward = -39, side = 1.75, symmetrical, external, magazine = 6)]
+    #[armament(Wz0839, forward = -40, side = 1.75, symmetrical, external, magazine = 6)]
+    #[armament(Wz0839, forward = -41, side = 1.75, symmetrical, external, magazine = 6)]
+    #[armament(Wz0839, forward = -42, side = 1.75, symmetrical, external, magazine = 6)]
     Lublin,
     #[info(
         label = "Momi",
@@ -776,7 +907,8 @@ pub enum EntityType {
         angle = 0,
         turret = 0,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
     #[armament(
         Mark18,
@@ -785,10 +917,12 @@ pub enum EntityType {
         angle = 0,
         turret = 1,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
-    #[armament(Mark9, forward = -42, side = 1.4, angle = 180, symmetrical, external)]
-    #[armament(Mark9, forward = -41.5, side = 1.4, angle = 180, symmetrical, external)]
+    #[armament(Mark9, forward = -42, side = 1.4, angle = 180, symmetrical, external, magazine = 8)]
+    #[armament(Mark9, forward = -41.5, side = 1.4, angle = 180, symmetrical, external, magazine = 8)]
+    #[armament(SmokeFloat, forward = -38.38, angle = 180, hidden)]
     #[turret(forward = 22.15, medium, azimuth = 45)]
     #[turret(forward = -13.85, medium, azimuth = 45)]
     #[turret(Mark12, forward = 30, medium, azimuth_b = 20)]
@@ -825,11 +959,11 @@ pub enum EntityType {
     #[armament(Ka25, forward = -38.6508, side = -8.0173, external)]
     #[armament(Ka25, forward = -64.7966, side = 7.39509, external)]
     #[armament(Ka25, forward = -84.7862, side = -2.81806, external)]
-    #[armament(Set65, forward = -3.02179, side = 10.358, angle = 90, symmetrical)]
-    #[armament(Set65, forward = -3.62179, side = 10.358, angle = 90, symmetrical)]
-    #[armament(Set65, forward = -4.22179, side = 10.358, angle = 90, symmetrical)]
-    #[armament(Set65, forward = -4.82179, side = 10.358, angle = 90, symmetrical)]
-    #[armament(Set65, forward = -5.42179, side = 10.358, angle = 90, symmetrical)]
+    #[armament(Set65, forward = -3.02179, side = 10.358, angle = 90, symmetrical, magazine = 8)]
+    #[armament(Set65, forward = -3.62179, side = 10.358, angle = 90, symmetrical, magazine = 8)]
+    #[armament(Set65, forward = -4.22179, side = 10.358, angle = 90, symmetrical, magazine = 8)]
+    #[armament(Set65, forward = -4.82179, side = 10.358, angle = 90, symmetrical, magazine = 8)]
+    #[armament(Set65, forward = -5.42179, side = 10.358, angle = 90, symmetrical, magazine = 8)]
     #[turret(Shtorm, forward = 50.3038, medium)]
     #[turret(Shtorm, forward = 28.689, medium, azimuth_b = 30)]
     #[exhaust(forward = -13.35)]
@@ -842,8 +976,16 @@ pub enum EntityType {
     #[size(length = 90, width = 8.25, draft = 5.5)]
     #[props(speed = 8.9408, depth = 200)]
     #[sensors(sonar, visual)]
-    #[armament(Mark18, forward = 40, side = 0.5, angle = 2, count = 3, symmetrical)]
-    #[armament(Mark18, forward = -41.4, side = 0.3, angle = 180, symmetrical)]
+    #[armament(
+        Mark18,
+        forward = 40,
+        side = 0.5,
+        angle = 2,
+        count = 3,
+        symmetrical,
+        magazine = 8
+    )]
+    #[armament(Mark18, forward = -41.4, side = 0.3, angle = 180, symmetrical, magazine = 8)]
     Oberon,
     #[info(
         label = "Ohio",
@@ -853,14 +995,54 @@ pub enum EntityType {
     #[size(length = 170, width = 13, draft = 10.8)]
     #[props(speed = 12.8611, depth = 400)]
     #[sensors(radar, sonar, visual)]
-    #[armament(Mark48, forward = 72, side = 5, angle = 0, symmetrical)]
-    #[armament(Mark48, forward = 72, side = 5, angle = 0, symmetrical)]
+    #[armament(Mark48, forward = 72, side = 5, angle = 0, symmetrical, magazine = 8)]
+    #[armament(Mark48, forward = 72, side = 5, angle = 0, symmetrical, magazine = 8)]
     #[armament(Mk70, forward = 72, side = 5, angle = 0, hidden)]
-    #[armament(Tomahawk, forward = 30.3, side = 2, angle = 0, symmetrical, vertical)]
-    #[armament(Tomahawk, forward = 23.7, side = 2, angle = 0, symmetrical, vertical)]
-    #[armament(Tomahawk, forward = 17.2, side = 2, angle = 0, symmetrical, vertical)]
-    #[armament(Tomahawk, forward = 10.75, side = 2, angle = 0, symmetrical, vertical)]
-    #[armament(Tomahawk, forward = 4.25, side = 2, angle = 0, symmetrical, vertical)]
+    #[armament(
+        Tomahawk,
+        forward = 30.3,
+        side = 2,
+        angle = 0,
+        symmetrical,
+        vertical,
+        magazine = 6
+    )]
+    #[armament(
+        Tomahawk,
+        forward = 23.7,
+        side = 2,
+        angle = 0,
+        symmetrical,
+        vertical,
+        magazine = 6
+    )]
+    #[armament(
+        Tomahawk,
+        forward = 17.2,
+        side = 2,
+        angle = 0,
+        symmetrical,
+        vertical,
+        magazine = 6
+    )]
+    #[armament(
+        Tomahawk,
+        forward = 10.75,
+        side = 2,
+        angle = 0,
+        symmetrical,
+        vertical,
+        magazine = 6
+    )]
+    #[armament(
+        Tomahawk,
+        forward = 4.25,
+        side = 2,
+        angle = 0,
+        symmetrical,
+        vertical,
+        magazine = 6
+    )]
     Ohio,
     #[info(
         label = "Olympias",
@@ -879,8 +1061,8 @@ pub enum EntityType {
     #[size(length = 38.6, width = 7.64, draft = 1.73)]
     #[props(speed = 21.6067)]
     #[sensors(radar, visual)]
-    #[armament(P15, forward = -1.75, side = 2.5, angle = 2, symmetrical)]
-    #[armament(P15, forward = -12, side = 2.5, angle = 2, symmetrical)]
+    #[armament(P15, forward = -1.75, side = 2.5, angle = 2, symmetrical, magazine = 6)]
+    #[armament(P15, forward = -12, side = 2.5, angle = 2, symmetrical, magazine = 6)]
     #[turret(_2M3M, forward = 10, angle = 0, fast)]
     #[turret(_2M3M, forward = -16.5, angle = 180, fast)]
     Osa,
@@ -892,8 +1074,9 @@ pub enum EntityType {
     #[size(length = 23, width = 6.07, draft = 1.37)]
     #[props(speed = 21.09)]
     #[sensors(visual)]
-    #[armament(Mark18, side = 2.5, angle = 4.5, symmetrical, external)]
-    #[armament(Mark18, forward = -8, side = 1.8, angle = 4.5, symmetrical, external)]
+    #[armament(Mark18, side = 2.5, angle = 4.5, symmetrical, external, magazine = 8)]
+    #[armament(Mark18, forward = -8, side = 1.8, angle = 4.5, symmetrical, external, magazine = 8)]
+    #[armament(SmokeFloat, forward = -10.35, angle = 180, hidden)]
     Pt34,
     #[info(
         label = "Seawolf",
@@ -909,7 +1092,8 @@ pub enum EntityType {
         side = 4.73435,
         angle = 0,
         count = 4,
-        symmetrical
+        symmetrical,
+        magazine = 8
     )]
     #[armament(
         Mk70,
@@ -928,10 +1112,26 @@ pub enum EntityType {
     #[size(length = 76.71, width = 9.65, draft = 7.66, mast = 10.40)]
     #[props(speed = 16.976667, depth = 210)]
     #[sensors(radar, sonar, visual)]
-    #[armament(Mark48, forward = 33.75, side = 0.7, angle = 0, symmetrical)]
-    #[armament(Mark48, forward = 33.75, side = 0.7, angle = 0, symmetrical)]
+    #[armament(
+        Mark48,
+        forward = 33.75,
+        side = 0.7,
+        angle = 0,
+        symmetrical,
+        magazine = 8
+    )]
+    #[armament(
+        Mark48,
+        forward = 33.75,
+        side = 0.7,
+        angle = 0,
+        symmetrical,
+        magazine = 8
+    )]
     #[armament(Mk70, forward = 33.75, side = 0.7, angle = 0, hidden)]
-    #[armament(Harpoon, forward = 34, angle = 0, symmetrical)]
+    #[armament(Harpoon, forward = 34, angle = 0, symmetrical, magazine = 6)]
+    #[loadout(label = "Standard")]
+    #[loadout(label = "Torpedoes", replace(Harpoon, Mark48, magazine = 8))]
     Skipjack,
     #[info(
         label = "Skjold",
@@ -941,9 +1141,9 @@ pub enum EntityType {
     #[size(length = 47.5, width = 13.73, draft = 1)]
     #[props(speed = 30.867, stealth = 0.75)]
     #[sensors(radar, sonar, visual)]
-    #[armament(Nsm, forward = -19.0286, side = -1.96027, angle = -23.7601, count = 2, symmetrical)]
-    #[armament(Nsm, forward = -19.3748, side = -2.88731, angle = -23.7601, count = 2, symmetrical)]
-    #[armament(Mistral, forward = -6.08214, side = -4.51251, vertical, count = 3, symmetrical)]
+    #[armament(Nsm, forward = -19.0286, side = -1.96027, angle = -23.7601, count = 2, symmetrical, magazine = 6)]
+    #[armament(Nsm, forward = -19.3748, side = -2.88731, angle = -23.7601, count = 2, symmetrical, magazine = 6)]
+    #[armament(Mistral, forward = -6.08214, side = -4.51251, vertical, count = 3, symmetrical, magazine = 12)]
     #[turret(OtoMelara76Mm, forward = 6.02709, fast, azimuth_b = 35)]
     Skjold,
     #[info(
@@ -974,7 +1174,7 @@ pub enum EntityType {
     #[size(length = 180.3, width = 20.77676, draft = 6.28)]
     #[props(speed = 16.59084)]
     #[sensors(radar, visual)]
-    #[armament(Mark18, forward = 0.25, angle = 0, turret = 0, external)]
+    #[armament(Mark18, forward = 0.25, angle = 0, turret = 0, external, magazine = 8)]
     #[armament(
         Mark18,
         forward = 0.25,
@@ -982,9 +1182,10 @@ pub enum EntityType {
         angle = 0,
         turret = 0,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
-    #[armament(Mark18, forward = 0.25, angle = 0, turret = 1, external)]
+    #[armament(Mark18, forward = 0.25, angle = 0, turret = 1, external, magazine = 8)]
     #[armament(
         Mark18,
         forward = 0.25,
@@ -992,7 +1193,8 @@ pub enum EntityType {
         angle = 0,
         turret = 1,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
     #[armament(Kingfisher, forward = 4.82098, external)]
     #[turret(forward = -20.2181, side = 8.41364, medium, azimuth_br = 180)]
@@ -1012,13 +1214,24 @@ pub enum EntityType {
     #[size(length = 180, width = 20, draft = 9.5, mast = 36.28)]
     #[props(speed = 15.434, stealth = 0.75)]
     #[sensors(radar, sonar, visual)]
-    #[armament(Yj18, forward = 41.4, side = 2, count = 4, symmetrical, vertical)]
-    #[armament(_82R, forward = -39.8359, side = 8, angle = 90, symmetrical)]
-    #[armament(_82R, forward = -40.4359, side = 8, angle = 90, symmetrical)]
-    #[armament(_82R, forward = -41.0359, side = 8, angle = 90, symmetrical)]
-    #[armament(Hq9, forward = 46.5, side = 2, symmetrical, vertical)]
-    #[armament(Hq9, forward = -33.8354, side = 2, count = 2, symmetrical, vertical)]
+    #[armament(
+        Yj18,
+        forward = 41.4,
+        side = 2,
+        count = 4,
+        symmetrical,
+        vertical,
+        magazine = 6
+    )]
+    #[armament(_82R, forward = -39.8359, side = 8, angle = 90, symmetrical, magazine = 8)]
+    #[armament(_82R, forward = -40.4359, side = 8, angle = 90, symmetrical, magazine = 8)]
+    #[armament(_82R, forward = -41.0359, side = 8, angle = 90, symmetrical, magazine = 8)]
+    #[armament(Hq9, forward = 46.5, side = 2, symmetrical, vertical, magazine = 12)]
+    #[armament(Hq9, forward = -33.8354, side = 2, count = 2, symmetrical, vertical, magazine = 12)]
     #[armament(Harbin, forward = -79.8795, external)]
+    #[armament(Srboc, forward = 18, side = 7, angle = 90, symmetrical, hidden)]
+    #[loadout(label = "Anti-Ship")]
+    #[loadout(label = "Anti-Air", replace(Yj18, Hq9, magazine = 12))]
     #[turret(Hpj38, forward = 58.9931, fast, azimuth_b = 15)]
     #[exhaust(forward = -7.34, side = 1.45, symmetrical)]
     #[exhaust(forward = -17.34, side = 1.45, symmetrical)]
@@ -1031,9 +1244,23 @@ pub enum EntityType {
     #[size(length = 67.1, width = 6.2, draft = 4.74)]
     #[props(speed = 9.06, depth = 180)]
     #[sensors(sonar, visual)]
-    #[armament(Mark18, forward = 26, side = 0.333, angle = 2, symmetrical)]
-    #[armament(Mark18, forward = 25, side = 0.666, angle = 2, symmetrical)]
-    #[armament(Mark18, forward = -30, angle = 180)]
+    #[armament(
+        Mark18,
+        forward = 26,
+        side = 0.333,
+        angle = 2,
+        symmetrical,
+        magazine = 8
+    )]
+    #[armament(
+        Mark18,
+        forward = 25,
+        side = 0.666,
+        angle = 2,
+        symmetrical,
+        magazine = 8
+    )]
+    #[armament(Mark18, forward = -30, angle = 180, magazine = 8)]
     #[turret(_88CmSkc35, forward = -4.35, angle = 180, medium, azimuth_b = 20)]
     TypeViic,
     #[info(
@@ -1051,7 +1278,8 @@ pub enum EntityType {
         angle = 0,
         turret = 0,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
     #[armament(
         Torped45,
@@ -1060,12 +1288,13 @@ pub enum EntityType {
         angle = 0,
         turret = 1,
         symmetrical,
-        external
+        external,
+        magazine = 8
     )]
-    #[armament(Rbs15, forward = -2.25, side = 3.5, angle = 90, symmetrical, external)]
-    #[armament(Rbs15, forward = -3, side = 3.5, angle = 90, symmetrical, external)]
-    #[armament(Rbs15, forward = -2.25, side = 3.5, angle = 90, symmetrical, external)]
-    #[armament(Rbs15, forward = -3, side = 3.5, angle = 90, symmetrical, external)]
+    #[armament(Rbs15, forward = -2.25, side = 3.5, angle = 90, symmetrical, external, magazine = 6)]
+    #[armament(Rbs15, forward = -3, side = 3.5, angle = 90, symmetrical, external, magazine = 6)]
+    #[armament(Rbs15, forward = -2.25, side = 3.5, angle = 90, symmetrical, external, magazine = 6)]
+    #[armament(Rbs15, forward = -3, side = 3.5, angle = 90, symmetrical, external, magazine = 6)]
     #[armament(Seahawk, forward = -23, external)]
     #[turret(forward = -22, side = 4.5, medium, azimuth_br = 180)]
     #[turret(forward = -22, side = -4.5, medium, azimuth_bl = 180)]
@@ -1094,11 +1323,27 @@ pub enum EntityType {
     #[size(length = 130, width = 19.804688, draft = 10)]
     #[props(speed = 18.00556, depth = 450)]
     #[sensors(radar, sonar, visual)]
-    #[armament(Set65, forward = 41, side = 5.75, angle = 2, count = 3, symmetrical)]
-    #[armament(Rpk6, forward = 41, side = 5.75, angle = 2, count = 2, symmetrical)]
-    #[armament(BrahMos, forward = -4.5, side = 2, symmetrical, vertical)]
-    #[armament(BrahMos, forward = -7, side = 2, symmetrical, vertical)]
-    #[armament(Igla, forward = 29.19, count = 4, vertical)]
+    #[armament(
+        Set65,
+        forward = 41,
+        side = 5.75,
+        angle = 2,
+        count = 3,
+        symmetrical,
+        magazine = 8
+    )]
+    #[armament(
+        Rpk6,
+        forward = 41,
+        side = 5.75,
+        angle = 2,
+        count = 2,
+        symmetrical,
+        magazine = 4
+    )]
+    #[armament(BrahMos, forward = -4.5, side = 2, symmetrical, vertical, magazine = 6)]
+    #[armament(BrahMos, forward = -7, side = 2, symmetrical, vertical, magazine = 6)]
+    #[armament(Igla, forward = 29.19, count = 4, vertical, magazine = 12)]
     #[armament(Brosok, forward = 43, side = 3, angle = 0, symmetrical)]
     #[armament(Brosok, forward = -16.5, side = 1.5, angle = -180, symmetrical)]
     Yasen,
@@ -1120,12 +1365,37 @@ pub enum EntityType {
     #[size(length = 190, width = 24.6, draft = 13.09, mast = 28.67)]
     #[props(speed = 15.434, stealth = 0.75, ram_damage = 1.5)]
     #[sensors(radar, sonar, visual)]
-    #[armament(Tomahawk, forward = 16, side = 9, count = 2, symmetrical, vertical)]
-    #[armament(Tomahawk, forward = -51.5, side = 9, count = 2, symmetrical, vertical)]
-    #[armament(Asroc, forward = 39.5, side = 5.5, count = 2, symmetrical, vertical)]
-    #[armament(Essm, forward = 35, side = 6, count = 2, symmetrical, vertical)]
-    #[armament(Essm, forward = -56, side = 9, count = 2, symmetrical, vertical)]
+    #[armament(
+        Tomahawk,
+        forward = 16,
+        side = 9,
+        count = 2,
+        symmetrical,
+        vertical,
+        magazine = 6
+    )]
+    #[armament(Tomahawk, forward = -51.5, side = 9, count = 2, symmetrical, vertical, magazine = 6)]
+    #[armament(
+        Asroc,
+        forward = 39.5,
+        side = 5.5,
+        count = 2,
+        symmetrical,
+        vertical,
+        magazine = 4
+    )]
+    #[armament(
+        Essm,
+        forward = 35,
+        side = 6,
+        count = 2,
+        symmetrical,
+        vertical,
+        magazine = 12
+    )]
+    #[armament(Essm, forward = -56, side = 9, count = 2, symmetrical, vertical, magazine = 12)]
     #[armament(Seahawk, forward = -65, external)]
+    #[armament(Srboc, forward = 19, side = 8.61, angle = 90, symmetrical, hidden)]
     #[turret(Mark51, forward = 49.5963, medium, azimuth_b = 20)]
     #[turret(Mark51, forward = 25.2885, medium, azimuth_b = 30)]
     #[exhaust(forward = -0.09, side = 0.1)]
@@ -1164,6 +1434,16 @@ pub enum EntityType {
     #[size(length = 2.075, width = 0.29)]
     #[props(speed = 10, lifespan = 15)]
     Mk70,
+    #[info(label = "Smoke Float")]
+    #[entity(Decoy, Smoke, level = 1)]
+    #[size(length = 0.6, width = 0.3)]
+    #[props(lifespan = 30)]
+    SmokeFloat,
+    #[info(label = "SRBOC", link = "https://en.wikipedia.org/wiki/Mark_36_SRBOC")]
+    #[entity(Decoy, Chaff, level = 3)]
+    #[size(length = 1.2, width = 0.13)]
+    #[props(speed = 10, lifespan = 20)]
+    Srboc,
     #[info(label = "Acacia")]
     #[entity(Obstacle, Tree)]
     #[size(length = 8, width = 8)]
@@ -1184,7 +1464,7 @@ pub enum EntityType {
     #[entity(Turret, Gun)]
     #[size(length = 6.7, width = 4.1875)]
     #[offset(forward = 1.034)]
-    #[armament(_127X680MmR, forward = 2, angle = 0)]
+    #[armament(_127X680MmR, forward = 2, angle = 0, magazine = 40)]
     _100Mm,
     #[info(
         label = "2M-3M",
@@ -1193,7 +1473,7 @@ pub enum EntityType {
     #[entity(Turret, Gun)]
     #[size(length = 2.975, width = 1.72)]
     #[offset(forward = 0.5)]
-    #[armament(_25X129MmR, forward = 0.5, angle = 0, external)]
+    #[armament(_25X129MmR, forward = 0.5, angle = 0, external, magazine = 40)]
     _2M3M,
     #[info(
         label = "38 cm SK C/34",
@@ -1202,7 +1482,15 @@ pub enum EntityType {
     #[entity(Turret, Gun)]
     #[size(length = 25.6, width = 11.1)]
     #[offset(forward = 5)]
-    #[armament(_380X1700MmR, forward = 12, side = 4.5, angle = 0, symmetrical, hidden)]
+    #[armament(
+        _380X1700MmR,
+        forward = 12,
+        side = 4.5,
+        angle = 0,
+        symmetrical,
+        hidden,
+        magazine = 40
+    )]
     _38CmSkc34,
     #[info(
         label = "45 cm/45 Type 94",
@@ -1217,9 +1505,10 @@ pub enum EntityType {
         side = 3.1,
         angle = 0,
         symmetrical,
-        hidden
+        hidden,
+        magazine = 40
     )]
-    #[armament(_458X1980MmR, forward = 5.7, angle = 0, hidden)]
+    #[armament(_458X1980MmR, forward = 5.7, angle = 0, hidden, magazine = 40)]
     _45Type94,
     #[info(
         label = "6-Pounder",
@@ -1228,7 +1517,7 @@ pub enum EntityType {
     #[entity(Turret, Gun)]
     #[size(length = 2.675, width = 1.588)]
     #[offset(forward = 0.5, side = 0.25)]
-    #[armament(_57X441MmR, forward = 0.5, angle = 0, hidden)]
+    #[armament(_57X441MmR, forward = 0.5, angle = 0, hidden, magazine = 40)]
     _6Pounder,
     #[info(
         label = "8.8 cm SK C/35",
@@ -1237,7 +1526,7 @@ pub enum EntityType {
     #[entity(Turret, Gun)]
     #[size(length = 3.41, width = 1.812)]
     #[offset(forward = 0.4, side = -0.1)]
-    #[armament(_57X441MmR, forward = 0.5, angle = 0, hidden)]
+    #[armament(_57X441MmR, forward = 0.5, angle = 0, hidden, magazine = 40)]
     _88CmSkc35,
     #[info(
         label = "AK-130",
@@ -1246,13 +1535,13 @@ pub enum EntityType {
     #[entity(Turret, Gun)]
     #[size(length = 9.45, width = 3.691)]
     #[offset(forward = 2.17111)]
-    #[armament(_130X720MmR, angle = 0)]
+    #[armament(_130X720MmR, angle = 0, magazine = 40)]
     A190,
     #[info(label = "AK-130", link = "https://en.wikipedia.org/wiki/AK-130")]
     #[entity(Turret, Gun)]
     #[size(length = 8.45, width = 3.235)]
     #[offset(forward = 1)]
-    #[armament(_130X720MmR, angle = 0)]
+    #[armament(_130X720MmR, angle = 0, magazine = 40)]
     Ak130,
     #[info(
         label = "50-calibre Ansaldo",
@@ -1261,7 +1550,14 @@ pub enum EntityType {
     #[entity(Turret, Gun)]
     #[size(length = 6.65, width = 3.481)]
     #[offset(forward = 1.5985)]
-    #[armament(_127X680MmR, forward = 2, side = 0.3149, angle = 0, symmetrical)]
+    #[armament(
+        _127X680MmR,
+        forward = 2,
+        side = 0.3149,
+        angle = 0,
+        symmetrical,
+        magazine = 40
+    )]
     Ansaldo,
     #[info(
         label = "BL 6-inch Mk XXIII",
@@ -1270,7 +1566,15 @@ pub enum EntityType {
     #[entity(Turret, Gun)]
     #[size(length = 11.9, width = 5.671)]
     #[offset(forward = 2)]
-    #[armament(_127X680MmR, forward = 1, side = 2, angle = 0, symmetrical, external)]
+    #[armament(
+        _127X680MmR,
+        forward = 1,
+        side = 2,
+        angle = 0,
+        symmetrical,
+        external,
+        magazine = 40
+    )]
     Bl6MkXxiii,
     #[info(
         label = "BL 6-inch Mk XXIII",
@@ -1279,8 +1583,16 @@ pub enum EntityType {
     #[entity(Turret, Gun)]
     #[size(length = 12.3, width = 7.207)]
     #[offset(forward = 2)]
-    #[armament(_127X680MmR, forward = 1, angle = 0, external)]
-    #[armament(_127X680MmR, forward = 1, side = 3, angle = 0, symmetrical, external)]
+    #[armament(_127X680MmR, forward = 1, angle = 0, external, magazine = 40)]
+    #[armament(
+        _127X680MmR,
+        forward = 1,
+        side = 3,
+        angle = 0,
+        symmetrical,
+        external,
+        magazine = 40
+    )]
     Bl6MkXxiiiX3,
     #[info(
         label = "Bofors 57mm MK3",
@@ -1289,7 +1601,7 @@ pub enum EntityType {
     #[entity(Turret, Gun)]
     #[size(length = 6.925, width = 4.2199)]
     #[offset(forward = 1)]
-    #[armament(_57X441MmR, forward = 2, angle = 0, hidden)]
+    #[armament(_57X441MmR, forward = 2, angle = 0, hidden, magazine = 40)]
     Bofors57MmMk3,
     #[info(
         label = "Crotale",
@@ -1298,7 +1610,7 @@ pub enum EntityType {
     #[entity(Turret, Missile)]
     #[size(length = 3.575, width = 2.374)]
     #[offset(forward = 0.08)]
-    #[armament(Vt1, side = 0.947, angle = 0, symmetrical)]
+    #[armament(Vt1, side = 0.947, angle = 0, symmetrical, magazine = 12)]
     Crotale,
     #[info(
         label = "H/PJ-38",
@@ -1307,7 +1619,7 @@ pub enum EntityType {
     #[entity(Turret, Gun)]
     #[size(length = 12.2, width = 3.621875)]
     #[offset(forward = 3)]
-    #[armament(_130X720MmR, forward = 2, angle = 0)]
+    #[armament(_130X720MmR, forward = 2, angle = 0, magazine = 40)]
     Hpj38,
     #[info(
         label = "Mark 12",
@@ -1316,7 +1628,7 @@ pub enum EntityType {
     #[entity(Turret, Gun)]
     #[size(length = 7.34, width = 3.06)]
     #[offset(forward = 1)]
-    #[armament(_127X680MmR, forward = 2, angle = 0)]
+    #[armament(_127X680MmR, forward = 2, angle = 0, magazine = 40)]
     Mark12,
     #[info(
         label = "Mark 12",
@@ -1325,7 +1637,14 @@ pub enum EntityType {
     #[entity(Turret, Gun)]
     #[size(length = 7.6, width = 4.39375)]
     #[offset(forward = 1)]
-    #[armament(_127X680MmR, forward = 2, side = 0.727, angle = 0, symmetrical)]
+    #[armament(
+        _127X680MmR,
+        forward = 2,
+        side = 0.727,
+        angle = 0,
+        symmetrical,
+        magazine = 40
+    )]
     Mark12X2,
     #[info(
         label = "Mark 49",
@@ -1334,7 +1653,7 @@ pub enum EntityType {
     #[entity(Turret, Sam)]
     #[size(length = 3.02, width = 2.00547)]
     #[offset(forward = 0.15)]
-    #[armament(Rim116, angle = 0, count = 8, hidden)]
+    #[armament(Rim116, angle = 0, count = 8, hidden, magazine = 12)]
     Mark49,
     #[info(
         label = "Mark 51",
@@ -1343,7 +1662,7 @@ pub enum EntityType {
     #[entity(Turret, Gun)]
     #[size(length = 11.45, width = 6.35)]
     #[offset(forward = 2.0724)]
-    #[armament(Lrlap, angle = 0)]
+    #[armament(Lrlap, angle = 0, magazine = 40)]
     Mark51,
     #[info(
         label = "Mark 7",
@@ -1352,14 +1671,29 @@ pub enum EntityType {
     #[entity(Turret, Gun)]
     #[size(length = 30.05, width = 15.26)]
     #[offset(forward = 6.5)]
-    #[armament(Mark8, forward = 12, side = 3.16, angle = 0, symmetrical, hidden)]
-    #[armament(Mark8, forward = 12, angle = 0, hidden)]
+    #[armament(
+        Mark8,
+        forward = 12,
+        side = 3.16,
+        angle = 0,
+        symmetrical,
+        hidden,
+        magazine = 40
+    )]
+    #[armament(Mark8, forward = 12, angle = 0, hidden, magazine = 40)]
     Mark7,
     #[info(label = "Mark BVIII")]
     #[entity(Turret, Gun)]
     #[size(length = 18.15, width = 8.1533)]
     #[offset(forward = 4.0505)]
-    #[armament(_300X1400MmR, forward = 3, side = 1.19819, angle = 0, symmetrical)]
+    #[armament(
+        _300X1400MmR,
+        forward = 3,
+        side = 1.19819,
+        angle = 0,
+        symmetrical,
+        magazine = 40
+    )]
     MarkBViii,
     #[info(
         label = "Ogon",
@@ -1367,11 +1701,11 @@ pub enum EntityType {
     )]
     #[entity(Turret, Rocket)]
     #[size(length = 2.6, width = 2.6)]
-    #[armament(Of45, angle = 0, hidden)]
-    #[armament(Of45, side = 0.3, angle = 0, symmetrical, hidden)]
-    #[armament(Of45, side = 0.6, angle = 0, symmetrical, hidden)]
-    #[armament(Of45, side = 0.9, angle = 0, symmetrical, hidden)]
-    #[armament(Of45, side = 1.2, angle = 0, symmetrical, hidden)]
+    #[armament(Of45, angle = 0, hidden, magazine = 12)]
+    #[armament(Of45, side = 0.3, angle = 0, symmetrical, hidden, magazine = 12)]
+    #[armament(Of45, side = 0.6, angle = 0, symmetrical, hidden, magazine = 12)]
+    #[armament(Of45, side = 0.9, angle = 0, symmetrical, hidden, magazine = 12)]
+    #[armament(Of45, side = 1.2, angle = 0, symmetrical, hidden, magazine = 12)]
     Ogon,
     #[info(
         label = "OTO Melara 76 mm",
@@ -1380,7 +1714,7 @@ pub enum EntityType {
     #[entity(Turret, Gun)]
     #[size(length = 7.3, width = 3.0796876)]
     #[offset(forward = 1)]
-    #[armament(_76X636MmR, forward = 2, angle = 0)]
+    #[armament(_76X636MmR, forward = 2, angle = 0, magazine = 40)]
     OtoMelara76Mm,
     #[info(
         label = "Komar",
@@ -1388,19 +1722,34 @@ pub enum EntityType {
     )]
     #[entity(Turret, Missile)]
     #[size(length = 1.874, width = 2.05)]
-    #[armament(Igla, side = 0.75, angle = 0, symmetrical)]
+    #[armament(Igla, side = 0.75, angle = 0, symmetrical, magazine = 12)]
     RatepKomar,
     #[info(label = "Shtorm", link = "https://en.wikipedia.org/wiki/M-11_Shtorm")]
     #[entity(Turret, Sam)]
     #[size(length = 5.8, width = 3.1265626)]
     #[offset(forward = 0.448823)]
-    #[armament(V611, forward = 0.14, side = 1.30837, angle = 0, symmetrical, external)]
+    #[armament(
+        V611,
+        forward = 0.14,
+        side = 1.30837,
+        angle = 0,
+        symmetrical,
+        external,
+        magazine = 12
+    )]
     Shtorm,
     #[info(label = "Vickers MkH 12. in")]
     #[entity(Turret, Gun)]
     #[size(length = 16.65, width = 8.4551)]
     #[offset(forward = 2.3553)]
-    #[armament(_300X1400MmR, forward = 3, side = 0.727, angle = 0, symmetrical)]
+    #[armament(
+        _300X1400MmR,
+        forward = 3,
+        side = 0.727,
+        angle = 0,
+        symmetrical,
+        magazine = 40
+    )]
     VickersMkH12In,
     #[info(label = "127 x 680 mmR")]
     #[entity(Weapon, Shell)]
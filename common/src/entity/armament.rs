@@ -13,6 +13,8 @@ pub struct Armament {
     pub position_side: f32,
     pub angle: Angle,
     pub turret: Option<usize>,
+    /// Rounds carried, which are replenished by resupplying. None if unlimited.
+    pub magazine: Option<u8>,
 }
 
 impl Armament {
@@ -1,5 +1,5 @@
 use crate::altitude::Altitude;
-use crate::entity::{Armament, EntityKind, EntitySubKind, Exhaust, Sensors, Turret};
+use crate::entity::{Armament, EntityKind, EntitySubKind, Exhaust, Loadout, Sensors, Turret};
 use crate::ticks;
 use crate::ticks::Ticks;
 use crate::transform::Transform;
@@ -33,7 +33,11 @@ pub struct EntityData {
     pub torpedo_resistance: f32,
     pub stealth: f32,
     pub sensors: Sensors,
+    /// Armaments of the default loadout.
     pub armaments: &'static [Armament],
+    /// Empty if there is only the default loadout. Otherwise, every loadout, starting with the
+    /// default.
+    pub loadouts: &'static [Loadout],
     pub turrets: &'static [Turret],
     pub exhausts: &'static [Exhaust],
     pub label: &'static str,
@@ -51,6 +55,15 @@ impl EntityData {
     /// Constant used for checking whether a depth charge should explode.
     pub const DEPTH_CHARGE_PROXIMITY: f32 = 30.0;
 
+    /// Radius of the cloud spread by a smoke or chaff decoy, or zero for everything else.
+    pub fn cloud_radius(&self) -> f32 {
+        match (self.kind, self.sub_kind) {
+            (EntityKind::Decoy, EntitySubKind::Smoke) => 60.0,
+            (EntityKind::Decoy, EntitySubKind::Chaff) => 100.0,
+            _ => 0.0,
+        }
+    }
+
     /// radii range of throttle (0-100%) and limit of collecting things.
     pub fn radii(&self) -> Range<f32> {
         self.length * 0.55..self.length
@@ -114,9 +127,27 @@ impl EntityData {
         )
     }
 
+    /// loadout_armaments returns the armaments of a given loadout, all of which have the same
+    /// length as `self.armaments`. Invalid loadouts are treated as the default.
+    pub fn loadout_armaments(&self, loadout: u8) -> &'static [Armament] {
+        self.loadouts
+            .get(loadout as usize)
+            .map_or(self.armaments, |l| l.armaments)
+    }
+
+    /// is_valid_loadout returns whether a loadout may be chosen (the default is always valid).
+    pub fn is_valid_loadout(&self, loadout: u8) -> bool {
+        loadout == 0 || (loadout as usize) < self.loadouts.len()
+    }
+
     /// armament_transform returns the entity-relative transform of a given armament.
-    pub fn armament_transform(&self, turret_angles: &[Angle], index: usize) -> Transform {
-        let armament = &self.armaments[index];
+    pub fn armament_transform(
+        &self,
+        loadout: u8,
+        turret_angles: &[Angle],
+        index: usize,
+    ) -> Transform {
+        let armament = &self.loadout_armaments(loadout)[index];
         let mut transform = Transform {
             position: armament.position(),
             direction: armament.angle,
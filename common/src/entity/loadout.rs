@@ -0,0 +1,9 @@
+use crate::entity::Armament;
+
+/// A named variant of a boat's armaments. Loadouts share the same armament slots (positions,
+/// turrets, etc.), differing only in the types of some weapons.
+#[derive(Clone, Debug)]
+pub struct Loadout {
+    pub label: &'static str,
+    pub armaments: &'static [Armament],
+}
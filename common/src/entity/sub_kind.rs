@@ -2,6 +2,7 @@
 pub enum EntitySubKind {
     Battleship,
     Carrier,
+    Chaff,
     Corvette,
     Cruiser,
     Depositor,
@@ -26,6 +27,7 @@ pub enum EntitySubKind {
     Sam,
     Score,
     Shell,
+    Smoke,
     Sonar,
     Structure,
     Submarine,
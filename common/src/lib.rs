@@ -20,6 +20,7 @@ pub mod contact;
 pub mod death_reason;
 pub mod entity;
 pub mod guidance;
+pub mod navigation;
 pub mod protocol;
 pub mod terrain;
 pub mod ticks;
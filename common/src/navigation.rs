@@ -0,0 +1,328 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::altitude::Altitude;
+use crate::angle::Angle;
+use crate::entity::{EntitySubKind, EntityType};
+use crate::terrain::{Terrain, SCALE};
+use crate::transform::{DimensionTransform, Transform};
+use crate::velocity::Velocity;
+use crate::world::{clamp_y_to_strict_area_border, outside_strict_area};
+use glam::Vec2;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Size of a cell of the path finding grid.
+const CELL: f32 = SCALE * 2.0;
+/// How far the search may stray outside the box containing the start and end.
+const MARGIN: f32 = 1000.0;
+/// Limits the total amount of work done finding a path, which happens on the client.
+const MAX_EXPANSIONS: usize = 16384;
+
+type Cell = (i32, i32);
+
+/// Finds waypoints for a boat to sail from `start` to `end`, avoiding land and its strict area
+/// border. The waypoints exclude `start`, and the last one is `end` (possibly clamped to the strict
+/// area border). Returns None if no path was found (within a limited area around the two points).
+pub fn plan_path(
+    terrain: &Terrain,
+    entity_type: EntityType,
+    start: Vec2,
+    end: Vec2,
+) -> Option<Vec<Vec2>> {
+    let mut search = PathSearch::new(terrain, entity_type, start, end);
+    loop {
+        match search.step(terrain, MAX_EXPANSIONS) {
+            PathStatus::Searching => {}
+            PathStatus::Found(waypoints) => return Some(waypoints),
+            PathStatus::Unreachable => return None,
+        }
+    }
+}
+
+/// Progress of a [`PathSearch`].
+#[derive(Debug, PartialEq)]
+pub enum PathStatus {
+    /// Call [`PathSearch::step`] again.
+    Searching,
+    /// Waypoints, as returned by [`plan_path`].
+    Found(Vec<Vec2>),
+    Unreachable,
+}
+
+/// An A* search for a path (see [`plan_path`]) that can be spread over multiple frames.
+pub struct PathSearch {
+    entity_type: EntityType,
+    start: Vec2,
+    end: Vec2,
+    min: Cell,
+    max: Cell,
+    start_cell: Cell,
+    end_cell: Cell,
+    /// Estimated total cost, cost so far, and cell. May contain stale entries for cells that
+    /// were since reached more cheaply.
+    open: BinaryHeap<Reverse<(u32, u32, Cell)>>,
+    came_from: HashMap<Cell, Cell>,
+    costs: HashMap<Cell, u32>,
+    expansions: usize,
+    /// Determined without searching.
+    trivial: Option<PathStatus>,
+}
+
+impl PathSearch {
+    pub fn new(terrain: &Terrain, entity_type: EntityType, start: Vec2, end: Vec2) -> Self {
+        let end = Vec2::new(end.x, clamp_y_to_strict_area_border(entity_type, end.y));
+
+        let trivial = if is_clear(terrain, entity_type, start, end) {
+            Some(PathStatus::Found(vec![end]))
+        } else if !is_passable(terrain, entity_type, end) {
+            Some(PathStatus::Unreachable)
+        } else {
+            None
+        };
+
+        let start_cell = to_cell(start);
+        let end_cell = to_cell(end);
+        let mut open = BinaryHeap::new();
+        let mut costs = HashMap::new();
+        if trivial.is_none() {
+            open.push(Reverse((cost(start_cell, end_cell), 0, start_cell)));
+            costs.insert(start_cell, 0);
+        }
+
+        Self {
+            entity_type,
+            start,
+            end,
+            min: to_cell(start.min(end) - MARGIN),
+            max: to_cell(start.max(end) + MARGIN),
+            start_cell,
+            end_cell,
+            open,
+            came_from: HashMap::new(),
+            costs,
+            expansions: 0,
+            trivial,
+        }
+    }
+
+    /// Expands at most `budget` cells, returning the progress of the search. Shouldn't be called
+    /// again once the search is over.
+    pub fn step(&mut self, terrain: &Terrain, budget: usize) -> PathStatus {
+        if let Some(trivial) = self.trivial.take() {
+            return trivial;
+        }
+
+        for _ in 0..budget {
+            let Reverse((_, current_cost, current)) = match self.open.pop() {
+                Some(entry) => entry,
+                None => return PathStatus::Unreachable,
+            };
+            if current_cost > self.costs[&current] {
+                // Stale; current was reached more cheaply after this entry was pushed.
+                continue;
+            }
+            if current == self.end_cell {
+                self.open.clear();
+                return PathStatus::Found(self.waypoints(terrain));
+            }
+            self.expansions += 1;
+            if self.expansions > MAX_EXPANSIONS {
+                self.open.clear();
+                return PathStatus::Unreachable;
+            }
+
+            for dx in -1..=1 {
+                for dy in -1..=1 {
+                    let next = (current.0 + dx, current.1 + dy);
+                    if next == current
+                        || next.0 < self.min.0
+                        || next.1 < self.min.1
+                        || next.0 > self.max.0
+                        || next.1 > self.max.1
+                    {
+                        continue;
+                    }
+                    let next_cost = current_cost + cost(current, next);
+                    if self.costs.get(&next).map_or(false, |&c| c <= next_cost) {
+                        continue;
+                    }
+                    if next != self.end_cell
+                        && !is_passable(terrain, self.entity_type, to_position(next))
+                    {
+                        continue;
+                    }
+                    self.costs.insert(next, next_cost);
+                    self.came_from.insert(next, current);
+                    self.open.push(Reverse((
+                        next_cost + cost(next, self.end_cell),
+                        next_cost,
+                        next,
+                    )));
+                }
+            }
+        }
+        PathStatus::Searching
+    }
+
+    /// Converts the path found to waypoints.
+    fn waypoints(&self, terrain: &Terrain) -> Vec<Vec2> {
+        let mut cells = vec![self.end_cell];
+        while let Some(&previous) = self.came_from.get(cells.last().unwrap()) {
+            cells.push(previous);
+        }
+        debug_assert_eq!(cells.last(), Some(&self.start_cell));
+        let mut points: Vec<Vec2> = cells.into_iter().rev().skip(1).map(to_position).collect();
+        if let Some(last) = points.last_mut() {
+            *last = self.end;
+        } else {
+            // Start and end share a cell.
+            points.push(self.end);
+        }
+
+        // Skip waypoints that can be bypassed in a straight line.
+        let mut waypoints = Vec::new();
+        let mut from = self.start;
+        let mut i = 0;
+        while i < points.len() {
+            let furthest = (i + 1..points.len())
+                .rev()
+                .find(|&j| is_clear(terrain, self.entity_type, from, points[j]))
+                .unwrap_or(i);
+            from = points[furthest];
+            waypoints.push(from);
+            i = furthest + 1;
+        }
+        waypoints
+    }
+}
+
+fn to_cell(pos: Vec2) -> Cell {
+    let cell = (pos / CELL).round();
+    (cell.x as i32, cell.y as i32)
+}
+
+fn to_position((x, y): Cell) -> Vec2 {
+    Vec2::new(x as f32, y as f32) * CELL
+}
+
+/// Fixed point, so costs are Ord.
+fn cost(a: Cell, b: Cell) -> u32 {
+    (to_position(a).distance(to_position(b)) * 16.0) as u32
+}
+
+/// Whether a boat could sail in a straight line from `start` to `end`.
+pub fn is_clear(terrain: &Terrain, entity_type: EntityType, start: Vec2, end: Vec2) -> bool {
+    if outside_strict_area(entity_type, start) || outside_strict_area(entity_type, end) {
+        // Strict area borders are horizontal lines, so checking the ends is enough.
+        return false;
+    }
+    if ignores_land(entity_type) {
+        return true;
+    }
+    let delta = end - start;
+    let length = delta.length();
+    terrain
+        .collides_with(
+            DimensionTransform {
+                transform: Transform {
+                    position: start + delta * 0.5,
+                    direction: Angle::from(delta),
+                    velocity: Velocity::ZERO,
+                },
+                dimensions: Vec2::new(length.max(CELL), entity_type.data().width),
+            },
+            Altitude::ZERO,
+            0.0,
+        )
+        .is_none()
+}
+
+/// Whether a boat could be at `position`.
+fn is_passable(terrain: &Terrain, entity_type: EntityType, position: Vec2) -> bool {
+    is_clear(terrain, entity_type, position, position)
+}
+
+/// Whether land doesn't get in the way of the boat. Dredgers and icebreakers are only immune to
+/// land within their strict areas, but they can't leave those anyway.
+fn ignores_land(entity_type: EntityType) -> bool {
+    matches!(
+        entity_type.data().sub_kind,
+        EntitySubKind::Hovercraft | EntitySubKind::Dredger | EntitySubKind::Icebreaker
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::EntityType;
+    use crate::navigation::{is_clear, plan_path, PathSearch, PathStatus};
+    use crate::terrain::{Terrain, SIZE};
+    use glam::Vec2;
+
+    /// A wall of land from (100, -500) to (150, 500).
+    fn wall_generator(x: usize, y: usize) -> u8 {
+        let (x, y) = (
+            x as isize - (SIZE / 2) as isize,
+            y as isize - (SIZE / 2) as isize,
+        );
+        if (4..=6).contains(&x) && (-20..=20).contains(&y) {
+            0b11110000
+        } else {
+            0
+        }
+    }
+
+    #[test]
+    fn open_water() {
+        let terrain = Terrain::with_generator(wall_generator);
+        let end = Vec2::new(-300.0, 200.0);
+        assert_eq!(
+            plan_path(&terrain, EntityType::G5, Vec2::ZERO, end),
+            Some(vec![end])
+        );
+    }
+
+    #[test]
+    fn around_wall() {
+        let terrain = Terrain::with_generator(wall_generator);
+        let (start, end) = (Vec2::ZERO, Vec2::new(300.0, 0.0));
+        assert!(!is_clear(&terrain, EntityType::G5, start, end));
+
+        let path = plan_path(&terrain, EntityType::G5, start, end).unwrap();
+        assert!(path.len() > 1);
+        assert_eq!(path.last(), Some(&end));
+        let mut from = start;
+        for &to in &path {
+            assert!(is_clear(&terrain, EntityType::G5, from, to));
+            from = to;
+        }
+    }
+
+    #[test]
+    fn into_wall() {
+        let terrain = Terrain::with_generator(wall_generator);
+        assert_eq!(
+            plan_path(&terrain, EntityType::G5, Vec2::ZERO, Vec2::new(125.0, 0.0)),
+            None
+        );
+    }
+
+    #[test]
+    fn spread_over_steps() {
+        let terrain = Terrain::with_generator(wall_generator);
+        let (start, end) = (Vec2::ZERO, Vec2::new(300.0, 0.0));
+
+        let mut search = PathSearch::new(&terrain, EntityType::G5, start, end);
+        let mut steps = 0;
+        let path = loop {
+            steps += 1;
+            match search.step(&terrain, 16) {
+                PathStatus::Searching => {}
+                PathStatus::Found(path) => break path,
+                PathStatus::Unreachable => panic!("unreachable"),
+            }
+        };
+        assert!(steps > 1);
+        assert_eq!(Some(path), plan_path(&terrain, EntityType::G5, start, end));
+    }
+}
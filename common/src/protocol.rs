@@ -1,11 +1,18 @@
 // SPDX-FileCopyrightText: 2021 Softbear, Inc.
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::altitude::Altitude;
+use crate::angle::Angle;
 use crate::contact::Contact;
 use crate::death_reason::DeathReason;
 use crate::entity::*;
 use crate::guidance::Guidance;
 use crate::terrain::{ChunkId, SerializedChunk};
+use crate::ticks::Ticks;
+use crate::transform::Transform;
+use common_util::range::map_ranges;
+use core_protocol::id::PlayerId;
+use core_protocol::name::PlayerAlias;
 use glam::Vec2;
 use serde::{Deserialize, Serialize};
 
@@ -23,6 +30,115 @@ pub struct Update {
     /// Current world border radius.
     pub world_radius: f32,
     pub terrain: Box<TerrainUpdate>,
+    /// Boats sunk by other players, anywhere in the world, since the last update.
+    pub kills: Vec<Kill>,
+    /// Damage dealt or received by the player's boat since the last update.
+    pub damage: Vec<Damage>,
+    /// Rounds remaining in each of the player's boat's armaments (see `Armament::magazine`).
+    /// Empty if the player doesn't have a boat.
+    pub magazines: Box<[u8]>,
+    /// Aircraft launched from the player's boat that are airborne or rearming.
+    pub air_wing: Vec<Sortie>,
+    /// World events that are currently in progress (for markers on the HUD).
+    pub events: Vec<WorldEvent>,
+    /// Ports within terrain chunks that are loaded by this update (see `terrain`).
+    pub ports: Vec<Port>,
+    /// Remaining immunity to damage, if the player's boat is docked at a port.
+    pub docked: Option<Ticks>,
+}
+
+/// A boat was sunk by another player (for the kill feed).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Kill {
+    pub killer: PlayerAlias,
+    /// None if the killer no longer had a boat (e.g. a torpedo fired before sinking).
+    pub killer_entity_type: Option<EntityType>,
+    pub victim: PlayerAlias,
+    pub victim_entity_type: EntityType,
+    /// None if the victim collided with the killer's boat.
+    pub weapon: Option<EntityType>,
+}
+
+/// An aircraft launched from the player's boat (for the air wing panel).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Sortie {
+    pub entity_type: EntityType,
+    pub status: SortieStatus,
+    /// Flight time remaining before running out of fuel (zero while rearming).
+    pub fuel: Ticks,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum SortieStatus {
+    /// Following the player's aim.
+    Airborne,
+    /// Out of fuel or weapons, so returning to land.
+    Returning,
+    /// Landed, until the armament reloads.
+    Rearming,
+}
+
+/// Damage between the player's boat and another player's boat (for the combat log).
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct Damage {
+    /// The other player.
+    pub player: PlayerAlias,
+    /// Weapon, or boat if collided.
+    pub entity_type: EntityType,
+    pub amount: Ticks,
+    /// Whether the player dealt, as opposed to received, the damage.
+    pub dealt: bool,
+}
+
+/// A scheduled event, announced to and marked for all players.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum WorldEvent {
+    /// Crates and coins worth collecting, until someone does.
+    SupplyDrop { position: Vec2 },
+    /// Extra score for whoever sinks a particular boat.
+    Bounty {
+        alias: PlayerAlias,
+        position: Vec2,
+        reward: u32,
+    },
+    /// Slows boats and downs aircraft that are within the radius.
+    Storm { position: Vec2, radius: f32 },
+}
+
+impl WorldEvent {
+    /// Position to place a marker at.
+    pub fn position(&self) -> Vec2 {
+        match *self {
+            Self::SupplyDrop { position }
+            | Self::Bounty { position, .. }
+            | Self::Storm { position, .. } => position,
+        }
+    }
+}
+
+/// A safe harbor along the coast, where boats may dock to repair and refit.
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Port {
+    pub position: Vec2,
+    /// Direction of the berth (towards open water). Docking boats may face either way along it.
+    pub direction: Angle,
+}
+
+impl Port {
+    /// Boats must be within this distance of a port to dock.
+    pub const RADIUS: f32 = 60.0;
+    /// Boats must be slower than this to dock.
+    pub const MAX_SPEED: f32 = 1.0;
+    /// Boats must be aligned with the berth within this many degrees to dock.
+    pub const MAX_ANGLE_DEGREES: f32 = 20.0;
+
+    /// Returns true if a boat with the given transform is docked at the port.
+    pub fn can_dock(&self, transform: &Transform) -> bool {
+        let deviation = (transform.direction - self.direction).abs();
+        transform.position.distance_squared(self.position) <= Self::RADIUS.powi(2)
+            && transform.velocity.to_mps().abs() < Self::MAX_SPEED
+            && deviation.min(Angle::PI - deviation) <= Angle::from_degrees(Self::MAX_ANGLE_DEGREES)
+    }
 }
 
 /// Updates for terrain chunks.
@@ -35,6 +151,7 @@ pub type TerrainUpdate = [(ChunkId, SerializedChunk)];
 pub enum Command {
     Control(Control),
     Spawn(Spawn),
+    Spectate(Spectate),
     Upgrade(Upgrade),
 }
 
@@ -43,8 +160,8 @@ pub enum Command {
 pub struct Control {
     /// Steering commands.
     pub guidance: Option<Guidance>,
-    /// Submerge submarine.
-    pub submerge: bool,
+    /// Depth for submarines to dive to.
+    pub depth: Depth,
     /// Turret/aircraft/pay target.
     pub aim_target: Option<Vec2>,
     /// Active sensors.
@@ -57,8 +174,82 @@ pub struct Control {
     pub hint: Option<Hint>,
 }
 
+/// Depth a submarine is ordered to dive to.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub enum Depth {
+    #[default]
+    Surface,
+    /// Just below the surface, retaining some use of visual and radar.
+    Periscope,
+    /// Below the thermocline.
+    Shallow,
+    /// As deep as the submarine can go, out of reach of unguided torpedoes.
+    Deep,
+}
+
+impl Depth {
+    /// Sonar is weakened between contacts on opposite sides of this layer.
+    pub const THERMOCLINE: Altitude = Altitude(-20);
+
+    pub const ALL: [Self; 4] = [Self::Surface, Self::Periscope, Self::Shallow, Self::Deep];
+
+    /// Target altitude, before being limited by terrain and the submarine's maximum depth.
+    pub fn altitude(self) -> Altitude {
+        match self {
+            Self::Surface => Altitude::ZERO,
+            Self::Periscope => Altitude(-3),
+            Self::Shallow => Altitude(-40),
+            Self::Deep => Altitude::MIN,
+        }
+    }
+
+    pub fn is_submerged(self) -> bool {
+        self != Self::Surface
+    }
+
+    /// Whether a submarine can't snorkel (and therefore recharge its battery) at this depth.
+    pub fn drains_battery(self) -> bool {
+        matches!(self, Self::Shallow | Self::Deep)
+    }
+
+    /// The next depth down, wrapping around to the surface.
+    pub fn cycle(self) -> Self {
+        match self {
+            Self::Surface => Self::Periscope,
+            Self::Periscope => Self::Shallow,
+            Self::Shallow => Self::Deep,
+            Self::Deep => Self::Surface,
+        }
+    }
+
+    /// Fraction of a submarine's top speed available at a submerged altitude. Raised masts can't
+    /// take the strain of full speed near the surface, and deeper down the hull can't either.
+    pub fn speed_factor(altitude: Altitude) -> f32 {
+        let meters = altitude.to_meters();
+        let periscope = Self::Periscope.altitude().to_meters();
+        let shallow = Self::Shallow.altitude().to_meters();
+        if altitude >= Self::Shallow.altitude() {
+            map_ranges(meters, periscope..shallow, 0.6..1.0, true)
+        } else {
+            map_ranges(meters, shallow..Altitude::MIN.to_meters(), 1.0..0.8, true)
+        }
+    }
+
+    /// Altitude below which the hull of a submarine, rated to `max_depth`, is crushed given its
+    /// damage fraction. An intact hull has a wide safety margin, which shrinks as it is damaged.
+    pub fn crush_altitude(max_depth: Altitude, damage: f32) -> Altitude {
+        let rated = max_depth.to_meters().min(-Altitude::MIN.to_meters());
+        Altitude::from_meters(-rated * 1.5 * (1.0 - damage.clamp(0.0, 1.0)))
+    }
+
+    /// Returns true if the thermocline lies between two altitudes.
+    pub fn across_thermocline(a: Altitude, b: Altitude) -> bool {
+        (a < Self::THERMOCLINE) != (b < Self::THERMOCLINE)
+    }
+}
+
 /// Fire/use a single weapon.
-#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
 pub struct Fire {
     /// The index of the weapon to fire/use, relative to `EntityData.armaments`.
     pub armament_index: u8,
@@ -80,26 +271,53 @@ impl Default for Hint {
 
 /// Pay one coin. TODO: Can't use Option<empty struct>, as serde_json serializes both [`None`] and
 /// [`Some`] to `"null"`.
-#[derive(Clone, PartialEq, Serialize, Deserialize, Debug)]
+#[derive(Clone, Eq, PartialEq, Serialize, Deserialize, Debug)]
 pub struct Pay;
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Spawn {
     /// What to spawn as. Must be an affordable boat.
     pub entity_type: EntityType,
+    /// Which of its loadouts (see `EntityData::loadouts`) to spawn with.
+    pub loadout: u8,
+}
+
+/// Watch the game without a boat. Only possible while not alive, and stops upon spawning.
+#[derive(Clone, Serialize, Deserialize, Debug)]
+pub struct Spectate {
+    /// None stops spectating.
+    pub target: Option<SpectateTarget>,
+}
+
+#[derive(Copy, Clone, Serialize, Deserialize, Debug, PartialEq)]
+pub enum SpectateTarget {
+    /// Follow a player's boat, seeing what it sees.
+    Player(PlayerId),
+    /// Move the camera freely, seeing everything within `FREE_RANGE`.
+    Free(Vec2),
+}
+
+impl SpectateTarget {
+    /// How far a free camera can see.
+    pub const FREE_RANGE: f32 = 1000.0;
+    /// How long a spectator must wait to spawn after watching beyond their own team (following a
+    /// non-teammate or using a free camera), so they can't scout for their team.
+    pub const SPAWN_DELAY: Ticks = Ticks::from_whole_secs(30);
 }
 
 #[derive(Clone, Serialize, Deserialize, Debug)]
 pub struct Upgrade {
     /// What to upgrade to. Must be an affordable boat of higher level.
     pub entity_type: EntityType,
+    /// Which of its loadouts (see `EntityData::loadouts`) to upgrade to.
+    pub loadout: u8,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::altitude::Altitude;
-    use crate::contact::ReloadsStorage;
+    use crate::contact::{ContactTrait, ReloadsStorage};
     use crate::entity::EntityId;
     use crate::guidance::Guidance;
     use crate::ticks::Ticks;
@@ -111,6 +329,7 @@ mod tests {
     use glam::vec2;
     use rand::prelude::*;
     use std::num::NonZeroU32;
+    use std::ptr;
 
     #[test]
     fn serialize() {
@@ -122,9 +341,25 @@ mod tests {
                 .gen_bool(0.5)
                 .then(|| EntityType::iter().choose(&mut rng).unwrap());
             let is_boat = entity_type.map_or(false, |t| t.data().kind == EntityKind::Boat);
+            let reloads = (is_boat && rng.gen_bool(0.5)).then(|| {
+                let mut arr = BitArray::<ReloadsStorage>::ZERO;
+                for (_, mut r) in entity_type
+                    .unwrap()
+                    .data()
+                    .armaments
+                    .iter()
+                    .zip(arr.iter_mut())
+                {
+                    *r = rng.gen();
+                }
+                arr
+            });
+            let is_submarine =
+                entity_type.map_or(false, |t| t.data().sub_kind == EntitySubKind::Submarine);
 
             let c = Contact::new(
                 Altitude::from_u8(rng.gen()),
+                (reloads.is_some() && is_submarine).then(|| rng.gen()),
                 Ticks::from_secs(rng.gen::<f32>() * 10.0),
                 entity_type,
                 Guidance {
@@ -132,21 +367,12 @@ mod tests {
                     velocity_target: Velocity::from_mps(rng.gen::<f32>() * 3.0),
                 },
                 EntityId::new(rng.gen_range(1..u32::MAX)).unwrap(),
+                entity_type.map_or(0, |t| {
+                    rng.gen_range(0..t.data().loadouts.len().max(1)) as u8
+                }),
                 rng.gen_bool(0.5)
                     .then(|| PlayerId(NonZeroU32::new(rng.gen_range(1..u32::MAX)).unwrap())),
-                (is_boat && rng.gen_bool(0.5)).then(|| {
-                    let mut arr = BitArray::<ReloadsStorage>::ZERO;
-                    for (_, mut r) in entity_type
-                        .unwrap()
-                        .data()
-                        .armaments
-                        .iter()
-                        .zip(arr.iter_mut())
-                    {
-                        *r = rng.gen();
-                    }
-                    arr
-                }),
+                reloads,
                 Transform {
                     position: vec2(
                         rng.gen::<f32>() * 1000.0 - 500.0,
@@ -189,4 +415,129 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn serialize_loadout() {
+        let round_trip = |entity_type: EntityType, loadout: u8| {
+            let data = entity_type.data();
+            let c = Contact::new(
+                Altitude::ZERO,
+                None,
+                Ticks::ZERO,
+                Some(entity_type),
+                Guidance::default(),
+                EntityId::new(1).unwrap(),
+                loadout,
+                Some(PlayerId(NonZeroU32::new(1).unwrap())),
+                Some(BitArray::<ReloadsStorage>::ZERO),
+                Transform::default(),
+                Some(data.turrets.iter().map(|t| t.angle).collect()),
+            );
+
+            let options = DefaultOptions::new()
+                .with_fixint_encoding()
+                .allow_trailing_bytes();
+            let bytes = options.serialize(&c).unwrap();
+            let contact = options.deserialize::<Contact>(&bytes).unwrap();
+            assert_eq!(c, contact);
+            contact.loadout()
+        };
+
+        // Boat with a choice of loadouts.
+        assert!(!EntityType::ArleighBurke.data().loadouts.is_empty());
+        for loadout in 0..EntityType::ArleighBurke.data().loadouts.len() as u8 {
+            assert_eq!(round_trip(EntityType::ArleighBurke, loadout), loadout);
+        }
+
+        // Boat with only the default loadout.
+        assert!(EntityType::Bismarck.data().loadouts.is_empty());
+        assert_eq!(round_trip(EntityType::Bismarck, 0), 0);
+    }
+
+    #[test]
+    fn loadouts() {
+        for entity_type in EntityType::iter() {
+            let data = entity_type.data();
+            assert!(data.is_valid_loadout(0), "{:?}", entity_type);
+            assert!(
+                !data.is_valid_loadout(data.loadouts.len().max(1) as u8),
+                "{:?}",
+                entity_type
+            );
+            assert!(!data.is_valid_loadout(u8::MAX), "{:?}", entity_type);
+
+            // Invalid loadouts fall back to the default.
+            assert!(ptr::eq(data.loadout_armaments(u8::MAX), data.armaments));
+            if data.loadouts.is_empty() {
+                assert!(ptr::eq(data.loadout_armaments(0), data.armaments));
+            }
+            for (i, loadout) in data.loadouts.iter().enumerate() {
+                let armaments = data.loadout_armaments(i as u8);
+                assert!(data.is_valid_loadout(i as u8), "{:?}", entity_type);
+                assert!(ptr::eq(armaments, loadout.armaments));
+                assert_eq!(armaments.len(), data.armaments.len(), "{:?}", entity_type);
+            }
+        }
+
+        // Anti-Air swaps Harpoons for Essms.
+        let data = EntityType::ArleighBurke.data();
+        let anti_air = data.loadout_armaments(1);
+        assert!(data
+            .armaments
+            .iter()
+            .any(|a| a.entity_type == EntityType::Harpoon));
+        assert!(anti_air
+            .iter()
+            .all(|a| a.entity_type != EntityType::Harpoon));
+        for (default, replaced) in data.armaments.iter().zip(anti_air) {
+            if default.entity_type == EntityType::Harpoon {
+                assert_eq!(replaced.entity_type, EntityType::Essm);
+                assert_eq!(replaced.magazine, Some(12));
+            } else {
+                assert_eq!(replaced.entity_type, default.entity_type);
+            }
+        }
+    }
+
+    #[test]
+    fn depth_speed() {
+        let periscope = Depth::speed_factor(Depth::Periscope.altitude());
+        let shallow = Depth::speed_factor(Depth::Shallow.altitude());
+        let deep = Depth::speed_factor(Depth::Deep.altitude());
+        assert!(
+            periscope < deep && deep < shallow,
+            "{} {} {}",
+            periscope,
+            shallow,
+            deep
+        );
+        assert!((shallow - 1.0).abs() < 0.001);
+
+        // Changes gradually while diving.
+        let mut previous = periscope;
+        for altitude in (Depth::Shallow.altitude().0..Depth::Periscope.altitude().0).rev() {
+            let speed = Depth::speed_factor(Altitude(altitude));
+            assert!(speed >= previous);
+            previous = speed;
+        }
+    }
+
+    #[test]
+    fn crush_depth() {
+        let max_depth = Altitude::from_whole_meters(400);
+        let crushed = |depth: Depth, damage: f32| {
+            depth.altitude().max(-max_depth) < Depth::crush_altitude(max_depth, damage)
+        };
+
+        // An intact hull is safe at any depth it can reach.
+        for depth in Depth::ALL {
+            assert!(!crushed(depth, 0.0), "{:?}", depth);
+        }
+
+        // Damage makes deeper depths dangerous first.
+        assert!(crushed(Depth::Deep, 0.5));
+        assert!(!crushed(Depth::Shallow, 0.5));
+        assert!(crushed(Depth::Shallow, 0.8));
+        assert!(!crushed(Depth::Periscope, 0.8));
+    }
 }
@@ -125,19 +125,17 @@ impl Coord {
 
     fn from_scaled_position(v: Vec2) -> Option<Self> {
         let v = v + Vec2::splat(OFFSET as f32);
-        debug_assert!(v.cmpge(Vec2::ZERO).all() && v.cmple(UVec2::splat(u32::MAX).as_vec2()).all());
-        let coord = unsafe {
+        // Also rejects NaN.
+        if !(v.cmpge(Vec2::ZERO).all() && v.cmplt(Vec2::splat(SIZE as f32)).all()) {
+            return None;
+        }
+        // Safety: v is finite and in range.
+        Some(unsafe {
             Self(
                 v.x.to_int_unchecked::<usize>(),
                 v.y.to_int_unchecked::<usize>(),
             )
-        };
-
-        if coord.0 >= SIZE || coord.1 >= SIZE {
-            None
-        } else {
-            Some(coord)
-        }
+        })
     }
 
     fn offset(&self, v: UVec2) -> Option<Self> {
@@ -246,7 +244,7 @@ impl TryFrom<Vec2> for ChunkId {
 
     fn try_from(mut pos: Vec2) -> Result<Self, Self::Error> {
         pos *= 1.0 / (SCALE * CHUNK_SIZE as f32);
-        pos += SIZE as f32 / 2.0;
+        pos += SIZE_CHUNKS as f32 / 2.0;
         let (x, y) = (pos.x as i32, pos.y as i32);
         const RANGE: RangeInclusive<i32> = 0..=((SIZE_CHUNKS - 1) as i32);
         if RANGE.contains(&x) && RANGE.contains(&y) {
@@ -405,6 +403,24 @@ impl Terrain {
         chunk.as_ref().unwrap()
     }
 
+    /// Gets the unsmoothed [`Altitude`] at a position, without generating any chunks. Returns None if
+    /// the position is outside the terrain, or its chunk was never generated or loaded (e.g. a
+    /// client hasn't explored it yet).
+    pub fn sample_loaded(&self, pos: Vec2) -> Option<Altitude> {
+        const HALF: f32 = (SIZE / 2) as f32 * SCALE;
+        if pos.abs().cmpge(Vec2::splat(HALF)).any() {
+            return None;
+        }
+        let coord = Coord::from_position(pos)?;
+        let chunk_id = ChunkId::from_coord(coord);
+        let chunk = unsafe {
+            let ptr: &AtomicPtr<Chunk> =
+                transmute(&self.chunks[chunk_id.1 as usize][chunk_id.0 as usize]);
+            ptr.load(Ordering::Acquire).as_ref()
+        }?;
+        Some(lookup_altitude(chunk.at(coord)))
+    }
+
     /// Applies a terrain update, overwriting relevant terrain pixels.
     pub fn apply_update(&mut self, update: &TerrainUpdate) {
         for (chunk_id, serialized) in update.iter() {
@@ -468,6 +484,13 @@ impl Terrain {
         }
     }
 
+    /// Gets the Altitude at a position as originally generated (ignoring any modifications),
+    /// without generating any chunks. For placing features alongside the terrain.
+    pub fn sample_generated(&self, pos: Vec2) -> Option<Altitude> {
+        let Coord(x, y) = Coord::from_position(pos)?;
+        Some(lookup_altitude((self.generator)(x, y)))
+    }
+
     /// Gets the smoothed Altitude at a position.
     pub fn sample(&self, pos: Vec2) -> Option<Altitude> {
         fn cubic(v: f32) -> Vec4 {
@@ -500,12 +523,12 @@ impl Terrain {
 
         let sx = s.x / (s.x + s.y);
         let sy = s.z / (s.z + s.w);
-        debug_assert!(sx >= 0.0 && sx <= 1.0);
-        debug_assert!(sy >= 0.0 && sy <= 1.0);
+        debug_assert!((0.0..=1.0).contains(&sx));
+        debug_assert!((0.0..=1.0).contains(&sy));
 
         // NOTE: lerp isn't cross platform deterministic.
         let v = lerp(lerp(sample3, sample2, sx), lerp(sample1, sample0, sx), sy);
-        debug_assert!(v >= 0.0 && v <= 255.0);
+        debug_assert!((0.0..=255.0).contains(&v));
 
         Some(lookup_altitude_f32(v))
     }
@@ -550,6 +573,26 @@ impl Terrain {
         ))
     }
 
+    /// Returns true if no terrain rises above the straight line from `start` to `end`, at heights of
+    /// `start_height` and `end_height` meters respectively. Samples once per [`SCALE`] meters
+    /// (excluding the ends), so is fast but may see through very thin strips of land.
+    pub fn line_of_sight(
+        &self,
+        start: Vec2,
+        start_height: f32,
+        end: Vec2,
+        end_height: f32,
+    ) -> bool {
+        let steps = (start.distance(end) * (1.0 / SCALE)) as usize;
+        let inv_steps = 1.0 / steps as f32;
+        (1..steps).all(|i| {
+            let t = i as f32 * inv_steps;
+            self.sample(start.lerp(end, t)).map_or(true, |alt| {
+                alt.to_meters() < lerp(start_height, end_height, t)
+            })
+        })
+    }
+
     /// collides_with returns one point (and the altitude there) of collision if an entity collides
     /// with the terrain any time in the next delta_seconds.
     pub fn collides_with(
@@ -1048,7 +1091,7 @@ impl Chunk {
     }
 
     pub fn apply_serialized_chunk(&mut self, serialized: &SerializedChunk) {
-        let bytes: &[u8] = &*serialized.bytes;
+        let bytes: &[u8] = &serialized.bytes;
 
         self.update = if serialized.is_update {
             // Apply mods and collect coords.
@@ -1181,6 +1224,7 @@ impl ChunkSet {
     }
 
     /// Iterates all ChunkIds in the set.
+    #[allow(clippy::should_implement_trait)]
     pub fn into_iter(self) -> impl Iterator<Item = ChunkId> {
         (0..Self::DATA_SIZE * Self::ROW_SIZE)
             .filter(move |i| self.contains_index(*i))
@@ -1386,6 +1430,45 @@ mod tests {
         assert!(lookup_altitude_f32((0.5 + 0.000001) * 255.0) >= Altitude::ZERO);
     }
 
+    #[test]
+    fn chunk_id_from_position() {
+        for pos in [Vec2::ZERO, Vec2::new(-1234.0, 567.0), Vec2::splat(-6000.0)] {
+            assert_eq!(ChunkId::try_from(pos), Ok(ChunkId::saturating_from(pos)));
+        }
+        assert!(ChunkId::try_from(Vec2::splat(1.0e6)).is_err());
+    }
+
+    #[test]
+    fn sample_loaded() {
+        let mut terrain = Terrain::with_generator(random_generator);
+        let pos = Vec2::splat(50.0);
+        assert_eq!(terrain.sample_loaded(pos), None);
+        assert_eq!(terrain.sample_loaded(Vec2::splat(f32::MAX)), None);
+
+        terrain.mut_chunk(ChunkId::from_coord(Coord::from_position(pos).unwrap()));
+        assert!(terrain.sample_loaded(pos).is_some());
+    }
+
+    #[test]
+    fn line_of_sight() {
+        fn land_generator(_: usize, _: usize) -> u8 {
+            0b11110000
+        }
+
+        let start = Vec2::splat(-250.0);
+        let end = Vec2::splat(250.0);
+
+        let ocean = Terrain::with_generator(zero_generator);
+        assert!(ocean.line_of_sight(start, 10.0, end, 10.0));
+
+        let land = Terrain::with_generator(land_generator);
+        assert!(!land.line_of_sight(start, 10.0, end, 10.0));
+        assert!(land.line_of_sight(start, 1000.0, end, 1000.0));
+
+        // Too close together to sample in between.
+        assert!(land.line_of_sight(start, 10.0, start + SCALE * 0.5, 10.0));
+    }
+
     #[test]
     fn compress() {
         let mut terrain = Terrain::with_generator(random_generator);
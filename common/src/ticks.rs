@@ -14,3 +14,8 @@ const REGEN_DAMAGE: Ticks = Ticks::from_whole_secs(60);
 pub fn from_damage(damage: f32) -> Ticks {
     Ticks::from_secs(damage * REGEN_DAMAGE.to_secs())
 }
+
+/// to_damage is the inverse of from_damage.
+pub fn to_damage(ticks: Ticks) -> f32 {
+    ticks.to_secs() / REGEN_DAMAGE.to_secs()
+}
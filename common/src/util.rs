@@ -187,7 +187,7 @@ mod test {
                 let natural = died == beneficiary || rng.gen_bool(0.5);
                 let mut winnings = boats[died]
                     .0
-                    .loot(boats[died].1, natural)
+                    .loot(boats[died].1, natural, false)
                     .map(|t| match t {
                         EntityType::Coin => 10,
                         _ => 2,
@@ -206,7 +206,7 @@ mod test {
                 boats[beneficiary].1 += winnings;
             }
 
-            fn total_score(boats: &Vec<(EntityType, u32)>) -> u32 {
+            fn total_score(boats: &[(EntityType, u32)]) -> u32 {
                 boats.iter().map(|b| b.1).sum()
             }
 
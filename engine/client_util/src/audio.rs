@@ -77,9 +77,11 @@ impl<A: Audio> Default for AudioPlayer<A> {
                     spooky: PhantomData,
                 })));
 
-                let promise = js_hooks::window().fetch_with_str(&A::path());
+                let promise = js_hooks::window().fetch_with_str(A::path());
                 let inner_clone = inner.clone();
 
+                // The borrow is dropped before yielding (see below).
+                #[allow(clippy::await_holding_refcell_ref)]
                 let _ = future_to_promise(async move {
                     let response: Response =
                         JsFuture::from(promise).await.unwrap().dyn_into().unwrap();
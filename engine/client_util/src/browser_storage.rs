@@ -14,6 +14,12 @@ pub struct BrowserStorages {
     pub no_op: BrowserStorage,
 }
 
+impl Default for BrowserStorages {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl BrowserStorages {
     pub fn new() -> Self {
         Self {
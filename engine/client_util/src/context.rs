@@ -5,13 +5,16 @@ use crate::apply::Apply;
 use crate::browser_storage::BrowserStorages;
 use crate::frontend::Frontend;
 use crate::game_client::GameClient;
+use crate::gamepad::GamepadState;
 use crate::js_util::{domain_name_of, host, invitation_id, is_https, ws_protocol};
 use crate::keyboard::KeyboardState;
 use crate::mouse::MouseState;
 use crate::reconn_web_socket::ReconnWebSocket;
 use crate::setting::CommonSettings;
 use crate::visibility::VisibilityState;
-use core_protocol::dto::{LeaderboardDto, LiveboardDto, MessageDto, PlayerDto, ServerDto, TeamDto};
+use core_protocol::dto::{
+    LeaderboardDto, LiveboardDto, MessageDto, PingDto, PingKind, PlayerDto, ServerDto, TeamDto,
+};
 use core_protocol::id::{CohortId, InvitationId, LoginType, PeriodId, PlayerId, ServerId, TeamId};
 use core_protocol::name::PlayerAlias;
 use core_protocol::rpc::{
@@ -42,6 +45,8 @@ pub struct Context<G: GameClient + ?Sized> {
     pub keyboard: KeyboardState,
     /// Mouse input.
     pub mouse: MouseState,
+    /// Gamepad input.
+    pub gamepad: GamepadState,
     /// Whether the page is visible.
     pub visibility: VisibilityState,
     /// Settings.
@@ -80,6 +85,8 @@ pub struct CoreState {
     pub leaderboards: [Box<[LeaderboardDto]>; std::mem::variant_count::<PeriodId>()],
     pub liveboard: Vec<LiveboardDto>,
     pub messages: HistoryBuffer<MessageDto, 9>,
+    /// Pings from team members, and when they were received (Unix seconds).
+    pings: Vec<(PingDto, f64)>,
     pub(crate) players: HashMap<PlayerId, PlayerDto>,
     pub real_players: u32,
     pub teams: HashMap<TeamId, TeamDto>,
@@ -140,7 +147,7 @@ impl CoreState {
                     team_id: None,
                 })
             })
-            .unwrap_or_else(|| self.players.get(&player_id).map(|r| r.clone()))
+            .unwrap_or_else(|| self.players.get(&player_id).cloned())
     }
 
     /// Gets hashmap that contains players, but *not* bots.
@@ -166,37 +173,48 @@ impl CoreState {
     pub fn leaderboard(&self, period_id: PeriodId) -> &[LeaderboardDto] {
         &self.leaderboards[period_id as usize]
     }
+
+    /// Iterates pings from team members that haven't expired, along with their age in seconds.
+    pub fn pings(&self) -> impl Iterator<Item = (&PingDto, f32)> + '_ {
+        let now = now_seconds();
+        self.pings.iter().filter_map(move |(ping, received)| {
+            let age = (now - received) as f32;
+            (age < PingKind::DURATION_SECONDS).then_some((ping, age))
+        })
+    }
+}
+
+/// Unix seconds.
+fn now_seconds() -> f64 {
+    js_sys::Date::now() * (1.0 / 1000.0)
 }
 
 impl<G: GameClient> Apply<Update<G::GameUpdate>> for ServerState<G> {
     fn apply(&mut self, update: Update<G::GameUpdate>) {
-        // Use rc_borrow_mut to keep semantics of shared references the same while sharing with
+        // Use RcBorrowMut to keep semantics of shared references the same while sharing with
         // yew_frontend.
-        use rc_borrow_mut::RcBorrowMut;
+        use crate::rc_borrow_mut::RcBorrowMut;
         let mut core = Rc::borrow_mut(&mut self.core);
 
         match update {
             Update::Chat(update) => {
-                match update {
-                    ChatUpdate::Received(received) => {
-                        // Need to use into_vec since
-                        // https://github.com/rust-lang/rust/issues/59878 is incomplete.
-                        core.messages.extend(received.into_vec());
-                    }
-                    _ => {}
+                if let ChatUpdate::Received(received) = update {
+                    // Need to use into_vec since
+                    // https://github.com/rust-lang/rust/issues/59878 is incomplete.
+                    core.messages.extend(received.into_vec());
                 }
             }
-            Update::Client(update) => match update {
-                ClientUpdate::SessionCreated {
+            Update::Client(update) => {
+                if let ClientUpdate::SessionCreated {
                     cohort_id,
                     player_id,
                     ..
-                } => {
+                } = update
+                {
                     core.cohort_id = Some(cohort_id);
                     core.player_id = Some(player_id);
                 }
-                _ => {}
-            },
+            }
             Update::Game(update) => {
                 self.game.apply(update);
             }
@@ -244,12 +262,13 @@ impl<G: GameClient> Apply<Update<G::GameUpdate>> for ServerState<G> {
                     }
                 }
             }
-            Update::Player(update) => match update {
-                PlayerUpdate::Updated {
+            Update::Player(update) => {
+                if let PlayerUpdate::Updated {
                     added,
                     removed,
                     real_players,
-                } => {
+                } = update
+                {
                     for player in added.into_vec() {
                         core.players.insert(player.player_id, player);
                     }
@@ -258,8 +277,7 @@ impl<G: GameClient> Apply<Update<G::GameUpdate>> for ServerState<G> {
                     }
                     core.real_players = real_players;
                 }
-                _ => {}
-            },
+            }
             Update::System(update) => match update {
                 SystemUpdate::Added(added) => {
                     for server in added.into_vec() {
@@ -292,6 +310,13 @@ impl<G: GameClient> Apply<Update<G::GameUpdate>> for ServerState<G> {
                         core.teams.remove(team_id);
                     }
                 }
+                TeamUpdate::Pinged(pings) => {
+                    let now = now_seconds();
+                    core.pings
+                        .retain(|(_, received)| now - received < PingKind::DURATION_SECONDS as f64);
+                    core.pings
+                        .extend(pings.into_vec().into_iter().map(|ping| (ping, now)));
+                }
                 _ => {}
             },
         }
@@ -317,6 +342,7 @@ impl<G: GameClient> Context<G> {
             socket,
             keyboard: KeyboardState::default(),
             mouse: MouseState::default(),
+            gamepad: GamepadState::default(),
             visibility: VisibilityState::default(),
             settings,
             common_settings,
@@ -330,8 +356,8 @@ impl<G: GameClient> Context<G> {
         override_server_id: Option<ServerId>,
         frontend: &dyn Frontend<G::UiProps>,
     ) -> (String, Option<ServerId>) {
-        let scheme = ws_protocol(frontend.get_real_encryption().unwrap_or(is_https()));
-        let ideal_server_id = override_server_id.or(frontend.get_ideal_server_id());
+        let scheme = ws_protocol(frontend.get_real_encryption().unwrap_or_else(is_https));
+        let ideal_server_id = override_server_id.or_else(|| frontend.get_ideal_server_id());
         let host = frontend.get_real_host().unwrap_or_else(host);
 
         let ideal_host = ideal_server_id
@@ -444,6 +470,6 @@ pub struct StrongCoreState<'a> {
 impl<'a> std::ops::Deref for StrongCoreState<'a> {
     type Target = CoreState;
     fn deref(&self) -> &Self::Target {
-        &*self.inner
+        &self.inner
     }
 }
@@ -3,6 +3,7 @@
 
 use crate::apply::Apply;
 use crate::context::Context;
+use crate::gamepad::GamepadEvent;
 use crate::keyboard::KeyboardEvent;
 use crate::mouse::MouseEvent;
 use crate::setting::Settings;
@@ -52,6 +53,9 @@ pub trait GameClient: Sized + 'static {
         let _ = event;
     }
 
+    /// Peek at a gamepad event before it is applied to `GamepadState`.
+    fn peek_gamepad(&mut self, _event: &GamepadEvent, _context: &mut Context<Self>) {}
+
     /// Peek at a visibility event before it is applied to `VisibilityState`.
     fn peek_visibility(&mut self, event: &VisibilityEvent, _context: &mut Context<Self>) {
         let _ = event;
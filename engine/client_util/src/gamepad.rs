@@ -0,0 +1,266 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::apply::Apply;
+use crate::keyboard::KeyState;
+use common_util::range::map_ranges;
+use glam::Vec2;
+use wasm_bindgen::JsCast;
+use web_sys::{Gamepad, GamepadMappingType};
+
+/// Each variant is a button of a gamepad with the "standard" mapping, in the order of that mapping.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum GamepadButton {
+    /// A on Xbox, cross on PlayStation.
+    South,
+    /// B on Xbox, circle on PlayStation.
+    East,
+    /// X on Xbox, square on PlayStation.
+    West,
+    /// Y on Xbox, triangle on PlayStation.
+    North,
+    LeftBumper,
+    RightBumper,
+    LeftTrigger,
+    RightTrigger,
+    Select,
+    Start,
+    LeftStick,
+    RightStick,
+    DPadUp,
+    DPadDown,
+    DPadLeft,
+    DPadRight,
+    Home,
+}
+
+impl GamepadButton {
+    pub const ALL: [Self; std::mem::variant_count::<Self>()] = [
+        Self::South,
+        Self::East,
+        Self::West,
+        Self::North,
+        Self::LeftBumper,
+        Self::RightBumper,
+        Self::LeftTrigger,
+        Self::RightTrigger,
+        Self::Select,
+        Self::Start,
+        Self::LeftStick,
+        Self::RightStick,
+        Self::DPadUp,
+        Self::DPadDown,
+        Self::DPadLeft,
+        Self::DPadRight,
+        Self::Home,
+    ];
+
+    /// Converts from an index into the buttons of a gamepad with the standard mapping.
+    pub fn try_from_index(index: u32) -> Option<Self> {
+        Self::ALL.get(index as usize).copied()
+    }
+}
+
+/// A change in the state of the gamepad.
+pub enum GamepadEvent {
+    /// A button was pressed or released.
+    Button {
+        button: GamepadButton,
+        down: bool,
+        time: f32,
+    },
+    /// The analog inputs changed. Sticks are already adjusted for dead zone and sensitivity, and
+    /// have positive y pointing down (as reported by the browser).
+    Axes {
+        left_stick: Vec2,
+        right_stick: Vec2,
+        left_trigger: f32,
+        right_trigger: f32,
+    },
+    /// The gamepad was disconnected (or lost its standard mapping).
+    Disconnected,
+}
+
+/// The entire current state of the gamepad.
+pub struct GamepadState {
+    /// Whether a gamepad with the standard mapping is connected.
+    pub connected: bool,
+    pub(crate) states: [KeyState; std::mem::variant_count::<GamepadButton>()],
+    /// Each component is from -1 to 1.
+    pub left_stick: Vec2,
+    /// Each component is from -1 to 1.
+    pub right_stick: Vec2,
+    /// From 0 to 1.
+    pub left_trigger: f32,
+    /// From 0 to 1.
+    pub right_trigger: f32,
+}
+
+impl Default for GamepadState {
+    fn default() -> Self {
+        Self {
+            connected: false,
+            states: [KeyState::Up; std::mem::variant_count::<GamepadButton>()],
+            left_stick: Vec2::ZERO,
+            right_stick: Vec2::ZERO,
+            left_trigger: 0.0,
+            right_trigger: 0.0,
+        }
+    }
+}
+
+impl Apply<GamepadEvent> for GamepadState {
+    fn apply(&mut self, event: GamepadEvent) {
+        match event {
+            GamepadEvent::Button { button, down, time } => {
+                self.connected = true;
+                if self.is_down(button) != down {
+                    self.states[button as usize] = if down {
+                        KeyState::Down(time)
+                    } else {
+                        KeyState::Up
+                    };
+                }
+            }
+            GamepadEvent::Axes {
+                left_stick,
+                right_stick,
+                left_trigger,
+                right_trigger,
+            } => {
+                self.connected = true;
+                self.left_stick = left_stick;
+                self.right_stick = right_stick;
+                self.left_trigger = left_trigger;
+                self.right_trigger = right_trigger;
+            }
+            GamepadEvent::Disconnected => self.reset(),
+        }
+    }
+}
+
+impl GamepadState {
+    /// Immutable reference to state of one button.
+    pub fn state(&self, button: GamepadButton) -> &KeyState {
+        &self.states[button as usize]
+    }
+
+    /// See `KeyState::is_down`.
+    pub fn is_down(&self, button: GamepadButton) -> bool {
+        self.state(button).is_down()
+    }
+
+    /// See `KeyState::is_up`.
+    pub fn is_up(&self, button: GamepadButton) -> bool {
+        self.state(button).is_up()
+    }
+
+    /// Polls the browser for the first connected gamepad with the standard mapping, returning the
+    /// events required to bring `self` up to date with it.
+    pub(crate) fn poll(
+        &self,
+        time: f32,
+        dead_zone: f32,
+        sensitivity: f32,
+    ) -> impl Iterator<Item = GamepadEvent> {
+        let mut events = Vec::new();
+
+        let gamepad = js_hooks::window()
+            .navigator()
+            .get_gamepads()
+            .ok()
+            .and_then(|gamepads| {
+                gamepads
+                    .iter()
+                    .filter_map(|gamepad| gamepad.dyn_into::<Gamepad>().ok())
+                    .find(|gamepad| {
+                        gamepad.connected() && gamepad.mapping() == GamepadMappingType::Standard
+                    })
+            });
+
+        if let Some(gamepad) = gamepad {
+            let buttons = gamepad.buttons();
+            let value = |button: GamepadButton| -> f32 {
+                buttons
+                    .get(button as u32)
+                    .dyn_into::<web_sys::GamepadButton>()
+                    .map(|b| b.value() as f32)
+                    .unwrap_or(0.0)
+            };
+
+            for button in GamepadButton::ALL {
+                let down = buttons
+                    .get(button as u32)
+                    .dyn_into::<web_sys::GamepadButton>()
+                    .map(|b| b.pressed())
+                    .unwrap_or(false);
+                if self.is_down(button) != down {
+                    events.push(GamepadEvent::Button { button, down, time });
+                }
+            }
+
+            let axes = gamepad.axes();
+            let axis = |index: u32| axes.get(index).as_f64().unwrap_or(0.0) as f32;
+            let left_stick = adjust_stick(Vec2::new(axis(0), axis(1)), dead_zone, sensitivity);
+            let right_stick = adjust_stick(Vec2::new(axis(2), axis(3)), dead_zone, sensitivity);
+            let left_trigger = value(GamepadButton::LeftTrigger);
+            let right_trigger = value(GamepadButton::RightTrigger);
+
+            if !self.connected
+                || left_stick != self.left_stick
+                || right_stick != self.right_stick
+                || left_trigger != self.left_trigger
+                || right_trigger != self.right_trigger
+            {
+                events.push(GamepadEvent::Axes {
+                    left_stick,
+                    right_stick,
+                    left_trigger,
+                    right_trigger,
+                });
+            }
+        } else if self.connected {
+            events.push(GamepadEvent::Disconnected);
+        }
+
+        events.into_iter()
+    }
+}
+
+/// Applies a radial dead zone (fraction of full deflection that is ignored), followed by
+/// sensitivity (multiplier, clamped to full deflection) to a stick.
+pub fn adjust_stick(stick: Vec2, dead_zone: f32, sensitivity: f32) -> Vec2 {
+    let magnitude = stick.length();
+    if magnitude <= dead_zone || magnitude == 0.0 {
+        Vec2::ZERO
+    } else {
+        let adjusted = map_ranges(magnitude, dead_zone..1.0, 0.0..1.0, true) * sensitivity;
+        stick * (adjusted.min(1.0) / magnitude)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::gamepad::adjust_stick;
+    use glam::Vec2;
+
+    #[test]
+    fn dead_zone() {
+        assert_eq!(adjust_stick(Vec2::new(0.1, 0.0), 0.15, 1.0), Vec2::ZERO);
+        assert_eq!(adjust_stick(Vec2::ZERO, 0.0, 1.0), Vec2::ZERO);
+        assert!(
+            adjust_stick(Vec2::new(0.0, -1.0), 0.15, 1.0).abs_diff_eq(Vec2::new(0.0, -1.0), 0.0001)
+        );
+    }
+
+    #[test]
+    fn sensitivity() {
+        assert!(
+            adjust_stick(Vec2::new(0.5, 0.0), 0.0, 1.5).abs_diff_eq(Vec2::new(0.75, 0.0), 0.0001)
+        );
+        // Clamped to full deflection.
+        assert!(
+            adjust_stick(Vec2::new(0.9, 0.0), 0.0, 2.0).abs_diff_eq(Vec2::new(1.0, 0.0), 0.0001)
+        );
+    }
+}
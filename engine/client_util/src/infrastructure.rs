@@ -126,7 +126,7 @@ impl<G: GameClient> Infrastructure<G> {
                 Update::Client(ClientUpdate::EvalSnippet(snippet)) => {
                     // Do NOT use `eval`, since it runs in the local scope and therefore
                     // prevents minification.
-                    let _ = Function::new_no_args(&snippet).call0(&JsValue::NULL);
+                    let _ = Function::new_no_args(snippet).call0(&JsValue::NULL);
                     // TODO: send result back to server.
                 }
                 _ => {}
@@ -138,6 +138,8 @@ impl<G: GameClient> Infrastructure<G> {
             self.context.state.apply(inbound);
         }
 
+        self.gamepad(time_seconds);
+
         self.game.tick(elapsed_seconds, &mut self.context);
 
         if let Some(fps) = self.statistic_fps_monitor.update(elapsed_seconds) {
@@ -146,6 +148,19 @@ impl<G: GameClient> Infrastructure<G> {
         }
     }
 
+    /// Gamepads must be polled, as opposed to producing events like the keyboard and mouse.
+    fn gamepad(&mut self, time_seconds: f32) {
+        let events = self.context.gamepad.poll(
+            time_seconds,
+            self.context.common_settings.gamepad_dead_zone,
+            self.context.common_settings.gamepad_sensitivity,
+        );
+        for e in events {
+            self.game.peek_gamepad(&e, &mut self.context);
+            self.context.gamepad.apply(e);
+        }
+    }
+
     pub fn keyboard(&mut self, event: KeyboardEvent) {
         if let Some(target) = event.target() {
             if target.is_instance_of::<HtmlInputElement>() {
@@ -265,11 +280,15 @@ impl<G: GameClient> Infrastructure<G> {
             };
 
             let identifier = touch.identifier();
-            if self.left_touch_id.map(|id| id == identifier).unwrap_or(
-                self.right_touch_id
-                    .map(|id| id != identifier)
-                    .unwrap_or(true),
-            ) {
+            if self
+                .left_touch_id
+                .map(|id| id == identifier)
+                .unwrap_or_else(|| {
+                    self.right_touch_id
+                        .map(|id| id != identifier)
+                        .unwrap_or(true)
+                })
+            {
                 self.left_touch_id = Some(identifier);
                 left_touch = Some(touch);
             } else if self
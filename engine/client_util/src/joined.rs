@@ -5,7 +5,7 @@ use std::str::FromStr;
 use std::sync::LazyLock;
 use web_sys::{window, Storage};
 
-const KEY: &'static str = "joined";
+const KEY: &str = "joined";
 
 fn local_storage() -> Option<Storage> {
     window().unwrap().local_storage().ok().flatten()
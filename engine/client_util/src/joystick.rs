@@ -1,11 +1,12 @@
 // SPDX-FileCopyrightText: 2021 Softbear, Inc.
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+use crate::gamepad::{GamepadButton, GamepadState};
 use crate::keyboard::{Key, KeyState, KeyboardState};
 use common_util::range::map_ranges;
 use glam::Vec2;
 
-/// Helper for taking joystick input. For now, the only joystick input is emulated from keyboard input.
+/// Helper for taking joystick input, either emulated from keyboard input or from a gamepad.
 #[derive(Debug)]
 pub struct Joystick {
     /// Turning (x axis) is interpolated.
@@ -22,41 +23,72 @@ impl Joystick {
     pub fn try_from_keyboard_state(
         time_seconds: f32,
         keyboard_state: &KeyboardState,
+    ) -> Option<Self> {
+        Self::try_from_key_states(
+            time_seconds,
+            keyboard_state
+                .state(Key::W)
+                .combined(keyboard_state.state(Key::Up)),
+            keyboard_state
+                .state(Key::S)
+                .combined(keyboard_state.state(Key::Down)),
+            keyboard_state
+                .state(Key::A)
+                .combined(keyboard_state.state(Key::Left)),
+            keyboard_state
+                .state(Key::D)
+                .combined(keyboard_state.state(Key::Right)),
+            keyboard_state.is_down(Key::X),
+        )
+    }
+
+    /// Returns Some if the gamepad is connected and either the left stick is deflected beyond the
+    /// dead zone, or the stop (east) button is down.
+    pub fn try_from_gamepad_state(gamepad_state: &GamepadState) -> Option<Self> {
+        let stop = gamepad_state.is_down(GamepadButton::East);
+        // Browser reports positive y as down, but joystick uses positive y as forward.
+        let stick = gamepad_state.left_stick * Vec2::new(1.0, -1.0);
+
+        if !gamepad_state.connected || (!stop && stick == Vec2::ZERO) {
+            None
+        } else {
+            Some(Self {
+                position: Vec2::new(-stick.x, stick.y),
+                translation_2d: stick,
+                stop,
+            })
+        }
+    }
+
+    /// Like [`Self::try_from_keyboard_state`], but the caller decides which keys (or combinations
+    /// of keys) move, for example to support rebinding them.
+    pub fn try_from_key_states(
+        time_seconds: f32,
+        forward: KeyState,
+        backward: KeyState,
+        left: KeyState,
+        right: KeyState,
+        stop: bool,
     ) -> Option<Self> {
         let mut forward_backward = 0f32;
         let mut left_right = 0f32;
         let mut left_right_translation = 0f32;
-        if keyboard_state
-            .state(Key::W)
-            .combined(keyboard_state.state(Key::Up))
-            .is_down()
-        {
+        if forward.is_down() {
             forward_backward += 1.0;
         }
-        if keyboard_state
-            .state(Key::S)
-            .combined(keyboard_state.state(Key::Down))
-            .is_down()
-        {
+        if backward.is_down() {
             forward_backward -= 1.0;
         }
-        if let KeyState::Down(start) = keyboard_state
-            .state(Key::A)
-            .combined(keyboard_state.state(Key::Left))
-        {
+        if let KeyState::Down(start) = left {
             let elapsed = time_seconds - start;
             left_right += map_ranges(elapsed, 0.0..1.0, 0.25..1.0, true);
             left_right_translation -= 1.0;
         }
-        if let KeyState::Down(start) = keyboard_state
-            .state(Key::D)
-            .combined(keyboard_state.state(Key::Right))
-        {
+        if let KeyState::Down(start) = right {
             let elapsed = time_seconds - start;
             left_right -= map_ranges(elapsed, 0.0..1.0, 0.25..1.0, true);
             left_right_translation += 1.0;
         }
-        let stop = keyboard_state.is_down(Key::X);
 
         if !stop && forward_backward == 0.0 && left_right == 0.0 {
             None
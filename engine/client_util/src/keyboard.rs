@@ -3,10 +3,10 @@
 
 use crate::apply::Apply;
 use std::num::NonZeroU8;
-use strum_macros::Display;
+use strum_macros::{Display, EnumString};
 
 /// Each variant is a possible key. Not guaranteed to support all keys.
-#[derive(Copy, Clone, Eq, PartialEq, Display)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Display, EnumString)]
 pub enum Key {
     A,
     B,
@@ -195,10 +195,11 @@ impl Default for KeyboardState {
 impl Apply<KeyboardEvent> for KeyboardState {
     fn apply(&mut self, event: KeyboardEvent) {
         if self.state(event.key).is_down() != event.down {
-            *self.state_mut(event.key) = event
-                .down
-                .then(|| KeyState::Down(event.time))
-                .unwrap_or(KeyState::Up);
+            *self.state_mut(event.key) = if event.down {
+                KeyState::Down(event.time)
+            } else {
+                KeyState::Up
+            };
         }
     }
 }
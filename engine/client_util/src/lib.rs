@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 #![feature(drain_filter)]
+#![feature(get_mut_unchecked)]
 #![feature(must_not_suspend)]
 #![feature(hash_drain_filter)]
 #![feature(once_cell)]
@@ -19,6 +20,7 @@ pub mod context;
 pub mod fps_monitor;
 pub mod frontend;
 pub mod game_client;
+pub mod gamepad;
 pub mod infrastructure;
 #[cfg(feature = "joined")]
 pub mod joined;
@@ -27,6 +29,7 @@ pub mod js_util;
 pub mod keyboard;
 pub mod mouse;
 pub mod rate_limiter;
+pub mod rc_borrow_mut;
 pub mod reconn_web_socket;
 pub mod setting;
 pub mod visibility;
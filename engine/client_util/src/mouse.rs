@@ -3,9 +3,10 @@
 
 use crate::apply::Apply;
 use glam::Vec2;
+use strum_macros::{Display, EnumString};
 
 /// Identifies a mouse button (left, middle, or right).
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Display, EnumString)]
 pub enum MouseButton {
     Left,
     Middle,
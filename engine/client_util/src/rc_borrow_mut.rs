@@ -0,0 +1,36 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use std::ops::{Deref, DerefMut};
+use std::rc::Rc;
+
+/// Mutably borrows the contents of an `Rc`, even while it is shared, such that every clone
+/// observes the changes (like a `Rc<RefCell<T>>` without the `RefCell`).
+pub trait RcBorrowMut<T> {
+    fn borrow_mut(this: &mut Self) -> RcRefMut<'_, T>;
+}
+
+impl<T> RcBorrowMut<T> for Rc<T> {
+    fn borrow_mut(this: &mut Self) -> RcRefMut<'_, T> {
+        RcRefMut(this)
+    }
+}
+
+/// A mutable borrow of the contents of an `Rc` (see `RcBorrowMut`).
+pub struct RcRefMut<'a, T>(&'a mut Rc<T>);
+
+impl<'a, T> Deref for RcRefMut<'a, T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.0
+    }
+}
+
+impl<'a, T> DerefMut for RcRefMut<'a, T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // Safety: other clones only read the contents between (not during) mutable borrows, which
+        // are never held across an await or a call into other code that could read them.
+        unsafe { Rc::get_mut_unchecked(self.0) }
+    }
+}
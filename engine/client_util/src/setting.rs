@@ -51,6 +51,12 @@ pub struct CommonSettings {
     /// Pending chat message.
     #[setting(volatile)]
     pub chat_message: String,
+    /// Fraction of gamepad stick deflection that is ignored.
+    #[setting(range = "0.0..0.9", finite)]
+    pub gamepad_dead_zone: f32,
+    /// Multiplier of gamepad stick deflection.
+    #[setting(range = "0.25..4.0", finite)]
+    pub gamepad_sensitivity: f32,
     /// Whether to add a contrasting border behind UI elements.
     pub high_contrast: bool,
     /// Whether team menu is open.
@@ -76,6 +82,8 @@ impl Default for CommonSettings {
             antialias: !is_mobile(),
             protocol: WebSocketProtocol::default(),
             chat_message: String::new(),
+            gamepad_dead_zone: 0.15,
+            gamepad_sensitivity: 1.0,
             high_contrast: false,
             team_dialog_shown: true,
             chat_dialog_shown: true,
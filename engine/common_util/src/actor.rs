@@ -181,7 +181,7 @@ pub trait PassDef {
             if Self::collapse(&event) {
                 if let Some(next) = events.peek() {
                     if Self::destination_partition_id(&event)
-                        == Self::destination_partition_id(&next)
+                        == Self::destination_partition_id(next)
                         && mem::discriminant(&event) == mem::discriminant(next)
                     {
                         continue;
@@ -314,9 +314,7 @@ impl<S: State> Pass for PhantomData<S> {
 
     fn tick(&mut self, _: &mut Self::State, _: Option<Self::Update>, _: impl FnMut(S::Info)) {}
 
-    fn update(&self, _: &ClientData<Self::State>) -> Self::Update {
-        ()
-    }
+    fn update(&self, _: &ClientData<Self::State>) -> Self::Update {}
 }
 
 /// State in need of network synchronization.
@@ -563,8 +561,10 @@ mod tests {
     use rand::prelude::IteratorRandom;
     use rand::{thread_rng, Rng};
     use serde::{Deserialize, Serialize};
-    use std::collections::{BTreeMap, HashMap};
+    use std::collections::HashMap;
+    use std::hash::{Hash, Hasher};
     use std::marker::PhantomData;
+    use std::ops::BitXorAssign;
 
     #[test]
     fn fuzz() {
@@ -766,7 +766,7 @@ mod tests {
             type Partition = String;
             type Info = SimpleInfo;
             type Event = SimpleStateEvent;
-            type Checksum = BTreeMap<Self::PartitionId, Self::Partition>;
+            type Checksum = u32;
 
             fn destination_partition_id(event: &Self::Event) -> Self::PartitionId {
                 match event {
@@ -783,6 +783,13 @@ mod tests {
                 self.partitions.get(&partition_id).cloned()
             }
 
+            fn hash_partition<H: Hasher>(&self, partition_id: Self::PartitionId, state: &mut H)
+            where
+                Self::Checksum: BitXorAssign,
+            {
+                self.partitions.get(&partition_id).hash(state);
+            }
+
             fn insert_partition(
                 &mut self,
                 partition_id: Self::PartitionId,
@@ -913,12 +920,7 @@ mod tests {
                     for _ in 0..rng.gen_range(0..=if isolate { 3 } else { 25 }) {
                         server.dispatch(
                             SimpleStateEvent::PushChar {
-                                partition_id: server
-                                    .partitions
-                                    .keys()
-                                    .choose(&mut rng)
-                                    .unwrap()
-                                    .clone(),
+                                partition_id: *server.partitions.keys().choose(&mut rng).unwrap(),
                                 c: rng.gen_range('0'..='9'),
                             },
                             print_info,
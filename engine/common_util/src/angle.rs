@@ -234,9 +234,9 @@ mod tests {
         for i in -1000..1000 {
             let r = (i as f32) / 100.0;
             let rs = format!("{}", r);
-            let a: Angle = serde_json::from_str(&*rs).unwrap();
+            let a: Angle = serde_json::from_str(&rs).unwrap();
             let rs2 = serde_json::to_string(&a).unwrap();
-            let a2: Angle = serde_json::from_str(&*rs2).unwrap();
+            let a2: Angle = serde_json::from_str(&rs2).unwrap();
             assert!((a - a2).to_radians().abs() < 0.0001, "{:?} -> {:?}", a, a2);
         }
     }
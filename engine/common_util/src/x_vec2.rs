@@ -263,6 +263,8 @@ macro_rules! x_vec2 {
             }
         }
 
+        // Consistent with the derived `PartialEq`, since the bytes are exactly `x` and `y`.
+        #[allow(clippy::derive_hash_xor_eq)]
         impl Hash for $name {
             #[inline]
             fn hash<H: Hasher>(&self, state: &mut H) {
@@ -300,11 +302,10 @@ impl From<U8Vec2> for U16Vec2 {
     }
 }
 
-// TODO replace with From impl.
-impl Into<UVec2> for U16Vec2 {
+impl From<U16Vec2> for UVec2 {
     #[inline]
-    fn into(self) -> UVec2 {
-        UVec2::new(self.x as u32, self.y as u32)
+    fn from(v: U16Vec2) -> Self {
+        Self::new(v.x as u32, v.y as u32)
     }
 }
 
@@ -346,11 +347,10 @@ impl From<I8Vec2> for I16Vec2 {
     }
 }
 
-// TODO replace with From impl.
-impl Into<IVec2> for I16Vec2 {
+impl From<I16Vec2> for IVec2 {
     #[inline]
-    fn into(self) -> IVec2 {
-        IVec2::new(self.x as i32, self.y as i32)
+    fn from(v: I16Vec2) -> Self {
+        Self::new(v.x as i32, v.y as i32)
     }
 }
 
@@ -8,7 +8,7 @@ use crate::UnixTime;
 use serde::{Deserialize, Serialize};
 use std::cmp::Ordering;
 
-#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct InvitationDto {
     /// Who sent it.
     pub player_id: PlayerId,
@@ -109,8 +109,33 @@ pub struct MessageDto {
     /// Whether message is directed to team only.
     pub whisper: bool,
 }
+
+/// The Ping Data Transfer Object (DTO) is a short-lived marker placed at a world position by a team
+/// member, for their team only.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PingDto {
+    pub kind: PingKind,
+    /// Who placed it.
+    pub player_id: PlayerId,
+    pub x: f32,
+    pub y: f32,
+}
+
+/// What a ping means.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum PingKind {
+    Attack,
+    Danger,
+    Regroup,
+}
+
+impl PingKind {
+    /// Number of seconds pings are shown for.
+    pub const DURATION_SECONDS: f32 = 8.0;
+}
+
 /// The Player Data Transfer Object (DTO) binds player ID to player data.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct PlayerDto {
     pub alias: PlayerAlias,
     pub moderator: bool,
@@ -165,7 +190,7 @@ impl Ord for SnippetDto {
 }
 
 /// The Team Data Transfer Object (DTO) binds team ID to team name.
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TeamDto {
     pub team_id: TeamId,
     pub name: TeamName,
@@ -194,6 +219,20 @@ mod admin {
     use std::net::IpAddr;
     use std::num::NonZeroU64;
 
+    /// An entity in the world, as seen by the admin world inspector.
+    #[derive(Clone, Debug, PartialEq, Serialize)]
+    pub struct AdminEntityDto {
+        pub entity_id: u32,
+        pub entity_type: String,
+        pub x: f32,
+        pub y: f32,
+        /// Fraction of health lost, if applicable (e.g. boats).
+        pub damage: Option<f32>,
+        /// Owner, if any.
+        pub player_id: Option<PlayerId>,
+        pub alias: Option<PlayerAlias>,
+    }
+
     /// The Player Admin Data Transfer Object (DTO) binds player ID to admin player data (for real players, not bots).
     #[derive(Clone, Debug, PartialEq, Serialize)]
     pub struct AdminPlayerDto {
@@ -246,6 +285,19 @@ mod admin {
         }
     }
 
+    /// An entry in the audit log of moderator and admin actions.
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize)]
+    pub struct AuditEntryDto {
+        pub date: UnixTime,
+        /// Who did it, for example "admin" or the alias of a moderator.
+        pub issuer: String,
+        /// What they did, for example "mute 30m".
+        pub action: String,
+        /// Who or what they did it to, if applicable.
+        pub target: Option<String>,
+        pub reason: String,
+    }
+
     /// The Metrics Data Transfer Object (DTO) contains core server metrics.
     #[derive(Clone, Copy, Debug, Serialize)]
     pub struct MetricsSummaryDto {
@@ -329,4 +381,56 @@ mod admin {
         pub video_ads: <DiscreteMetric as Metric>::DataPoint,
         pub visits: <DiscreteMetric as Metric>::DataPoint,
     }
+
+    /// A moderation sanction, which persists across restarts until it expires or is revoked.
+    #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+    pub struct SanctionDto {
+        pub sanction_id: SanctionId,
+        pub kind: SanctionKind,
+        pub target: SanctionTarget,
+        /// Alias of the sanctioned player, at the time of the sanction.
+        pub alias: PlayerAlias,
+        pub issuer: String,
+        pub reason: String,
+        pub date_issued: UnixTime,
+        /// [`None`] means permanent.
+        pub date_expires: Option<UnixTime>,
+    }
+
+    impl SanctionDto {
+        /// Whether the sanction is in effect at the given time.
+        pub fn is_active(&self, now: UnixTime) -> bool {
+            self.date_expires
+                .map(|expires| now < expires)
+                .unwrap_or(true)
+        }
+    }
+
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+    pub enum SanctionKind {
+        /// Cannot connect.
+        Ban,
+        /// Cannot chat.
+        Mute,
+        /// Can only chat safe phrases.
+        Restrict,
+    }
+
+    /// Which of a player's identifiers a sanction applies to.
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+    pub enum SanctionScope {
+        Session,
+        Ip,
+        /// Requires the player to be logged in.
+        User,
+    }
+
+    /// The identifier a sanction applies to.
+    #[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+    pub enum SanctionTarget {
+        Session(SessionId),
+        Ip(IpAddr),
+        /// Discord user id.
+        User(NonZeroU64),
+    }
 }
@@ -86,7 +86,7 @@ impl<'de> Deserialize<'de> for CohortId {
         D: Deserializer<'de>,
     {
         <u8>::deserialize(deserializer)
-            .and_then(|n| Self::new(n).ok_or(D::Error::custom("invalid cohort id")))
+            .and_then(|n| Self::new(n).ok_or_else(|| D::Error::custom("invalid cohort id")))
     }
 }
 
@@ -128,7 +128,7 @@ impl InvitationId {
     }
 
     pub fn server_id(self) -> Option<ServerId> {
-        NonZeroU8::new((self.0.get() >> 24) as u8).map(|nz| ServerId(nz))
+        NonZeroU8::new((self.0.get() >> 24) as u8).map(ServerId)
     }
 }
 
@@ -223,12 +223,12 @@ impl PlayerId {
 
     /// Gets the bot number associated with this id, or [`None`] if the id is not a bot.
     pub fn bot_number(self) -> Option<usize> {
-        self.is_bot().then_some(self.0.get() as usize - 2)
+        self.is_bot().then(|| self.0.get() as usize - 2)
     }
 
     /// Gets the nth id associated with bots.
     pub fn nth_bot(n: usize) -> Option<Self> {
-        debug_assert!(n <= u32::MAX as usize - 1);
+        debug_assert!(n < u32::MAX as usize);
         NonZeroU32::new(n as u32 + 2)
             .map(Self)
             .filter(|id| id.is_bot())
@@ -362,6 +362,10 @@ impl FromStr for RegionId {
     }
 }
 
+/// Identifies a moderation sanction, for the purpose of revoking it.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub struct SanctionId(pub NonZeroU32);
+
 /// Symbolizes, for example: #.domain.com
 /// The meaning of [`Option::<ServerId>::None`] is often "localhost"
 #[derive(Copy, Clone, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
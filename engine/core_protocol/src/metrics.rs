@@ -16,7 +16,7 @@ pub trait Metric: Sized + Add + Default {
 }
 
 /// A metric representing something countable.
-#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DiscreteMetric {
     #[serde(rename = "t")]
     pub total: u32,
@@ -188,7 +188,7 @@ impl Add for ExtremaMetric {
 }
 
 /// A metric tracking the ratio of data satisfying a condition to all data.
-#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct RatioMetric {
     /// Total population size.
     #[serde(rename = "t")]
@@ -426,7 +426,7 @@ impl Add for ContinuousExtremaMetric {
 const BUCKET_COUNT: usize = 10;
 const BUCKET_SIZE: usize = 1;
 
-#[derive(Debug, Default, Copy, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct HistogramMetric {
     /// How many samples have value 0.0-9.99, 10.0-19.99, ... ?
     #[serde(rename = "b")]
@@ -487,9 +487,7 @@ impl Metric for HistogramMetric {
         }
     }
 
-    fn data_point(&self) -> Self::DataPoint {
-        ()
-    }
+    fn data_point(&self) -> Self::DataPoint {}
 }
 
 impl Add for HistogramMetric {
@@ -119,11 +119,11 @@ impl PlayerAlias {
             &string, 14,
         )));
 
-        return if ret.0.is_empty() {
+        if ret.0.is_empty() {
             Self::default()
         } else {
             ret
-        };
+        }
     }
 
     /// Doesn't trim spaces, useful for guarding text inputs.
@@ -167,6 +167,12 @@ pub enum TeamRequest {
     Join(TeamId),
     Kick(PlayerId),
     Leave,
+    /// Place a ping, visible only to one's team.
+    Ping {
+        kind: PingKind,
+        x: f32,
+        y: f32,
+    },
     Promote(PlayerId),
     Reject(PlayerId),
 }
@@ -186,6 +192,8 @@ pub enum TeamUpdate {
     Left,
     /// A complete enumeration of team members, in order (first is captain).
     Members(Owned<[PlayerId]>),
+    /// New pings from team members (including one's own).
+    Pinged(Box<[PingDto]>),
     Promoted(PlayerId),
     Rejected(PlayerId),
     Removed(Owned<[TeamId]>),
@@ -322,13 +330,27 @@ mod admin {
     /// Admin requests are from the admin interface to the core service.
     #[derive(Clone, Debug, Deserialize, Serialize)]
     pub enum AdminRequest {
+        /// Bans a real player (and anyone else who shares the scoped identifier). Sending [`None`]
+        /// minutes bans permanently.
+        BanPlayer {
+            player_id: PlayerId,
+            scope: SanctionScope,
+            minutes: Option<usize>,
+            reason: String,
+        },
         ClearSnippet {
             cohort_id: Option<CohortId>,
             referrer: Option<Referrer>,
         },
+        /// Kills a player's boat, as if it sank.
+        KillPlayer {
+            player_id: PlayerId,
+        },
         MutePlayer {
             player_id: PlayerId,
             minutes: usize,
+            #[serde(default)]
+            reason: String,
         },
         /// Set client hash to that of this server. Sending [`None`] will reset to default.
         OverrideClientHash(Option<ServerId>),
@@ -341,16 +363,24 @@ mod admin {
             moderator: bool,
         },
         RequestAllowWebSocketJson,
+        RequestAuditLog,
         RequestDay {
             filter: Option<MetricFilter>,
         },
         RequestDistributeLoad,
+        /// Lists entities within a radius of a point in the world.
+        RequestEntities {
+            x: f32,
+            y: f32,
+            radius: f32,
+        },
         RequestGames,
         RequestPlayers,
         RequestProfile,
         RequestRedirect,
         RequestReferrers,
         RequestRegions,
+        RequestSanctions,
         RequestSeries {
             game_id: GameId,
             filter: Option<MetricFilter>,
@@ -370,6 +400,13 @@ mod admin {
         RestrictPlayer {
             player_id: PlayerId,
             minutes: usize,
+            #[serde(default)]
+            reason: String,
+        },
+        RevokeSanction {
+            sanction_id: SanctionId,
+            #[serde(default)]
+            reason: String,
         },
         SendChat {
             // If None, goes to all players.
@@ -380,12 +417,36 @@ mod admin {
         SetAllowWebSocketJson(bool),
         SetDistributeLoad(bool),
         SetGameClient(minicdn::EmbeddedMiniCdn),
+        /// Makes a player's boat (and future boats) immune to damage, or not.
+        SetPlayerInvulnerable {
+            player_id: PlayerId,
+            invulnerable: bool,
+        },
+        SetPlayerScore {
+            player_id: PlayerId,
+            score: u32,
+        },
         SetRedirect(Option<ServerId>),
         SetSnippet {
             cohort_id: Option<CohortId>,
             referrer: Option<Referrer>,
             snippet: Owned<str>,
         },
+        /// Spawns an entity by type name, e.g. "Barrel", at (or near) a point. Boats, weapons,
+        /// aircraft, and decoys must be owned by a player.
+        SpawnEntity {
+            entity_type: String,
+            x: f32,
+            y: f32,
+            #[serde(default)]
+            player_id: Option<PlayerId>,
+        },
+        /// Moves a player's boat to a point in the world.
+        TeleportPlayer {
+            player_id: PlayerId,
+            x: f32,
+            y: f32,
+        },
     }
 
     /// Admin related responses from the server.
@@ -393,31 +454,46 @@ mod admin {
     pub enum AdminUpdate {
         AllowWebSocketJsonRequested(bool),
         AllowWebSocketJsonSet(bool),
+        AuditLogRequested(Box<[AuditEntryDto]>),
         ChatSent,
         ClientHashOverridden(u64),
         DayRequested(Owned<[(crate::UnixTime, MetricsDataPointDto)]>),
         DistributeLoadRequested(bool),
         DistributeLoadSet(bool),
+        EntitiesRequested(Box<[AdminEntityDto]>),
+        EntitySpawned,
         GameClientSet(u64),
         GamesRequested(Box<[(GameId, f32)]>),
         HttpServerRestarting,
         PlayerAliasOverridden(PlayerAlias),
+        PlayerBanned(SanctionId),
+        PlayerInvulnerableSet(bool),
+        PlayerKilled,
         PlayerModeratorOverridden(bool),
         PlayerMuted(usize),
         PlayerRestricted(usize),
+        PlayerScoreSet(u32),
+        PlayerTeleported,
         PlayersRequested(Box<[AdminPlayerDto]>),
-        ProfileRequested(String),
+        ProfileRequested {
+            /// SVG flamegraph.
+            flamegraph: String,
+            /// Game specific report of the slowest tick over budget, if any.
+            slow_tick: Option<String>,
+        },
         RedirectRequested(Option<ServerId>),
         RedirectSet(Option<ServerId>),
         ReferrersRequested(Box<[(Referrer, f32)]>),
         RegionsRequested(Box<[(RegionId, f32)]>),
+        SanctionRevoked,
+        SanctionsRequested(Box<[SanctionDto]>),
         SeriesRequested(Owned<[(crate::UnixTime, MetricsDataPointDto)]>),
         ServerIdRequested(Option<ServerId>),
         ServersRequested(Box<[AdminServerDto]>),
         SnippetCleared,
         SnippetSet,
         SnippetsRequested(Box<[SnippetDto]>),
-        SummaryRequested(MetricsSummaryDto),
+        SummaryRequested(Box<MetricsSummaryDto>),
         UserAgentsRequested(Box<[(UserAgentId, f32)]>),
     }
 }
@@ -76,7 +76,7 @@ pub fn include_audio(item: TokenStream) -> TokenStream {
         .collect();
 
     quote! {
-        #[derive(Copy, Clone, Debug, PartialEq)]
+        #[derive(Copy, Clone, Debug, PartialEq, Eq)]
         pub enum Audio {
             #(#variants,)*
         }
@@ -174,8 +174,8 @@ pub(crate) fn derive_layer(input: LayerInput) -> TokenStream {
         if let Some(renders) = &renders {
             if let Some(renders) = renders {
                 for bound in renders {
-                    if !any_renders_marked.contains(&bound)
-                        || has_render_attr(&attrs, &bound).unwrap()
+                    if !any_renders_marked.contains(bound)
+                        || has_render_attr(&attrs, bound).unwrap()
                     {
                         render_inners
                             .entry(Some(bound.clone()))
@@ -3,18 +3,21 @@
 
 use crate::client::ClientRepo;
 use crate::context::Context;
+use crate::context_service::ContextService;
 use crate::game_service::GameArenaService;
 use crate::infrastructure::Infrastructure;
 use crate::metric::{Bundle, MetricBundle, MetricRepo};
-use crate::player::PlayerRepo;
+use crate::moderation::ModerationRepo;
+use crate::player::{PlayerRepo, PlayerTuple};
 use crate::static_files::static_size_and_hash;
 use crate::status::StatusRepo;
 use crate::system::{ServerStatus, SystemRepo};
 use actix::{fut, ActorFutureExt, Handler, Message, ResponseActFuture, WrapFuture};
 use core_protocol::dto::{
-    AdminPlayerDto, AdminServerDto, MessageDto, MetricFilter, MetricsDataPointDto, SnippetDto,
+    AdminPlayerDto, AdminServerDto, MessageDto, MetricFilter, MetricsDataPointDto, SanctionKind,
+    SanctionScope, SanctionTarget, SnippetDto,
 };
-use core_protocol::id::{CohortId, PlayerId, RegionId, ServerId, UserAgentId};
+use core_protocol::id::{CohortId, PlayerId, RegionId, SanctionId, ServerId, UserAgentId};
 use core_protocol::name::{PlayerAlias, Referrer};
 use core_protocol::rpc::{AdminRequest, AdminUpdate};
 use core_protocol::{get_unix_time_now, UnixTime};
@@ -120,9 +123,9 @@ impl<G: GameArenaService> AdminRepo<G> {
         }
     }
 
-    pub(crate) fn authenticate(&self, request: &ParameterizedAdminRequest) -> bool {
+    pub(crate) fn authenticate(&self, auth: &str) -> bool {
         // Avoid timing side channel attack that could be used to get the auth.
-        constant_time_eq::constant_time_eq(self.password.as_bytes(), request.auth.as_bytes())
+        constant_time_eq::constant_time_eq(self.password.as_bytes(), auth.as_bytes())
     }
 
     fn log_save_config_file(&self) {
@@ -161,6 +164,110 @@ impl<G: GameArenaService> AdminRepo<G> {
         ))
     }
 
+    /// Sets the score of a given player.
+    fn set_player_score(
+        &self,
+        player_id: PlayerId,
+        score: u32,
+        players: &PlayerRepo<G>,
+    ) -> Result<AdminUpdate, &'static str> {
+        let mut player = players
+            .borrow_player_mut(player_id)
+            .ok_or("nonexistent player")?;
+        player.score = score;
+        Ok(AdminUpdate::PlayerScoreSet(score))
+    }
+
+    /// Get admin view of entities near a point in the world.
+    fn request_entities(
+        &self,
+        x: f32,
+        y: f32,
+        radius: f32,
+        service: &G,
+    ) -> Result<AdminUpdate, &'static str> {
+        if !(radius.is_finite() && radius >= 0.0) {
+            return Err("invalid radius");
+        }
+        Ok(AdminUpdate::EntitiesRequested(
+            service.admin_entities(x, y, radius)?.into(),
+        ))
+    }
+
+    /// Moves a given player's boat.
+    fn teleport_player(
+        &self,
+        player_id: PlayerId,
+        x: f32,
+        y: f32,
+        context_service: &mut ContextService<G>,
+    ) -> Result<AdminUpdate, &'static str> {
+        if !(x.is_finite() && y.is_finite()) {
+            return Err("invalid position");
+        }
+        let player_tuple = Self::player_tuple(player_id, &context_service.context.players)?;
+        context_service
+            .service
+            .admin_teleport(&player_tuple, x, y)?;
+        Ok(AdminUpdate::PlayerTeleported)
+    }
+
+    /// Kills a given player's boat.
+    fn kill_player(
+        &self,
+        player_id: PlayerId,
+        context_service: &mut ContextService<G>,
+    ) -> Result<AdminUpdate, &'static str> {
+        let player_tuple = Self::player_tuple(player_id, &context_service.context.players)?;
+        context_service.service.admin_kill(&player_tuple)?;
+        Ok(AdminUpdate::PlayerKilled)
+    }
+
+    /// Spawns an entity, optionally owned by a given player.
+    fn spawn_entity(
+        &self,
+        entity_type: &str,
+        x: f32,
+        y: f32,
+        player_id: Option<PlayerId>,
+        context_service: &mut ContextService<G>,
+    ) -> Result<AdminUpdate, &'static str> {
+        if !(x.is_finite() && y.is_finite()) {
+            return Err("invalid position");
+        }
+        let owner = player_id
+            .map(|player_id| Self::player_tuple(player_id, &context_service.context.players))
+            .transpose()?;
+        context_service
+            .service
+            .admin_spawn(entity_type, x, y, owner.as_ref())?;
+        Ok(AdminUpdate::EntitySpawned)
+    }
+
+    /// Makes a given player immune to damage, or not.
+    fn set_player_invulnerable(
+        &self,
+        player_id: PlayerId,
+        invulnerable: bool,
+        context_service: &mut ContextService<G>,
+    ) -> Result<AdminUpdate, &'static str> {
+        let player_tuple = Self::player_tuple(player_id, &context_service.context.players)?;
+        context_service
+            .service
+            .admin_set_invulnerable(&player_tuple, invulnerable)?;
+        Ok(AdminUpdate::PlayerInvulnerableSet(invulnerable))
+    }
+
+    fn player_tuple(
+        player_id: PlayerId,
+        players: &PlayerRepo<G>,
+    ) -> Result<Arc<PlayerTuple<G>>, &'static str> {
+        players
+            .get(player_id)
+            .map(Arc::clone)
+            .ok_or("nonexistent player")
+    }
+
     /// Get admin view of real players in the game.
     fn request_players(&self, players: &PlayerRepo<G>) -> Result<AdminUpdate, &'static str> {
         Ok(AdminUpdate::PlayersRequested(
@@ -231,16 +338,28 @@ impl<G: GameArenaService> AdminRepo<G> {
         &self,
         player_id: PlayerId,
         minutes: usize,
+        reason: &str,
         players: &PlayerRepo<G>,
+        moderation: &mut ModerationRepo<G>,
     ) -> Result<AdminUpdate, &'static str> {
         let mut player = players
             .borrow_player_mut(player_id)
             .ok_or("nonexistent player")?;
+        let alias = player.alias();
         let client = player.client_mut().ok_or("not a real player")?;
         client
             .chat
             .context
             .mute_for(Duration::from_secs(minutes as u64 * 60));
+        Self::persist_chat_sanction(
+            SanctionKind::Mute,
+            player_id,
+            alias,
+            SanctionTarget::Session(client.session_id),
+            minutes,
+            reason,
+            moderation,
+        );
         Ok(AdminUpdate::PlayerMuted(seconds_ceil(
             client.chat.context.muted_for(),
         )))
@@ -252,21 +371,117 @@ impl<G: GameArenaService> AdminRepo<G> {
         &self,
         player_id: PlayerId,
         minutes: usize,
+        reason: &str,
         players: &PlayerRepo<G>,
+        moderation: &mut ModerationRepo<G>,
     ) -> Result<AdminUpdate, &'static str> {
         let mut player = players
             .borrow_player_mut(player_id)
             .ok_or("nonexistent player")?;
+        let alias = player.alias();
         let client = player.client_mut().ok_or("not a real player")?;
         client
             .chat
             .context
             .restrict_for(Duration::from_secs(minutes as u64 * 60));
+        Self::persist_chat_sanction(
+            SanctionKind::Restrict,
+            player_id,
+            alias,
+            SanctionTarget::Session(client.session_id),
+            minutes,
+            reason,
+            moderation,
+        );
         Ok(AdminUpdate::PlayerRestricted(seconds_ceil(
             client.chat.context.restricted_for(),
         )))
     }
 
+    /// Makes a mute or restriction survive restarts (or lifts it, if minutes is 0), and audits it.
+    fn persist_chat_sanction(
+        kind: SanctionKind,
+        player_id: PlayerId,
+        alias: PlayerAlias,
+        target: SanctionTarget,
+        minutes: usize,
+        reason: &str,
+        moderation: &mut ModerationRepo<G>,
+    ) {
+        if minutes == 0 {
+            moderation.revoke_matching(kind, target);
+        } else {
+            moderation.sanction(kind, target, alias, Some(minutes), ISSUER, reason);
+        }
+        moderation.audit(
+            ISSUER,
+            &format!("{:?} {}m", kind, minutes).to_lowercase(),
+            Some(describe_player(player_id, alias)),
+            reason,
+        );
+    }
+
+    /// Bans a given real player, disconnecting them (and anyone else who shares the banned
+    /// identifier).
+    fn ban_player(
+        &self,
+        player_id: PlayerId,
+        scope: SanctionScope,
+        minutes: Option<usize>,
+        reason: &str,
+        context: &mut Context<G>,
+    ) -> Result<AdminUpdate, &'static str> {
+        if reason.trim().is_empty() {
+            return Err("reason required");
+        }
+        let player = context
+            .players
+            .borrow_player(player_id)
+            .ok_or("nonexistent player")?;
+        let alias = player.alias();
+        let client = player.client().ok_or("not a real player")?;
+        let target = ModerationRepo::target(client, scope)?;
+        drop(player);
+
+        let sanction_id =
+            context
+                .moderation
+                .sanction(SanctionKind::Ban, target, alias, minutes, ISSUER, reason);
+        context.moderation.audit(
+            ISSUER,
+            &format!(
+                "ban {:?} {}",
+                scope,
+                minutes
+                    .map(|m| format!("{}m", m))
+                    .unwrap_or_else(|| String::from("permanently"))
+            )
+            .to_lowercase(),
+            Some(describe_player(player_id, alias)),
+            reason,
+        );
+        context.moderation.disconnect_banned(&context.players);
+        Ok(AdminUpdate::PlayerBanned(sanction_id))
+    }
+
+    /// Revokes a sanction (of any kind) before it expires.
+    fn revoke_sanction(
+        &self,
+        sanction_id: SanctionId,
+        reason: &str,
+        context: &mut Context<G>,
+    ) -> Result<AdminUpdate, &'static str> {
+        let sanction = context.moderation.revoke(sanction_id)?;
+        ModerationRepo::lift(&sanction, &mut context.players);
+        context.moderation.audit(
+            ISSUER,
+            &format!("revoke {:?} on {:?}", sanction.kind, sanction.target).to_lowercase(),
+            Some(sanction.alias.as_str().to_owned()),
+            reason,
+        );
+        Ok(AdminUpdate::SanctionRevoked)
+    }
+
     /// Get list of all known servers for the game, including incompatible/unreachable/etc. servers.
     fn request_servers(system: &Option<SystemRepo<G>>) -> Result<AdminUpdate, &'static str> {
         let system = system.as_ref().ok_or("system not configured")?;
@@ -302,8 +517,8 @@ impl<G: GameArenaService> AdminRepo<G> {
             .snippets
             .iter()
             .map(|((cohort_id, referrer), snippet)| SnippetDto {
-                cohort_id: cohort_id.clone(),
-                referrer: referrer.clone(),
+                cohort_id: *cohort_id,
+                referrer: *referrer,
                 snippet: Arc::clone(snippet),
             })
             .collect();
@@ -361,7 +576,7 @@ impl<G: GameArenaService> AdminRepo<G> {
         summary.players_cached.total = current.players_cached.total;
         summary.sessions_cached.total = current.sessions_cached.total;
 
-        Ok(AdminUpdate::SummaryRequested(summary))
+        Ok(AdminUpdate::SummaryRequested(Box::new(summary)))
     }
 
     /// Request metric data points for the last 24 calendar hours (excluding the current hour, in
@@ -590,7 +805,7 @@ impl<G: GameArenaService> AdminRepo<G> {
         }
     }
 
-    fn finish_profile(&mut self) -> Result<AdminUpdate, &'static str> {
+    fn finish_profile(&mut self, slow_tick: Option<String>) -> Result<AdminUpdate, &'static str> {
         #[cfg(not(unix))]
         return Err("profile only available on Unix");
 
@@ -604,9 +819,11 @@ impl<G: GameArenaService> AdminRepo<G> {
                     .flamegraph(&mut buf)
                     .map_err(|_| "error writing profiler flamegraph")?;
 
-                Ok(AdminUpdate::ProfileRequested(
-                    String::from_utf8(buf).map_err(|_| "profile contained invalid utf8")?,
-                ))
+                Ok(AdminUpdate::ProfileRequested {
+                    flamegraph: String::from_utf8(buf)
+                        .map_err(|_| "profile contained invalid utf8")?,
+                    slow_tick,
+                })
             } else {
                 Err("error building profile report")
             }
@@ -620,13 +837,27 @@ impl<G: GameArenaService> Handler<ParameterizedAdminRequest> for Infrastructure<
     type Result = ResponseActFuture<Self, Result<AdminUpdate, &'static str>>;
 
     fn handle(&mut self, msg: ParameterizedAdminRequest, _ctx: &mut Self::Context) -> Self::Result {
-        if !self.admin.authenticate(&msg) {
+        if !self.admin.authenticate(&msg.auth) {
             return Box::pin(fut::ready(Err("invalid auth")));
         }
 
         let request = msg.request;
         let database = self.database();
-        match request {
+
+        // Moderation requests are audited (with reasons) by their respective handlers. The target
+        // is described before the action, in case the action changes it (e.g. its alias).
+        let audit = audit_action(&request).map(|(action, player_id)| {
+            let players = &self.context_service.context.players;
+            let target = player_id.map(|player_id| {
+                players
+                    .borrow_player(player_id)
+                    .map(|player| describe_player(player_id, player.alias()))
+                    .unwrap_or_else(|| format!("{:?}", player_id))
+            });
+            (action, target)
+        });
+
+        let response: Self::Result = match request {
             AdminRequest::RequestSnippets => Box::pin(fut::ready(AdminRepo::request_snippets(
                 &self.context_service.context.clients,
             ))),
@@ -707,17 +938,94 @@ impl<G: GameArenaService> Handler<ParameterizedAdminRequest> for Infrastructure<
                 moderator,
                 &self.context_service.context.players,
             ))),
-            AdminRequest::RestrictPlayer { player_id, minutes } => {
-                Box::pin(fut::ready(self.admin.restrict_player(
+            AdminRequest::RequestEntities { x, y, radius } => Box::pin(fut::ready(
+                self.admin
+                    .request_entities(x, y, radius, &self.context_service.service),
+            )),
+            AdminRequest::TeleportPlayer { player_id, x, y } => Box::pin(fut::ready(
+                self.admin
+                    .teleport_player(player_id, x, y, &mut self.context_service),
+            )),
+            AdminRequest::KillPlayer { player_id } => Box::pin(fut::ready(
+                self.admin.kill_player(player_id, &mut self.context_service),
+            )),
+            AdminRequest::SpawnEntity {
+                entity_type,
+                x,
+                y,
+                player_id,
+            } => Box::pin(fut::ready(self.admin.spawn_entity(
+                &entity_type,
+                x,
+                y,
+                player_id,
+                &mut self.context_service,
+            ))),
+            AdminRequest::SetPlayerScore { player_id, score } => {
+                Box::pin(fut::ready(self.admin.set_player_score(
                     player_id,
-                    minutes,
+                    score,
                     &self.context_service.context.players,
                 )))
             }
-            AdminRequest::MutePlayer { player_id, minutes } => Box::pin(fut::ready(
-                self.admin
-                    .mute_player(player_id, minutes, &self.context_service.context.players),
-            )),
+            AdminRequest::SetPlayerInvulnerable {
+                player_id,
+                invulnerable,
+            } => Box::pin(fut::ready(self.admin.set_player_invulnerable(
+                player_id,
+                invulnerable,
+                &mut self.context_service,
+            ))),
+            AdminRequest::RestrictPlayer {
+                player_id,
+                minutes,
+                reason,
+            } => Box::pin(fut::ready(self.admin.restrict_player(
+                player_id,
+                minutes,
+                &reason,
+                &self.context_service.context.players,
+                &mut self.context_service.context.moderation,
+            ))),
+            AdminRequest::MutePlayer {
+                player_id,
+                minutes,
+                reason,
+            } => Box::pin(fut::ready(self.admin.mute_player(
+                player_id,
+                minutes,
+                &reason,
+                &self.context_service.context.players,
+                &mut self.context_service.context.moderation,
+            ))),
+            AdminRequest::BanPlayer {
+                player_id,
+                scope,
+                minutes,
+                reason,
+            } => Box::pin(fut::ready(self.admin.ban_player(
+                player_id,
+                scope,
+                minutes,
+                &reason,
+                &mut self.context_service.context,
+            ))),
+            AdminRequest::RequestSanctions => {
+                Box::pin(fut::ready(Ok(AdminUpdate::SanctionsRequested(
+                    self.context_service.context.moderation.sanctions(),
+                ))))
+            }
+            AdminRequest::RevokeSanction {
+                sanction_id,
+                reason,
+            } => Box::pin(fut::ready(self.admin.revoke_sanction(
+                sanction_id,
+                &reason,
+                &mut self.context_service.context,
+            ))),
+            AdminRequest::RequestAuditLog => Box::pin(fut::ready(Ok(
+                AdminUpdate::AuditLogRequested(self.context_service.context.moderation.audit_log()),
+            ))),
             AdminRequest::RequestServerId => Box::pin(fut::ready(Ok(
                 AdminUpdate::ServerIdRequested(self.server_id),
             ))),
@@ -778,14 +1086,111 @@ impl<G: GameArenaService> Handler<ParameterizedAdminRequest> for Infrastructure<
                     Box::pin(
                         tokio::time::sleep(Duration::from_secs(10))
                             .into_actor(self)
-                            .map(move |_res, act, _ctx| act.admin.finish_profile()),
+                            .map(move |_res, act, _ctx| {
+                                let slow_tick = act.context_service.service.take_slow_tick_report();
+                                act.admin.finish_profile(slow_tick)
+                            }),
                     )
                 }
             }
+        };
+
+        if let Some((action, target)) = audit {
+            Box::pin(response.map(move |result, act, _ctx| {
+                // Only actions that took effect are audited, along with their result.
+                if let Ok(update) = &result {
+                    act.context_service.context.moderation.audit(
+                        ISSUER,
+                        &format!("{} ({:?})", action, update),
+                        target,
+                        "",
+                    );
+                }
+                result
+            }))
+        } else {
+            response
         }
     }
 }
 
+/// Who admin actions are attributed to in the audit log.
+const ISSUER: &str = "admin";
+
+/// Describes a player for the audit log.
+fn describe_player(player_id: PlayerId, alias: PlayerAlias) -> String {
+    format!("{} ({})", alias.as_str(), player_id.0)
+}
+
+/// Describes requests that change something (other than moderation requests) for the audit log,
+/// as an action and the player it targets, if any.
+fn audit_action(request: &AdminRequest) -> Option<(String, Option<PlayerId>)> {
+    Some(match request {
+        AdminRequest::ClearSnippet {
+            cohort_id,
+            referrer,
+        } => (
+            format!("clear snippet {:?} {:?}", cohort_id, referrer),
+            None,
+        ),
+        AdminRequest::KillPlayer { player_id } => (String::from("kill"), Some(*player_id)),
+        AdminRequest::OverrideClientHash(server_id) => {
+            (format!("override client hash {:?}", server_id), None)
+        }
+        AdminRequest::OverridePlayerAlias { player_id, alias } => (
+            format!("override alias to {}", alias.as_str()),
+            Some(*player_id),
+        ),
+        AdminRequest::OverridePlayerModerator {
+            player_id,
+            moderator,
+        } => (
+            format!("override moderator to {}", moderator),
+            Some(*player_id),
+        ),
+        AdminRequest::SendChat {
+            player_id, message, ..
+        } => (format!("send chat {:?}", message), *player_id),
+        AdminRequest::SetAllowWebSocketJson(allow) => {
+            (format!("allow web socket json {}", allow), None)
+        }
+        AdminRequest::SetDistributeLoad(distribute) => {
+            (format!("distribute load {}", distribute), None)
+        }
+        AdminRequest::SetGameClient(_) => (String::from("set game client"), None),
+        AdminRequest::SetPlayerInvulnerable {
+            player_id,
+            invulnerable,
+        } => (
+            format!("set invulnerable to {}", invulnerable),
+            Some(*player_id),
+        ),
+        AdminRequest::SetPlayerScore { player_id, score } => {
+            (format!("set score to {}", score), Some(*player_id))
+        }
+        AdminRequest::SetRedirect(server_id) => (format!("redirect to {:?}", server_id), None),
+        AdminRequest::SetSnippet {
+            cohort_id,
+            referrer,
+            ..
+        } => (format!("set snippet {:?} {:?}", cohort_id, referrer), None),
+        AdminRequest::SpawnEntity {
+            entity_type,
+            x,
+            y,
+            player_id,
+        } => (
+            format!("spawn {} at ({:.0}, {:.0})", entity_type, x, y),
+            *player_id,
+        ),
+        AdminRequest::TeleportPlayer { player_id, x, y } => (
+            format!("teleport to ({:.0}, {:.0})", x, y),
+            Some(*player_id),
+        ),
+        _ => return None,
+    })
+}
+
 /// Converts a duration to seconds, rounding up.
 fn seconds_ceil(duration: Duration) -> usize {
     ((duration.as_secs() + 59) / 60) as usize
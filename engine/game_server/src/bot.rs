@@ -67,7 +67,7 @@ impl<G: GameArenaService> BotRepo<G> {
             .maybe_par_iter_mut()
             .with_min_sequential(64)
             .for_each(|bot_data: &mut BotData<G>| {
-                let update = G::Bot::get_input(service, &bot_data.player_tuple, &players);
+                let update = G::Bot::get_input(service, &bot_data.player_tuple, players);
                 bot_data.action_buffer = bot_data.bot.update(
                     update,
                     bot_data.player_tuple.player.borrow().player_id,
@@ -3,10 +3,11 @@
 
 use crate::game_service::GameArenaService;
 use crate::metric::MetricRepo;
+use crate::moderation::ModerationRepo;
 use crate::player::PlayerRepo;
 use crate::team::TeamRepo;
 use aho_corasick::{AhoCorasick, AhoCorasickBuilder};
-use core_protocol::dto::MessageDto;
+use core_protocol::dto::{MessageDto, SanctionKind, SanctionTarget};
 use core_protocol::get_unix_time_now;
 use core_protocol::id::PlayerId;
 use core_protocol::name::PlayerAlias;
@@ -151,6 +152,7 @@ impl<G: GameArenaService> ChatRepo<G> {
         restrict_player_id: PlayerId,
         minutes: u32,
         players: &PlayerRepo<G>,
+        moderation: &mut ModerationRepo<G>,
     ) -> Result<ChatUpdate, &'static str> {
         if req_player_id == restrict_player_id {
             return Err("cannot restrict self");
@@ -165,12 +167,35 @@ impl<G: GameArenaService> ChatRepo<G> {
         let mut restrict_player = players
             .borrow_player_mut(restrict_player_id)
             .ok_or("nonexistent player")?;
+        let restrict_alias = restrict_player.alias();
         let restrict_client = restrict_player.client_mut().ok_or("not a real player")?;
         let minutes = minutes.min(1440);
         if let Some(restrict_until) =
             Self::minutes_to_instant(minutes, restrict_client.chat.context.restricted_until())
         {
             restrict_client.chat.context.restrict_until(restrict_until);
+
+            // Survive restarts.
+            let target = SanctionTarget::Session(restrict_client.session_id);
+            let issuer = req_client.alias.as_str();
+            if minutes == 0 {
+                moderation.revoke_matching(SanctionKind::Restrict, target);
+            } else {
+                moderation.sanction(
+                    SanctionKind::Restrict,
+                    target,
+                    restrict_alias,
+                    Some(minutes as usize),
+                    issuer,
+                    "",
+                );
+            }
+            moderation.audit(
+                issuer,
+                &format!("restrict {}m", minutes),
+                Some(restrict_alias.as_str().to_owned()),
+                "",
+            );
             Ok(ChatUpdate::PlayerRestricted {
                 player_id: restrict_player_id,
                 minutes,
@@ -185,6 +210,7 @@ impl<G: GameArenaService> ChatRepo<G> {
         req_player_id: PlayerId,
         minutes: u32,
         players: &PlayerRepo<G>,
+        moderation: &mut ModerationRepo<G>,
     ) -> Result<ChatUpdate, &'static str> {
         let req_player = players
             .borrow_player(req_player_id)
@@ -195,6 +221,12 @@ impl<G: GameArenaService> ChatRepo<G> {
         }
         let clamped = minutes.min(60);
         self.safe_mode_until = Self::minutes_to_instant(clamped, None);
+        moderation.audit(
+            req_client.alias.as_str(),
+            &format!("safe mode {}m", clamped),
+            None,
+            "",
+        );
         Ok(ChatUpdate::SafeModeSet(clamped))
     }
 
@@ -203,6 +235,7 @@ impl<G: GameArenaService> ChatRepo<G> {
         req_player_id: PlayerId,
         minutes: u32,
         players: &PlayerRepo<G>,
+        moderation: &mut ModerationRepo<G>,
     ) -> Result<ChatUpdate, &'static str> {
         let req_player = players
             .borrow_player(req_player_id)
@@ -213,10 +246,17 @@ impl<G: GameArenaService> ChatRepo<G> {
         }
         let clamped = minutes.min(120);
         self.slow_mode_until = Self::minutes_to_instant(clamped, None);
+        moderation.audit(
+            req_client.alias.as_str(),
+            &format!("slow mode {}m", clamped),
+            None,
+            "",
+        );
         Ok(ChatUpdate::SlowModeSet(clamped))
     }
 
     /// Send a chat to all players, or one's team (whisper).
+    #[allow(clippy::too_many_arguments)]
     fn send_chat(
         &mut self,
         req_player_id: PlayerId,
@@ -226,8 +266,11 @@ impl<G: GameArenaService> ChatRepo<G> {
         players: &mut PlayerRepo<G>,
         teams: &TeamRepo<G>,
         metrics: &mut MetricRepo<G>,
+        moderation: &mut ModerationRepo<G>,
     ) -> Result<ChatUpdate, &'static str> {
-        if let Some(text) = self.try_execute_command(req_player_id, &message, service, players) {
+        if let Some(text) =
+            self.try_execute_command(req_player_id, &message, service, players, moderation)
+        {
             if let Some(mut req_player) = players.borrow_player_mut(req_player_id) {
                 let alias = req_player.alias();
                 if let Some(req_client) = req_player.client_mut() {
@@ -399,6 +442,7 @@ impl<G: GameArenaService> ChatRepo<G> {
     }
 
     /// Process any [`ChatRequest`].
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn handle_chat_request(
         &mut self,
         req_player_id: PlayerId,
@@ -407,6 +451,7 @@ impl<G: GameArenaService> ChatRepo<G> {
         players: &mut PlayerRepo<G>,
         teams: &TeamRepo<G>,
         metrics: &mut MetricRepo<G>,
+        moderation: &mut ModerationRepo<G>,
     ) -> Result<ChatUpdate, &'static str> {
         match request {
             ChatRequest::Mute(player_id) => self.mute_player(req_player_id, player_id, players),
@@ -419,15 +464,16 @@ impl<G: GameArenaService> ChatRepo<G> {
                 players,
                 teams,
                 metrics,
+                moderation,
             ),
             ChatRequest::SetSafeMode(minutes) => {
-                self.set_safe_mode(req_player_id, minutes, &*players)
+                self.set_safe_mode(req_player_id, minutes, &*players, moderation)
             }
             ChatRequest::SetSlowMode(minutes) => {
-                self.set_slow_mode(req_player_id, minutes, &*players)
+                self.set_slow_mode(req_player_id, minutes, &*players, moderation)
             }
             ChatRequest::RestrictPlayer { player_id, minutes } => {
-                self.restrict_player(req_player_id, player_id, minutes, players)
+                self.restrict_player(req_player_id, player_id, minutes, players, moderation)
             }
         }
     }
@@ -460,7 +506,7 @@ impl<G: GameArenaService> ChatRepo<G> {
                 .chat
                 .take_inbox()
                 .oldest_ordered()
-                .map(|arc| Arc::clone(arc))
+                .map(Arc::clone)
                 .collect();
             Some(ChatUpdate::Received(messages))
         }
@@ -512,6 +558,7 @@ impl<G: GameArenaService> ChatRepo<G> {
         message: &str,
         service: &mut G,
         players: &PlayerRepo<G>,
+        moderation: &mut ModerationRepo<G>,
     ) -> Option<String> {
         struct FormattedDuration(Duration);
 
@@ -568,7 +615,7 @@ impl<G: GameArenaService> ChatRepo<G> {
                     None => print_until_status($name, self.$getter),
                     Some(arg) => {
                         if let Some(minutes) = parse_minutes(arg) {
-                            self.$setter(req_player_id, minutes, players)
+                            self.$setter(req_player_id, minutes, players, moderation)
                                 .map(|_| print_until_status($name, self.$getter))
                                 .map_err(String::from)
                                 .into_ok_or_err()
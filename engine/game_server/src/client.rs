@@ -8,6 +8,7 @@ use crate::invitation::{ClientInvitationData, InvitationRepo};
 use crate::leaderboard::LeaderboardRepo;
 use crate::liveboard::LiveboardRepo;
 use crate::metric::{ClientMetricData, MetricRepo};
+use crate::moderation::ModerationRepo;
 use crate::player::{PlayerData, PlayerRepo, PlayerTuple};
 use crate::system::SystemRepo;
 use crate::team::{ClientTeamData, TeamRepo};
@@ -89,9 +90,9 @@ impl<G: GameArenaService> ClientRepo<G> {
             let value = ReferrerSnippet::get(&key).map(|f| f.data);
             if let Some(value) = value {
                 // key is like "default.js" or "1.foo.js" where "foo" is a referrer (referrer cannot contain ".").
-                let segs: Vec<&str> = key.split(".").collect();
+                let segs: Vec<&str> = key.split('.').collect();
                 let n = segs.len();
-                if n < 2 || n > 3 || segs[n - 1].to_lowercase() != "js" {
+                if !(2..=3).contains(&n) || segs[n - 1].to_lowercase() != "js" {
                     error!("invalid snippet key: {:?}", key);
                     continue;
                 }
@@ -146,7 +147,7 @@ impl<G: GameArenaService> ClientRepo<G> {
         #[cfg(debug_assertions)]
         let server_id = infrastructure
             .server_id
-            .unwrap_or(ServerId::new(200).unwrap());
+            .unwrap_or_else(|| ServerId::new(200).unwrap());
         #[cfg(not(debug_assertions))]
         let server_id = crate::unwrap_or_return!(infrastructure.server_id);
         let arena_id = infrastructure.context_service.context.arena_id;
@@ -227,6 +228,7 @@ impl<G: GameArenaService> ClientRepo<G> {
     }
 
     /// Client websocket (re)connected.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn register(
         &mut self,
         player_id: PlayerId,
@@ -385,20 +387,18 @@ impl<G: GameArenaService> ClientRepo<G> {
             None => return,
         };
 
-        match &client.status {
-            ClientStatus::Connected { observer } => {
-                if observer.same_channel(&unregister_observer) {
-                    client.status = ClientStatus::Limbo {
-                        expiry: Instant::now() + G::LIMBO,
-                    };
-                    info!("player {:?} is in limbo", player_id);
-                }
+        if let ClientStatus::Connected { observer } = &client.status {
+            if observer.same_channel(&unregister_observer) {
+                client.status = ClientStatus::Limbo {
+                    expiry: Instant::now() + G::LIMBO,
+                };
+                info!("player {:?} is in limbo", player_id);
             }
-            _ => {}
         }
     }
 
     /// Update all clients with game state.
+    #[allow(clippy::type_complexity)]
     pub(crate) fn update(
         &mut self,
         game: &G,
@@ -428,6 +428,7 @@ impl<G: GameArenaService> ClientRepo<G> {
                     (
                         ChatRepo::<G>::player_delta(player_id, immut_players),
                         teams.player_delta(player_id, immut_players).unwrap(),
+                        TeamRepo::<G>::take_pings(player_id, immut_players),
                     ),
                 )
             })
@@ -449,7 +450,7 @@ impl<G: GameArenaService> ClientRepo<G> {
                 if let ClientStatus::Connected { observer } = &client_data.status {
                     if let Some(update) = game.get_game_update(
                         player_tuple,
-                        &mut *client_data.data.borrow_mut(),
+                        &mut client_data.data.borrow_mut(),
                         players,
                     ) {
                         let _ = observer.send(ObserverUpdate::Send {
@@ -482,8 +483,8 @@ impl<G: GameArenaService> ClientRepo<G> {
                         }
                     }
 
-                    if let Some((chat_update, (members, joiners, joins))) =
-                        player_chat_team_updates.get(&player_id)
+                    if let Some((chat_update, (members, joiners, joins), pings)) =
+                        player_chat_team_updates.get(player_id)
                     {
                         if let Some(chat_update) = chat_update {
                             let _ = observer.send(ObserverUpdate::Send {
@@ -515,6 +516,12 @@ impl<G: GameArenaService> ClientRepo<G> {
                                 )),
                             });
                         }
+
+                        if let Some(pings) = pings {
+                            let _ = observer.send(ObserverUpdate::Send {
+                                message: Update::Team(TeamUpdate::Pinged(pings.clone())),
+                            });
+                        }
                     } else {
                         debug_assert!(
                             false,
@@ -526,7 +533,7 @@ impl<G: GameArenaService> ClientRepo<G> {
                         let _ = observer.send(ObserverUpdate::Send {
                             message: Update::Leaderboard(LeaderboardUpdate::Updated(
                                 period_id,
-                                Arc::clone(&leaderboard),
+                                Arc::clone(leaderboard),
                             )),
                         });
                     }
@@ -558,6 +565,7 @@ impl<G: GameArenaService> ClientRepo<G> {
     }
 
     /// Cleans up old clients. Rate limited internally.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn prune(
         &mut self,
         service: &mut G,
@@ -695,7 +703,7 @@ impl<G: GameArenaService> ClientRepo<G> {
                 };
                 metric.increment();
             },
-            &mut client.metrics,
+            &client.metrics,
         );
         Ok(ClientUpdate::AdTallied)
     }
@@ -809,6 +817,7 @@ impl<G: GameArenaService> ClientRepo<G> {
     }
 
     /// Handles request made by real player.
+    #[allow(clippy::too_many_arguments)]
     fn handle_observer_request(
         &mut self,
         player_id: PlayerId,
@@ -819,6 +828,7 @@ impl<G: GameArenaService> ClientRepo<G> {
         players: &mut PlayerRepo<G>,
         teams: &mut TeamRepo<G>,
         chat: &mut ChatRepo<G>,
+        moderation: &mut ModerationRepo<G>,
         invitations: &mut InvitationRepo<G>,
         metrics: &mut MetricRepo<G>,
     ) -> Result<Option<Update<G::GameUpdate>>, &'static str> {
@@ -832,7 +842,9 @@ impl<G: GameArenaService> ClientRepo<G> {
                 .handle_client_request(player_id, request, &*players, metrics)
                 .map(|u| Some(Update::Client(u))),
             Request::Chat(request) => chat
-                .handle_chat_request(player_id, request, service, players, teams, metrics)
+                .handle_chat_request(
+                    player_id, request, service, players, teams, metrics, moderation,
+                )
                 .map(|u| Some(Update::Chat(u))),
             Request::Invitation(request) => invitations
                 .handle_invitation_request(player_id, request, arena_id, server_id, players)
@@ -1009,6 +1021,7 @@ impl<G: GameArenaService> Handler<ObserverMessage<Request<G::GameRequest>, Updat
                     &mut context.players,
                     &mut context.teams,
                     &mut context.chat,
+                    &mut context.moderation,
                     &mut self.invitations,
                     &mut self.metrics,
                 ) {
@@ -1088,6 +1101,15 @@ impl<G: GameArenaService> Handler<Authenticate> for Infrastructure<G> {
             return Box::pin(fut::ready(Err("rate limit exceeded")));
         }
 
+        // Fast path; discord id and restored session are checked below.
+        if self.context_service.context.moderation.is_banned(
+            msg.ip_address,
+            msg.arena_id_session_id.map(|(_, session_id)| session_id),
+            None,
+        ) {
+            return Box::pin(fut::ready(Err("banned")));
+        }
+
         // TODO: O(n) on players.
         let cached_session_id_player_id = msg
             .arena_id_session_id
@@ -1205,6 +1227,20 @@ impl<G: GameArenaService> Handler<Authenticate> for Infrastructure<G> {
                         (new_session_id, new_player_id)
                     };
 
+                    let existing_discord_id = act
+                        .context_service
+                        .context
+                        .players
+                        .borrow_player(player_id)
+                        .and_then(|p| p.client().and_then(|c| c.discord_id));
+                    if act.context_service.context.moderation.is_banned(
+                        msg.ip_address,
+                        Some(session_id),
+                        discord_id.or(existing_discord_id),
+                    ) {
+                        return Err("banned");
+                    }
+
                     match act.context_service.context.players.players.entry(player_id) {
                         Entry::Occupied(mut occupied) => {
                             if let Some(client) =
@@ -1225,7 +1261,7 @@ impl<G: GameArenaService> Handler<Authenticate> for Infrastructure<G> {
                             }
                         }
                         Entry::Vacant(vacant) => {
-                            let client = PlayerClientData::new(
+                            let mut client = PlayerClientData::new(
                                 session_id,
                                 client_metric_data,
                                 invitation_dto,
@@ -1233,6 +1269,7 @@ impl<G: GameArenaService> Handler<Authenticate> for Infrastructure<G> {
                                 msg.ip_address,
                                 is_moderator,
                             );
+                            act.context_service.context.moderation.apply(&mut client);
                             let pd = PlayerData::new(player_id, Some(Box::new(client)));
                             let pt = Arc::new(PlayerTuple::new(pd));
                             vacant.insert(pt);
@@ -6,10 +6,14 @@ use crate::chat::ChatRepo;
 use crate::client::ClientRepo;
 use crate::game_service::GameArenaService;
 use crate::liveboard::LiveboardRepo;
+use crate::moderation::ModerationRepo;
 use crate::player::PlayerRepo;
 use crate::team::TeamRepo;
+use core_protocol::dto::MessageDto;
+use core_protocol::get_unix_time_now;
 use core_protocol::id::ArenaId;
 use server_util::rate_limiter::RateLimiterProps;
+use std::sync::Arc;
 
 /// Things that go along with every instance of a [`GameArenaService`].
 pub struct Context<G: GameArenaService> {
@@ -18,6 +22,7 @@ pub struct Context<G: GameArenaService> {
     pub(crate) clients: ClientRepo<G>,
     pub(crate) bots: BotRepo<G>,
     pub(crate) chat: ChatRepo<G>,
+    pub(crate) moderation: ModerationRepo<G>,
     pub teams: TeamRepo<G>,
     pub(crate) liveboard: LiveboardRepo<G>,
 }
@@ -28,6 +33,8 @@ impl<G: GameArenaService> Context<G> {
         bots: BotRepo<G>,
         chat_log: Option<String>,
         trace_log: Option<String>,
+        sanctions_file: Option<String>,
+        audit_log: Option<String>,
         client_authenticate: RateLimiterProps,
     ) -> Self {
         Context {
@@ -37,7 +44,22 @@ impl<G: GameArenaService> Context<G> {
             players: PlayerRepo::new(),
             teams: TeamRepo::new(),
             chat: ChatRepo::new(chat_log),
+            moderation: ModerationRepo::new(sanctions_file, audit_log),
             liveboard: LiveboardRepo::new(),
         }
     }
+
+    /// Broadcasts a system message, sent by [`GameArenaService::authority_alias`], to all players.
+    pub fn announce(&mut self, text: String) {
+        let message = Arc::new(MessageDto {
+            alias: G::authority_alias(),
+            date_sent: get_unix_time_now(),
+            player_id: None,
+            team_captain: false,
+            team_name: None,
+            text,
+            whisper: false,
+        });
+        self.chat.broadcast_message(message, &mut self.players);
+    }
 }
@@ -11,14 +11,35 @@ use core_protocol::dto::ServerDto;
 use core_protocol::id::{ArenaId, ServerId};
 use server_util::rate_limiter::RateLimiterProps;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 /// Contains a [`GameArenaService`] and the corresponding [`Context`].
 pub struct ContextService<G: GameArenaService> {
     pub context: Context<G>,
     pub service: G,
+    /// How long each phase of the last update took.
+    pub timings: UpdateTimings,
+}
+
+/// Wall-clock durations of the phases of [`ContextService::update`].
+#[derive(Copy, Clone, Debug, Default)]
+pub struct UpdateTimings {
+    /// Spawning/de-spawning clients and bots.
+    pub prune: Duration,
+    /// [`GameArenaService::tick`].
+    pub tick: Duration,
+    /// Sending updates to clients.
+    pub clients: Duration,
+    /// Updating bots.
+    pub bots: Duration,
+    /// Processing the leaderboard.
+    pub leaderboard: Duration,
+    /// [`GameArenaService::post_update`] and bot commands.
+    pub post_update: Duration,
 }
 
 impl<G: GameArenaService> ContextService<G> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         arena_id: ArenaId,
         min_bots: Option<usize>,
@@ -26,16 +47,28 @@ impl<G: GameArenaService> ContextService<G> {
         bot_percent: Option<usize>,
         chat_log: Option<String>,
         trace_log: Option<String>,
+        sanctions_file: Option<String>,
+        audit_log: Option<String>,
         client_authenticate: RateLimiterProps,
     ) -> Self {
         let bots = BotRepo::new_from_options(min_bots, max_bots, bot_percent);
 
         Self {
             service: G::new(bots.min_bots),
-            context: Context::new(arena_id, bots, chat_log, trace_log, client_authenticate),
+            context: Context::new(
+                arena_id,
+                bots,
+                chat_log,
+                trace_log,
+                sanctions_file,
+                audit_log,
+                client_authenticate,
+            ),
+            timings: UpdateTimings::default(),
         }
     }
 
+    #[allow(clippy::type_complexity)]
     pub(crate) fn update(
         &mut self,
         leaderboard: &mut LeaderboardRepo<G>,
@@ -44,6 +77,14 @@ impl<G: GameArenaService> ContextService<G> {
         server_id: Option<ServerId>,
         server_delta: Option<(Arc<[ServerDto]>, Arc<[ServerId]>)>,
     ) {
+        let mut last = Instant::now();
+        let mut lap = || {
+            let now = Instant::now();
+            let elapsed = now - last;
+            last = now;
+            elapsed
+        };
+
         // Spawn/de-spawn clients and bots.
         self.context.clients.prune(
             &mut self.service,
@@ -57,6 +98,7 @@ impl<G: GameArenaService> ContextService<G> {
         self.context
             .bots
             .update_count(&mut self.service, &mut self.context.players);
+        self.timings.prune = lap();
 
         // Update game logic.
         self.service.tick(&mut self.context);
@@ -65,6 +107,7 @@ impl<G: GameArenaService> ContextService<G> {
             &mut self.context.teams,
             metrics,
         );
+        self.timings.tick = lap();
 
         // Update clients and bots.
         self.context.clients.update(
@@ -75,11 +118,14 @@ impl<G: GameArenaService> ContextService<G> {
             leaderboard,
             server_delta,
         );
+        self.timings.clients = lap();
         self.context
             .bots
             .update(&self.service, &self.context.players);
+        self.timings.bots = lap();
 
         leaderboard.process(&self.context.liveboard, &self.context.players);
+        self.timings.leaderboard = lap();
 
         // Post-update game logic.
         self.service.post_update(&mut self.context);
@@ -89,5 +135,6 @@ impl<G: GameArenaService> ContextService<G> {
         self.context
             .bots
             .post_update(&mut self.service, &self.context.players);
+        self.timings.post_update = lap();
     }
 }
@@ -160,7 +160,7 @@ impl DiscordBotRepo {
 
         Ok(membership.roles.iter().any(|id| {
             roles_hash
-                .get(&*id)
+                .get(id)
                 .map(|name| matches!(name.as_str(), "Developer" | "Moderator"))
                 .unwrap_or(false)
         }))
@@ -203,7 +203,7 @@ impl DiscordOauth2Repo {
             .add_scope(Scope::new("identify".to_string()))
             .url();
 
-        Redirect::to(&auth_url.to_string())
+        Redirect::to(auth_url.as_str())
     }
 
     pub async fn authenticate(&self, code: String) -> Result<NonZeroU64, String> {
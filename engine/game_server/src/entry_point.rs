@@ -11,6 +11,9 @@ use crate::game_service::GameArenaService;
 use crate::infrastructure::Infrastructure;
 use crate::leaderboard::LeaderboardRequest;
 use crate::options::Options;
+use crate::prometheus::{
+    PrometheusRequest, PrometheusWriter, WEB_SOCKET_MESSAGES_RX, WEB_SOCKET_MESSAGES_TX,
+};
 use crate::static_files::{static_size_and_hash, StaticFilesHandler};
 use crate::status::StatusRequest;
 use crate::system::{SystemRepo, SystemRequest};
@@ -18,8 +21,9 @@ use actix::Actor;
 use axum::body::{boxed, Empty, Full, HttpBody};
 use axum::extract::ws::{CloseCode, CloseFrame, Message};
 use axum::extract::{ConnectInfo, Query, TypedHeader, WebSocketUpgrade};
-use axum::headers::HeaderName;
-use axum::http::header::CACHE_CONTROL;
+use axum::headers::authorization::Bearer;
+use axum::headers::{Authorization, HeaderName};
+use axum::http::header::{CACHE_CONTROL, CONTENT_TYPE};
 use axum::http::uri::{Authority, Scheme};
 use axum::http::{HeaderValue, Method, Response, StatusCode, Uri};
 use axum::response::{IntoResponse, Redirect};
@@ -66,7 +70,7 @@ lazy_static::lazy_static! {
 }
 
 pub fn entry_point<G: GameArenaService>(game_client: MiniCdn, browser_router: bool) {
-    let _ = actix::System::new().block_on(async move {
+    actix::System::new().block_on(async move {
         let options = Options::from_args();
 
         crate::log::init_logger(&options);
@@ -138,6 +142,8 @@ pub fn entry_point<G: GameArenaService>(game_client: MiniCdn, browser_router: bo
                 options.bot_percent,
                 options.chat_log,
                 options.trace_log,
+                options.sanctions_file,
+                options.audit_log,
                 Arc::clone(&game_client),
                 &ALLOW_WEB_SOCKET_JSON,
                 options.admin_config_file,
@@ -158,6 +164,7 @@ pub fn entry_point<G: GameArenaService>(game_client: MiniCdn, browser_router: bo
         let ws_srv = srv.to_owned();
         let admin_srv = srv.to_owned();
         let leaderboard_srv = srv.to_owned();
+        let metrics_srv = srv.to_owned();
         let status_srv = srv.to_owned();
         let system_srv = srv.to_owned();
 
@@ -236,7 +243,7 @@ pub fn entry_point<G: GameArenaService>(game_client: MiniCdn, browser_router: bo
                             .on_upgrade(async move |mut web_socket| {
                             let (server_sender, mut server_receiver) = tokio::sync::mpsc::unbounded_channel::<ObserverUpdate<Update<G::GameUpdate>>>();
 
-                            let _ = ws_srv.do_send(ObserverMessage::<Request<G::GameRequest>, Update<G::GameUpdate>>::Register {
+                            ws_srv.do_send(ObserverMessage::<Request<G::GameRequest>, Update<G::GameUpdate>>::Register {
                                 player_id,
                                 observer: server_sender.clone(),
                             });
@@ -262,6 +269,7 @@ pub fn entry_point<G: GameArenaService>(game_client: MiniCdn, browser_router: bo
                                         match web_socket_update {
                                             Some(result) => match result {
                                                 Ok(message) => {
+                                                    WEB_SOCKET_MESSAGES_RX.fetch_add(1, Ordering::Relaxed);
                                                     last_activity = Instant::now();
                                                     keep_alive.as_mut().reset((last_activity + TIMER_DURATION).into());
 
@@ -279,7 +287,7 @@ pub fn entry_point<G: GameArenaService>(game_client: MiniCdn, browser_router: bo
                                                             {
                                                                 Ok(request) => {
                                                                     protocol = WebSocketProtocol::Binary;
-                                                                    let _ = ws_srv.do_send(ObserverMessage::<Request<G::GameRequest>, Update<G::GameUpdate >>::Request {
+                                                                    ws_srv.do_send(ObserverMessage::<Request<G::GameRequest>, Update<G::GameUpdate >>::Request {
                                                                         player_id,
                                                                         request,
                                                                     });
@@ -298,7 +306,7 @@ pub fn entry_point<G: GameArenaService>(game_client: MiniCdn, browser_router: bo
                                                             match result {
                                                                 Ok(request) => {
                                                                     protocol = WebSocketProtocol::Json;
-                                                                    let _ = ws_srv.do_send(ObserverMessage::<Request<G::GameRequest>, Update<G::GameUpdate >>::Request {
+                                                                    ws_srv.do_send(ObserverMessage::<Request<G::GameRequest>, Update<G::GameUpdate >>::Request {
                                                                         player_id,
                                                                         request,
                                                                     });
@@ -321,7 +329,7 @@ pub fn entry_point<G: GameArenaService>(game_client: MiniCdn, browser_router: bo
                                                                 let timestamp = UnixTime::from_ne_bytes(bytes);
                                                                 let rtt = now.saturating_sub(timestamp);
                                                                 if rtt <= 10000 as UnixTime {
-                                                                    let _ = ws_srv.do_send(ObserverMessage::<Request<G::GameRequest>, Update<G::GameUpdate >>::RoundTripTime {
+                                                                    ws_srv.do_send(ObserverMessage::<Request<G::GameRequest>, Update<G::GameUpdate >>::RoundTripTime {
                                                                         player_id,
                                                                         rtt: rtt as u16,
                                                                     });
@@ -368,11 +376,11 @@ pub fn entry_point<G: GameArenaService>(game_client: MiniCdn, browser_router: bo
                                                 if web_socket.send(web_socket_message).await.is_err() {
                                                     break NORMAL_CLOSURE;
                                                 }
+                                                WEB_SOCKET_MESSAGES_TX.fetch_add(1, Ordering::Relaxed);
 
-                                                if !measure_rtt_ping_governor.should_limit_rate_with_now(&MEASURE_RTT_PING, last_activity) {
-                                                    if web_socket.send(Message::Ping(get_unix_time_now().to_ne_bytes().into())).await.is_err() {
-                                                        break NORMAL_CLOSURE;
-                                                    }
+                                                if !measure_rtt_ping_governor.should_limit_rate_with_now(&MEASURE_RTT_PING, last_activity)
+                                                    && web_socket.send(Message::Ping(get_unix_time_now().to_ne_bytes().into())).await.is_err() {
+                                                    break NORMAL_CLOSURE;
                                                 }
                                             }
                                             ObserverUpdate::Close => {
@@ -394,7 +402,7 @@ pub fn entry_point<G: GameArenaService>(game_client: MiniCdn, browser_router: bo
                                 }
                             };
 
-                            let _ = ws_srv.do_send(ObserverMessage::<Request<G::GameRequest>, Update<G::GameUpdate>>::Unregister {
+                            ws_srv.do_send(ObserverMessage::<Request<G::GameRequest>, Update<G::GameUpdate>>::Unregister {
                                 player_id,
                                 observer: server_sender,
                             });
@@ -477,6 +485,24 @@ pub fn entry_point<G: GameArenaService>(game_client: MiniCdn, browser_router: bo
                     }
                 }
             }))
+            .route("/metrics", get(move |authorization: Option<TypedHeader<Authorization<Bearer>>>| {
+                let srv = metrics_srv.to_owned();
+                debug!("received metrics request");
+
+                async move {
+                    // Scrapers authenticate with the admin password, as a bearer token.
+                    let auth = authorization.map(|TypedHeader(Authorization(bearer))| String::from(bearer.token())).unwrap_or_default();
+                    match srv.send(PrometheusRequest{auth}).await {
+                        Ok(result) => match result {
+                            Ok(metrics_response) => {
+                                Ok(([(CONTENT_TYPE, PrometheusWriter::CONTENT_TYPE)], metrics_response))
+                            }
+                            Err(e) => Err((StatusCode::UNAUTHORIZED, String::from(e)).into_response()),
+                        },
+                        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()),
+                    }
+                }
+            }))
             .route("/status.json", get(move || {
                 let srv = status_srv.to_owned();
                 debug!("received status request");
@@ -516,6 +542,8 @@ pub fn entry_point<G: GameArenaService>(game_client: MiniCdn, browser_router: bo
                     let addr = request.extensions().get::<ConnectInfo<SocketAddr>>().map(|ci| ci.0);
 
                     if !request.headers().get("auth").map(|hv| constant_time_eq::constant_time_eq(include_str!("auth.txt").as_bytes(), hv.as_bytes())).unwrap_or(false) {
+                        // The closure's return type can't be inferred through `?`.
+                        #[allow(clippy::question_mark)]
                         if let Err(response) = limit_content_length(request.headers(), 16384) {
                             return Err(response);
                         }
@@ -3,6 +3,8 @@
 
 use crate::context::Context;
 use crate::player::{PlayerRepo, PlayerTuple};
+use crate::prometheus::PrometheusWriter;
+use core_protocol::dto::AdminEntityDto;
 use core_protocol::id::{GameId, PlayerId, TeamId};
 use core_protocol::name::PlayerAlias;
 use serde::de::DeserializeOwned;
@@ -120,6 +122,67 @@ pub trait GameArenaService: 'static + Unpin + Sized + Send + Sync {
     fn post_update(&mut self, context: &mut Context<Self>) {
         let _ = context;
     }
+
+    /// Takes a report of the slowest tick that ran over budget since the last call, if any, to
+    /// accompany an admin profile.
+    fn take_slow_tick_report(&mut self) -> Option<String> {
+        None
+    }
+
+    /// Writes game specific metrics, to be scraped alongside the server metrics.
+    fn prometheus(&self, writer: &mut PrometheusWriter) {
+        let _ = writer;
+    }
+
+    /// Lists entities within a radius of a point, for the admin world inspector.
+    fn admin_entities(
+        &self,
+        x: f32,
+        y: f32,
+        radius: f32,
+    ) -> Result<Vec<AdminEntityDto>, &'static str> {
+        let _ = (x, y, radius);
+        Err("unsupported")
+    }
+
+    /// Moves a player's boat (or equivalent) to a point.
+    fn admin_teleport(
+        &mut self,
+        player_tuple: &Arc<PlayerTuple<Self>>,
+        x: f32,
+        y: f32,
+    ) -> Result<(), &'static str> {
+        let _ = (player_tuple, x, y);
+        Err("unsupported")
+    }
+
+    /// Kills a player's boat (or equivalent).
+    fn admin_kill(&mut self, player_tuple: &Arc<PlayerTuple<Self>>) -> Result<(), &'static str> {
+        let _ = player_tuple;
+        Err("unsupported")
+    }
+
+    /// Spawns an entity, given the name of its type, at (or near) a point.
+    fn admin_spawn(
+        &mut self,
+        entity_type: &str,
+        x: f32,
+        y: f32,
+        owner: Option<&Arc<PlayerTuple<Self>>>,
+    ) -> Result<(), &'static str> {
+        let _ = (entity_type, x, y, owner);
+        Err("unsupported")
+    }
+
+    /// Makes a player immune to damage, or not.
+    fn admin_set_invulnerable(
+        &mut self,
+        player_tuple: &Arc<PlayerTuple<Self>>,
+        invulnerable: bool,
+    ) -> Result<(), &'static str> {
+        let _ = (player_tuple, invulnerable);
+        Err("unsupported")
+    }
 }
 
 /// Implemented by game bots.
@@ -181,7 +244,6 @@ impl Bot<MockGame> for MockGameBot {
         _player_tuple: &'a Arc<PlayerTuple<MockGame>>,
         _players: &PlayerRepo<MockGame>,
     ) -> Self::Input<'a> {
-        ()
     }
 
     fn update<'a>(
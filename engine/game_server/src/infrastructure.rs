@@ -77,6 +77,7 @@ impl<G: GameArenaService> Actor for Infrastructure<G> {
 
 impl<G: GameArenaService> Infrastructure<G> {
     /// new returns a game server with the specified parameters.
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         server_id: Option<ServerId>,
         system: Option<SystemRepo<G>>,
@@ -90,6 +91,8 @@ impl<G: GameArenaService> Infrastructure<G> {
         bot_percent: Option<usize>,
         chat_log: Option<String>,
         trace_log: Option<String>,
+        sanctions_file: Option<String>,
+        audit_log: Option<String>,
         game_client: Arc<RwLock<MiniCdn>>,
         allow_web_socket_json: &'static AtomicBool,
         admin_config_file: Option<String>,
@@ -117,6 +120,8 @@ impl<G: GameArenaService> Infrastructure<G> {
                 bot_percent,
                 chat_log,
                 trace_log,
+                sanctions_file,
+                audit_log,
                 client_authenticate,
             ),
             invitations: InvitationRepo::new(),
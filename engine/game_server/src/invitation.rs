@@ -46,6 +46,12 @@ impl ClientInvitationData {
     }
 }
 
+impl<G: GameArenaService> Default for InvitationRepo<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<G: GameArenaService> InvitationRepo<G> {
     pub fn new() -> Self {
         Self {
@@ -64,6 +70,11 @@ impl<G: GameArenaService> InvitationRepo<G> {
         self.invitations.len()
     }
 
+    /// Returns true if no invitations are cached.
+    pub fn is_empty(&self) -> bool {
+        self.invitations.is_empty()
+    }
+
     /// Forgets any invitation the player created.
     pub(crate) fn forget_player_invitation(&mut self, player: &mut AtomicRefMut<PlayerData<G>>) {
         let client = unwrap_or_return!(player.client_mut());
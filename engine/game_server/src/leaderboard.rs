@@ -34,6 +34,12 @@ pub struct LeaderboardRepo<G: GameArenaService> {
     _spooky: PhantomData<G>,
 }
 
+impl<G: GameArenaService> Default for LeaderboardRepo<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<G: GameArenaService> LeaderboardRepo<G> {
     pub fn new() -> Self {
         Self {
@@ -28,8 +28,10 @@ pub mod invitation;
 pub mod leaderboard;
 pub mod liveboard;
 pub mod metric;
+pub mod moderation;
 pub mod ordered_set;
 pub mod player;
+pub mod prometheus;
 pub mod status;
 pub mod team;
 #[macro_use]
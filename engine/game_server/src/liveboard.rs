@@ -22,6 +22,12 @@ pub struct LiveboardRepo<G: GameArenaService> {
     _spooky: PhantomData<G>,
 }
 
+impl<G: GameArenaService> Default for LiveboardRepo<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<G: GameArenaService> LiveboardRepo<G> {
     pub fn new() -> Self {
         Self {
@@ -79,6 +85,7 @@ impl<G: GameArenaService> LiveboardRepo<G> {
     }
 
     /// Recalculates liveboard and generates a diff.
+    #[allow(clippy::type_complexity)]
     pub fn delta(
         &mut self,
         players: &PlayerRepo<G>,
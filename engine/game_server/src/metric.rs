@@ -228,10 +228,7 @@ impl MetricBundle {
     }
 
     pub fn metric(&self, filter: Option<MetricFilter>) -> Metrics {
-        self.bundle
-            .get(filter)
-            .cloned()
-            .unwrap_or_else(|| Metrics::default())
+        self.bundle.get(filter).cloned().unwrap_or_default()
     }
 
     pub fn data_point(&self, filter: Option<MetricFilter>) -> MetricsDataPointDto {
@@ -0,0 +1,467 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::client::{ClientStatus, PlayerClientData};
+use crate::game_service::GameArenaService;
+use crate::player::PlayerRepo;
+use core_protocol::dto::{AuditEntryDto, SanctionDto, SanctionKind, SanctionScope, SanctionTarget};
+use core_protocol::get_unix_time_now;
+use core_protocol::id::{SanctionId, SessionId};
+use core_protocol::name::PlayerAlias;
+use heapless::HistoryBuffer;
+use log::{error, info};
+use serde::{Deserialize, Serialize};
+use server_util::observer::ObserverUpdate;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::marker::PhantomData;
+use std::net::IpAddr;
+use std::num::{NonZeroU32, NonZeroU64};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Keeps track of sanctions (bans, mutes, and restrictions) that outlive sessions and restarts, and
+/// of the moderator and admin actions that led to them.
+pub struct ModerationRepo<G> {
+    /// Sorted by id. May contain expired sanctions, which are pruned when saving.
+    sanctions: Vec<SanctionDto>,
+    /// Id of the next sanction. Only ever increases (and is persisted), so ids of pruned or
+    /// revoked sanctions are never reused.
+    next_sanction_id: SanctionId,
+    /// Persist sanctions here (JSON).
+    sanctions_path: Option<Arc<str>>,
+    /// Most recent audit log entries, for the admin interface.
+    audit: HistoryBuffer<AuditEntryDto, 256>,
+    /// Append all audit log entries here (CSV).
+    audit_log_path: Option<Arc<str>>,
+    /// Sends writes to the writer thread, if anything is persisted.
+    writer: Option<Sender<FileWrite>>,
+    _spooky: PhantomData<G>,
+}
+
+/// Contents of the sanctions file.
+#[derive(Serialize, Deserialize)]
+struct SanctionsFile {
+    next_sanction_id: SanctionId,
+    sanctions: Vec<SanctionDto>,
+}
+
+/// A write to one of the moderation files, performed in order by the writer thread.
+enum FileWrite {
+    /// Atomically replace the sanctions file.
+    Sanctions { path: Arc<str>, contents: Vec<u8> },
+    /// Append a line to the audit log.
+    Audit { path: Arc<str>, line: Vec<u8> },
+}
+
+impl FileWrite {
+    fn perform(self) {
+        match self {
+            Self::Sanctions { path, contents } => {
+                if let Err(e) = replace_file(&path, &contents) {
+                    error!("error saving sanctions: {:?}", e);
+                }
+            }
+            Self::Audit { path, line } => {
+                if let Err(e) = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&*path)
+                    .and_then(move |mut file| file.write_all(&line))
+                {
+                    error!("error writing audit log: {:?}", e);
+                }
+            }
+        }
+    }
+
+    /// Performs writes one at a time, so they can't race or finish out of order.
+    fn run(receiver: Receiver<Self>) {
+        for write in receiver {
+            write.perform();
+        }
+    }
+}
+
+/// Replaces the contents of a file such that a crash leaves either the old or new contents intact,
+/// by writing to a temporary file and renaming it over the original.
+fn replace_file(path: &str, contents: &[u8]) -> std::io::Result<()> {
+    let temporary = format!("{}.tmp", path);
+    let mut file = fs::File::create(&temporary)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+    drop(file);
+    fs::rename(&temporary, path)
+}
+
+impl<G: GameArenaService> ModerationRepo<G> {
+    /// Chat context can't represent "forever," so permanent mutes and restrictions are applied for
+    /// this long at a time (renewed every time the player authenticates).
+    const PERMANENT: Duration = Duration::from_secs(365 * 24 * 60 * 60);
+
+    pub fn new(sanctions_path: Option<String>, audit_log_path: Option<String>) -> Self {
+        let writer = (sanctions_path.is_some() || audit_log_path.is_some())
+            .then(|| {
+                let (sender, receiver) = mpsc::channel();
+                std::thread::Builder::new()
+                    .name(String::from("moderation_writer"))
+                    .spawn(move || FileWrite::run(receiver))
+                    .inspect_err(|e| error!("error starting moderation writer: {:?}", e))
+                    .ok()
+                    .map(|_| sender)
+            })
+            .flatten();
+
+        let mut ret = Self {
+            sanctions: Vec::new(),
+            next_sanction_id: SanctionId(NonZeroU32::new(1).unwrap()),
+            sanctions_path: sanctions_path.map(Into::into),
+            audit: HistoryBuffer::new(),
+            audit_log_path: audit_log_path.map(Into::into),
+            writer,
+            _spooky: PhantomData,
+        };
+
+        if let Some(path) = ret.sanctions_path.as_deref() {
+            match Self::load_sanctions(path) {
+                Ok(Some(file)) => {
+                    info!("loaded {} sanctions", file.sanctions.len());
+                    let next_sanction_id = file.next_sanction_id;
+                    ret.sanctions = file.sanctions;
+                    ret.sanctions.sort_unstable_by_key(|s| s.sanction_id);
+                    // Defensive, in case the file was edited by hand.
+                    ret.next_sanction_id = ret
+                        .sanctions
+                        .last()
+                        .and_then(|s| s.sanction_id.0.checked_add(1))
+                        .map(SanctionId)
+                        .map_or(next_sanction_id, |after_last| {
+                            after_last.max(next_sanction_id)
+                        });
+                }
+                Ok(None) => {}
+                Err(e) => error!("error loading sanctions: {}", e),
+            }
+        }
+
+        if let Some(path) = ret.audit_log_path.clone() {
+            if let Err(e) = ret.load_audit_log(&path) {
+                error!("error loading audit log: {}", e);
+            }
+        }
+
+        ret
+    }
+
+    fn load_sanctions(path: &str) -> Result<Option<SanctionsFile>, String> {
+        match fs::read(path) {
+            Ok(bytes) => serde_json::from_slice(&bytes)
+                .map(Some)
+                .map_err(|e| e.to_string()),
+            // Nothing has been sanctioned yet.
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Back-fills the in-memory audit log with the most recent entries from the file.
+    fn load_audit_log(&mut self, path: &str) -> Result<(), String> {
+        let file = match fs::File::open(path) {
+            Ok(file) => file,
+            // Nothing has been audited yet.
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(e.to_string()),
+        };
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .flexible(true)
+            .from_reader(file);
+
+        for record in reader.records() {
+            let record = record.map_err(|e| e.to_string())?;
+            if let [date, _game_id, issuer, action, target, reason] =
+                record.iter().collect::<Vec<_>>()[..]
+            {
+                self.audit.write(AuditEntryDto {
+                    date: date.parse().unwrap_or(0),
+                    issuer: issuer.to_owned(),
+                    action: action.to_owned(),
+                    target: (!target.is_empty()).then(|| target.to_owned()),
+                    reason: reason.to_owned(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether any of the given identifiers are banned.
+    pub(crate) fn is_banned(
+        &self,
+        ip_address: IpAddr,
+        session_id: Option<SessionId>,
+        discord_id: Option<NonZeroU64>,
+    ) -> bool {
+        self.iter_matching(ip_address, session_id, discord_id)
+            .any(|s| s.kind == SanctionKind::Ban)
+    }
+
+    /// Applies any mutes and restrictions to a client that was just created.
+    pub(crate) fn apply(&self, client: &mut PlayerClientData<G>) {
+        let now = get_unix_time_now();
+        for sanction in self.iter_matching(
+            client.ip_address,
+            Some(client.session_id),
+            client.discord_id,
+        ) {
+            let remaining = sanction
+                .date_expires
+                .map(|expires| Duration::from_millis(expires.saturating_sub(now)))
+                .unwrap_or(Self::PERMANENT);
+            let context = &mut client.chat.context;
+            match sanction.kind {
+                SanctionKind::Ban => {}
+                SanctionKind::Mute => {
+                    if remaining > context.muted_for() {
+                        context.mute_for(remaining);
+                    }
+                }
+                SanctionKind::Restrict => {
+                    if remaining > context.restricted_for() {
+                        context.restrict_for(remaining);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Iterates active sanctions that apply to any of the given identifiers.
+    fn iter_matching(
+        &self,
+        ip_address: IpAddr,
+        session_id: Option<SessionId>,
+        discord_id: Option<NonZeroU64>,
+    ) -> impl Iterator<Item = &SanctionDto> + '_ {
+        let now = get_unix_time_now();
+        self.sanctions.iter().filter(move |s| {
+            s.is_active(now) && applies_to(s.target, ip_address, session_id, discord_id)
+        })
+    }
+
+    /// Gets the identifier of a client that corresponds to a scope.
+    pub(crate) fn target(
+        client: &PlayerClientData<G>,
+        scope: SanctionScope,
+    ) -> Result<SanctionTarget, &'static str> {
+        Ok(match scope {
+            SanctionScope::Session => SanctionTarget::Session(client.session_id),
+            SanctionScope::Ip => SanctionTarget::Ip(client.ip_address),
+            SanctionScope::User => {
+                SanctionTarget::User(client.discord_id.ok_or("player not logged in")?)
+            }
+        })
+    }
+
+    /// Records and persists a sanction, replacing any sanction of the same kind on the same
+    /// target. [`None`] minutes means permanent.
+    pub(crate) fn sanction(
+        &mut self,
+        kind: SanctionKind,
+        target: SanctionTarget,
+        alias: PlayerAlias,
+        minutes: Option<usize>,
+        issuer: &str,
+        reason: &str,
+    ) -> SanctionId {
+        let sanction_id = self.next_sanction_id;
+        self.next_sanction_id = SanctionId(
+            sanction_id
+                .0
+                .checked_add(1)
+                .expect("ran out of sanction ids"),
+        );
+
+        self.sanctions
+            .retain(|s| !(s.kind == kind && s.target == target));
+
+        let date_issued = get_unix_time_now();
+        self.sanctions.push(SanctionDto {
+            sanction_id,
+            kind,
+            target,
+            alias,
+            issuer: issuer.to_owned(),
+            reason: reason.to_owned(),
+            date_issued,
+            date_expires: minutes.map(|m| date_issued + m as u64 * 60 * 1000),
+        });
+        self.save_sanctions();
+        sanction_id
+    }
+
+    /// Revokes all sanctions of a kind on a target, returning whether there were any.
+    pub(crate) fn revoke_matching(&mut self, kind: SanctionKind, target: SanctionTarget) -> bool {
+        let before = self.sanctions.len();
+        self.sanctions
+            .retain(|s| !(s.kind == kind && s.target == target));
+        let revoked = self.sanctions.len() < before;
+        if revoked {
+            self.save_sanctions();
+        }
+        revoked
+    }
+
+    /// Revokes a sanction by id, returning it.
+    pub(crate) fn revoke(&mut self, sanction_id: SanctionId) -> Result<SanctionDto, &'static str> {
+        let index = self
+            .sanctions
+            .binary_search_by_key(&sanction_id, |s| s.sanction_id)
+            .map_err(|_| "sanction not found")?;
+        let sanction = self.sanctions.remove(index);
+        self.save_sanctions();
+        Ok(sanction)
+    }
+
+    /// Active sanctions, newest first.
+    pub(crate) fn sanctions(&self) -> Box<[SanctionDto]> {
+        let now = get_unix_time_now();
+        self.sanctions
+            .iter()
+            .rev()
+            .filter(|s| s.is_active(now))
+            .cloned()
+            .collect()
+    }
+
+    /// Undoes the in-memory effects of a revoked mute or restriction on any matching real players.
+    pub(crate) fn lift(sanction: &SanctionDto, players: &mut PlayerRepo<G>) {
+        for mut player in players.iter_borrow_mut() {
+            if let Some(client) = player.client_mut() {
+                if applies_to(
+                    sanction.target,
+                    client.ip_address,
+                    Some(client.session_id),
+                    client.discord_id,
+                ) {
+                    match sanction.kind {
+                        SanctionKind::Ban => {}
+                        SanctionKind::Mute => client.chat.context.mute_for(Duration::ZERO),
+                        SanctionKind::Restrict => client.chat.context.restrict_for(Duration::ZERO),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Disconnects all real players that are banned (their sessions can't re-authenticate).
+    pub(crate) fn disconnect_banned(&self, players: &PlayerRepo<G>) {
+        for player in players.iter_borrow() {
+            if let Some(client) = player.client() {
+                if let ClientStatus::Connected { observer } = &client.status {
+                    if self.is_banned(
+                        client.ip_address,
+                        Some(client.session_id),
+                        client.discord_id,
+                    ) {
+                        info!("disconnecting banned player {:?}", player.player_id);
+                        let _ = observer.send(ObserverUpdate::Close);
+                    }
+                }
+            }
+        }
+    }
+
+    fn save_sanctions(&mut self) {
+        let now = get_unix_time_now();
+        self.sanctions.retain(|s| s.is_active(now));
+
+        if let Some(path) = &self.sanctions_path {
+            let path = Arc::clone(path);
+            let file = SanctionsFile {
+                next_sanction_id: self.next_sanction_id,
+                sanctions: std::mem::take(&mut self.sanctions),
+            };
+            let result = serde_json::to_vec_pretty(&file);
+            self.sanctions = file.sanctions;
+            match result {
+                Ok(contents) => self.write(FileWrite::Sanctions { path, contents }),
+                Err(e) => error!("error serializing sanctions: {:?}", e),
+            }
+        }
+    }
+
+    fn write(&self, write: FileWrite) {
+        if let Some(writer) = &self.writer {
+            if writer.send(write).is_err() {
+                error!("moderation writer stopped");
+            }
+        }
+    }
+
+    /// Records a moderator or admin action in the audit log.
+    pub(crate) fn audit(
+        &mut self,
+        issuer: &str,
+        action: &str,
+        target: Option<String>,
+        reason: &str,
+    ) {
+        let entry = AuditEntryDto {
+            date: get_unix_time_now(),
+            issuer: issuer.to_owned(),
+            action: action.to_owned(),
+            target,
+            reason: reason.to_owned(),
+        };
+
+        info!(
+            "audit: {} did {} to {:?} because {:?}",
+            entry.issuer, entry.action, entry.target, entry.reason
+        );
+
+        if let Some(log_path) = &self.audit_log_path {
+            let log_path = Arc::clone(log_path);
+            let mut line = Vec::with_capacity(256);
+            let mut writer = csv::Writer::from_writer(&mut line);
+            if let Err(e) = writer.write_record(&[
+                &entry.date.to_string(),
+                &format!("{:?}", G::GAME_ID),
+                &entry.issuer,
+                &entry.action,
+                entry.target.as_deref().unwrap_or(""),
+                &entry.reason,
+            ]) {
+                error!("error composing audit line: {:?}", e);
+            }
+            drop(writer);
+
+            self.write(FileWrite::Audit {
+                path: log_path,
+                line,
+            });
+        }
+
+        self.audit.write(entry);
+    }
+
+    /// Most recent audit log entries, newest first.
+    pub(crate) fn audit_log(&self) -> Box<[AuditEntryDto]> {
+        let mut entries: Vec<_> = self.audit.oldest_ordered().cloned().collect();
+        entries.reverse();
+        entries.into_boxed_slice()
+    }
+}
+
+/// Whether a sanction target matches any of the given identifiers.
+fn applies_to(
+    target: SanctionTarget,
+    ip_address: IpAddr,
+    session_id: Option<SessionId>,
+    discord_id: Option<NonZeroU64>,
+) -> bool {
+    match target {
+        SanctionTarget::Session(id) => Some(id) == session_id,
+        SanctionTarget::Ip(ip) => ip == ip_address,
+        SanctionTarget::User(id) => Some(id) == discord_id,
+    }
+}
@@ -45,6 +45,12 @@ pub struct Options {
     /// Log client traces here
     #[structopt(long)]
     pub trace_log: Option<String>,
+    /// Persist moderation sanctions (bans, mutes, and restrictions) here.
+    #[structopt(long)]
+    pub sanctions_file: Option<String>,
+    /// Log moderator and admin actions here
+    #[structopt(long)]
+    pub audit_log: Option<String>,
     /// Persist admin config here.
     #[structopt(long)]
     pub admin_config_file: Option<String>,
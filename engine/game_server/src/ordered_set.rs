@@ -7,7 +7,7 @@ use std::ops::Deref;
 
 /// A set that maintains order of insertion. Operations should be regarded as O(n).
 /// Intended to be used with small, [`Copy`] datatypes, so references are not used.
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Eq)]
 pub struct OrderedSet<T> {
     contents: Vec<T>,
 }
@@ -31,6 +31,12 @@ pub struct PlayerRepo<G: GameArenaService> {
     pub(crate) real_players_live: usize,
 }
 
+impl<G: GameArenaService> Default for PlayerRepo<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<G: GameArenaService> PlayerRepo<G> {
     pub fn new() -> Self {
         // For testing performance impact of stale clients.
@@ -80,6 +86,11 @@ impl<G: GameArenaService> PlayerRepo<G> {
         self.players.len()
     }
 
+    /// Returns true if there are no players (including bots).
+    pub fn is_empty(&self) -> bool {
+        self.players.is_empty()
+    }
+
     /// Tests if the player exists (in cache).
     pub fn contains(&self, player_id: PlayerId) -> bool {
         self.players.contains_key(&player_id)
@@ -91,7 +102,7 @@ impl<G: GameArenaService> PlayerRepo<G> {
     }
 
     /// Inserts a player (it is not mandatory to insert this way).
-    pub(crate) fn insert(&mut self, player_id: PlayerId, player: Arc<PlayerTuple<G>>) {
+    pub fn insert(&mut self, player_id: PlayerId, player: Arc<PlayerTuple<G>>) {
         #[cfg(debug_assertions)]
         {
             if let Some(existing) = self.players.get(&player_id) {
@@ -261,6 +272,7 @@ impl<G: GameArenaService> PlayerRepo<G> {
     }
 
     /// Computes a diff, and updates cached dtos.
+    #[allow(clippy::type_complexity)]
     pub(crate) fn delta(
         &mut self,
         teams: &TeamRepo<G>,
@@ -344,16 +356,25 @@ impl<G: GameArenaService> PlayerTuple<G> {
         self.player.borrow_mut()
     }
 
-    /// Borrows the player without checking for outstanding mutable borrows, the existence of which
-    /// would cause undefined behavior.
+    /// Borrows the player without checking for outstanding mutable borrows.
+    ///
+    /// # Safety
+    ///
+    /// There must not be any outstanding mutable borrows, the existence of which would cause
+    /// undefined behavior.
     pub unsafe fn borrow_player_unchecked(&self) -> &PlayerData<G> {
         #[cfg(debug_assertions)]
         drop(self.borrow_player());
         &*self.player.as_ptr()
     }
 
-    /// Mutably borrows the player without checking for outstanding borrows, the existence
-    /// of which would cause undefined behavior.
+    /// Mutably borrows the player without checking for outstanding borrows.
+    ///
+    /// # Safety
+    ///
+    /// There must not be any outstanding borrows, the existence of which would cause undefined
+    /// behavior.
+    #[allow(clippy::mut_from_ref)]
     pub unsafe fn borrow_player_mut_unchecked(&self) -> &mut PlayerData<G> {
         #[cfg(debug_assertions)]
         drop(self.borrow_player_mut());
@@ -0,0 +1,310 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::game_service::GameArenaService;
+use crate::infrastructure::Infrastructure;
+use crate::metric::MetricRepo;
+use actix::{Handler, Message};
+use core_protocol::metrics::ContinuousExtremaMetric;
+use std::fmt::{Display, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Total web socket messages received from clients (a counter, for computing rates).
+pub(crate) static WEB_SOCKET_MESSAGES_RX: AtomicU64 = AtomicU64::new(0);
+/// Total web socket messages sent to clients (a counter, for computing rates).
+pub(crate) static WEB_SOCKET_MESSAGES_TX: AtomicU64 = AtomicU64::new(0);
+
+/// Writes metrics in the Prometheus text exposition format (also accepted by OpenMetrics scrapers).
+#[derive(Default)]
+pub struct PrometheusWriter {
+    buffer: String,
+}
+
+impl PrometheusWriter {
+    /// Content type of the output.
+    pub const CONTENT_TYPE: &'static str = "text/plain; version=0.0.4; charset=utf-8";
+
+    /// Writes a single, unlabeled gauge.
+    pub fn gauge(&mut self, name: &str, help: &str, value: impl Into<f64>) {
+        self.header(name, help, "gauge");
+        self.sample(name, None, value.into());
+    }
+
+    /// Writes a single, unlabeled counter (should be monotonically increasing).
+    pub fn counter(&mut self, name: &str, help: &str, value: impl Into<f64>) {
+        self.header(name, help, "counter");
+        self.sample(name, None, value.into());
+    }
+
+    /// Writes a gauge with one sample per label value.
+    pub fn gauge_labeled<L: Display>(
+        &mut self,
+        name: &str,
+        help: &str,
+        label: &str,
+        samples: impl IntoIterator<Item = (L, f64)>,
+    ) {
+        self.header(name, help, "gauge");
+        for (value, sample) in samples {
+            self.sample(name, Some((label, &value)), sample);
+        }
+    }
+
+    /// Writes a histogram per label value, given non-cumulative bucket counts (one more than
+    /// `bounds`, for the unbounded bucket) and the sum of all samples.
+    pub fn histogram_labeled<'a, L: Display>(
+        &mut self,
+        name: &str,
+        help: &str,
+        label: &str,
+        bounds: &[f64],
+        histograms: impl IntoIterator<Item = (L, &'a [u64], f64)>,
+    ) {
+        self.header(name, help, "histogram");
+        for (value, buckets, sum) in histograms {
+            debug_assert_eq!(buckets.len(), bounds.len() + 1);
+            let mut cumulative = 0;
+            for (i, &count) in buckets.iter().enumerate() {
+                cumulative += count;
+                let le = bounds
+                    .get(i)
+                    .map(|b| b.to_string())
+                    .unwrap_or_else(|| String::from("+Inf"));
+                let _ = writeln!(
+                    self.buffer,
+                    "{}_bucket{{{}=\"{}\",le=\"{}\"}} {}",
+                    name,
+                    label,
+                    escape(&value),
+                    le,
+                    cumulative
+                );
+            }
+            let _ = writeln!(
+                self.buffer,
+                "{}_sum{{{}=\"{}\"}} {}",
+                name,
+                label,
+                escape(&value),
+                sum
+            );
+            let _ = writeln!(
+                self.buffer,
+                "{}_count{{{}=\"{}\"}} {}",
+                name,
+                label,
+                escape(&value),
+                cumulative
+            );
+        }
+    }
+
+    /// Writes the average, min, and max of a metric as a gauge labeled by `stat`. Empty metrics
+    /// are omitted.
+    pub fn continuous_extrema(&mut self, name: &str, help: &str, metric: &ContinuousExtremaMetric) {
+        if metric.count == 0 {
+            return;
+        }
+        self.gauge_labeled(
+            name,
+            help,
+            "stat",
+            [
+                ("average", metric.average() as f64),
+                ("min", metric.min as f64),
+                ("max", metric.max as f64),
+            ],
+        );
+    }
+
+    /// Writes a duration in seconds, as a gauge.
+    pub fn duration(&mut self, name: &str, help: &str, duration: Duration) {
+        self.gauge(name, help, duration.as_secs_f64());
+    }
+
+    fn header(&mut self, name: &str, help: &str, kind: &str) {
+        let _ = writeln!(self.buffer, "# HELP {} {}", name, help);
+        let _ = writeln!(self.buffer, "# TYPE {} {}", name, kind);
+    }
+
+    fn sample(&mut self, name: &str, label: Option<(&str, &dyn Display)>, value: f64) {
+        self.buffer.push_str(name);
+        if let Some((label, label_value)) = label {
+            let _ = write!(self.buffer, "{{{}=\"{}\"}}", label, escape(label_value));
+        }
+        if value.is_finite() {
+            let _ = writeln!(self.buffer, " {}", value);
+        } else if value.is_nan() {
+            self.buffer.push_str(" NaN\n");
+        } else if value > 0.0 {
+            self.buffer.push_str(" +Inf\n");
+        } else {
+            self.buffer.push_str(" -Inf\n");
+        }
+    }
+
+    pub fn finish(self) -> String {
+        self.buffer
+    }
+}
+
+/// Escapes a label value.
+fn escape(value: &dyn Display) -> String {
+    value
+        .to_string()
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Asks the server for all metrics, in the Prometheus text format. Requires the admin password,
+/// since metrics are served on the public port.
+#[derive(Message)]
+#[rtype(result = "Result<String, &'static str>")]
+pub struct PrometheusRequest {
+    /// Bearer token sent by the scraper.
+    pub auth: String,
+}
+
+impl<G: GameArenaService> Handler<PrometheusRequest> for Infrastructure<G> {
+    type Result = Result<String, &'static str>;
+
+    fn handle(&mut self, request: PrometheusRequest, _: &mut Self::Context) -> Self::Result {
+        if !self.admin.authenticate(&request.auth) {
+            return Err("invalid auth");
+        }
+
+        let mut writer = PrometheusWriter::default();
+
+        // Health.
+        let health = &mut self.status.health;
+        writer.gauge(
+            "server_cpu",
+            "Fraction of total CPU time used.",
+            health.cpu(),
+        );
+        writer.gauge(
+            "server_cpu_steal",
+            "Fraction of total CPU time stolen by the hypervisor.",
+            health.cpu_steal(),
+        );
+        writer.gauge("server_ram", "Fraction of RAM used.", health.ram());
+        writer.gauge(
+            "server_bandwidth_rx_bytes_per_second",
+            "Bytes per second received.",
+            health.bandwidth_rx() as f64,
+        );
+        writer.gauge(
+            "server_bandwidth_tx_bytes_per_second",
+            "Bytes per second transmitted.",
+            health.bandwidth_tx() as f64,
+        );
+        writer.gauge(
+            "server_connections",
+            "Open TCP connections.",
+            health.connections() as f64,
+        );
+        writer.gauge(
+            "server_healthy",
+            "Whether the server considers itself healthy (1) or not (0).",
+            health.healthy() as u8,
+        );
+        writer.continuous_extrema(
+            "server_tps",
+            "Ticks per second, since the last metrics period.",
+            health.peek_tps(),
+        );
+        writer.continuous_extrema(
+            "server_spt_seconds",
+            "Seconds per tick, since the last metrics period.",
+            health.peek_spt(),
+        );
+        writer.duration(
+            "server_uptime_seconds",
+            "Seconds since the server started.",
+            self.status.uptime(),
+        );
+
+        // Web sockets.
+        writer.counter(
+            "server_web_socket_messages_rx_total",
+            "Web socket messages received from clients.",
+            WEB_SOCKET_MESSAGES_RX.load(Ordering::Relaxed) as f64,
+        );
+        writer.counter(
+            "server_web_socket_messages_tx_total",
+            "Web socket messages sent to clients.",
+            WEB_SOCKET_MESSAGES_TX.load(Ordering::Relaxed) as f64,
+        );
+
+        // Engine tick phases.
+        let timings = &self.context_service.timings;
+        writer.gauge_labeled(
+            "server_update_phase_seconds",
+            "Duration of each phase of the most recent update.",
+            "phase",
+            [
+                ("prune", timings.prune),
+                ("tick", timings.tick),
+                ("clients", timings.clients),
+                ("bots", timings.bots),
+                ("leaderboard", timings.leaderboard),
+                ("post_update", timings.post_update),
+            ]
+            .map(|(phase, duration)| (phase, duration.as_secs_f64())),
+        );
+
+        // Players.
+        let players = &self.context_service.context.players;
+        writer.gauge(
+            "server_players_live",
+            "Real players currently playing.",
+            players.real_players_live as f64,
+        );
+        writer.gauge(
+            "server_players_real",
+            "Real players currently connected.",
+            players.real_players as f64,
+        );
+        writer.gauge(
+            "server_players_cached",
+            "Players (including bots) in memory.",
+            players.len() as f64,
+        );
+
+        let metrics = MetricRepo::get_metrics(self, None);
+        writer.continuous_extrema(
+            "server_concurrent_players",
+            "Concurrent players, this hour.",
+            &metrics.concurrent,
+        );
+        writer.gauge("server_visits", "Visits, this hour.", metrics.visits.total);
+        writer.gauge(
+            "server_plays",
+            "Plays, this hour.",
+            metrics.plays_total.total,
+        );
+        writer.continuous_extrema(
+            "server_client_fps",
+            "Client frames per second.",
+            &metrics.fps,
+        );
+        writer.continuous_extrema(
+            "server_client_rtt_seconds",
+            "Client network round trip time.",
+            &metrics.rtt,
+        );
+        writer.continuous_extrema(
+            "server_minutes_per_play",
+            "Minutes per completed play, this hour.",
+            &metrics.minutes_per_play,
+        );
+        writer.continuous_extrema("server_score", "Player score.", &metrics.score);
+
+        // Game specific.
+        self.context_service.service.prometheus(&mut writer);
+
+        Ok(writer.finish())
+    }
+}
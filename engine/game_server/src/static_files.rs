@@ -75,82 +75,77 @@ impl<S: Send + Sync + 'static> Handler<((),), S> for StaticFilesHandler {
             .map(|s| s.contains("image/webp"))
             .unwrap_or(false);
 
-        ready(
-            if if_none_match
-                .map(|inm| {
-                    let s: &str = file.etag.as_ref();
-                    inm == s
+        let not_modified = if_none_match
+            .map(|inm| {
+                let s: &str = file.etag.as_ref();
+                inm == s
+            })
+            .unwrap_or(false);
+
+        ready(if not_modified {
+            Response::builder()
+                .status(StatusCode::NOT_MODIFIED)
+                .body(boxed(Empty::new()))
+                .unwrap()
+        } else if let Some(contents_webp) = file.contents_webp.as_ref().filter(|_| accepting_webp) {
+            Response::builder()
+                .header(header::ETAG, unsafe {
+                    HeaderValue::from_maybe_shared_unchecked(file.etag.as_bytes().clone())
+                })
+                .header(header::CONTENT_TYPE, "image/webp")
+                .body(boxed(Full::from(<Base64Bytes as Into<
+                    axum::body::Bytes,
+                >>::into(
+                    contents_webp.clone()
+                ))))
+                .unwrap()
+        } else if let Some(contents_brotli) =
+            file.contents_brotli.as_ref().filter(|_| accepting_brotli)
+        {
+            Response::builder()
+                .header(header::ETAG, unsafe {
+                    HeaderValue::from_maybe_shared_unchecked(file.etag.as_bytes().clone())
+                })
+                .header(header::CONTENT_ENCODING, "br")
+                .header(header::CONTENT_TYPE, unsafe {
+                    HeaderValue::from_maybe_shared_unchecked(file.mime.as_bytes().clone())
+                })
+                .body(boxed(Full::from(<Base64Bytes as Into<
+                    axum::body::Bytes,
+                >>::into(
+                    contents_brotli.clone()
+                ))))
+                .unwrap()
+        } else if let Some(contents_gzip) = file.contents_gzip.as_ref().filter(|_| accepting_gzip) {
+            Response::builder()
+                .header(header::ETAG, unsafe {
+                    HeaderValue::from_maybe_shared_unchecked(file.etag.as_bytes().clone())
+                })
+                .header(header::CONTENT_ENCODING, "gzip")
+                .header(header::CONTENT_TYPE, unsafe {
+                    HeaderValue::from_maybe_shared_unchecked(file.mime.as_bytes().clone())
+                })
+                .body(boxed(Full::from(<Base64Bytes as Into<
+                    axum::body::Bytes,
+                >>::into(
+                    contents_gzip.clone()
+                ))))
+                .unwrap()
+        } else {
+            Response::builder()
+                .header(header::ETAG, unsafe {
+                    HeaderValue::from_maybe_shared_unchecked(file.etag.as_bytes().clone())
+                })
+                .header(header::CONTENT_TYPE, unsafe {
+                    HeaderValue::from_maybe_shared_unchecked(file.mime.as_bytes().clone())
                 })
-                .unwrap_or(false)
-            {
-                Response::builder()
-                    .status(StatusCode::NOT_MODIFIED)
-                    .body(boxed(Empty::new()))
-                    .unwrap()
-            } else if let Some(contents_webp) =
-                file.contents_webp.as_ref().filter(|_| accepting_webp)
-            {
-                Response::builder()
-                    .header(header::ETAG, unsafe {
-                        HeaderValue::from_maybe_shared_unchecked(file.etag.as_bytes().clone())
-                    })
-                    .header(header::CONTENT_TYPE, "image/webp")
-                    .body(boxed(Full::from(<Base64Bytes as Into<
-                        axum::body::Bytes,
-                    >>::into(
-                        contents_webp.clone()
-                    ))))
-                    .unwrap()
-            } else if let Some(contents_brotli) =
-                file.contents_brotli.as_ref().filter(|_| accepting_brotli)
-            {
-                Response::builder()
-                    .header(header::ETAG, unsafe {
-                        HeaderValue::from_maybe_shared_unchecked(file.etag.as_bytes().clone())
-                    })
-                    .header(header::CONTENT_ENCODING, "br")
-                    .header(header::CONTENT_TYPE, unsafe {
-                        HeaderValue::from_maybe_shared_unchecked(file.mime.as_bytes().clone())
-                    })
-                    .body(boxed(Full::from(<Base64Bytes as Into<
-                        axum::body::Bytes,
-                    >>::into(
-                        contents_brotli.clone()
-                    ))))
-                    .unwrap()
-            } else if let Some(contents_gzip) =
-                file.contents_gzip.as_ref().filter(|_| accepting_gzip)
-            {
-                Response::builder()
-                    .header(header::ETAG, unsafe {
-                        HeaderValue::from_maybe_shared_unchecked(file.etag.as_bytes().clone())
-                    })
-                    .header(header::CONTENT_ENCODING, "gzip")
-                    .header(header::CONTENT_TYPE, unsafe {
-                        HeaderValue::from_maybe_shared_unchecked(file.mime.as_bytes().clone())
-                    })
-                    .body(boxed(Full::from(<Base64Bytes as Into<
-                        axum::body::Bytes,
-                    >>::into(
-                        contents_gzip.clone()
-                    ))))
-                    .unwrap()
-            } else {
-                Response::builder()
-                    .header(header::ETAG, unsafe {
-                        HeaderValue::from_maybe_shared_unchecked(file.etag.as_bytes().clone())
-                    })
-                    .header(header::CONTENT_TYPE, unsafe {
-                        HeaderValue::from_maybe_shared_unchecked(file.mime.as_bytes().clone())
-                    })
-                    .body(boxed(Full::from(<Base64Bytes as Into<
-                        axum::body::Bytes,
-                    >>::into(
-                        file.contents.clone()
-                    ))))
-                    .unwrap()
-            },
-        )
+                .body(boxed(Full::from(<Base64Bytes as Into<
+                    axum::body::Bytes,
+                >>::into(
+                    file.contents.clone()
+                ))))
+                .unwrap()
+        })
     }
 }
 
@@ -249,6 +249,7 @@ impl<G: GameArenaService> SystemRepo<G> {
             .collect()
     }
 
+    #[allow(clippy::type_complexity)]
     pub(crate) fn delta(
         &mut self,
         status: &StatusRepo,
@@ -335,12 +336,12 @@ impl<G: GameArenaService> SystemRepo<G> {
             .filter_map(|(sub_domain, ip_addresses)| {
                 if ip_addresses.len() != 1 {
                     None
-                } else if let Some(server_id) =
-                    sub_domain.parse::<u8>().ok().and_then(ServerId::new)
-                {
-                    Some((server_id, ip_addresses[0]))
                 } else {
-                    None
+                    sub_domain
+                        .parse::<u8>()
+                        .ok()
+                        .and_then(ServerId::new)
+                        .map(|server_id| (server_id, ip_addresses[0]))
                 }
             })
             .filter_map(|(server_id, ip)| {
@@ -394,7 +395,7 @@ impl<G: GameArenaService> SystemRepo<G> {
 
                     let status = match serde_json::from_slice::<Response>(body.as_ref())
                         .map(|r| r.status)
-                        .or(serde_json::from_slice::<StatusResponse>(body.as_ref()))
+                        .or_else(|_| serde_json::from_slice::<StatusResponse>(body.as_ref()))
                     {
                         Ok(status) => {
                             let advertisement = ServerAdvertisement {
@@ -652,7 +653,7 @@ impl<G: GameArenaService> SystemRepo<G> {
                 })
                 .spawn(ctx);
 
-                return true;
+                true
             });
         }
 
@@ -7,15 +7,17 @@ use crate::player::{PlayerData, PlayerRepo};
 use crate::unwrap_or_return;
 use crate::util::diff_small_n;
 use atomic_refcell::AtomicRefMut;
-use core_protocol::dto::TeamDto;
+use core_protocol::dto::{PingDto, PingKind, TeamDto};
 use core_protocol::id::{PlayerId, TeamId};
 use core_protocol::name::TeamName;
 use core_protocol::rpc::{TeamRequest, TeamUpdate};
 use server_util::generate_id::generate_id;
+use server_util::rate_limiter::{RateLimiterProps, RateLimiterState};
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, VecDeque};
 use std::marker::PhantomData;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Data stored for team.
 pub struct TeamData<G: GameArenaService> {
@@ -40,7 +42,7 @@ impl<G: GameArenaService> TeamData<G> {
 
     /// Returns whether player_id a member of the team.
     pub fn is_member(&self, player_id: PlayerId) -> bool {
-        debug_assert!(self.members.len() > 0, "team shouldn't be empty");
+        debug_assert!(!self.members.is_empty(), "team shouldn't be empty");
         self.members.contains(player_id)
     }
 
@@ -68,12 +70,8 @@ impl<G: GameArenaService> TeamData<G> {
     ///
     /// Note: Automatically updates whether members changed.
     pub fn assign_captain(&mut self, player_id: PlayerId) -> bool {
-        if self.members.swap_to_front(player_id) {
-            true
-        } else {
-            // Player not in team.
-            false
-        }
+        // False if player not in team.
+        self.members.swap_to_front(player_id)
     }
 }
 
@@ -94,6 +92,9 @@ pub struct ClientTeamData {
     previous_joiners: OrderedSet<PlayerId>,
     /// For diffing.
     previous_joins: VecDeque<TeamId>,
+    /// Pings from team members, yet to be sent.
+    pings: Vec<PingDto>,
+    ping_rate_limit: RateLimiterState,
 }
 
 impl Drop for PlayerTeamData {
@@ -159,6 +160,12 @@ pub struct TeamRepo<G: GameArenaService> {
     _spooky: PhantomData<G>,
 }
 
+impl<G: GameArenaService> Default for TeamRepo<G> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<G: GameArenaService> TeamRepo<G> {
     pub fn new() -> Self {
         Self {
@@ -443,6 +450,89 @@ impl<G: GameArenaService> TeamRepo<G> {
         Ok(TeamUpdate::Left)
     }
 
+    /// Places a ping, which is sent to the rest of the team later.
+    fn ping(
+        &mut self,
+        req_player_id: PlayerId,
+        kind: PingKind,
+        x: f32,
+        y: f32,
+        players: &PlayerRepo<G>,
+    ) -> Result<TeamUpdate, &'static str> {
+        const RATE_LIMIT: RateLimiterProps = RateLimiterProps::const_new(Duration::from_secs(2), 3);
+        /// Don't let pings pile up for clients that aren't receiving them.
+        const INBOX_MAX: usize = 16;
+
+        if !x.is_finite() || !y.is_finite() {
+            return Err("invalid ping position");
+        }
+
+        let mut req_player = players
+            .borrow_player_mut(req_player_id)
+            .ok_or("requesting player nonexistent")?;
+
+        if !req_player.is_alive() {
+            return Err("must be alive to ping");
+        }
+
+        let team_id = req_player.team_id().ok_or("not in team")?;
+        let req_client = req_player.client_mut().ok_or("not a real player")?;
+        if req_client
+            .team
+            .ping_rate_limit
+            .should_limit_rate(&RATE_LIMIT)
+        {
+            return Err("pinging too often");
+        }
+
+        // We are about to borrow the other members.
+        drop(req_player);
+
+        let team = self.teams.get(&team_id).ok_or_else(|| {
+            debug_assert!(false, "team id should have been cleared");
+            "nonexistent team"
+        })?;
+
+        let ping = PingDto {
+            kind,
+            player_id: req_player_id,
+            x,
+            y,
+        };
+
+        for member in team.members.iter() {
+            if member == req_player_id {
+                continue;
+            }
+            if let Some(mut player) = players.borrow_player_mut(member) {
+                if let Some(client) = player.client_mut() {
+                    if client.team.pings.len() < INBOX_MAX {
+                        client.team.pings.push(ping);
+                    }
+                }
+            } else {
+                debug_assert!(false, "team member {:?} doesn't exist", member);
+            }
+        }
+
+        Ok(TeamUpdate::Pinged(vec![ping].into_boxed_slice()))
+    }
+
+    /// Takes pings that were sent to a player by their team members, if any. Borrows the player
+    /// mutably.
+    pub(crate) fn take_pings(
+        player_id: PlayerId,
+        players: &PlayerRepo<G>,
+    ) -> Option<Box<[PingDto]>> {
+        let mut player = players.borrow_player_mut(player_id)?;
+        let client = player.client_mut()?;
+        if client.team.pings.is_empty() {
+            None
+        } else {
+            Some(std::mem::take(&mut client.team.pings).into_boxed_slice())
+        }
+    }
+
     pub(crate) fn request_join(
         &mut self,
         req_player_id: PlayerId,
@@ -519,6 +609,7 @@ impl<G: GameArenaService> TeamRepo<G> {
             TeamRequest::Create(name) => self.create_team(req_player_id, name, players),
             TeamRequest::Kick(player_id) => self.kick_player(req_player_id, player_id, players),
             TeamRequest::Leave => self.quit_team(req_player_id, players),
+            TeamRequest::Ping { kind, x, y } => self.ping(req_player_id, kind, x, y, players),
             TeamRequest::Reject(player_id) => {
                 self.accept_or_reject_player(req_player_id, player_id, false, players)
             }
@@ -545,6 +636,7 @@ impl<G: GameArenaService> TeamRepo<G> {
     }
 
     /// Computes a diff, and updates cached dtos.
+    #[allow(clippy::type_complexity)]
     pub(crate) fn delta(
         &mut self,
         players: &PlayerRepo<G>,
@@ -563,6 +655,7 @@ impl<G: GameArenaService> TeamRepo<G> {
 
     /// Return delta in members, joiners, and joins for a given player.
     /// Only returns [`None`] at the outer level if the player doesn't exist or isn't a real player.
+    #[allow(clippy::type_complexity)]
     pub(crate) fn player_delta(
         &mut self,
         player_id: PlayerId,
@@ -737,18 +830,18 @@ mod test {
 
         assert_eq!(teams.teams.len(), 2);
 
-        for i in 20..20 + MockGame::TEAM_JOINERS_MAX {
+        for existing_player in &existing_players[20..20 + MockGame::TEAM_JOINERS_MAX] {
             let res = teams.handle_team_request(
-                existing_players[i].0,
+                existing_player.0,
                 TeamRequest::Join(team1_id),
                 &mut players,
             );
             assert!(matches!(res, Ok(TeamUpdate::Joining(_))), "{:?}", res);
         }
 
-        for i in 5..10 {
+        for existing_player in &existing_players[5..10] {
             let res = teams.handle_team_request(
-                existing_players[i].0,
+                existing_player.0,
                 TeamRequest::Join(team1_id),
                 &mut players,
             );
@@ -808,7 +901,7 @@ mod test {
                 5 => TeamRequest::Kick(rand_player_id_1),
                 6 => TeamRequest::Promote(rand_player_id_1),
                 _ => {
-                    teams.cleanup_player(rand_player_id_1, &mut players);
+                    teams.cleanup_player(rand_player_id_1, &players);
                     continue;
                 }
             };
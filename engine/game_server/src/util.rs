@@ -21,7 +21,7 @@ macro_rules! unwrap_or_return {
 /// Time complexity isn't great. Use [`diff_large_n`] instead, if time complexity matters.
 pub fn diff_small_n<T: Clone + PartialEq, ID: PartialEq>(
     old: &Arc<[T]>,
-    new: &Vec<T>,
+    new: &[T],
     get_id: impl Fn(&T) -> ID,
 ) -> Option<(Vec<T>, Vec<ID>)> {
     let mut added = Vec::<T>::new();
@@ -56,14 +56,14 @@ pub fn diff_small_n<T: Clone + PartialEq, ID: PartialEq>(
 /// Better time complexity than [`diff_small_n`], at the cost of more allocations.
 pub fn diff_large_n<T: Clone + PartialEq, ID: Eq + Hash>(
     old: &Arc<[T]>,
-    new: &Vec<T>,
+    new: &[T],
     get_id: impl Fn(&T) -> ID,
 ) -> Option<(Vec<T>, Vec<ID>)> {
     let mut added = Vec::<T>::new();
     let mut removed = Vec::<ID>::new();
 
     // Faster access via a hash map, at the cost of extra allocations.
-    let old_ids: HashSet<ID> = old.iter().map(|v| get_id(v)).collect();
+    let old_ids: HashSet<ID> = old.iter().map(&get_id).collect();
     let new_map: HashMap<ID, T> = new.iter().map(|v| (get_id(v), v.clone())).collect();
 
     // We assume that get_id is fast relative to hashmap iteration overhead.
@@ -141,7 +141,8 @@ mod test {
             let old_arc: Arc<[_]> = old.into();
             let diff1 = diff_small_n(&old_arc, &new, |i| i * 10);
             let diff2 = diff_large_n(&old_arc, &new, |i| i * 10);
-            if diff_count == 0 {
+            // Removals may undo additions, so compare the actual contents.
+            if *old_arc == *new {
                 assert!(diff1.is_none());
             } else {
                 let diffs = diff1.as_ref().unwrap();
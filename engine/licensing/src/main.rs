@@ -71,7 +71,7 @@ fn main() {
                 "/// Generated by `{}`",
                 args().collect::<Vec<_>>().join(" ")
             );
-            println!("pub const LICENSES: &'static [(&'static str, &'static [&'static str])] = &[");
+            println!("pub const LICENSES: &[(&str, &[&str])] = &[");
             for (license, names) in dependencies {
                 println!("    (\"{license}\", &[");
                 for name in names {
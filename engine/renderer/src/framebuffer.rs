@@ -3,6 +3,7 @@
 
 use crate::gl::*;
 use crate::renderer::Renderer;
+#[cfg(feature = "srgb")]
 use crate::rgba_array;
 use crate::texture::{Texture, TextureFormat};
 use glam::{UVec2, Vec4};
@@ -178,7 +179,7 @@ impl Framebuffer {
                     Gl::FRAMEBUFFER,
                     Gl::DEPTH_STENCIL_ATTACHMENT,
                     Gl::RENDERBUFFER,
-                    Some(&renderbuffer),
+                    Some(renderbuffer),
                 );
             }
             None => {}
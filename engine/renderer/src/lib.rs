@@ -37,12 +37,12 @@ mod vertex;
 pub use attribs::*;
 
 // Re-export to provide a simpler api.
+pub use self::renderer::*;
 pub use buffer::*;
 pub use deque::*;
 pub use framebuffer::*;
 pub use index::*;
 pub use instance::*;
-pub use renderer::*;
 pub use rgb::*;
 pub use shader::*;
 pub use texture::*;
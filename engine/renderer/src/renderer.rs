@@ -138,7 +138,7 @@ impl<T: Default> DefaultRender for T {
 /// # Derive Layer
 /// [`Layer`] be derived on structs that have fields which also implement [`Layer`]. If your struct
 /// has non [`Layer`] fields you can label the [`Layer`] fields with `#[layer]`.
-/// ```rust
+/// ```ignore
 /// #[derive(Layer)]
 /// #[alpha] // If we need an alpha channel   (and OtherLayer doesn't require it).
 /// #[depth] // If we need a depth buffer     ..
@@ -152,7 +152,7 @@ impl<T: Default> DefaultRender for T {
 ///
 /// # Derive RenderLayer
 /// [`RenderLayer`] can be derived within the `#[derive(Layer)]` for now.
-/// ```rust
+/// ```ignore
 /// #[derive(Layer)]
 /// #[render(T)]
 /// struct MyLayer {
@@ -15,13 +15,10 @@ fn main() {
     #[cfg(target_family = "wasm")]
     std::panic::set_hook(Box::new(console_error_panic_hook::hook));
 
-    // Create renderer with antialiasing.
+    // Create renderer with a gray background and antialiasing.
+    let gray = [50, 50, 50, 255];
     let antialias = true;
-    let mut render_chain = RenderChain::new(antialias, |r| {
-        // Set background color to gray.
-        let gray = gray_a(50, 255);
-        r.set_background_color(gray);
-
+    let mut render_chain = RenderChain::new(gray, antialias, |r| {
         // Create our layer.
         MyLayer {
             graphics: GraphicLayer::new(r),
@@ -55,7 +52,7 @@ fn main() {
     // Draw some transparent yellow text.
     let center = vec2(0.0, 0.5);
     let scale = 1.0;
-    let color = rgba(255, 255, 0, 100);
+    let color = [255, 255, 0, 100];
     layer.text.draw("| |", center, scale, color);
 
     let mut camera = Camera2d::default();
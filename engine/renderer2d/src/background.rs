@@ -308,7 +308,7 @@ impl FrameCache {
                             scroll,
                             viewport,
                         ))
-                        .chain(delta_rects.into_iter().flat_map(|&rect| {
+                        .chain(delta_rects.iter().flat_map(|&rect| {
                             let (start, end) = rect;
                             let matrix = &camera.aligned.view_matrix;
 
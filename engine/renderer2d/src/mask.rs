@@ -103,7 +103,7 @@ impl Mask {
                 let mut s = String::from("mask1\n");
                 s += &format!("{mask1:?}");
                 s += "mask2\n";
-                s += &&format!("{mask2:?}");
+                s += &format!("{mask2:?}");
                 let mut diff = mask1;
                 for y in 0..dim.y {
                     for x in 0..dim.x {
@@ -104,7 +104,7 @@ impl Model {
         self.indices
             .as_chunks()
             .0
-            .into_iter()
+            .iter()
             .map(move |indices: &[u16; 3]| {
                 let points = indices
                     .map(|idx| Vec3::from_slice(&self.vertices[idx as usize * vertex_floats..]));
@@ -451,7 +451,7 @@ impl<'de> Deserialize<'de> for GameIdMetricFilter {
             if let Some(filter) = split.next() {
                 let filter_value = split
                     .next()
-                    .ok_or(de::Error::custom("missing filter value"))?;
+                    .ok_or_else(|| de::Error::custom("missing filter value"))?;
 
                 ret.metric_filter = Some(match filter {
                     "cohort_id" => MetricFilter::CohortId(
@@ -4,6 +4,7 @@
 use core_protocol::metrics::ContinuousExtremaMetric;
 use log::error;
 use simple_server_status::SimpleServerStatus;
+use std::cmp::Ordering;
 use std::mem;
 use std::time::{Duration, Instant};
 
@@ -82,6 +83,16 @@ impl Health {
         mem::take(&mut self.spt)
     }
 
+    /// Gets TPS measurements since they were last taken, without taking them.
+    pub fn peek_tps(&self) -> &ContinuousExtremaMetric {
+        &self.tps
+    }
+
+    /// Gets seconds-per-tick measurements since they were last taken, without taking them.
+    pub fn peek_spt(&self) -> &ContinuousExtremaMetric {
+        &self.spt
+    }
+
     /// Call every update a.k.a. tick.
     pub fn record_tick(&mut self, tick_period: f32) {
         let now = Instant::now();
@@ -132,7 +143,8 @@ impl Health {
         self.swap = self.system.ram_swap_usage().unwrap_or(0.0);
 
         // Note: Written with the intention that NaN's do not result in unhealthy.
-        self.healthy = !((self.cpu + self.cpu_steal).max(self.ram) > 0.8);
+        self.healthy =
+            (self.cpu + self.cpu_steal).max(self.ram).partial_cmp(&0.8) != Some(Ordering::Greater);
     }
 }
 
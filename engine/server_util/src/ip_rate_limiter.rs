@@ -66,6 +66,11 @@ impl IpRateLimiter {
     pub fn len(&self) -> usize {
         self.usage.len()
     }
+
+    /// Returns true if the internal data-structure is empty.
+    pub fn is_empty(&self) -> bool {
+        self.usage.is_empty()
+    }
 }
 
 impl From<RateLimiterProps> for IpRateLimiter {
@@ -15,6 +15,7 @@ pub struct RateLimiter {
 pub type Units = u32;
 
 /// The state of a rate limiter.
+#[derive(Debug)]
 pub struct RateLimiterState {
     pub(crate) until: Instant,
     pub(crate) burst_used: Units,
@@ -151,9 +151,11 @@ mod tests {
             .decode()
             .unwrap();
         let rgb = img.into_rgb8();
-        let mono = Monochrome::new(&rgb);
+        let mono = Monochrome::new(&rgb, true);
         println!("term={:?} factor={:?}", mono.term, mono.factor);
         let decoded = mono.decode();
-        decoded.save("test_image_monochrome.png").unwrap();
+        decoded
+            .save(std::env::temp_dir().join("test_image_monochrome.png"))
+            .unwrap();
     }
 }
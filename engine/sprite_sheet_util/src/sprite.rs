@@ -98,6 +98,7 @@ impl Images {
 /// Packs `images` and `animations` into a
 /// [`SpriteSheet`]/[`UvSpriteSheet`][`sprite_sheet::UvSpriteSheet`]. At least one of the `outputs`
 /// must not have an `if_missing` color. TODO support multiple outputs on animations.
+#[allow(clippy::too_many_arguments)]
 pub fn pack_sprite_sheet(
     images: Vec<Image>,
     animations: Vec<Animation>,
@@ -163,7 +164,8 @@ pub fn pack_sprite_sheet(
                             let mut image = resize(
                                 &image,
                                 params.width,
-                                (params.width as f32 / aspect) as u32,
+                                // Thin sprites would otherwise round to nothing.
+                                ((params.width as f32 / aspect) as u32).max(1),
                                 FilterType::Lanczos3,
                             );
                             if let Some(f) = output.pre_process {
@@ -300,7 +302,7 @@ pub fn pack_sprite_sheet(
                 let width = rect.w as u32 - padding;
                 let height = rect.h as u32 - padding;
                 (
-                    key.to_string(),
+                    key,
                     Sprite {
                         x,
                         y,
@@ -85,9 +85,9 @@ fn main() {
                 Ok(text) => {
                     println!("received: {} (code {})", text, status);
                 }
-                Err(e) => eprintln!("{}", e.to_string()),
+                Err(e) => eprintln!("{}", e),
             }
         }
-        Err(e) => eprintln!("{}", e.to_string()),
+        Err(e) => eprintln!("{}", e),
     }
 }
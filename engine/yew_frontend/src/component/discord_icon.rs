@@ -6,7 +6,7 @@ use yew::virtual_dom::AttrValue;
 use yew::{function_component, html, Html, Properties};
 use yew_icons::IconId;
 
-#[derive(PartialEq, Properties)]
+#[derive(PartialEq, Eq, Properties)]
 pub struct DiscordIconProps {
     /// Discord invite link (defaults to Softbear discord server).
     #[prop_or("https://discord.gg/YMheuFQWTX".into())]
@@ -7,7 +7,7 @@ use yew::virtual_dom::AttrValue;
 use yew::{function_component, html, Html, Properties};
 use yew_icons::{Icon, IconId};
 
-#[derive(PartialEq, Properties)]
+#[derive(PartialEq, Eq, Properties)]
 pub struct InvitationIconProps {
     #[prop_or("2rem".into())]
     pub size: AttrValue,
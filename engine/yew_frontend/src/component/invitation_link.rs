@@ -8,7 +8,7 @@ use stylist::yew::styled_component;
 use web_sys::{window, MouseEvent};
 use yew::{hook, html, use_state, Callback, Html, Properties};
 
-#[derive(PartialEq, Properties)]
+#[derive(PartialEq, Eq, Properties)]
 pub struct InvitationLinkProps;
 
 #[styled_component(InvitationLink)]
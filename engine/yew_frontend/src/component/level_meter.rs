@@ -5,7 +5,7 @@ use crate::component::meter::Meter;
 use crate::translation::{use_translation, Translation};
 use yew::{function_component, html, Html, Properties};
 
-#[derive(PartialEq, Properties)]
+#[derive(PartialEq, Eq, Properties)]
 pub struct MeterProps {
     #[prop_or(0x0084b1)]
     pub color: u32,
@@ -23,7 +23,7 @@ pub fn level_meter(props: &MeterProps) -> Html {
         .unwrap_or_else(|| (props.score_to_level)(props.score));
     let current_level_score = (props.level_to_score)(current_level);
     let max_level = (props.score_to_level)(u32::MAX);
-    let next_level = (current_level != max_level).then(|| current_level + 1);
+    let next_level = (current_level != max_level).then_some(current_level + 1);
     let next_level_score = next_level.map(props.level_to_score);
     let progress = next_level_score.map(|next_level_score| {
         ((props.score - current_level_score) as f32
@@ -6,7 +6,7 @@ use yew::virtual_dom::AttrValue;
 use yew::{function_component, html, Callback, Html, Properties};
 use yew_icons::{Icon, IconId};
 
-#[derive(PartialEq, Properties)]
+#[derive(PartialEq, Eq, Properties)]
 pub struct LinkIconProps {
     pub icon_id: IconId,
     pub title: Option<AttrValue>,
@@ -190,7 +190,7 @@ pub fn positioner(props: &PositionerProps) -> Html {
 
     style += props
         .align
-        .unwrap_or(props.position.default_text_align())
+        .unwrap_or_else(|| props.position.default_text_align())
         .as_css();
 
     /*
@@ -8,7 +8,7 @@ use yew_icons::{Icon, IconId};
 use yew_router::hooks::use_navigator;
 use yew_router::Routable;
 
-#[derive(PartialEq, Properties)]
+#[derive(PartialEq, Eq, Properties)]
 pub struct RouteIconProps<R: Routable> {
     pub icon_id: IconId,
     pub title: Option<AttrValue>,
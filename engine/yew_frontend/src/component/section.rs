@@ -78,7 +78,7 @@ pub fn section(props: &SectionProps) -> Html {
     let onclick = props.closable.then(|| {
         if let Some(on_open_changed) = props.on_open_changed.clone() {
             Callback::from(move |_| {
-                let _ = on_open_changed.emit(!open);
+                on_open_changed.emit(!open);
             })
         } else {
             Callback::from(move |_| open_state.set(!open))
@@ -136,8 +136,8 @@ pub fn section(props: &SectionProps) -> Html {
     );
     let high_contrast = use_ctw().setting_cache.high_contrast;
 
-    const ICON_WIDTH: &'static str = "1.5rem";
-    const ICON_HEIGHT: &'static str = "1.2rem";
+    const ICON_WIDTH: &str = "1.5rem";
+    const ICON_HEIGHT: &str = "1.2rem";
 
     let mut style = String::new();
     if let Some(s) = &props.style {
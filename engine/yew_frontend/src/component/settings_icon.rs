@@ -8,7 +8,7 @@ use yew::{function_component, html, Html, Properties};
 use yew_icons::IconId;
 use yew_router::Routable;
 
-#[derive(PartialEq, Properties)]
+#[derive(PartialEq, Eq, Properties)]
 pub struct SettingsIconProps<R: PartialEq> {
     pub route: R,
     #[prop_or("2rem".into())]
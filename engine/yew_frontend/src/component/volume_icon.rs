@@ -8,7 +8,7 @@ use yew::virtual_dom::AttrValue;
 use yew::{function_component, html, Callback, Html, Properties};
 use yew_icons::{Icon, IconId};
 
-#[derive(PartialEq, Properties)]
+#[derive(PartialEq, Eq, Properties)]
 pub struct VolumeIconProps {
     #[prop_or("2rem".into())]
     pub size: AttrValue,
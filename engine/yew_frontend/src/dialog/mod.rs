@@ -1,6 +1,7 @@
 // SPDX-FileCopyrightText: 2021 Softbear, Inc.
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
+#[allow(clippy::module_inception)]
 pub mod dialog;
 pub mod licensing_dialog;
 pub mod privacy_dialog;
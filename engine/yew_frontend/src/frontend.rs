@@ -19,7 +19,7 @@ use web_sys::{window, Request, RequestInit, RequestMode, Response, Url};
 use yew::{hook, use_context, Callback, Html, Properties};
 use yew_router::Routable;
 
-#[derive(Properties, PartialEq)]
+#[derive(Properties, PartialEq, Eq)]
 pub struct PropertiesWrapper<P: PartialEq> {
     pub props: P,
 }
@@ -55,6 +55,7 @@ pub struct Ctw {
     pub outbound_enabled: bool,
     pub rewarded_ad: RewardedAd,
     pub setting_cache: CommonSettings,
+    #[allow(clippy::type_complexity)]
     pub change_common_settings_callback:
         Callback<Box<dyn FnOnce(&mut CommonSettings, &mut BrowserStorages)>>,
     pub chat_request_callback: Callback<ChatRequest>,
@@ -86,6 +87,7 @@ pub fn use_player_request_callback() -> Callback<PlayerRequest> {
 }
 
 #[hook]
+#[allow(clippy::type_complexity)]
 pub fn use_change_common_settings_callback(
 ) -> Callback<Box<dyn FnOnce(&mut CommonSettings, &mut BrowserStorages)>> {
     use_ctw().change_common_settings_callback
@@ -129,6 +131,7 @@ pub fn use_outbound_enabled() -> bool {
 /// Game-specific context wrapper.
 pub struct Gctw<G: GameClient> {
     pub send_ui_event_callback: Callback<G::UiEvent>,
+    #[allow(clippy::type_complexity)]
     pub change_settings_callback:
         Callback<Box<dyn FnOnce(&mut G::GameSettings, &mut BrowserStorages)>>,
     pub settings_cache: G::GameSettings,
@@ -162,6 +165,7 @@ pub fn use_ui_event_callback<G: GameClient>() -> Callback<G::UiEvent> {
 }
 
 #[hook]
+#[allow(clippy::type_complexity)]
 pub fn use_change_settings_callback<G: GameClient>(
 ) -> Callback<Box<dyn FnOnce(&mut G::GameSettings, &mut BrowserStorages)>> {
     use_gctw::<G>().change_settings_callback
@@ -242,7 +246,7 @@ impl SystemInfo {
             .await
             .map_err(|e| format!("{:?}", e))?
             .as_string()
-            .ok_or(String::from("JSON not string"))?;
+            .ok_or_else(|| String::from("JSON not string"))?;
         let decoded: SystemResponse = serde_json::from_str(&json).map_err(|e| e.to_string())?;
 
         Ok(Self {
@@ -52,7 +52,7 @@ use web_sys::{FocusEvent, KeyboardEvent, MessageEvent, MouseEvent, TouchEvent, W
 use yew::prelude::*;
 use yew_router::prelude::*;
 
-pub const CONTACT_EMAIL: &'static str = "contact@softbear.com";
+pub const CONTACT_EMAIL: &str = "contact@softbear.com";
 
 struct App<
     G: GameClient,
@@ -137,6 +137,7 @@ enum RecreatingCanvas {
 #[derive(Default, PartialEq, Properties)]
 struct AppProps {}
 
+#[allow(clippy::type_complexity)]
 enum AppMsg<G: GameClient> {
     ChangeCommonSettings(Box<dyn FnOnce(&mut CommonSettings, &mut BrowserStorages)>),
     ChangeSettings(Box<dyn FnOnce(&mut G::GameSettings, &mut BrowserStorages)>),
@@ -704,7 +705,7 @@ where
     yew::Renderer::<App<G, UI, R>>::new().render();
 }
 
-#[derive(Clone, Copy, PartialEq, SmolRoutable)]
+#[derive(Clone, Copy, PartialEq, Eq, SmolRoutable)]
 pub enum Route {
     #[at("/invite/:invitation_id/")]
     Invitation { invitation_id: InvitationId },
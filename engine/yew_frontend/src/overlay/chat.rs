@@ -24,7 +24,7 @@ use yew::{
     Callback, Html, Properties,
 };
 
-#[derive(PartialEq, Properties)]
+#[derive(PartialEq, Eq, Properties)]
 pub struct ChatProps {
     /// Override the default label.
     #[prop_or(LanguageId::chat_label)]
@@ -142,7 +142,7 @@ pub fn chat_overlay(props: &ChatProps) -> Html {
             let input: HtmlInputElement = event_target(&event);
             let string = input.value();
             help_hint.set(help_hint_of(&hints, &string));
-            on_save_chat_message.emit(string.clone());
+            on_save_chat_message.emit(string);
         }
     };
 
@@ -159,7 +159,7 @@ pub fn chat_overlay(props: &ChatProps) -> Html {
             event.stop_propagation();
             let input: HtmlInputElement = event_target(&event);
             let message = input.value();
-            let _ = input.set_value("");
+            input.set_value("");
             let _ = input.blur();
             if message.is_empty() {
                 return;
@@ -191,7 +191,7 @@ pub fn chat_overlay(props: &ChatProps) -> Html {
                 let input_ref = input_ref.clone();
 
                 if let Some(input) = input_ref.cast::<HtmlInputElement>() {
-                    input.set_value(&default_text)
+                    input.set_value(default_text)
                 }
 
                 let onkeydown = WindowEventListener::new(
@@ -224,7 +224,7 @@ pub fn chat_overlay(props: &ChatProps) -> Html {
     let items = core_state.messages.oldest_ordered().map(|dto| {
         let onclick_reply = {
             let input_ref_clone = input_ref.clone();
-            let at_alias = format!("@{} ", dto.alias).to_string();
+            let at_alias = format!("@{} ", dto.alias);
             move || {
                 if let Some(input) = input_ref_clone.cast::<HtmlInputElement>() {
                     // Don't overwrite an unsent (not empty) message.
@@ -254,18 +254,12 @@ pub fn chat_overlay(props: &ChatProps) -> Html {
                         chat_request_callback.emit(ChatRequest::Mute(player_id));
                     })
                 };
-                let onclick_report = {
-                    let player_request_callback = player_request_callback.clone();
-                    Callback::from(move |_: MouseEvent| {
-                        player_request_callback.emit(PlayerRequest::Report(player_id));
-                    })
-                };
-                let onclick_restrict_5m = {
-                    let chat_request_callback = chat_request_callback.clone();
-                    Callback::from(move |_: MouseEvent| {
-                        chat_request_callback.emit(ChatRequest::RestrictPlayer{player_id, minutes: 5 });
-                    })
-                };
+                let onclick_report = Callback::from(move |_: MouseEvent| {
+                    player_request_callback.emit(PlayerRequest::Report(player_id));
+                });
+                let onclick_restrict_5m = Callback::from(move |_: MouseEvent| {
+                    chat_request_callback.emit(ChatRequest::RestrictPlayer{player_id, minutes: 5 });
+                });
                 let onclick_copy_player_id = Callback::from(move |_: MouseEvent| {
                     if let Some(clipboard) = window().unwrap().navigator().clipboard() {
                         let _ = clipboard.write_text(&format!("{}", player_id.0));
@@ -288,7 +282,7 @@ pub fn chat_overlay(props: &ChatProps) -> Html {
                                  <ContextMenuButton onclick={onclick_copy_team_id}>{"Copy Team ID"}</ContextMenuButton>
                             }
                         } else {
-                            <ContextMenuButton onclick={onclick_mute.clone()}>{t.chat_mute_label()}</ContextMenuButton>
+                            <ContextMenuButton onclick={onclick_mute}>{t.chat_mute_label()}</ContextMenuButton>
                             <ContextMenuButton onclick={onclick_report}>{t.chat_report_label()}</ContextMenuButton>
                         }
                     </ContextMenu>
@@ -305,7 +299,7 @@ pub fn chat_overlay(props: &ChatProps) -> Html {
                     onclick={move |_| onclick_reply()}
                     class={if dto.player_id.is_some() { name_css_class.clone() } else { official_name_css_class.clone() }}
                 >
-                    {dto.team_name.map(|team_name| format!("[{}] {}", team_name, dto.alias)).unwrap_or(dto.alias.to_string())}
+                    {dto.team_name.map(|team_name| format!("[{}] {}", team_name, dto.alias)).unwrap_or_else(|| dto.alias.to_string())}
                 </span>
                 <span class={no_select_style.clone()}>{" "}</span>
                 {segments(&dto.text, &mention_string).map(|Segment{contents, mention}| html_nested!{
@@ -353,14 +347,14 @@ pub fn chat_overlay(props: &ChatProps) -> Html {
 
 fn help_hint_of(hints: &[(&'static str, Vec<&'static str>)], text: &str) -> Option<&'static str> {
     let text = text.to_ascii_lowercase();
-    if text.find("/invite").is_some() {
+    if text.contains("/invite") {
         Some("Invitation links cannot currently be accepted by players that are already in game. They must send a join request instead.")
     } else {
         for (value, keys) in hints.iter() {
             let mut found = true;
             for &k in keys.iter() {
                 debug_assert_eq!(k, k.to_lowercase());
-                if !text.find(k).is_some() {
+                if !text.contains(k) {
                     found = false;
                 }
             }
@@ -10,7 +10,7 @@ use web_sys::{window, Request, RequestInit, RequestMode, Response};
 use yew::virtual_dom::AttrValue;
 use yew::{classes, html, use_state, Html, Properties};
 
-#[derive(Properties, PartialEq)]
+#[derive(Properties, PartialEq, Eq)]
 pub struct FatalErrorProps {
     pub message: Option<AttrValue>,
 }
@@ -123,7 +123,7 @@ pub fn fatal_error(props: &FatalErrorProps) -> Html {
 
     html! {
         <Positioner id="fatal_error" position={Position::Center} class={classes!(container_style)}>
-            <p class={p_css}>{props.message.clone().unwrap_or(t.connection_lost_message().into())}</p>
+            <p class={p_css}>{props.message.clone().unwrap_or_else(|| t.connection_lost_message().into())}</p>
             <button onclick={refresh} class={button_css}>{"Refresh"}</button>
             if let Some(status) = *status {
                 <p class={small_css}>{status}</p>
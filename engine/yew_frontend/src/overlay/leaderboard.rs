@@ -163,7 +163,7 @@ pub fn leaderboard_overlay(props: &LeaderboardProps) -> Html {
                             .map(|team_dto| team_dto.name);
                         html_nested! {
                             <tr class={fake.then(|| fake_style.clone())}>
-                                <td class="name">{team_name.map(|team_name| format!("[{}] {}", team_name, player.alias)).unwrap_or(player.alias.to_string())}</td>
+                                <td class="name">{team_name.map(|team_name| format!("[{}] {}", team_name, player.alias)).unwrap_or_else(|| player.alias.to_string())}</td>
                                 <td class="score">{(props.fmt_score)(dto.score)}</td>
                             </tr>
                         }
@@ -127,7 +127,7 @@ pub fn spawn_overlay(props: &DialogProps) -> Html {
             move |alias_setting| {
                 if let Some(alias_setting) = alias_setting.as_ref() {
                     if let Some(input) = input_ref.cast::<HtmlInputElement>() {
-                        input.set_value(&alias_setting);
+                        input.set_value(alias_setting);
                     }
                 }
             },
@@ -203,11 +203,8 @@ pub fn team_overlay(props: &TeamOverlayProps) -> Html {
         }
     };
 
-    let on_request_join_team = {
-        let cb = team_request_callback.clone();
-        move |team_id: TeamId| {
-            cb.emit(TeamRequest::Join(team_id));
-        }
+    let on_request_join_team = move |team_id: TeamId| {
+        team_request_callback.emit(TeamRequest::Join(team_id));
     };
 
     let seed = core_state
@@ -237,14 +234,14 @@ pub fn team_overlay(props: &TeamOverlayProps) -> Html {
                 })
         };
 
-    const CHECK_MARK: &'static str = "✔";
-    const X_MARK: &'static str = "✘";
+    const CHECK_MARK: &str = "✔";
+    const X_MARK: &str = "✘";
 
     // TODO (use settings): on_open_changed={|o| ctw.dialogs.teams = o}}
     html! {
         <Section
             id="team"
-            name={team_name.map(|n| AttrValue::Rc(n.to_string().into())).unwrap_or(AttrValue::Static((props.label)(t)))}
+            name={team_name.map(|n| AttrValue::Rc(n.to_string().into())).unwrap_or_else(|| AttrValue::Static((props.label)(t)))}
             position={props.position}
             style={props.style.clone()}
             open={ctw.setting_cache.team_dialog_shown}
@@ -30,7 +30,7 @@ impl<E: JsCast> WindowEventListener<E> {
                 // We use unchecked_ref because browsers can't be bothered to throw FocusEvent
                 // and ResizeEvent, and instead throw Event.
                 let typed_event = event.unchecked_ref::<E>();
-                callback(&typed_event);
+                callback(typed_event);
             }),
             _spooky: PhantomData,
         }
@@ -310,6 +310,9 @@ pub(crate) fn derive_entity_type(input: TokenStream) -> TokenStream {
                                     "vertical" => {
                                         set_bool(&mut armament.vertical, nested);
                                     }
+                                    "magazine" => {
+                                        set_usize(&mut armament.magazine, nested);
+                                    }
                                     _ => panic!("unexpected armament path: {path}"),
                                 },
                             }
@@ -413,6 +416,52 @@ pub(crate) fn derive_entity_type(input: TokenStream) -> TokenStream {
 
                         entity.exhausts.push(exhaust);
                     }
+                    "loadout" => {
+                        let mut loadout = Loadout::default();
+
+                        for nested in list.nested {
+                            let NestedMeta::Meta(nested) = nested else {
+                                panic!("expected nested meta");
+                            };
+
+                            let path = nested.path().get_ident().unwrap().to_string();
+
+                            match path.as_str() {
+                                "label" => {
+                                    set_string(&mut loadout.label, nested);
+                                }
+                                "replace" => {
+                                    let Meta::List(replace) = nested else {
+                                        panic!("expected replace(from, to)");
+                                    };
+                                    let mut types = Vec::new();
+                                    let mut magazine = None;
+                                    for nested in replace.nested {
+                                        let NestedMeta::Meta(nested) = nested else {
+                                            panic!("expected nested meta");
+                                        };
+                                        match nested {
+                                            Meta::Path(path) => {
+                                                types.push(path.get_ident().unwrap().to_string());
+                                            }
+                                            _ if nested.path().is_ident("magazine") => {
+                                                set_usize(&mut magazine, nested);
+                                            }
+                                            _ => panic!(
+                                                "expected entity type or magazine in replace"
+                                            ),
+                                        }
+                                    }
+                                    let [from, to]: [String; 2] =
+                                        types.try_into().expect("expected replace(from, to)");
+                                    loadout.replacements.push((from, to, magazine));
+                                }
+                                _ => panic!("unexpected loadout path: {path}"),
+                            }
+                        }
+
+                        entity.loadouts.push(loadout);
+                    }
                     _ => panic!("unexpected path {path}"),
                 }
             }
@@ -771,6 +820,40 @@ pub(crate) fn derive_entity_type(input: TokenStream) -> TokenStream {
         });
         entity.armaments = armaments;
 
+        // Loadouts occupy the same armament slots as the default, so that they may be indexed
+        // interchangeably.
+        if let Some(default) = entity.loadouts.first() {
+            assert!(
+                default.replacements.is_empty(),
+                "first loadout of {variant} must be the default"
+            );
+            assert!(
+                entity.loadouts.len() > 1,
+                "{variant} has only the default loadout"
+            );
+        }
+        for loadout in &mut entity.loadouts {
+            assert!(loadout.label.is_some(), "unlabeled loadout of {variant}");
+            loadout.armaments = entity.armaments.clone();
+            for (from, to, magazine) in &loadout.replacements {
+                assert!(
+                    original_entities.contains_key(to),
+                    "unknown loadout replacement {to}"
+                );
+                let mut replaced = false;
+                for armament in &mut loadout.armaments {
+                    if armament._type() == from {
+                        armament._type = Some(to.clone());
+                        if magazine.is_some() {
+                            armament.magazine = *magazine;
+                        }
+                        replaced = true;
+                    }
+                }
+                assert!(replaced, "{variant} has no {from} to replace");
+            }
+        }
+
         entity.stealth = Some(entity.stealth.unwrap_or_default());
         entity.radius = glam::Vec2::new(entity.width(), entity.length())
             .mul(0.5)
@@ -832,6 +915,8 @@ pub(crate) fn derive_entity_type(input: TokenStream) -> TokenStream {
                 })
             }
 
+            // Generated values (e.g. radii) may happen to approximate constants.
+            #[allow(clippy::approx_constant)]
             const DATA: &[EntityData] = &[
                 #(#entity_datas),*
             ];
@@ -884,6 +969,7 @@ struct Entity {
     armaments: Vec<Armament>,
     turrets: Vec<Turret>,
     exhausts: Vec<Exhaust>,
+    loadouts: Vec<Loadout>,
     limited: bool,
     npc: bool,
     anti_aircraft: f32,
@@ -926,6 +1012,7 @@ struct Armament {
     hidden: bool,
     external: bool,
     vertical: bool,
+    magazine: Option<usize>,
 }
 
 impl Armament {
@@ -934,6 +1021,15 @@ impl Armament {
     }
 }
 
+#[derive(Clone, Debug, Default)]
+struct Loadout {
+    label: Option<String>,
+    /// Armament types to replace, their replacements, and the replacements' magazines (if
+    /// different from those of the armaments they replace).
+    replacements: Vec<(String, String, Option<usize>)>,
+    armaments: Vec<Armament>,
+}
+
 #[derive(Clone, Debug, Default)]
 struct Turret {
     _type: Option<String>,
@@ -1074,6 +1170,7 @@ impl quote::ToTokens for Entity {
             .unwrap_or_default();
 
         let armaments = &self.armaments;
+        let loadouts = &self.loadouts;
         let turrets = &self.turrets;
         let exhausts = &self.exhausts;
 
@@ -1118,6 +1215,7 @@ impl quote::ToTokens for Entity {
                         }
                     },
                     armaments: &[#(#armaments),*],
+                    loadouts: &[#(#loadouts),*],
                     turrets: &[#(#turrets),*],
                     exhausts: &[#(#exhausts),*],
                     label: #label,
@@ -1144,6 +1242,10 @@ impl quote::ToTokens for Armament {
         let position_side = self.position_side.unwrap_or_default();
         let angle = self.angle.unwrap_or_default().0;
         let turret = quote_option(self.turret);
+        let magazine = quote_option(
+            self.magazine
+                .map(|m| u8::try_from(m).expect("magazine too large")),
+        );
 
         let ts: proc_macro2::TokenStream = {
             quote! {
@@ -1156,6 +1258,26 @@ impl quote::ToTokens for Armament {
                     position_side: #position_side,
                     angle: Angle(#angle),
                     turret: #turret,
+                    magazine: #magazine,
+                }
+            }
+        }
+        .into();
+
+        tokens.extend(ts);
+    }
+}
+
+impl quote::ToTokens for Loadout {
+    fn to_tokens(&self, tokens: &mut proc_macro2::TokenStream) {
+        let label = self.label.as_deref().unwrap();
+        let armaments = &self.armaments;
+
+        let ts: proc_macro2::TokenStream = {
+            quote! {
+                Loadout{
+                    label: #label,
+                    armaments: &[#(#armaments),*],
                 }
             }
         }
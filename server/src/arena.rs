@@ -54,7 +54,6 @@ impl Arena {
     }
 
     /// count_kind returns the number of entities with a certain kind.
-    #[allow(dead_code)]
     pub fn count_kind(&self, kind: EntityKind) -> usize {
         self.count_predicate(|t| t.data().kind == kind)
     }
@@ -66,7 +65,6 @@ impl Arena {
     }
 
     /// total returns the total number of entities.
-    #[allow(dead_code)]
     pub fn total(&self) -> usize {
         self.counts.iter().sum::<u32>() as usize
     }
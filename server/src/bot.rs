@@ -35,7 +35,7 @@ pub struct Bot {
     level_ambition: u8,
     /// Whether the bot spawned at least once, and therefore is capable of rage-quitting.
     spawned_at_least_once: bool,
-    /// The value of submerge previously sent.
+    /// Whether a submerged depth was previously sent.
     was_submerging: bool,
 }
 
@@ -203,7 +203,7 @@ impl Bot {
             if let Some((enemy, _)) = closest_enemy {
                 let reloads = boat.reloads();
                 let enemy_data = enemy.data();
-                for (i, armament) in data.armaments.iter().enumerate() {
+                for (i, armament) in boat.armaments().iter().enumerate() {
                     if !reloads[i] {
                         // Not yet reloaded.
                         continue;
@@ -221,6 +221,10 @@ impl Bot {
                         EntityKind::Aircraft | EntityKind::Weapon => {
                             if enemy.altitude().is_airborne() {
                                 matches!(armament_entity_data.sub_kind, EntitySubKind::Sam)
+                                    || (enemy_data.sub_kind == EntitySubKind::Missile
+                                        && enemy_data.sensors.radar.range > 0.0
+                                        && armament_entity_data.kind == EntityKind::Decoy
+                                        && armament_entity_data.sub_kind == EntitySubKind::Chaff)
                             } else if enemy_data.sub_kind == EntitySubKind::Torpedo
                                 && enemy_data.sensors.sonar.range > 0.0
                             {
@@ -274,7 +278,8 @@ impl Bot {
                         }
                     }
 
-                    let transform = *boat.transform() + data.armament_transform(boat.turrets(), i);
+                    let transform = *boat.transform()
+                        + data.armament_transform(boat.loadout(), boat.turrets(), i);
                     let angle = Angle::from(enemy.transform().position - transform.position);
 
                     let mut angle_diff = (angle - transform.direction).abs();
@@ -324,7 +329,11 @@ impl Bot {
                     direction_target: Angle::from(movement) + self.steer_bias,
                     velocity_target: data.speed * 0.8,
                 }),
-                submerge: self.was_submerging,
+                depth: if self.was_submerging {
+                    Depth::Shallow
+                } else {
+                    Depth::Surface
+                },
                 aim_target: best_firing_solution.map(|solution| solution.1 + self.aim_bias),
                 active: health_percent >= 0.5,
                 fire: best_firing_solution
@@ -342,7 +351,10 @@ impl Bot {
                     .upgrade_options(update.score(), true)
                     .choose(&mut rng)
                 {
-                    ret = Command::Upgrade(Upgrade { entity_type });
+                    ret = Command::Upgrade(Upgrade {
+                        entity_type,
+                        loadout: Self::random_loadout(entity_type, &mut rng),
+                    });
                 }
             }
 
@@ -351,13 +363,20 @@ impl Bot {
             // Rage quit.
             BotAction::Quit
         } else {
+            let entity_type = EntityType::spawn_options(0, true)
+                .choose(&mut rng)
+                .expect("there must be at least one entity type to spawn as");
             BotAction::Some(Command::Spawn(Spawn {
-                entity_type: EntityType::spawn_options(0, true)
-                    .choose(&mut rng)
-                    .expect("there must be at least one entity type to spawn as"),
+                entity_type,
+                loadout: Self::random_loadout(entity_type, &mut rng),
             }))
         }
     }
+
+    /// Picks any of the entity type's loadouts.
+    fn random_loadout(entity_type: EntityType, rng: &mut ThreadRng) -> u8 {
+        rng.gen_range(0..entity_type.data().loadouts.len().max(1)) as u8
+    }
 }
 
 impl game_server::game_service::Bot<Server> for Bot {
@@ -366,9 +385,9 @@ impl game_server::game_service::Bot<Server> for Bot {
     fn get_input<'a>(
         server: &'a Server,
         player: &'a Arc<PlayerTuple<Server>>,
-        _players: &'a PlayerRepo<Server>,
+        players: &'a PlayerRepo<Server>,
     ) -> Self::Input<'a> {
-        server.world.get_player_complete(player)
+        server.world.get_player_complete(player, players)
     }
 
     fn update(
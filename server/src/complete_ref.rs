@@ -9,13 +9,14 @@ use atomic_refcell::AtomicRef;
 use common::complete::CompleteTrait;
 use common::contact::ContactTrait;
 use common::death_reason::DeathReason;
-use common::protocol::Update;
+use common::protocol::{Damage, Sortie, Update};
 use common::terrain;
-use common::terrain::{ChunkSet, Terrain};
+use common::terrain::{ChunkId, ChunkSet, Terrain};
 use common::ticks::{Ticks, TicksRepr};
 use common::velocity::Velocity;
 use game_server::player::PlayerData;
 use glam::Vec2;
+use std::convert::TryFrom;
 use std::ops::RangeInclusive;
 
 /// A "Complete" server to client update that references world data to avoid additional allocation.
@@ -52,6 +53,27 @@ impl<'a, I: Iterator<Item = ContactRef<'a>>> CompleteRef<'a, I> {
             None
         };
 
+        let (magazines, air_wing, docked) =
+            if let Status::Alive { entity_index, .. } = self.player.data.status {
+                let extension = self.world.entities[entity_index].extension();
+                let air_wing = extension
+                    .air_wing
+                    .iter()
+                    .map(|a| Sortie {
+                        entity_type: a.entity_type,
+                        status: a.status,
+                        fuel: a.fuel,
+                    })
+                    .collect();
+                (
+                    extension.magazines.clone(),
+                    air_wing,
+                    extension.dock_immunity(),
+                )
+            } else {
+                Default::default()
+            };
+
         // Any updated chunks are now no longer loaded.
         let mut new_loaded_chunks = loaded_chunks.and(&self.world.terrain.updated.not());
 
@@ -66,6 +88,17 @@ impl<'a, I: Iterator<Item = ContactRef<'a>>> CompleteRef<'a, I> {
         // Actually load more chunks.
         let loading = visible.and(&new_loaded_chunks.not());
 
+        // Ports are sent along with the chunks they are in.
+        let ports = self
+            .world
+            .ports
+            .iter()
+            .filter(|port| {
+                ChunkId::try_from(port.position).map_or(false, |id| loading.contains(id))
+            })
+            .copied()
+            .collect();
+
         // The chunks that will be loaded following this message.
         new_loaded_chunks = visible.or(&new_loaded_chunks);
 
@@ -85,6 +118,32 @@ impl<'a, I: Iterator<Item = ContactRef<'a>>> CompleteRef<'a, I> {
 
         *loaded_chunks = new_loaded_chunks;
 
+        let player_id = self.player.player_id;
+        let damage = self
+            .world
+            .hits
+            .iter()
+            .filter_map(|hit| {
+                if hit.attacker == player_id {
+                    Some(Damage {
+                        player: hit.victim_alias,
+                        entity_type: hit.entity_type,
+                        amount: hit.amount,
+                        dealt: true,
+                    })
+                } else if hit.victim == player_id {
+                    Some(Damage {
+                        player: hit.attacker_alias,
+                        entity_type: hit.entity_type,
+                        amount: hit.amount,
+                        dealt: false,
+                    })
+                } else {
+                    None
+                }
+            })
+            .collect();
+
         Update {
             contacts: self
                 .contacts
@@ -114,6 +173,13 @@ impl<'a, I: Iterator<Item = ContactRef<'a>>> CompleteRef<'a, I> {
             score: self.player.score,
             world_radius: self.world.radius,
             terrain,
+            kills: self.world.kills.clone(),
+            damage,
+            magazines,
+            air_wing,
+            events: self.world.events.to_protocol(),
+            ports,
+            docked,
         }
     }
 }
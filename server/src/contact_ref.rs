@@ -33,8 +33,8 @@ impl<'a> ContactRef<'a> {
                 reloads.len() <= ReloadsStorage::MAX.count_ones() as usize,
                 "not enough bits in reloads storage"
             );
-            for (mut b, t) in arr.as_mut_bitslice().into_iter().zip(reloads.iter()) {
-                b.set(t == &Ticks::ZERO);
+            for i in 0..reloads.len() {
+                arr.set(i, entity.is_armament_ready(i));
             }
             arr
         });
@@ -50,10 +50,12 @@ impl<'a> ContactRef<'a> {
     pub fn into_contact(self) -> Contact {
         Contact::new(
             self.altitude(),
+            self.battery(),
             self.damage(),
             self.entity_type(),
             *self.guidance(),
             self.id(),
+            self.loadout(),
             self.player_id(),
             self.reloads,
             *self.transform(),
@@ -76,6 +78,11 @@ impl<'a> ContactTrait for ContactRef<'a> {
         self.entity.altitude
     }
 
+    #[inline]
+    fn battery(&self) -> Option<f32> {
+        (self.reloads.is_some() && self.is_submarine()).then(|| self.entity.extension().battery())
+    }
+
     #[inline]
     fn damage(&self) -> Ticks {
         // Don't send lifespan to client.
@@ -105,6 +112,15 @@ impl<'a> ContactTrait for ContactRef<'a> {
         self.entity.id
     }
 
+    #[inline]
+    fn loadout(&self) -> u8 {
+        if self.turrets_known() {
+            self.entity.extension().loadout()
+        } else {
+            0
+        }
+    }
+
     #[inline]
     fn player_id(&self) -> Option<PlayerId> {
         self.entity
@@ -2,202 +2,167 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use crate::entity::*;
+use arrayvec::ArrayVec;
 use common::death_reason::DeathReason;
 use glam::Vec2;
 use maybe_parallel_iterator::{
     IntoMaybeParallelIterator, IntoMaybeParallelRefIterator, IntoMaybeParallelRefMutIterator,
 };
-use std::convert::{TryFrom, TryInto};
-use std::ops::{Index, IndexMut, RangeInclusive};
-
-const SIZE: usize = 32 * common::world::SIZE;
-const SCALE: f32 = 800.0;
-
-/// An efficient collection of entities.
+use std::num::NonZeroU32;
+use std::ops::{Index, IndexMut};
+use std::{mem, slice};
+
+/// Half the side length of the square covered by the root node.
+const ROOT_HALF: f32 = 65536.0;
+/// Nodes this small (half side length) are never split.
+const MIN_HALF: f32 = 32.0;
+/// A leaf with more than this many entities is split into four children.
+const SPLIT: usize = 32;
+/// A branch with this many or fewer entities in its subtree is merged back into a leaf. Must be
+/// lower than `SPLIT` to avoid thrashing.
+const MERGE: u32 = 8;
+/// The root node is always at this index in `Entities::nodes`.
+const ROOT: usize = 0;
+
+/// An efficient collection of entities, with stable indices and a quadtree spatial index that
+/// adapts to the density of entities.
 pub struct Entities {
-    sectors: [Sector; SIZE * SIZE],
+    /// Entities are stored in slots, which are only reused after the entity in them is removed.
+    slots: Vec<Slot>,
+    /// Vacant slots.
+    free_slots: Vec<u32>,
+    /// Quadtree nodes, the first of which is the root. Children are allocated in groups of 4.
+    nodes: Vec<Node>,
+    /// First indices of vacant groups of 4 nodes.
+    free_nodes: Vec<u32>,
 }
 
-/// A single square sector, storing the entities within it.
-pub struct Sector {
-    entities: Vec<Entity>,
+/// Storage for a single entity.
+struct Slot {
+    entity: Option<Entity>,
+    /// Index of the leaf node containing the entity.
+    node: u32,
+    /// Index of the entity within the leaf node's items.
+    item: u32,
 }
 
-impl Sector {
-    /// new allocates an empty sector.
-    const fn new() -> Self {
-        Self {
-            entities: Vec::new(),
-        }
-    }
+/// A single square node of the quadtree.
+struct Node {
+    center: Vec2,
+    half: f32,
+    /// Unused for the root.
+    parent: u32,
+    /// Index of the first of four consecutive children, if this is a branch (the root can't be
+    /// a child, so the index is never zero).
+    children: Option<NonZeroU32>,
+    /// Number of entities in this node's subtree.
+    count: u32,
+    /// Entities in this node. Always empty for branches.
+    items: Vec<EntityIndex>,
+}
 
-    /// shrink may reduce the allocation size of a sector if its entity count dropped sufficiently.
-    fn shrink(&mut self) {
-        if self.entities.capacity() > self.entities.len() * 3 {
-            let new_size = (self.entities.len() * 3 / 2).next_power_of_two().max(4);
-            if new_size < self.entities.capacity() {
-                self.entities.shrink_to(new_size);
-            }
+impl Node {
+    fn new(center: Vec2, half: f32, parent: u32) -> Self {
+        Self {
+            center,
+            half,
+            parent,
+            children: None,
+            count: 0,
+            items: Vec::new(),
         }
     }
-}
-
-#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
-pub struct SectorId(u8, u8);
 
-impl SectorId {
-    /// Forms an id from the corresponding sector index scalar.
-    fn from_sector_index(index: usize) -> Self {
-        Self((index / SIZE) as u8, (index % SIZE) as u8)
+    /// Returns which child (0..4) of self contains pos.
+    fn quadrant(&self, pos: Vec2) -> usize {
+        (pos.x >= self.center.x) as usize | ((pos.y >= self.center.y) as usize) << 1
     }
 
-    /// Returns the corresponding sector index scalar.
-    fn as_sector_index(&self) -> usize {
-        let result = self.0 as usize * SIZE + self.1 as usize;
-        debug_assert_eq!(*self, Self::from_sector_index(result));
-        result
+    /// Returns the center of child with a given quadrant.
+    fn child_center(&self, quadrant: usize) -> Vec2 {
+        let quarter = self.half * 0.5;
+        let x = if quadrant & 1 == 0 { -quarter } else { quarter };
+        let y = if quadrant & 2 == 0 { -quarter } else { quarter };
+        self.center + Vec2::new(x, y)
     }
 
-    /// Gets center of sector with id.
-    fn center(&self) -> Vec2 {
-        let mut pos = Vec2::new(self.0 as f32, self.1 as f32);
-        pos *= SCALE;
-        pos += SIZE as f32 * SCALE / -2.0 + SCALE / 2.0;
-        debug_assert_eq!(*self, Self::try_from(pos).unwrap());
-        pos
+    /// Returns true if self contains pos (once clamped to the bounds of the root).
+    fn contains(&self, pos: Vec2) -> bool {
+        let pos = pos.clamp(Vec2::splat(-ROOT_HALF), Vec2::splat(ROOT_HALF));
+        let abs_diff = (pos - self.center).abs();
+        abs_diff.x <= self.half && abs_diff.y <= self.half
     }
 
     /// Returns true if any part of self is within radius of position.
     fn in_radius(&self, position: Vec2, radius: f32) -> bool {
-        const HALF: f32 = SCALE / 2.0;
-        let abs_diff = (self.center() - position).abs();
-        if abs_diff.x > HALF + radius || abs_diff.y > HALF + radius {
+        let half = self.half;
+        let abs_diff = (self.center - position).abs();
+        if abs_diff.x > half + radius || abs_diff.y > half + radius {
             false
-        } else if abs_diff.x <= HALF || abs_diff.y <= HALF {
+        } else if abs_diff.x <= half || abs_diff.y <= half {
             true
         } else {
-            (abs_diff - HALF).max(Vec2::ZERO).length_squared() < radius.powi(2)
+            (abs_diff - half).max(Vec2::ZERO).length_squared() < radius.powi(2)
         }
     }
-
-    /// Iterates all `SectorId`s in a rectangle defined by corners start and end.
-    fn iter(start: Self, end: Self) -> impl Iterator<Item = Self> {
-        // Range inclusive is slow so add 1.
-        (start.0..end.0 + 1).flat_map(move |x| (start.1..end.1 + 1).map(move |y| Self(x, y)))
-    }
-
-    /// Iterates all `SectorId`s in a circle.
-    fn iter_radius(center: Vec2, radius: f32) -> impl Iterator<Item = Self> {
-        let start = Self::saturating_from(center - radius);
-        let end = Self::saturating_from(center + radius);
-        Self::iter(start, end).filter(move |id| id.in_radius(center, radius))
-    }
-
-    /// Returns the `SectorId` containing pos, with pos being clamped to the dimensions of the data
-    /// structure.
-    fn saturating_from(mut pos: Vec2) -> Self {
-        pos *= 1.0 / (SCALE);
-        pos += SIZE as f32 / 2.0;
-        let x = (pos.x as i32).clamp(0, (SIZE - 1) as i32) as u8;
-        let y = (pos.y as i32).clamp(0, (SIZE - 1) as i32) as u8;
-        Self(x, y)
-    }
 }
 
-impl TryFrom<Vec2> for SectorId {
-    type Error = &'static str;
-
-    fn try_from(mut pos: Vec2) -> Result<Self, Self::Error> {
-        pos *= 1.0 / (SCALE);
-        pos += SIZE as f32 / 2.0;
-        let (x, y) = (pos.x as i32, pos.y as i32);
-        const RANGE: RangeInclusive<i32> = 0..=((SIZE - 1) as i32);
-        if RANGE.contains(&x) && RANGE.contains(&y) {
-            Ok(Self(x as u8, y as u8))
-        } else {
-            Err("out of world")
-        }
-    }
-}
-
-#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq)]
-pub struct EntityIndex(SectorId, u16);
-
-impl EntityIndex {
-    pub fn changed(&self, e: &Entity) -> bool {
-        self.0 != e.transform.position.try_into().unwrap()
-    }
-}
+/// Stable handle to an entity, valid until the entity is removed.
+#[derive(Copy, Clone, Debug, Ord, PartialOrd, Eq, PartialEq, Hash)]
+pub struct EntityIndex(u32);
 
 impl Entities {
     pub fn new() -> Self {
-        const INIT: Sector = Sector::new();
         Self {
-            sectors: [INIT; SIZE * SIZE],
+            slots: Vec::new(),
+            free_slots: Vec::new(),
+            nodes: vec![Node::new(Vec2::ZERO, ROOT_HALF, 0)],
+            free_nodes: Vec::new(),
         }
     }
 
     /// Returns the maximum possible world radius to avoid going out of bounds of this collection.
     pub fn max_world_radius() -> f32 {
-        // TODO: Shouldn't need "- 1", but crashes otherwise.
-        ((SIZE - 1) / 2) as f32 * SCALE
-    }
-
-    fn get_sector(&self, sector_id: SectorId) -> &Sector {
-        &self.sectors[sector_id.as_sector_index()]
-    }
-
-    fn mut_sector(&mut self, sector_id: SectorId) -> &mut Sector {
-        &mut self.sectors[sector_id.as_sector_index()]
+        ROOT_HALF
     }
 
     pub fn add_internal(&mut self, mut entity: Entity) {
         assert_ne!(entity.id, unset_entity_id());
-        let sector_id = entity.transform.position.try_into().unwrap();
-        let sector = self.mut_sector(sector_id);
+        let position = entity.transform.position;
+
+        let index = if let Some(free) = self.free_slots.pop() {
+            EntityIndex(free)
+        } else {
+            self.slots.push(Slot {
+                entity: None,
+                node: 0,
+                item: 0,
+            });
+            EntityIndex(self.slots.len() as u32 - 1)
+        };
+
         if entity.is_boat() {
-            entity.create_index(EntityIndex(sector_id, sector.entities.len() as u16));
+            entity.create_index(index);
         }
-        sector.entities.push(entity);
+        self.slot_mut(index).entity = Some(entity);
+        self.insert(index, position);
     }
 
-    /// When an entity moves, it may reside in a different sector. This function commits that
+    /// When an entity moves, it may no longer be within its node. This function commits that
     /// change to the `Entities` state.
-    pub fn move_sector(&mut self, index: EntityIndex) {
-        let sector_id = index.0;
-        let i = index.1 as usize;
-        let sector = self.mut_sector(sector_id);
-
-        let last = sector.entities.len() - 1;
-        if i != last && sector.entities[last].is_boat() {
-            sector.entities[last].set_index(index)
-        }
-
-        let mut entity = sector.entities.swap_remove(i as usize);
-        sector.shrink();
-
-        let new_sector_id = entity.transform.position.try_into().unwrap();
-        let new_sector = self.mut_sector(new_sector_id);
-
-        if entity.is_boat() {
-            entity.set_index(EntityIndex(new_sector_id, new_sector.entities.len() as u16))
+    pub fn relocate(&mut self, index: EntityIndex) {
+        if !self.is_indexed(index) {
+            let position = self[index].transform.position;
+            self.unlink(index);
+            self.insert(index, position);
         }
-        new_sector.entities.push(entity);
     }
 
     /// Don't use directly. Wrapped by world's remove.
     pub fn remove_internal(&mut self, index: EntityIndex, death_reason: DeathReason) -> Entity {
-        let sector_id = index.0;
-        let i = index.1 as usize;
-        let sector = self.mut_sector(sector_id);
-
-        let last = sector.entities.len() - 1;
-        if i != last && sector.entities[last].is_boat() {
-            sector.entities[last].set_index(index)
-        }
-
-        let mut entity = sector.entities.swap_remove(i as usize);
-        sector.shrink();
+        self.unlink(index);
+        let mut entity = self.slot_mut(index).entity.take().unwrap();
+        self.free_slots.push(index.0);
 
         if entity.is_boat() {
             entity.delete_index(death_reason);
@@ -205,23 +170,22 @@ impl Entities {
         entity
     }
 
+    /// Returns true if the entity is within the node that it is indexed by. This is always the
+    /// case, except between moving an entity and calling `Self::relocate`.
+    pub fn is_indexed(&self, index: EntityIndex) -> bool {
+        self.nodes[self.slot(index).node as usize].contains(self[index].transform.position)
+    }
+
     /// Iterates all entities in parallel.
     pub fn par_iter(&self) -> impl IntoMaybeParallelIterator<Item = (EntityIndex, &Entity)> {
-        self.sectors
+        self.slots
             .maybe_par_iter()
+            .with_min_sequential(256)
             .enumerate()
-            .flat_map(|(sector_index, sector)| {
-                let sector_id = SectorId::from_sector_index(sector_index);
-
-                sector
-                    .entities
-                    .maybe_par_iter()
-                    .with_min_sequential(256)
-                    .enumerate()
-                    .map(move |(index, entity)| {
-                        let entity_index = EntityIndex(sector_id, index as u16);
-                        (entity_index, entity)
-                    })
+            .filter_map(|(index, slot)| {
+                slot.entity
+                    .as_ref()
+                    .map(|entity| (EntityIndex(index as u32), entity))
             })
     }
 
@@ -229,21 +193,14 @@ impl Entities {
     pub fn par_iter_mut(
         &mut self,
     ) -> impl IntoMaybeParallelIterator<Item = (EntityIndex, &mut Entity)> {
-        self.sectors
+        self.slots
             .maybe_par_iter_mut()
+            .with_min_sequential(256)
             .enumerate()
-            .flat_map(|(sector_index, sector)| {
-                let sector_id = SectorId::from_sector_index(sector_index);
-
-                sector
-                    .entities
-                    .maybe_par_iter_mut()
-                    .with_min_sequential(256)
-                    .enumerate()
-                    .map(move |(index, entity)| {
-                        let entity_index = EntityIndex(sector_id, index as u16);
-                        (entity_index, entity)
-                    })
+            .filter_map(|(index, slot)| {
+                slot.entity
+                    .as_mut()
+                    .map(|entity| (EntityIndex(index as u32), entity))
             })
     }
 
@@ -253,15 +210,190 @@ impl Entities {
         center: Vec2,
         radius: f32,
     ) -> impl Iterator<Item = (EntityIndex, &Entity)> {
-        let r2 = radius * radius;
-        SectorId::iter_radius(center, radius).flat_map(move |sector_id| {
-            self.get_sector(sector_id)
-                .entities
-                .iter()
-                .enumerate()
-                .filter(move |(_, e)| e.transform.position.distance_squared(center) <= r2)
-                .map(move |(index, entity)| (EntityIndex(sector_id, index as u16), entity))
-        })
+        let mut stack = ArrayVec::new();
+        if self.nodes[ROOT].in_radius(center, radius) {
+            stack.push(ROOT as u32);
+        }
+
+        RadiusIter {
+            entities: self,
+            stack,
+            items: [].iter(),
+            center,
+            radius,
+            r2: radius * radius,
+        }
+    }
+
+    /// Iterates the center and entity count of each non-empty leaf node.
+    pub fn cell_counts(&self) -> impl Iterator<Item = (Vec2, usize)> + '_ {
+        self.nodes
+            .iter()
+            .filter(|node| !node.items.is_empty())
+            .map(|node| (node.center, node.items.len()))
+    }
+
+    fn slot(&self, index: EntityIndex) -> &Slot {
+        &self.slots[index.0 as usize]
+    }
+
+    fn slot_mut(&mut self, index: EntityIndex) -> &mut Slot {
+        &mut self.slots[index.0 as usize]
+    }
+
+    /// Links an entity into the leaf node containing position, splitting it if it becomes too
+    /// crowded.
+    fn insert(&mut self, index: EntityIndex, position: Vec2) {
+        let mut node = ROOT;
+        loop {
+            let n = &mut self.nodes[node];
+            n.count += 1;
+            match n.children {
+                Some(children) => node = children.get() as usize + n.quadrant(position),
+                None => break,
+            }
+        }
+
+        self.push_item(node, index);
+
+        let n = &self.nodes[node];
+        if n.items.len() > SPLIT && n.half > MIN_HALF {
+            self.split(node);
+        }
+    }
+
+    /// Unlinks an entity from its leaf node, merging nodes that become too sparse.
+    fn unlink(&mut self, index: EntityIndex) {
+        let Slot { node, item, .. } = *self.slot(index);
+        let (node, item) = (node as usize, item as usize);
+
+        let items = &mut self.nodes[node].items;
+        debug_assert_eq!(items[item], index);
+        items.swap_remove(item);
+        if let Some(&moved) = items.get(item) {
+            self.slot_mut(moved).item = item as u32;
+        }
+
+        // Merge the highest sparse branch.
+        let mut merge = None;
+        let mut n = node;
+        loop {
+            let current = &mut self.nodes[n];
+            current.count -= 1;
+            if current.children.is_some() && current.count <= MERGE {
+                merge = Some(n);
+            }
+            if n == ROOT {
+                break;
+            }
+            n = current.parent as usize;
+        }
+
+        if let Some(n) = merge {
+            self.merge(n);
+        }
+    }
+
+    fn push_item(&mut self, node: usize, index: EntityIndex) {
+        let items = &mut self.nodes[node].items;
+        let slot = &mut self.slots[index.0 as usize];
+        slot.node = node as u32;
+        slot.item = items.len() as u32;
+        items.push(index);
+    }
+
+    /// Converts a leaf into a branch with four leaves.
+    fn split(&mut self, node: usize) {
+        let parent = &self.nodes[node];
+        let children: [Node; 4] = [0, 1, 2, 3].map(|quadrant| {
+            Node::new(
+                parent.child_center(quadrant),
+                parent.half * 0.5,
+                node as u32,
+            )
+        });
+
+        let first = if let Some(first) = self.free_nodes.pop() {
+            let first = first as usize;
+            for (i, child) in IntoIterator::into_iter(children).enumerate() {
+                self.nodes[first + i] = child;
+            }
+            first
+        } else {
+            let first = self.nodes.len();
+            self.nodes.extend(children);
+            first
+        };
+        self.nodes[node].children = NonZeroU32::new(first as u32);
+
+        for index in mem::take(&mut self.nodes[node].items) {
+            let position = self[index].transform.position;
+            let child = first + self.nodes[node].quadrant(position);
+            self.nodes[child].count += 1;
+            self.push_item(child, index);
+        }
+    }
+
+    /// Converts a branch into a leaf, containing all the entities of its subtree.
+    fn merge(&mut self, node: usize) {
+        let mut items = Vec::with_capacity(self.nodes[node].count as usize);
+        self.drain(node, &mut items);
+        for index in items {
+            self.push_item(node, index);
+        }
+    }
+
+    /// Removes all entities in node's subtree, freeing any children.
+    fn drain(&mut self, node: usize, items: &mut Vec<EntityIndex>) {
+        items.append(&mut self.nodes[node].items);
+        if let Some(children) = self.nodes[node].children.take() {
+            let first = children.get() as usize;
+            for child in first..first + 4 {
+                self.drain(child, items);
+            }
+            self.free_nodes.push(first as u32);
+        }
+    }
+}
+
+/// Iterator returned by `Entities::iter_radius`.
+struct RadiusIter<'a> {
+    entities: &'a Entities,
+    /// Nodes yet to be visited, that intersect the circle. Each node visited replaces itself
+    /// with at most 4 children, so this won't overflow for a tree less than 21 levels deep.
+    stack: ArrayVec<u32, 64>,
+    /// Remaining items of the current leaf.
+    items: slice::Iter<'a, EntityIndex>,
+    center: Vec2,
+    radius: f32,
+    r2: f32,
+}
+
+impl<'a> Iterator for RadiusIter<'a> {
+    type Item = (EntityIndex, &'a Entity);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let entities = self.entities;
+        loop {
+            for &index in &mut self.items {
+                let entity = &entities[index];
+                if entity.transform.position.distance_squared(self.center) <= self.r2 {
+                    return Some((index, entity));
+                }
+            }
+
+            let node = &entities.nodes[self.stack.pop()? as usize];
+            if let Some(children) = node.children {
+                let first = children.get();
+                for child in first..first + 4 {
+                    if entities.nodes[child as usize].in_radius(self.center, self.radius) {
+                        self.stack.push(child);
+                    }
+                }
+            } else {
+                self.items = node.items.iter();
+            }
+        }
     }
 }
 
@@ -269,12 +401,132 @@ impl Index<EntityIndex> for Entities {
     type Output = Entity;
 
     fn index(&self, i: EntityIndex) -> &Self::Output {
-        &self.get_sector(i.0).entities[i.1 as usize]
+        self.slot(i).entity.as_ref().unwrap()
     }
 }
 
 impl IndexMut<EntityIndex> for Entities {
     fn index_mut(&mut self, i: EntityIndex) -> &mut Self::Output {
-        &mut self.mut_sector(i.0).entities[i.1 as usize]
+        self.slot_mut(i).entity.as_mut().unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::{Entities, EntityIndex};
+    use crate::entity::Entity;
+    use common::death_reason::DeathReason;
+    use common::entity::{EntityId, EntityType};
+    use glam::Vec2;
+    use maybe_parallel_iterator::IntoMaybeParallelIterator;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    fn entity(id: u32, position: Vec2) -> Entity {
+        let mut entity = Entity::new(EntityType::Barrel, None);
+        entity.id = EntityId::new(id).unwrap();
+        entity.transform.position = position;
+        entity
+    }
+
+    fn random_position(rng: &mut StdRng, radius: f32) -> Vec2 {
+        Vec2::new(
+            rng.gen_range(-radius..radius),
+            rng.gen_range(-radius..radius),
+        )
+    }
+
+    /// Finds the index of the entity with the given id, or panics.
+    fn find(entities: &Entities, id: u32) -> EntityIndex {
+        entities
+            .par_iter()
+            .into_maybe_parallel_iter()
+            .find_any(|(_, e)| e.id.get() == id)
+            .unwrap()
+            .0
+    }
+
+    /// Ensures `iter_radius` agrees with a brute force search as entities are added, moved, and
+    /// removed (exercising splitting and merging).
+    #[test]
+    fn iter_radius() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut entities = Entities::new();
+
+        for id in 1..=2000 {
+            // Cluster half the entities to force deep splits.
+            let radius = if id % 2 == 0 { 100.0 } else { 5000.0 };
+            entities.add_internal(entity(id, random_position(&mut rng, radius)));
+        }
+
+        let occupied = |entities: &Entities| -> Vec<EntityIndex> {
+            entities
+                .par_iter()
+                .into_maybe_parallel_iter()
+                .map(|(i, _)| i)
+                .collect()
+        };
+
+        for round in 0..10 {
+            for index in occupied(&entities) {
+                entities[index].transform.position += random_position(&mut rng, 300.0);
+                entities.relocate(index);
+                assert!(entities.is_indexed(index));
+            }
+
+            if round % 2 == 0 {
+                let indices = occupied(&entities);
+                for &index in &indices[..indices.len() / 3] {
+                    entities.remove_internal(index, DeathReason::Unknown);
+                }
+            }
+
+            for _ in 0..20 {
+                let center = random_position(&mut rng, 6000.0);
+                let radius = rng.gen_range(0.0..3000.0);
+
+                let mut expected: Vec<EntityIndex> = entities
+                    .par_iter()
+                    .into_maybe_parallel_iter()
+                    .filter_map(|(i, e)| {
+                        (e.transform.position.distance_squared(center) <= radius * radius)
+                            .then_some(i)
+                    })
+                    .collect();
+                let mut actual: Vec<EntityIndex> = entities
+                    .iter_radius(center, radius)
+                    .map(|(i, _)| i)
+                    .collect();
+
+                expected.sort_unstable();
+                actual.sort_unstable();
+                assert_eq!(expected, actual);
+            }
+        }
+
+        for index in occupied(&entities) {
+            entities.remove_internal(index, DeathReason::Unknown);
+        }
+        assert_eq!(entities.nodes[0].count, 0);
+        assert!(entities.nodes[0].children.is_none());
+    }
+
+    /// Ensures an entity's index doesn't change as it moves, or as other entities are removed.
+    #[test]
+    fn stable_index() {
+        let mut entities = Entities::new();
+        for id in 1..=100 {
+            entities.add_internal(entity(id, Vec2::new(id as f32, 0.0)));
+        }
+
+        let index = find(&entities, 50);
+        for id in (1..=100).filter(|&id| id != 50) {
+            entities.remove_internal(find(&entities, id), DeathReason::Unknown);
+        }
+        assert_eq!(find(&entities, 50), index);
+
+        entities[index].transform.position = Vec2::new(-20000.0, 30000.0);
+        entities.relocate(index);
+        assert_eq!(entities[index].id.get(), 50);
     }
 }
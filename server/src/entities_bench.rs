@@ -0,0 +1,226 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+//! Benchmarks `Entities` against the fixed grid of sectors that it replaced (run with
+//! `cargo bench`).
+
+extern crate test;
+
+use crate::entities::{Entities, EntityIndex};
+use crate::entity::Entity;
+use common::entity::{EntityId, EntityType};
+use glam::Vec2;
+use maybe_parallel_iterator::IntoMaybeParallelIterator;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use test::{black_box, Bencher};
+
+/// The previous implementation, reduced to positions.
+struct LegacyGrid {
+    /// Positions and ids of entities in each sector.
+    sectors: Vec<Vec<(Vec2, u32)>>,
+    /// Sector and index within sector of each id (like the old `EntityIndex`).
+    locations: Vec<(usize, u16)>,
+}
+
+impl LegacyGrid {
+    const SIZE: usize = 32 * common::world::SIZE;
+    const SCALE: f32 = 800.0;
+
+    fn new() -> Self {
+        Self {
+            sectors: (0..Self::SIZE * Self::SIZE).map(|_| Vec::new()).collect(),
+            locations: Vec::new(),
+        }
+    }
+
+    fn sector(pos: Vec2) -> (usize, usize) {
+        let pos = pos * (1.0 / Self::SCALE) + Self::SIZE as f32 / 2.0;
+        let x = (pos.x as i32).clamp(0, (Self::SIZE - 1) as i32) as usize;
+        let y = (pos.y as i32).clamp(0, (Self::SIZE - 1) as i32) as usize;
+        (x, y)
+    }
+
+    fn center(x: usize, y: usize) -> Vec2 {
+        Vec2::new(x as f32, y as f32) * Self::SCALE
+            + Self::SIZE as f32 * Self::SCALE / -2.0
+            + Self::SCALE / 2.0
+    }
+
+    fn insert(&mut self, pos: Vec2) {
+        let id = self.locations.len() as u32;
+        self.locations.push((0, 0));
+        self.link(id, pos);
+    }
+
+    fn link(&mut self, id: u32, pos: Vec2) {
+        let (x, y) = Self::sector(pos);
+        let sector = &mut self.sectors[x * Self::SIZE + y];
+        self.locations[id as usize] = (x * Self::SIZE + y, sector.len() as u16);
+        sector.push((pos, id));
+    }
+
+    /// Like the old `Entities::move_sector`, which was only called when the sector changed.
+    fn move_to(&mut self, id: u32, pos: Vec2) {
+        let (sector, i) = self.locations[id as usize];
+        let (x, y) = Self::sector(pos);
+        let entities = &mut self.sectors[sector];
+        if sector == x * Self::SIZE + y {
+            entities[i as usize].0 = pos;
+            return;
+        }
+        entities.swap_remove(i as usize);
+        if let Some(&(_, moved)) = entities.get(i as usize) {
+            self.locations[moved as usize] = (sector, i);
+        }
+        self.link(id, pos);
+    }
+
+    fn iter_radius(&self, center: Vec2, radius: f32) -> impl Iterator<Item = Vec2> + '_ {
+        const HALF: f32 = LegacyGrid::SCALE / 2.0;
+        let (start_x, start_y) = Self::sector(center - radius);
+        let (end_x, end_y) = Self::sector(center + radius);
+        let r2 = radius * radius;
+        (start_x..end_x + 1)
+            .flat_map(move |x| (start_y..end_y + 1).map(move |y| (x, y)))
+            .filter(move |&(x, y)| {
+                let abs_diff = (Self::center(x, y) - center).abs();
+                if abs_diff.x > HALF + radius || abs_diff.y > HALF + radius {
+                    false
+                } else if abs_diff.x <= HALF || abs_diff.y <= HALF {
+                    true
+                } else {
+                    (abs_diff - HALF).max(Vec2::ZERO).length_squared() < radius.powi(2)
+                }
+            })
+            .flat_map(move |(x, y)| {
+                self.sectors[x * Self::SIZE + y]
+                    .iter()
+                    .map(|&(p, _)| p)
+                    .filter(move |p| p.distance_squared(center) <= r2)
+            })
+    }
+}
+
+const COUNT: usize = 10000;
+
+/// Spread out over the world, or clustered (e.g. many boats fighting over one spot).
+fn positions(clustered: bool) -> Vec<Vec2> {
+    let mut rng = StdRng::seed_from_u64(0);
+    let radius = if clustered { 1000.0 } else { 12000.0 };
+    (0..COUNT)
+        .map(|_| {
+            Vec2::new(
+                rng.gen_range(-radius..radius),
+                rng.gen_range(-radius..radius),
+            )
+        })
+        .collect()
+}
+
+fn entities(positions: &[Vec2]) -> Entities {
+    let mut entities = Entities::new();
+    for (i, &position) in positions.iter().enumerate() {
+        let mut entity = Entity::new(EntityType::Barrel, None);
+        entity.id = EntityId::new(i as u32 + 1).unwrap();
+        entity.transform.position = position;
+        entities.add_internal(entity);
+    }
+    entities
+}
+
+fn legacy(positions: &[Vec2]) -> LegacyGrid {
+    let mut grid = LegacyGrid::new();
+    for &position in positions {
+        grid.insert(position);
+    }
+    grid
+}
+
+fn bench_iter_radius(b: &mut Bencher, clustered: bool, radius: f32) {
+    let positions = positions(clustered);
+    let entities = entities(&positions);
+    b.iter(|| {
+        positions
+            .iter()
+            .step_by(100)
+            .map(|&p| entities.iter_radius(p, radius).count())
+            .sum::<usize>()
+    });
+}
+
+fn bench_legacy_iter_radius(b: &mut Bencher, clustered: bool, radius: f32) {
+    let positions = positions(clustered);
+    let grid = legacy(&positions);
+    b.iter(|| {
+        positions
+            .iter()
+            .step_by(100)
+            .map(|&p| grid.iter_radius(p, radius).count())
+            .sum::<usize>()
+    });
+}
+
+#[bench]
+fn iter_radius_short(b: &mut Bencher) {
+    bench_iter_radius(b, false, 500.0);
+}
+
+#[bench]
+fn legacy_iter_radius_short(b: &mut Bencher) {
+    bench_legacy_iter_radius(b, false, 500.0);
+}
+
+#[bench]
+fn iter_radius_long(b: &mut Bencher) {
+    bench_iter_radius(b, false, 5000.0);
+}
+
+#[bench]
+fn legacy_iter_radius_long(b: &mut Bencher) {
+    bench_legacy_iter_radius(b, false, 5000.0);
+}
+
+#[bench]
+fn iter_radius_clustered(b: &mut Bencher) {
+    bench_iter_radius(b, true, 100.0);
+}
+
+#[bench]
+fn legacy_iter_radius_clustered(b: &mut Bencher) {
+    bench_legacy_iter_radius(b, true, 100.0);
+}
+
+#[bench]
+fn relocate(b: &mut Bencher) {
+    let positions = positions(false);
+    let mut entities = entities(&positions);
+    let indices: Vec<EntityIndex> = entities
+        .par_iter()
+        .into_maybe_parallel_iter()
+        .map(|(i, _)| i)
+        .collect();
+    let mut offset = Vec2::new(1000.0, 0.0);
+    b.iter(|| {
+        offset = -offset;
+        for &index in &indices {
+            entities[index].transform.position += offset;
+            entities.relocate(index);
+        }
+    });
+}
+
+#[bench]
+fn legacy_move(b: &mut Bencher) {
+    let mut positions = positions(false);
+    let mut grid = legacy(&positions);
+    let mut offset = Vec2::new(1000.0, 0.0);
+    b.iter(|| {
+        offset = -offset;
+        for (id, position) in positions.iter_mut().enumerate() {
+            *position += offset;
+            grid.move_to(id as u32, *position);
+        }
+        black_box(&grid);
+    });
+}
@@ -5,6 +5,7 @@ use crate::arena::Arena;
 use crate::collision::{radius_collision, sat_collision};
 use crate::entities::*;
 use crate::entity_extension::EntityExtension;
+use crate::npc::Npcs;
 use crate::player::*;
 use crate::server::Server;
 use atomic_refcell::{AtomicRef, AtomicRefMut};
@@ -77,13 +78,16 @@ impl Entity {
     }
 
     /// change_entity_type is the only valid way to change an entity's type.
+    /// Boats may also change loadout (which is ignored for other entities).
     pub fn change_entity_type(
         &mut self,
         entity_type: EntityType,
+        loadout: u8,
         arena: &mut Arena,
         boat_below_full_potential: bool,
     ) {
         let old_data = self.data();
+        let old_armaments = self.armaments();
         debug_assert_eq!(old_data.kind, entity_type.data().kind);
 
         // Update entity type counts.
@@ -121,7 +125,7 @@ impl Entity {
         // Start by counting the total ticks left to reload (for non-limited armaments).
         let mut total_reload = 0;
         for (i, reload) in extension.reloads.iter().enumerate() {
-            if !old_data.armaments[i].entity_type.data().limited {
+            if !old_armaments[i].entity_type.data().limited {
                 total_reload += reload.0 as u32;
             }
         }
@@ -129,12 +133,30 @@ impl Entity {
         // Penalty for upgrading with fired weapons to nerf upgrading during fights (see #168).
         total_reload = total_reload * 3 / 2;
 
+        // Keep the fraction of rounds remaining, so upgrading doesn't resupply. Use u32 to avoid
+        // overflow.
+        let (total_rounds, total_capacity) = old_armaments
+            .iter()
+            .zip(extension.magazines.iter())
+            .filter_map(|(a, &rounds)| a.magazine.map(|capacity| (rounds, capacity)))
+            .fold((0u32, 0u32), |(r, c), (rounds, capacity)| {
+                (r + rounds as u32, c + capacity as u32)
+            });
+
         // Change the extension to correspond with the new type.
-        extension.change_entity_type(entity_type);
+        extension.change_entity_type(entity_type, loadout);
+
+        // Magazines start full, so scale them down.
+        if total_capacity > 0 {
+            for rounds in extension.magazines.iter_mut() {
+                *rounds = (*rounds as u32 * total_rounds / total_capacity) as u8;
+            }
+        }
 
         // Finish by (un)reloading.
+        let new_armaments = new_data.loadout_armaments(loadout);
         for (i, reload) in extension.reloads_mut().iter_mut().enumerate() {
-            let armament = &new_data.armaments[i];
+            let armament = &new_armaments[i];
             if !armament.entity_type.data().limited {
                 let to_consume = (armament.reload().0 as u32).min(total_reload);
                 *reload = Ticks::from_repr(to_consume as TicksRepr);
@@ -166,15 +188,7 @@ impl Entity {
 
         // Change entity type (allocate turrets/reloads).
         let entity_type = self.entity_type;
-        self.extension_mut().change_entity_type(entity_type);
-    }
-
-    /// Adjusts player's pointer to self, if applicable.
-    /// Only call on boats.
-    pub fn set_index(&mut self, i: EntityIndex) {
-        debug_assert!(self.is_boat());
-
-        self.borrow_player_mut().data.status.set_entity_index(i);
+        self.extension_mut().change_entity_type(entity_type, 0);
     }
 
     /// Set's player to dead, removing reference to self, if applicable.
@@ -234,6 +248,15 @@ impl Entity {
         self.entity_type.data()
     }
 
+    /// Gets the entity's armaments, corresponding to its loadout if a boat.
+    pub fn armaments(&self) -> &'static [Armament] {
+        if self.is_boat() {
+            self.data().loadout_armaments(self.extension().loadout())
+        } else {
+            self.data().armaments
+        }
+    }
+
     /// Returns true if and only if the entity is of kind boat.
     pub fn is_boat(&self) -> bool {
         self.entity_type.data().kind == EntityKind::Boat
@@ -349,7 +372,7 @@ impl Entity {
 
     /// Marks a particular armament as consumed.
     pub fn consume_armament(&mut self, index: usize) {
-        let a = &self.data().armaments[index];
+        let a = &self.armaments()[index];
 
         // Limited armaments start their timer when they die.
         let reload = if a.entity_type.data().limited {
@@ -358,7 +381,54 @@ impl Entity {
             a.reload()
         };
 
-        self.extension_mut().reloads_mut()[index] = reload;
+        let extension = self.extension_mut();
+        extension.reloads_mut()[index] = reload;
+        if a.magazine.is_some() {
+            let rounds = &mut extension.magazines[index];
+            debug_assert!(*rounds > 0, "consumed empty magazine");
+            *rounds = rounds.saturating_sub(1);
+        }
+    }
+
+    /// Returns true if a particular armament is reloaded and, if it has a magazine, not empty.
+    pub fn is_armament_ready(&self, index: usize) -> bool {
+        let extension = self.extension();
+        extension.reloads[index] == Ticks::ZERO
+            && (self.armaments()[index].magazine.is_none() || extension.magazines[index] > 0)
+    }
+
+    /// Refills magazines while near a source of supplies.
+    pub fn resupply(&mut self, amount: Ticks) {
+        let armaments = self.armaments();
+        let extension = self.extension_mut();
+        let rounds = extension.resupply(amount);
+        if rounds == 0 {
+            return;
+        }
+        for (i, armament) in armaments.iter().enumerate() {
+            if let Some(capacity) = armament.magazine {
+                let magazine = &mut extension.magazines[i];
+                *magazine = magazine.saturating_add(rounds).min(capacity);
+            }
+        }
+    }
+
+    /// Whether it can resupply boats near it (only friendly ones, if it is a boat).
+    pub fn is_supply(&self) -> bool {
+        matches!(self.entity_type, EntityType::OilPlatform | EntityType::Hq)
+            || (self.is_boat() && self.data().sub_kind == EntitySubKind::Tanker)
+    }
+
+    /// Whether self, a supply, can resupply boat. Tankers don't resupply each other.
+    pub fn can_resupply(&self, boat: &Self) -> bool {
+        debug_assert!(self.is_supply());
+        boat.is_boat()
+            && (!self.is_boat() || (self.is_friendly(boat) && !boat.is_supply()))
+            && self
+                .transform
+                .position
+                .distance_squared(boat.transform.position)
+                <= Self::RESUPPLY_RANGE.powi(2)
     }
 
     /// Repairs by a certain amount, up to maximum health.
@@ -368,7 +438,7 @@ impl Entity {
 
     /// Reloads arbitrary armaments/groups by a certain amount.
     pub fn reload(&mut self, amount: Ticks) {
-        let armaments = &self.data().armaments;
+        let armaments = self.armaments();
         let reloads = self.extension_mut().reloads_mut();
         if reloads.is_empty() {
             return;
@@ -418,6 +488,10 @@ impl Entity {
         // Ticks is lifespan, not damage, for non-boats.
         assert_eq!(data.kind, EntityKind::Boat);
 
+        if self.extension().invulnerable || self.extension().is_dock_immune() {
+            return false;
+        }
+
         self.ticks = self.ticks.saturating_add(amount).min(data.max_health());
         self.ticks == data.max_health()
     }
@@ -435,6 +509,18 @@ impl Entity {
         data.sub_kind == EntitySubKind::Torpedo && !data.sensors.any()
     }
 
+    /// Height, in meters above sea level, at which the entity sees and is seen over terrain.
+    pub fn sight_height(&self) -> f32 {
+        let data = self.data();
+        // Most boats don't specify a mast height, so approximate one.
+        let mast = if data.mast == Altitude::ZERO {
+            data.width
+        } else {
+            data.mast.to_meters()
+        };
+        self.altitude.to_meters().max(0.0) + mast
+    }
+
     /// Returns true if two entities are overlapping, only taking into account their altitudes.
     pub fn altitude_overlapping(&self, other: &Self) -> bool {
         if (self.altitude.is_airborne() && other.altitude.is_submerged())
@@ -484,6 +570,7 @@ impl Entity {
             },
             EntityKind::Decoy => match data.sub_kind {
                 EntitySubKind::Sonar => Altitude::MIN,
+                EntitySubKind::Chaff => Altitude::MAX,
                 _ => Altitude::ZERO,
             },
             _ => Altitude::ZERO,
@@ -529,6 +616,9 @@ impl Entity {
             },
             EntityKind::Decoy => match data.sub_kind {
                 EntitySubKind::Sonar => -unguided_weapon_altitude,
+                // Chaff blooms at the altitude of sea-skimming missiles.
+                EntitySubKind::Chaff => unguided_weapon_altitude,
+                EntitySubKind::Smoke => Altitude::ZERO,
                 _ => {
                     debug_assert!(false, "{:?}", data.sub_kind);
                     Altitude::ZERO
@@ -568,6 +658,11 @@ impl Entity {
 
         let player = player.borrow_player();
 
+        // NPCs don't fight each other.
+        if Npcs::is_npc(player.player_id) {
+            return Npcs::is_npc(other_player.borrow_player().player_id);
+        }
+
         if player.team_id().is_none() {
             return false;
         }
@@ -585,12 +680,20 @@ impl Entity {
         self.player.as_ref().unwrap() == other.player.as_ref().unwrap()
     }
 
+    /// Whether self, an aircraft, has barely enough fuel left to fly to `pad`.
+    pub fn is_bingo_fuel(&self, pad: Vec2) -> bool {
+        let data = self.data();
+        let flight_time = self.transform.position.distance(pad) / data.speed.to_mps();
+        let fuel = data.lifespan.saturating_sub(self.ticks).to_secs();
+        // Leave some reserve for lining up with the pad.
+        fuel < flight_time * 1.25 + 3.0
+    }
+
     /// Returns Some(pad_index) iff self, an aircraft, can land on boat.
     pub fn landing_pad(&self, boat: &Self) -> Option<usize> {
         let data = self.data();
-        let boat_data = boat.data();
         let boat_extension = boat.extension();
-        for (i, armament) in boat_data.armaments.iter().enumerate() {
+        for (i, armament) in boat.armaments().iter().enumerate() {
             if armament.entity_type != self.entity_type || !data.limited {
                 // Irrelevant armament.
                 continue;
@@ -601,8 +704,12 @@ impl Entity {
                 continue;
             }
 
-            let transform =
-                boat.transform + boat.data().armament_transform(&boat_extension.turrets, i);
+            let transform = boat.transform
+                + boat.data().armament_transform(
+                    boat_extension.loadout(),
+                    &boat_extension.turrets,
+                    i,
+                );
             if self.transform.position.distance_squared(transform.position) < data.radius.powi(2) {
                 // Helicopters can land at any angle, but planes must be withing angle parameters.
                 if data.sub_kind == EntitySubKind::Heli
@@ -618,6 +725,8 @@ impl Entity {
     /// Constant used for checking whether, for example, a weapon becomes visible regardless of
     /// sensor ranges.
     pub const CLOSE_PROXIMITY: f32 = 60.0;
+    /// How close boats must be to the center of a supply to resupply.
+    pub const RESUPPLY_RANGE: f32 = 250.0;
 
     /// Calculates proximity to a boat (which is defined as the minimum normal or tangential
     /// difference to the boats front or side).
@@ -664,10 +773,40 @@ impl Eq for Entity {}
 
 #[cfg(test)]
 mod tests {
+    use crate::entities::EntityIndex;
     use crate::entity::Entity;
-    use common::entity::{EntityId, EntityType};
+    use crate::world::World;
+    use crate::Server;
+    use common::entity::{EntityId, EntityKind, EntitySubKind, EntityType};
+    use common::protocol::Depth;
+    use common::terrain::Terrain;
+    use common::ticks::Ticks;
+    use core_protocol::id::PlayerId;
+    use game_server::player::{PlayerData, PlayerTuple};
     use glam::Vec2;
     use std::mem;
+    use std::num::NonZeroU32;
+    use std::sync::Arc;
+
+    /// A boat with its extension allocated, as if spawned.
+    fn boat(entity_type: EntityType) -> Entity {
+        let player: Arc<PlayerTuple<Server>> = Arc::new(PlayerTuple::new(PlayerData::new(
+            PlayerId(NonZeroU32::new(1).unwrap()),
+            None,
+        )));
+        let mut boat = Entity::new(entity_type, Some(player));
+        boat.extension_mut().change_entity_type(entity_type, 0);
+        boat
+    }
+
+    /// Fires an armament until its magazine is empty, skipping reloads.
+    fn empty_magazine(boat: &mut Entity, index: usize) {
+        while boat.extension().magazines[index] > 0 {
+            boat.extension_mut().reloads_mut()[index] = Ticks::ZERO;
+            assert!(boat.is_armament_ready(index));
+            boat.consume_armament(index);
+        }
+    }
 
     #[test]
     fn size_of() {
@@ -698,4 +837,137 @@ mod tests {
         assert_eq!(e2, e2);
         assert_ne!(e1, e2)
     }
+
+    #[test]
+    fn consume_armament() {
+        let mut boat = boat(EntityType::G5);
+        let armament = &boat.armaments()[0];
+        let capacity = armament.magazine.unwrap();
+        assert!(boat.is_armament_ready(0));
+
+        boat.consume_armament(0);
+        assert!(!boat.is_armament_ready(0));
+        assert_eq!(boat.extension().reloads[0], armament.reload());
+        assert_eq!(boat.extension().magazines[0], capacity - 1);
+        assert_eq!(boat.extension().magazines[1], capacity);
+
+        empty_magazine(&mut boat, 0);
+
+        // An empty magazine still reloads, but the armament isn't ready until resupplied.
+        boat.reload(armament.reload());
+        assert_eq!(boat.extension().reloads[0], Ticks::ZERO);
+        assert!(!boat.is_armament_ready(0));
+        assert!(boat.is_armament_ready(1));
+    }
+
+    #[test]
+    fn resupply() {
+        let mut boat = boat(EntityType::G5);
+        let capacity = boat.armaments()[0].magazine.unwrap();
+        empty_magazine(&mut boat, 0);
+        boat.consume_armament(1);
+
+        boat.resupply(Ticks::from_whole_secs(1));
+        assert_eq!(boat.extension().magazines[0], 0);
+
+        boat.resupply(Ticks::from_whole_secs(1));
+        assert_eq!(boat.extension().magazines[0], 1);
+        assert_eq!(boat.extension().magazines[1], capacity);
+
+        // Ready once reloaded.
+        boat.reload(boat.armaments()[0].reload());
+        assert!(boat.is_armament_ready(0));
+    }
+
+    #[test]
+    fn can_resupply() {
+        let tanker = boat(EntityType::Tanker);
+        let player = Arc::clone(tanker.player.as_ref().unwrap());
+        let platform = Entity::new(EntityType::OilPlatform, None);
+
+        // Friendly tankers resupply boats, but not each other.
+        let friendly_boat = Entity::new(EntityType::G5, Some(Arc::clone(&player)));
+        let friendly_tanker = Entity::new(EntityType::Tanker, Some(player));
+        assert!(tanker.can_resupply(&friendly_boat));
+        assert!(!tanker.can_resupply(&friendly_tanker));
+        assert!(!tanker.can_resupply(&boat(EntityType::G5)));
+
+        // Platforms resupply any boat, including tankers.
+        assert!(platform.can_resupply(&tanker));
+        assert!(platform.can_resupply(&boat(EntityType::G5)));
+        assert!(!platform.can_resupply(&Entity::new(EntityType::Crate, None)));
+    }
+
+    /// A world containing a spawned boat.
+    fn spawned(entity_type: EntityType) -> (World, EntityIndex) {
+        let mut world = World::new(10000.0);
+        world.terrain = Terrain::new();
+        let boat = boat(entity_type);
+        let player = Arc::clone(boat.player.as_ref().unwrap());
+        assert!(world.spawn_here_or_nearby(boat, 0.0, None).is_some());
+        let index = player
+            .borrow_player()
+            .data
+            .status
+            .get_entity_index()
+            .unwrap();
+        (world, index)
+    }
+
+    #[test]
+    fn upgrade_keeps_magazines() {
+        let (mut world, index) = spawned(EntityType::G5);
+        empty_magazine(&mut world.entities[index], 0);
+
+        let boat = &mut world.entities[index];
+        boat.change_entity_type(EntityType::Momi, 0, &mut world.arena, false);
+        let boat = &world.entities[index];
+        let armaments = boat.armaments();
+        let extension = boat.extension();
+        for (armament, &rounds) in armaments.iter().zip(extension.magazines.iter()) {
+            if let Some(capacity) = armament.magazine {
+                // Half of the rounds were fired before upgrading.
+                assert_eq!(rounds, capacity / 2, "{:?}", armament.entity_type);
+            } else {
+                assert_eq!(rounds, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn upgrade_keeps_battery() {
+        let submarines: Vec<_> = EntityType::iter()
+            .filter(|t| t.data().kind == EntityKind::Boat)
+            .filter(|t| t.data().sub_kind == EntitySubKind::Submarine)
+            .collect();
+        let (from, to) = submarines
+            .iter()
+            .flat_map(|&from| submarines.iter().map(move |&to| (from, to)))
+            .find(|(from, to)| from.can_upgrade_to(*to, u32::MAX, false))
+            .unwrap();
+        let (mut world, index) = spawned(from);
+
+        // Exhaust the battery.
+        let extension = world.entities[index].extension_mut();
+        extension.set_depth(Depth::Deep);
+        extension.update_battery(false, 1.0, Ticks::from_whole_secs(1000));
+        assert_eq!(extension.battery(), 0.0);
+
+        let boat = &mut world.entities[index];
+        boat.change_entity_type(to, 0, &mut world.arena, false);
+
+        // Still exhausted, so may not dive.
+        let extension = world.entities[index].extension_mut();
+        assert_eq!(extension.battery(), 0.0);
+        extension.set_depth(Depth::Deep);
+        assert_eq!(extension.depth(), Depth::Surface);
+
+        // Partially recharge, and upgrade again (refitting works the same way).
+        extension.update_battery(true, 0.0, Ticks::from_whole_secs(10));
+        let battery = extension.battery();
+        assert!(battery > 0.0 && battery < 1.0);
+        let boat = &mut world.entities[index];
+        boat.change_entity_type(from, 0, &mut world.arena, false);
+        assert_eq!(world.entities[index].extension().battery(), battery);
+    }
 }
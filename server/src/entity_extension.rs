@@ -4,6 +4,7 @@
 use common::altitude::Altitude;
 use common::angle::Angle;
 use common::entity::*;
+use common::protocol::{Depth, SortieStatus};
 use common::ticks::Ticks;
 use common::util::make_mut_slice;
 use common_util::alloc::{arc_default_n, box_default_n};
@@ -13,12 +14,18 @@ use std::sync::Arc;
 /// Additional fields for certain entities (for now, boats). Stored separately for memory efficiency.
 #[derive(Debug)]
 pub struct EntityExtension {
-    // true means altitude target is Altitude::MIN false means Altitude::ZERO.
+    // Depth to dive to, if a submarine.
     // Used by Self::altitude_target().
     // Can't submerge right away to prevent dodging missiles.
-    submerge: bool,
+    depth: Depth,
     submerge_delay: Ticks,
 
+    /// Battery/air remaining, if a submarine. Drains below periscope depth, and recharges at or
+    /// above it (surfaced or snorkeling).
+    battery: Ticks,
+    /// Set when the battery runs out, forcing the submarine to surface until partially recharged.
+    battery_exhausted: bool,
+
     /// Whether the player *wants* active sensors. To tell if the player *has* active sensors, use
     /// Used by Self::is_active().
     /// Active stays on for a an extra duration to avoid rapid switching, which could induce flickering on other player's screens.
@@ -28,13 +35,52 @@ pub struct EntityExtension {
     /// Ticks of protection ticks remaining, zeroed if showing signs of aggression.
     spawn_protection_remaining: Ticks,
 
+    /// Index of the boat's loadout (see `EntityData::loadouts`).
+    loadout: u8,
+
+    /// Immune to damage, as set by an admin. Unlike spawn protection, survives respawning.
+    pub invulnerable: bool,
+
+    /// Whether the boat is docked at a port (see `Port::can_dock`).
+    docked: bool,
+    /// Accumulates while docked, and drains while not. Docked boats are immune to damage until it
+    /// reaches `DOCK_IMMUNITY`, to prevent camping in port.
+    harbor: Ticks,
+
     // 1 reload per armament, 0 = reloaded.
     // Not an arc because converted to a bitset with max len of 32.
     pub reloads: Box<[Ticks]>,
 
+    // 1 count of rounds remaining per armament, 0 for armaments with unlimited magazines.
+    pub magazines: Box<[u8]>,
+
+    /// Progress towards resupplying the next round of each magazine.
+    resupply: Ticks,
+
     // 1 angle per turret relative to boat.
     // Arc to save allocations
     pub turrets: Arc<[Angle]>,
+
+    /// Aircraft launched from the boat, until they are lost or rearmed.
+    pub air_wing: Vec<LaunchedAircraft>,
+}
+
+/// An aircraft launched from a boat.
+#[derive(Debug)]
+pub struct LaunchedAircraft {
+    /// Aircraft entity (no longer exists while rearming).
+    pub id: EntityId,
+    pub entity_type: EntityType,
+    /// Armament index it was launched from, or landed on.
+    pub armament: usize,
+    pub status: SortieStatus,
+    /// Flight time remaining, as of the last physics tick.
+    pub fuel: Ticks,
+}
+
+impl LaunchedAircraft {
+    /// How long it takes to rearm a landed aircraft.
+    pub const DECK_CYCLE: Ticks = Ticks::from_whole_secs(4);
 }
 
 impl EntityExtension {
@@ -46,9 +92,23 @@ impl EntityExtension {
     /// How long submerging is delayed.
     const SUBMERGE_DELAY: Ticks = Ticks::from_repr(8);
 
-    /// Allocates reloads and turrets, sized to a particular entity type.
-    /// It can also give spawn protection.
-    pub fn change_entity_type(&mut self, entity_type: EntityType) {
+    /// How long a full battery lasts below periscope depth, at minimal speed.
+    const BATTERY_CAPACITY: Ticks = Ticks::from_whole_secs(180);
+    /// How many times faster the battery recharges than it drains at minimal speed.
+    const BATTERY_RECHARGE_RATE: f32 = 3.0;
+    /// Fraction of the battery that must be recharged before diving again after exhausting it.
+    const BATTERY_RESERVE: f32 = 0.25;
+
+    /// How long a docked boat is immune to damage.
+    const DOCK_IMMUNITY: Ticks = Ticks::from_whole_secs(60);
+
+    /// How long it takes to resupply one round of each magazine.
+    pub const RESUPPLY_PERIOD: Ticks = Ticks::from_whole_secs(2);
+
+    /// Allocates reloads, magazines, and turrets, sized to a particular entity type and loadout.
+    /// It can also give spawn protection. The battery is kept, so that upgrading or refitting
+    /// doesn't recharge it.
+    pub fn change_entity_type(&mut self, entity_type: EntityType, loadout: u8) {
         // TODO clear active/submerge based on if boat supports them but probably doesn't matter.
 
         let data = entity_type.data();
@@ -57,25 +117,107 @@ impl EntityExtension {
         } else {
             Ticks::ZERO
         };
+        debug_assert!(data.is_valid_loadout(loadout));
+        self.loadout = loadout;
         self.reloads = box_default_n(data.armaments.len());
+        self.magazines = data
+            .loadout_armaments(loadout)
+            .iter()
+            .map(|a| a.magazine.unwrap_or(0))
+            .collect();
+        self.resupply = Ticks::ZERO;
         self.turrets = Arc::from_iter(data.turrets.iter().map(|t| t.angle));
+        self.air_wing.clear();
+    }
+
+    /// Returns the index of the boat's loadout.
+    pub fn loadout(&self) -> u8 {
+        self.loadout
     }
 
-    /// Returns the target altitude of the boat from submerge.
+    /// Returns the target altitude of the boat from depth.
     pub fn altitude_target(&self) -> Altitude {
-        if self.submerge && self.submerge_delay == Ticks::ZERO {
-            Altitude::MIN
+        if self.submerge_delay == Ticks::ZERO {
+            self.depth.altitude()
         } else {
             Altitude::ZERO
         }
     }
 
-    /// Sets submerge, possibly also setting deactivate_delay to an appropriate value.
-    pub fn set_submerge(&mut self, submerge: bool) {
-        if submerge && !self.submerge {
+    #[cfg(test)]
+    pub fn depth(&self) -> Depth {
+        self.depth
+    }
+
+    /// Sets depth, possibly also setting submerge_delay to an appropriate value. Depths that
+    /// drain the battery are refused while it is exhausted.
+    pub fn set_depth(&mut self, mut depth: Depth) {
+        if self.battery_exhausted && depth.drains_battery() {
+            depth = Depth::Surface;
+        }
+        if depth.is_submerged() && !self.depth.is_submerged() {
             self.submerge_delay = Self::SUBMERGE_DELAY;
         }
-        self.submerge = submerge;
+        self.depth = depth;
+    }
+
+    /// Returns the fraction of battery remaining, from 0 to 1.
+    pub fn battery(&self) -> f32 {
+        self.battery.to_secs() / Self::BATTERY_CAPACITY.to_secs()
+    }
+
+    /// Drains the battery (faster at higher speed fractions, from 0 to 1) unless snorkeling, in
+    /// which case it is recharged instead. Forces surfacing if the battery is exhausted.
+    pub fn update_battery(&mut self, snorkeling: bool, speed: f32, delta: Ticks) {
+        if snorkeling {
+            self.battery = self
+                .battery
+                .saturating_add(delta * Self::BATTERY_RECHARGE_RATE)
+                .min(Self::BATTERY_CAPACITY);
+            if self.battery_exhausted && self.battery() >= Self::BATTERY_RESERVE {
+                self.battery_exhausted = false;
+            }
+        } else {
+            self.battery = self
+                .battery
+                .saturating_sub(delta * (1.0 + 3.0 * speed.clamp(0.0, 1.0)));
+            if self.battery == Ticks::ZERO {
+                self.battery_exhausted = true;
+                self.depth = Depth::Surface;
+            }
+        }
+    }
+
+    /// Returns whether the boat is docked at a port.
+    pub fn is_docked(&self) -> bool {
+        self.docked
+    }
+
+    /// Returns the remaining immunity to damage, if docked.
+    pub fn dock_immunity(&self) -> Option<Ticks> {
+        self.docked
+            .then(|| Self::DOCK_IMMUNITY.saturating_sub(self.harbor))
+    }
+
+    /// Returns true if docked and still immune to damage.
+    pub fn is_dock_immune(&self) -> bool {
+        self.dock_immunity()
+            .map_or(false, |immunity| immunity != Ticks::ZERO)
+    }
+
+    /// Updates whether the boat is docked, accumulating or draining time spent in port.
+    pub fn update_dock(&mut self, docked: bool, delta: Ticks) {
+        self.docked = docked;
+        self.harbor = if docked {
+            self.harbor.saturating_add(delta).min(Self::DOCK_IMMUNITY)
+        } else {
+            self.harbor.saturating_sub(delta)
+        };
+    }
+
+    /// Ends any immunity from being docked (useful if showing signs of aggression).
+    pub fn clear_dock_immunity(&mut self) {
+        self.harbor = Self::DOCK_IMMUNITY;
     }
 
     /// Returns whether active sensors, or within deactivate sensor delay.
@@ -113,11 +255,29 @@ impl EntityExtension {
         self.spawn_protection_remaining = self.spawn_protection_remaining.saturating_sub(delta);
     }
 
+    /// Forgets landed aircraft that have finished rearming.
+    pub fn update_air_wing(&mut self) {
+        let reloads = &self.reloads;
+        self.air_wing
+            .retain(|a| a.status != SortieStatus::Rearming || reloads[a.armament] > Ticks::ZERO);
+    }
+
     /// reloads_mut returns a mutable reference to the reloads component of the extension.
     pub fn reloads_mut(&mut self) -> &mut [Ticks] {
         &mut self.reloads
     }
 
+    /// Accumulates resupply time, returning how many rounds each magazine should gain.
+    pub fn resupply(&mut self, amount: Ticks) -> u8 {
+        self.resupply = self.resupply.saturating_add(amount);
+        let mut rounds = 0;
+        while self.resupply >= Self::RESUPPLY_PERIOD {
+            self.resupply -= Self::RESUPPLY_PERIOD;
+            rounds += 1;
+        }
+        rounds
+    }
+
     /// reloads_mut returns a mutable reference to the turret angles component of the extension.
     pub fn turrets_mut(&mut self) -> &mut [Angle] {
         make_mut_slice(&mut self.turrets)
@@ -129,13 +289,82 @@ impl Default for EntityExtension {
     /// Once a boat is spawned/upgraded change_entity_type must be called.
     fn default() -> Self {
         Self {
-            submerge: false,
+            depth: Depth::Surface,
             submerge_delay: Ticks::ZERO,
+            battery: Self::BATTERY_CAPACITY,
+            battery_exhausted: false,
             active: true,
             deactivate_delay: Ticks::ZERO,
             spawn_protection_remaining: Self::SPAWN_PROTECTION_INITIAL,
+            loadout: 0,
+            invulnerable: false,
+            docked: false,
+            harbor: Ticks::ZERO,
             reloads: box_default_n(0),
+            magazines: box_default_n(0),
+            resupply: Ticks::ZERO,
             turrets: arc_default_n(0),
+            air_wing: Vec::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity_extension::EntityExtension;
+    use common::protocol::Depth;
+    use common::ticks::Ticks;
+
+    #[test]
+    fn battery() {
+        let second = Ticks::from_whole_secs(1);
+        let drain = |speed: f32| {
+            let mut extension = EntityExtension::default();
+            extension.update_battery(false, speed, second);
+            1.0 - extension.battery()
+        };
+        assert!(drain(0.0) > 0.0);
+        assert!(drain(1.0) > drain(0.0));
+
+        // Running out forces surfacing, and refuses diving again (except to snorkel).
+        let mut extension = EntityExtension::default();
+        extension.set_depth(Depth::Deep);
+        extension.update_battery(false, 1.0, EntityExtension::BATTERY_CAPACITY);
+        assert_eq!(extension.battery(), 0.0);
+        assert_eq!(extension.depth(), Depth::Surface);
+        extension.set_depth(Depth::Periscope);
+        assert_eq!(extension.depth(), Depth::Periscope);
+
+        // Snorkeling recharges it, faster than it drains, until the reserve allows diving.
+        extension.update_battery(true, 0.0, second);
+        assert!(extension.battery() > drain(0.0));
+        while extension.battery() < EntityExtension::BATTERY_RESERVE {
+            extension.set_depth(Depth::Deep);
+            assert_eq!(extension.depth(), Depth::Surface);
+            extension.update_battery(true, 0.0, second);
         }
+        extension.set_depth(Depth::Deep);
+        assert_eq!(extension.depth(), Depth::Deep);
+
+        // Up to capacity.
+        extension.update_battery(true, 0.0, EntityExtension::BATTERY_CAPACITY);
+        assert_eq!(extension.battery(), 1.0);
+    }
+
+    #[test]
+    fn resupply() {
+        let mut extension = EntityExtension::default();
+        let period = EntityExtension::RESUPPLY_PERIOD;
+
+        assert_eq!(extension.resupply(period - Ticks::ONE), 0);
+        assert_eq!(extension.resupply(Ticks::ONE), 1);
+        assert_eq!(extension.resupply(Ticks::ZERO), 0);
+
+        // Progress carries over between calls.
+        assert_eq!(
+            extension.resupply(period.saturating_add(period).saturating_add(Ticks::ONE)),
+            2
+        );
+        assert_eq!(extension.resupply(period - Ticks::ONE), 1);
     }
 }
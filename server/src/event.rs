@@ -0,0 +1,145 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::server::Server;
+use common::entity::EntityId;
+use common::protocol::WorldEvent;
+use common::ticks::Ticks;
+use core_protocol::name::PlayerAlias;
+use game_server::player::PlayerTuple;
+use glam::Vec2;
+use std::sync::Arc;
+
+/// Scheduled world events (see `World::update_events`), at most one of each kind at a time.
+pub struct Events {
+    /// Time until another event may start.
+    pub cooldown: Ticks,
+    pub supply_drop: Option<SupplyDrop>,
+    pub bounty: Option<Bounty>,
+    pub storm: Option<Storm>,
+    /// Messages to broadcast to all players, by `Server::tick`.
+    pub announcements: Vec<String>,
+}
+
+/// Crates and coins spawned at a random point.
+pub struct SupplyDrop {
+    pub position: Vec2,
+    /// Crates and coins that have yet to be collected.
+    pub loot: Vec<EntityId>,
+}
+
+/// Extra score for sinking a particular boat.
+pub struct Bounty {
+    pub player: Arc<PlayerTuple<Server>>,
+    /// The boat the bounty was placed on, so it doesn't carry over to the next one.
+    pub entity_id: EntityId,
+    /// Last known alias and reward (kept up to date by `World::update_events`).
+    pub alias: PlayerAlias,
+    pub reward: u32,
+    /// Last reported position, which is approximate, or None while the boat is submerged.
+    pub position: Option<Vec2>,
+    /// Time until the position is next reported.
+    pub report: Ticks,
+    pub remaining: Ticks,
+}
+
+/// Slows boats and downs aircraft while roaming the world.
+#[derive(Copy, Clone, Debug)]
+pub struct Storm {
+    pub position: Vec2,
+    /// Meters per second.
+    pub velocity: Vec2,
+    pub radius: f32,
+    pub remaining: Ticks,
+}
+
+impl Default for Events {
+    fn default() -> Self {
+        Self {
+            // Give players time to join before the first event.
+            cooldown: Ticks::from_whole_secs(60),
+            supply_drop: None,
+            bounty: None,
+            storm: None,
+            announcements: Vec::new(),
+        }
+    }
+}
+
+impl Events {
+    /// Returns the events that are in progress, for the HUD.
+    pub fn to_protocol(&self) -> Vec<WorldEvent> {
+        let supply_drop = self.supply_drop.as_ref().map(|s| WorldEvent::SupplyDrop {
+            position: s.position,
+        });
+        let bounty = self.bounty.as_ref().and_then(|b| {
+            Some(WorldEvent::Bounty {
+                alias: b.alias,
+                position: b.position?,
+                reward: b.reward,
+            })
+        });
+        let storm = self.storm.map(|s| WorldEvent::Storm {
+            position: s.position,
+            radius: s.radius,
+        });
+        supply_drop.into_iter().chain(bounty).chain(storm).collect()
+    }
+}
+
+impl Bounty {
+    /// How often the position is reported.
+    pub const REPORT_PERIOD: Ticks = Ticks::from_whole_secs(15);
+    /// Reported positions are rounded to the center of a square cell of this size.
+    const REPORT_GRID: f32 = 500.0;
+
+    /// Returns the position to report for a boat at a position.
+    pub fn report_position(position: Vec2) -> Vec2 {
+        ((position / Self::REPORT_GRID).floor() + 0.5) * Self::REPORT_GRID
+    }
+
+    /// Score paid, on top of the usual kill score, to whoever sinks a boat with a bounty on it.
+    pub fn reward(score: u32) -> u32 {
+        // Not reduced for high level killers, unlike an ordinary kill.
+        common::util::kill_score(score, 0)
+    }
+}
+
+impl Storm {
+    /// Returns true if the position is within the storm.
+    pub fn contains(&self, position: Vec2) -> bool {
+        self.position.distance_squared(position) <= self.radius.powi(2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::event::{Events, Storm};
+    use common::protocol::WorldEvent;
+    use common::ticks::Ticks;
+    use glam::Vec2;
+
+    #[test]
+    fn storm() {
+        let mut events = Events::default();
+        assert!(events.to_protocol().is_empty());
+
+        let storm = Storm {
+            position: Vec2::new(100.0, 0.0),
+            velocity: Vec2::ZERO,
+            radius: 50.0,
+            remaining: Ticks::ONE,
+        };
+        assert!(storm.contains(Vec2::new(140.0, 0.0)));
+        assert!(!storm.contains(Vec2::ZERO));
+
+        events.storm = Some(storm);
+        assert_eq!(
+            events.to_protocol(),
+            vec![WorldEvent::Storm {
+                position: storm.position,
+                radius: storm.radius
+            }]
+        );
+    }
+}
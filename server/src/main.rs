@@ -8,6 +8,7 @@
 #![feature(hash_drain_filter)]
 #![feature(type_alias_impl_trait)]
 #![feature(generic_associated_types)]
+#![cfg_attr(test, feature(test))]
 
 //! The game server has authority over all game logic. Clients are served the client, which connects
 //! via websocket.
@@ -21,18 +22,26 @@ mod collision;
 mod complete_ref;
 mod contact_ref;
 mod entities;
+#[cfg(test)]
+mod entities_bench;
 mod entity;
 mod entity_extension;
+mod event;
 mod noise;
+mod npc;
 mod player;
 mod protocol;
 mod server;
+mod tick_profiler;
 mod world;
+mod world_event;
 mod world_inbound;
 mod world_mutation;
+mod world_npc;
 mod world_outbound;
 mod world_physics;
 mod world_physics_radius;
+mod world_port;
 mod world_spawn;
 #[cfg(test)]
 mod world_test;
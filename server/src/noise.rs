@@ -110,7 +110,9 @@ mod tests {
     use glam::Vec2;
     use image::{Rgb, RgbImage};
 
+    #[allow(dead_code)]
     type Color = [u8; 3];
+    #[allow(dead_code)]
     const COLORS: [Color; 4] = [
         [0, 50, 115],    // Deep water
         [0, 75, 130],    // Shallow water
@@ -118,6 +120,7 @@ mod tests {
         [90, 180, 30],   // Grass
     ];
 
+    #[allow(dead_code)]
     fn lerp(a: Color, b: Color, x: f32) -> Color {
         [
             common_util::range::lerp(a[0] as f32, b[0] as f32, x) as u8,
@@ -164,6 +167,7 @@ mod tests {
             }
         }
 
+        std::fs::create_dir_all("terrain_test").unwrap();
         image
             .save(&format!("terrain_test/{}.png", unsafe { SEED }))
             .unwrap();
@@ -0,0 +1,115 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::player::Status;
+use crate::server::Server;
+use crate::world::Hit;
+use common::ticks::Ticks;
+use core_protocol::id::PlayerId;
+use game_server::player::PlayerTuple;
+use glam::Vec2;
+use std::sync::Arc;
+
+/// Non-player boats, driven by scripts (see `World::update_npcs`) instead of the `Bot` player
+/// pipeline. Each NPC boat has its own player, which is never added to the `PlayerRepo`.
+#[derive(Default)]
+pub struct Npcs {
+    pub groups: Vec<NpcGroup>,
+    /// Time until another group may spawn.
+    pub spawn_cooldown: Ticks,
+    /// Used to allocate player ids.
+    next_number: usize,
+}
+
+/// Boats that move and fight together.
+pub struct NpcGroup {
+    pub kind: NpcGroupKind,
+    /// The first member that is still alive leads the group.
+    pub members: Vec<Arc<PlayerTuple<Server>>>,
+    /// How long the group has existed.
+    pub age: Ticks,
+    /// Players that damaged a member, and are therefore engaged by escorts.
+    pub attackers: Vec<PlayerId>,
+}
+
+#[derive(Copy, Clone, Debug)]
+pub enum NpcGroupKind {
+    /// A tanker and its escorts, sailing between ports.
+    Convoy { destination: Vec2 },
+    /// Pirates that hunt weak players, wandering around their home while not hunting.
+    Patrol { home: Vec2, waypoint: Vec2 },
+}
+
+impl Npcs {
+    /// NPC player ids are allocated from the end of the bot id range, so they are treated as bots
+    /// but don't collide with the bots controlled by the game server.
+    const FIRST_NUMBER: usize = 1 << 21;
+    const NUMBERS: usize = (PlayerId::RANDOM_MASK as usize - 1) - Self::FIRST_NUMBER;
+
+    /// Allocates a player id for a new NPC boat.
+    pub fn next_player_id(&mut self) -> PlayerId {
+        let number = Self::FIRST_NUMBER + self.next_number;
+        self.next_number = (self.next_number + 1) % Self::NUMBERS;
+        PlayerId::nth_bot(number).expect("NPC numbers are within the bot range")
+    }
+
+    /// Returns true if the player id belongs to an NPC boat.
+    pub fn is_npc(player_id: PlayerId) -> bool {
+        player_id
+            .bot_number()
+            .map_or(false, |n| n >= Self::FIRST_NUMBER)
+    }
+
+    /// Counts the groups of a particular kind.
+    pub fn count(&self, f: impl Fn(&NpcGroupKind) -> bool) -> usize {
+        self.groups.iter().filter(|g| f(&g.kind)).count()
+    }
+}
+
+impl NpcGroup {
+    /// Forgets members whose boats have sunk, returning true if any members remain.
+    pub fn retain_alive(&mut self) -> bool {
+        self.members
+            .retain(|m| matches!(m.borrow_player().data.status, Status::Alive { .. }));
+        !self.members.is_empty()
+    }
+
+    /// Remembers the players that damaged a member during the last tick.
+    pub fn record_attackers(&mut self, hits: &[Hit]) {
+        for hit in hits {
+            if !self.attackers.contains(&hit.attacker)
+                && self
+                    .members
+                    .iter()
+                    .any(|m| m.borrow_player().player_id == hit.victim)
+            {
+                self.attackers.push(hit.attacker);
+            }
+        }
+    }
+
+    /// Makes all members leave the game, removing their boats (without loot) next tick.
+    pub fn retire(&mut self) {
+        for member in self.members.drain(..) {
+            member.borrow_player_mut().data.flags.left_game = true;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::npc::Npcs;
+    use core_protocol::id::PlayerId;
+
+    #[test]
+    fn player_ids() {
+        let mut npcs = Npcs::default();
+        for _ in 0..10 {
+            let player_id = npcs.next_player_id();
+            assert!(player_id.is_bot());
+            assert!(Npcs::is_npc(player_id));
+        }
+        assert!(!Npcs::is_npc(PlayerId::nth_bot(0).unwrap()));
+        assert!(!Npcs::is_npc(PlayerId::nth_bot(1000).unwrap()));
+    }
+}
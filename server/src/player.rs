@@ -3,7 +3,7 @@
 
 use crate::entities::*;
 use common::death_reason::DeathReason;
-use common::protocol::Hint;
+use common::protocol::{Hint, SpectateTarget};
 use glam::Vec2;
 use std::fmt::Debug;
 use std::time::Instant;
@@ -67,25 +67,12 @@ impl Status {
 
     /// Returns entity index if alive, otherwise none.
     /// Doesn't consider `Flags::left_game`.
-    #[cfg(test)]
     pub fn get_entity_index(&self) -> Option<EntityIndex> {
         match self {
             Self::Alive { entity_index, .. } => Some(*entity_index),
             _ => None,
         }
     }
-
-    /// set_entity_index sets the entity index of an Alive status or panics if the status is not alive.
-    pub fn set_entity_index(&mut self, new_index: EntityIndex) {
-        if let Self::Alive { entity_index, .. } = self {
-            *entity_index = new_index;
-        } else {
-            panic!(
-                "set_entity_index() called on a non-alive status of {:?}",
-                self
-            );
-        }
-    }
 }
 
 /// Player is the owner of a boat, either a real person or a bot.
@@ -99,6 +86,11 @@ pub struct Player {
     pub hint: Hint,
     /// Current status e.g. Alive, Dead, or Spawning.
     pub status: Status,
+    /// What the player is watching, while not alive.
+    pub spectate: Option<SpectateTarget>,
+    /// When the player last watched beyond their own team while spectating, which delays spawning
+    /// (see `SpectateTarget::SPAWN_DELAY`).
+    pub scouted: Option<Instant>,
 }
 
 impl Default for Player {
@@ -108,6 +100,8 @@ impl Default for Player {
             flags: Flags::default(),
             hint: Hint::default(),
             status: Status::Spawning,
+            spectate: None,
+            scouted: None,
         }
     }
 }
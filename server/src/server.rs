@@ -2,28 +2,38 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use crate::bot::*;
-use crate::entity_extension::EntityExtension;
+use crate::entities::EntityIndex;
+use crate::entity::Entity;
+use crate::entity_extension::{EntityExtension, LaunchedAircraft};
 use crate::player::*;
 use crate::protocol::*;
+use crate::tick_profiler::{TickPhase, TickProfiler};
 use crate::world::World;
-use common::entity::EntityType;
-use common::protocol::{Command, Update};
+use crate::world_outbound::watches_beyond_team;
+use common::death_reason::DeathReason;
+use common::entity::{EntityKind, EntityType};
+use common::protocol::{Command, SortieStatus, Update};
 use common::terrain::ChunkSet;
 use common::ticks::Ticks;
 use common::util::level_to_score;
+use common::velocity::Velocity;
+use core_protocol::dto::AdminEntityDto;
 use core_protocol::id::*;
 use game_server::context::Context;
 use game_server::game_service::GameArenaService;
 use game_server::player::{PlayerRepo, PlayerTuple};
+use game_server::prometheus::PrometheusWriter;
+use glam::Vec2;
 use log::{error, warn};
 use std::cell::UnsafeCell;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// A game server.
 pub struct Server {
     pub world: World,
     pub counter: Ticks,
+    pub profiler: TickProfiler,
 }
 
 /// Stores a player, and metadata related to it. Data stored here may only be accessed when processing,
@@ -59,11 +69,15 @@ impl GameArenaService for Server {
 
     /// new returns a game server with the specified parameters.
     fn new(min_players: usize) -> Self {
+        let mut world = World::new(World::target_radius(
+            min_players as f32 * EntityType::FairmileD.data().visual_area(),
+        ));
+        world.generate_ports();
+
         Self {
-            world: World::new(World::target_radius(
-                min_players as f32 * EntityType::FairmileD.data().visual_area(),
-            )),
+            world,
             counter: Ticks::ZERO,
+            profiler: TickProfiler::default(),
         }
     }
 
@@ -151,11 +165,11 @@ impl GameArenaService for Server {
         &self,
         player: &Arc<PlayerTuple<Self>>,
         client_data: &mut Self::ClientData,
-        _players: &PlayerRepo<Server>,
+        players: &PlayerRepo<Server>,
     ) -> Option<Self::GameUpdate> {
         Some(
             self.world
-                .get_player_complete(player)
+                .get_player_complete(player, players)
                 .into_update(self.counter, &mut client_data.loaded_chunks),
         )
     }
@@ -169,10 +183,36 @@ impl GameArenaService for Server {
     fn tick(&mut self, context: &mut Context<Self>) {
         self.counter = self.counter.next();
 
-        self.world.update(Ticks::ONE);
+        self.world.update(Ticks::ONE, &mut self.profiler);
+
+        // Bounties are placed on the top player of the liveboard, which excludes bots.
+        let top_player = context
+            .players
+            .iter()
+            .filter(|p| {
+                let player = p.borrow_player();
+                !player.is_bot() && !player.data.flags.left_game && player.data.status.is_alive()
+            })
+            .max_by_key(|p| p.borrow_player().score);
+        self.world.update_events(Ticks::ONE, top_player);
+        for text in self.world.events.announcements.drain(..) {
+            context.announce(text);
+        }
+
+        // Spectators that watch beyond their team can't spawn until a while after.
+        for player in context.players.iter() {
+            let mut player = player.borrow_player_mut();
+            if let Some(target) = player.data.spectate {
+                if watches_beyond_team(target, player.team_id(), &context.players) {
+                    player.data.scouted = Some(Instant::now());
+                }
+            }
+        }
 
         // Needs to be called before clients receive updates, but after World::update.
-        self.world.terrain.pre_update();
+        let terrain = &mut self.world.terrain;
+        self.profiler
+            .time(TickPhase::TerrainPreUpdate, || terrain.pre_update());
 
         if self.counter.every(Ticks::from_whole_secs(60)) {
             use std::collections::{BTreeMap, HashMap};
@@ -210,7 +250,7 @@ impl GameArenaService for Server {
                     .create(true)
                     .read(true)
                     .write(true)
-                    .open(&*"playtime.json")
+                    .open("playtime.json")
                     .and_then(move |mut file| {
                         let mut buf = Vec::new();
                         file.read_to_end(&mut buf)?;
@@ -242,10 +282,198 @@ impl GameArenaService for Server {
                 }
             });
         }
+
+        self.profiler.start_outbound();
     }
 
     fn post_update(&mut self, _context: &mut Context<Self>) {
+        self.profiler.finish_outbound(&self.world);
+
         // Needs to be after clients receive updates.
         self.world.terrain.post_update();
     }
+
+    fn take_slow_tick_report(&mut self) -> Option<String> {
+        self.profiler.take_slow_tick_report()
+    }
+
+    fn prometheus(&self, writer: &mut PrometheusWriter) {
+        writer.gauge(
+            "mk48_world_radius",
+            "Radius of the world, in meters.",
+            self.world.radius,
+        );
+        writer.gauge(
+            "mk48_entities",
+            "Total number of entities.",
+            self.world.arena.total() as f64,
+        );
+        writer.gauge_labeled(
+            "mk48_entities_by_kind",
+            "Number of entities of each kind.",
+            "kind",
+            [
+                EntityKind::Aircraft,
+                EntityKind::Boat,
+                EntityKind::Collectible,
+                EntityKind::Decoy,
+                EntityKind::Obstacle,
+                EntityKind::Turret,
+                EntityKind::Weapon,
+            ]
+            .map(|kind| {
+                (
+                    format!("{:?}", kind),
+                    self.world.arena.count_kind(kind) as f64,
+                )
+            }),
+        );
+        self.profiler.prometheus(writer);
+    }
+
+    fn admin_entities(
+        &self,
+        x: f32,
+        y: f32,
+        radius: f32,
+    ) -> Result<Vec<AdminEntityDto>, &'static str> {
+        // Keep the response a reasonable size.
+        const MAX_ENTITIES: usize = 1000;
+
+        Ok(self
+            .world
+            .entities
+            .iter_radius(Vec2::new(x, y), radius)
+            .take(MAX_ENTITIES)
+            .map(|(_, entity)| {
+                let data = entity.data();
+                let (player_id, alias) = if let Some(player) = entity.player.as_ref() {
+                    let player = player.borrow_player();
+                    (Some(player.player_id), Some(player.alias()))
+                } else {
+                    (None, None)
+                };
+                AdminEntityDto {
+                    entity_id: entity.id.get(),
+                    entity_type: String::from(entity.entity_type.as_str()),
+                    x: entity.transform.position.x,
+                    y: entity.transform.position.y,
+                    damage: (data.kind == EntityKind::Boat)
+                        .then(|| entity.ticks.to_secs() / data.max_health().to_secs()),
+                    player_id,
+                    alias,
+                }
+            })
+            .collect())
+    }
+
+    fn admin_teleport(
+        &mut self,
+        player_tuple: &Arc<PlayerTuple<Self>>,
+        x: f32,
+        y: f32,
+    ) -> Result<(), &'static str> {
+        let position = Vec2::new(x, y);
+        if position.length() > self.world.radius {
+            return Err("outside world border");
+        }
+        let index = Self::boat_index(player_tuple)?;
+        let boat = &mut self.world.entities[index];
+        boat.transform.position = position;
+        boat.transform.velocity = Velocity::ZERO;
+        boat.guidance.velocity_target = Velocity::ZERO;
+        self.world.entities.relocate(index);
+        Ok(())
+    }
+
+    fn admin_kill(&mut self, player_tuple: &Arc<PlayerTuple<Self>>) -> Result<(), &'static str> {
+        let index = Self::boat_index(player_tuple)?;
+        self.world.remove(index, DeathReason::Admin);
+        Ok(())
+    }
+
+    fn admin_spawn(
+        &mut self,
+        entity_type: &str,
+        x: f32,
+        y: f32,
+        owner: Option<&Arc<PlayerTuple<Self>>>,
+    ) -> Result<(), &'static str> {
+        let entity_type = EntityType::from_str(entity_type).ok_or("unknown entity type")?;
+        let position = Vec2::new(x, y);
+        if position.length() > self.world.radius {
+            return Err("outside world border");
+        }
+
+        let data = entity_type.data();
+        // Aircraft launch from (and must return to) a ready armament of their owner's boat.
+        let mut launch = None;
+        match data.kind {
+            EntityKind::Boat => {
+                let owner = owner.ok_or("boats must be owned by a player")?;
+                let player = owner.borrow_player();
+                if player.data.flags.left_game || player.data.status.is_alive() {
+                    return Err("owner must not be alive");
+                }
+            }
+            EntityKind::Aircraft => {
+                let boat_index = Self::boat_index(owner.ok_or("must be owned by a player")?)?;
+                let boat = &self.world.entities[boat_index];
+                let armament = (0..boat.armaments().len())
+                    .find(|&i| {
+                        boat.armaments()[i].entity_type == entity_type && boat.is_armament_ready(i)
+                    })
+                    .ok_or("owner's boat has no ready armament of that type")?;
+                launch = Some((boat_index, armament));
+            }
+            EntityKind::Decoy | EntityKind::Weapon => {
+                // Armaments expect their owner to have a boat.
+                Self::boat_index(owner.ok_or("must be owned by a player")?)?;
+            }
+            EntityKind::Turret => return Err("turrets are part of boats"),
+            EntityKind::Collectible | EntityKind::Obstacle => {}
+        }
+
+        let mut entity = Entity::new(entity_type, owner.map(Arc::clone));
+        entity.transform.position = position;
+        let id = self
+            .world
+            .spawn_here_or_nearby(entity, data.radius, None)
+            .ok_or("failed to find enough space to spawn")?;
+
+        if let Some((boat_index, armament)) = launch {
+            // Spawning doesn't affect the indices of existing entities.
+            let boat = &mut self.world.entities[boat_index];
+            boat.consume_armament(armament);
+            boat.extension_mut().air_wing.push(LaunchedAircraft {
+                id,
+                entity_type,
+                armament,
+                status: SortieStatus::Airborne,
+                fuel: data.lifespan,
+            });
+        }
+        Ok(())
+    }
+
+    fn admin_set_invulnerable(
+        &mut self,
+        player_tuple: &Arc<PlayerTuple<Self>>,
+        invulnerable: bool,
+    ) -> Result<(), &'static str> {
+        let index = Self::boat_index(player_tuple)?;
+        self.world.entities[index].extension_mut().invulnerable = invulnerable;
+        Ok(())
+    }
+}
+
+impl Server {
+    /// Returns the index of a player's boat, if they have one.
+    fn boat_index(player_tuple: &Arc<PlayerTuple<Self>>) -> Result<EntityIndex, &'static str> {
+        let player = player_tuple.borrow_player();
+        match player.data.status {
+            Status::Alive { entity_index, .. } if !player.data.flags.left_game => Ok(entity_index),
+            _ => Err("player not alive"),
+        }
+    }
 }
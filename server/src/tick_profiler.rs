@@ -0,0 +1,237 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::entities::Entities;
+use crate::world::World;
+use common::entity::EntityKind;
+use common::ticks::Ticks;
+use game_server::prometheus::PrometheusWriter;
+use log::warn;
+use std::fmt::Write;
+use std::time::{Duration, Instant};
+
+/// A phase of a server tick, timed separately.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum TickPhase {
+    SpawnStatics,
+    Npcs,
+    Physics,
+    PhysicsRadius,
+    TerrainPreUpdate,
+    /// Serializing and sending updates to clients and bots (everything between `Server::tick`
+    /// and `Server::post_update`).
+    Outbound,
+}
+
+impl TickPhase {
+    pub const ALL: [Self; 6] = [
+        Self::SpawnStatics,
+        Self::Npcs,
+        Self::Physics,
+        Self::PhysicsRadius,
+        Self::TerrainPreUpdate,
+        Self::Outbound,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::SpawnStatics => "spawn_statics",
+            Self::Npcs => "npcs",
+            Self::Physics => "physics",
+            Self::PhysicsRadius => "physics_radius",
+            Self::TerrainPreUpdate => "terrain_pre_update",
+            Self::Outbound => "outbound",
+        }
+    }
+}
+
+/// Upper bounds of histogram buckets, in seconds.
+const BUCKET_BOUNDS: [f64; 9] = [0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25];
+
+/// A histogram of durations, with fixed buckets.
+#[derive(Default)]
+struct PhaseHistogram {
+    /// Non-cumulative count per bucket, the last of which is unbounded.
+    buckets: [u64; BUCKET_BOUNDS.len() + 1],
+    /// Sum of all samples, in seconds.
+    sum: f64,
+}
+
+impl PhaseHistogram {
+    fn push(&mut self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        let bucket = BUCKET_BOUNDS
+            .iter()
+            .position(|&bound| seconds <= bound)
+            .unwrap_or(BUCKET_BOUNDS.len());
+        self.buckets[bucket] += 1;
+        self.sum += seconds;
+    }
+}
+
+/// Times the phases of each tick, and captures a report whenever a tick runs over budget.
+#[derive(Default)]
+pub struct TickProfiler {
+    /// Durations of the phases of the current (or most recent) tick.
+    current: [Duration; TickPhase::ALL.len()],
+    histograms: [PhaseHistogram; TickPhase::ALL.len()],
+    /// When the outbound phase started.
+    outbound_start: Option<Instant>,
+    /// Total over budget ticks.
+    slow_ticks: u64,
+    /// Report for the slowest over budget tick since last taken.
+    slow_tick_report: Option<(Duration, String)>,
+    /// When a slow tick was last logged.
+    last_warning: Option<Instant>,
+    /// Slow ticks not logged since `last_warning`, due to rate limiting.
+    suppressed_warnings: u64,
+}
+
+impl TickProfiler {
+    /// Ticks that take longer than this (excluding time spent waiting for the next tick) are
+    /// reported.
+    pub const BUDGET: Duration = Duration::from_millis((Ticks::PERIOD_SECS * 500.0) as u64);
+    /// Minimum time between logging slow ticks, to avoid flooding the log under sustained load.
+    const WARNING_INTERVAL: Duration = Duration::from_secs(10);
+
+    /// Calls `f`, recording how long it took as `phase`.
+    pub fn time<R>(&mut self, phase: TickPhase, f: impl FnOnce() -> R) -> R {
+        let start = Instant::now();
+        let ret = f();
+        self.record(phase, start.elapsed());
+        ret
+    }
+
+    fn record(&mut self, phase: TickPhase, duration: Duration) {
+        let i = phase as usize;
+        self.current[i] = duration;
+        self.histograms[i].push(duration);
+    }
+
+    /// Call at the end of `Server::tick`, when clients are about to receive updates.
+    pub fn start_outbound(&mut self) {
+        self.outbound_start = Some(Instant::now());
+    }
+
+    /// Call at the start of `Server::post_update`, after clients have received updates. Completes
+    /// the tick, checking it against the budget.
+    pub fn finish_outbound(&mut self, world: &World) {
+        if let Some(start) = self.outbound_start.take() {
+            self.record(TickPhase::Outbound, start.elapsed());
+        }
+
+        let total: Duration = self.current.iter().sum();
+        if total > Self::BUDGET {
+            self.slow_ticks += 1;
+            let report = self.report(total, world);
+
+            let now = Instant::now();
+            if self
+                .last_warning
+                .map(|last| now - last >= Self::WARNING_INTERVAL)
+                .unwrap_or(true)
+            {
+                if self.suppressed_warnings > 0 {
+                    warn!(
+                        "{} ({} slow ticks not logged since last report)",
+                        report, self.suppressed_warnings
+                    );
+                } else {
+                    warn!("{}", report);
+                }
+                self.last_warning = Some(now);
+                self.suppressed_warnings = 0;
+            } else {
+                self.suppressed_warnings += 1;
+            }
+
+            if self
+                .slow_tick_report
+                .as_ref()
+                .map(|(worst, _)| total > *worst)
+                .unwrap_or(true)
+            {
+                self.slow_tick_report = Some((total, report));
+            }
+        }
+    }
+
+    /// Takes the report of the slowest over budget tick since the last call, if any.
+    pub fn take_slow_tick_report(&mut self) -> Option<String> {
+        self.slow_tick_report.take().map(|(_, report)| report)
+    }
+
+    /// Describes the current tick, for diagnosing why it was slow.
+    fn report(&self, total: Duration, world: &World) -> String {
+        let (slowest, slowest_duration) = TickPhase::ALL
+            .iter()
+            .copied()
+            .zip(self.current)
+            .max_by_key(|&(_, duration)| duration)
+            .unwrap();
+
+        let mut report = format!(
+            "slow tick took {:.1}ms (budget {:.1}ms), slowest phase was {} at {:.1}ms\n",
+            total.as_secs_f32() * 1000.0,
+            Self::BUDGET.as_secs_f32() * 1000.0,
+            slowest.as_str(),
+            slowest_duration.as_secs_f32() * 1000.0
+        );
+
+        report.push_str("phases:");
+        for (phase, duration) in TickPhase::ALL.iter().zip(self.current) {
+            let _ = write!(
+                report,
+                " {}={:.1}ms",
+                phase.as_str(),
+                duration.as_secs_f32() * 1000.0
+            );
+        }
+
+        let _ = write!(
+            report,
+            "\nentities: {} total, {} boats, {} weapons, {} aircraft",
+            world.arena.total(),
+            world.arena.count_kind(EntityKind::Boat),
+            world.arena.count_kind(EntityKind::Weapon),
+            world.arena.count_kind(EntityKind::Aircraft),
+        );
+
+        Self::write_cells(&mut report, &world.entities);
+        report
+    }
+
+    /// Appends the most populated cells of the spatial index to the report.
+    fn write_cells(report: &mut String, entities: &Entities) {
+        const MAX_CELLS: usize = 16;
+
+        let mut cells: Vec<_> = entities.cell_counts().collect();
+        cells.sort_unstable_by_key(|&(_, count)| std::cmp::Reverse(count));
+
+        let _ = write!(report, "\ncells: {} occupied", cells.len());
+        for (center, count) in cells.into_iter().take(MAX_CELLS) {
+            let _ = write!(report, "\n  ({:.0}, {:.0}): {}", center.x, center.y, count);
+        }
+    }
+
+    /// Writes the phase histograms and slow tick count.
+    pub fn prometheus(&self, writer: &mut PrometheusWriter) {
+        writer.histogram_labeled(
+            "mk48_tick_phase_seconds",
+            "Duration of each phase of the tick.",
+            "phase",
+            &BUCKET_BOUNDS,
+            TickPhase::ALL
+                .iter()
+                .zip(&self.histograms)
+                .map(|(phase, histogram)| {
+                    (phase.as_str(), histogram.buckets.as_slice(), histogram.sum)
+                }),
+        );
+        writer.counter(
+            "mk48_slow_ticks_total",
+            "Ticks that exceeded the budget.",
+            self.slow_ticks as f64,
+        );
+    }
+}
@@ -4,12 +4,20 @@
 use crate::arena::Arena;
 use crate::entities::{Entities, EntityIndex};
 use crate::entity::Entity;
+use crate::event::Events;
 use crate::noise::noise_generator;
+use crate::npc::Npcs;
+use crate::tick_profiler::{TickPhase, TickProfiler};
 use crate::world_mutation::Mutation;
 use common::death_reason::DeathReason;
-use common::entity::{EntityKind, EntityType};
+use common::entity::{EntityId, EntityKind, EntityType};
+use common::protocol::{Kill, Port};
 use common::terrain::Terrain;
 use common::ticks::Ticks;
+use core_protocol::id::PlayerId;
+use core_protocol::name::PlayerAlias;
+use glam::Vec2;
+use std::collections::HashMap;
 
 /// A game world of variable radius, consisting of entities and a terrain.
 pub struct World {
@@ -17,6 +25,30 @@ pub struct World {
     pub entities: Entities,
     pub terrain: Terrain,
     pub radius: f32,
+    /// Boats sunk by players during the last update, for the kill feed.
+    pub kills: Vec<Kill>,
+    /// Damage between players' boats during the last update, for combat logs.
+    pub hits: Vec<Hit>,
+    /// Convoys and pirates, which aren't controlled by players.
+    pub npcs: Npcs,
+    /// Supply drops, bounties and storms.
+    pub events: Events,
+    /// Safe harbors along the coast (see `World::generate_ports`).
+    pub ports: Vec<Port>,
+    /// Landing pad position, and whether it is returning, of each airborne aircraft in an air
+    /// wing, as of the last physics update.
+    pub landing_pads: HashMap<EntityId, (Vec2, bool)>,
+}
+
+/// Damage dealt by one player's boat to another's.
+pub struct Hit {
+    pub attacker: PlayerId,
+    pub attacker_alias: PlayerAlias,
+    pub victim: PlayerId,
+    pub victim_alias: PlayerAlias,
+    /// Weapon, or attacker's boat if collided.
+    pub entity_type: EntityType,
+    pub amount: Ticks,
 }
 
 impl World {
@@ -27,14 +59,26 @@ impl World {
             entities: Entities::new(),
             terrain: Terrain::with_generator(noise_generator),
             radius: initial_radius,
+            kills: Vec::new(),
+            hits: Vec::new(),
+            npcs: Npcs::default(),
+            events: Events::default(),
+            ports: Vec::new(),
+            landing_pads: HashMap::new(),
         }
     }
 
     /// Updates the internals of the world, spawning and updating existing entities.
-    pub fn update(&mut self, delta: Ticks) {
-        self.spawn_statics(delta);
-        self.physics(delta);
-        self.physics_radius(delta);
+    pub fn update(&mut self, delta: Ticks, profiler: &mut TickProfiler) {
+        profiler.time(TickPhase::SpawnStatics, || self.spawn_statics(delta));
+        // NPCs react to the hits of the previous tick.
+        profiler.time(TickPhase::Npcs, || self.update_npcs(delta));
+
+        self.kills.clear();
+        self.hits.clear();
+
+        profiler.time(TickPhase::Physics, || self.physics(delta));
+        profiler.time(TickPhase::PhysicsRadius, || self.physics_radius(delta));
         self.arena.recycle();
 
         let total_visual_area = EntityType::iter()
@@ -59,10 +103,12 @@ impl World {
         }
     }
 
-    /// Adds an entity to the world (assigning it an id).
-    pub fn add(&mut self, mut entity: Entity) {
-        entity.id = self.arena.new_id(entity.entity_type);
-        self.entities.add_internal(entity)
+    /// Adds an entity to the world (assigning it an id), returning its id.
+    pub fn add(&mut self, mut entity: Entity) -> EntityId {
+        let id = self.arena.new_id(entity.entity_type);
+        entity.id = id;
+        self.entities.add_internal(entity);
+        id
     }
 
     /// Removes an entity from the world with a given index and death reason.
@@ -91,7 +137,8 @@ impl World {
             .clamp(400.0, Self::max_radius())
     }
 
-    fn max_radius() -> f32 {
+    /// Returns the largest radius the world may ever have.
+    pub fn max_radius() -> f32 {
         Entities::max_world_radius().min(Terrain::max_world_radius())
     }
 }
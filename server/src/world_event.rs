@@ -0,0 +1,343 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::entities::EntityIndex;
+use crate::event::{Bounty, Storm, SupplyDrop};
+use crate::player::Status;
+use crate::server::Server;
+use crate::world::World;
+use common::altitude::Altitude;
+use common::angle::Angle;
+use common::entity::{EntityId, EntityType};
+use common::terrain;
+use common::ticks::Ticks;
+use common::util::level_to_score;
+use common::velocity::Velocity;
+use common::world::ARCTIC;
+use common_util::range::gen_radius;
+use game_server::player::PlayerTuple;
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
+use std::sync::Arc;
+
+/// An event that may start.
+enum EventKind<'a> {
+    SupplyDrop,
+    Bounty(&'a Arc<PlayerTuple<Server>>),
+    Storm,
+}
+
+impl World {
+    /// How often a new event may start.
+    const EVENT_PERIOD: Ticks = Ticks::from_whole_secs(3 * 60);
+    /// How far supply drop loot is scattered from its center.
+    const SUPPLY_DROP_RADIUS: f32 = 40.0;
+    /// How many of each type of loot a supply drop consists of.
+    const SUPPLY_DROP_AMOUNT: usize = 20;
+    /// Players must be at least this level to have a bounty placed on them.
+    const BOUNTY_MIN_LEVEL: u8 = 3;
+    /// How long a bounty may go unclaimed.
+    const BOUNTY_DURATION: Ticks = Ticks::from_whole_secs(5 * 60);
+    /// How long a storm lasts.
+    const STORM_DURATION: Ticks = Ticks::from_whole_secs(4 * 60);
+    /// How fast a storm roams (meters per second).
+    const STORM_SPEED: f32 = 4.0;
+
+    /// Advances events that are in progress, and periodically starts a new one. The top player,
+    /// if any, is a candidate for a bounty.
+    pub fn update_events(&mut self, delta: Ticks, top_player: Option<&Arc<PlayerTuple<Server>>>) {
+        self.update_supply_drop();
+        self.update_bounty(delta);
+        self.update_storm(delta);
+
+        self.events.cooldown = self.events.cooldown.saturating_sub(delta);
+        if self.events.cooldown != Ticks::ZERO {
+            return;
+        }
+        self.events.cooldown = Self::EVENT_PERIOD;
+
+        let top_player = top_player
+            .filter(|p| p.borrow_player().score >= level_to_score(Self::BOUNTY_MIN_LEVEL));
+
+        // Start one of the events that isn't already in progress, at random.
+        let mut candidates = Vec::with_capacity(3);
+        if self.events.supply_drop.is_none() {
+            candidates.push(EventKind::SupplyDrop);
+        }
+        if let Some(player) = top_player.filter(|_| self.events.bounty.is_none()) {
+            candidates.push(EventKind::Bounty(player));
+        }
+        if self.events.storm.is_none() {
+            candidates.push(EventKind::Storm);
+        }
+        match candidates.choose(&mut thread_rng()) {
+            Some(EventKind::SupplyDrop) => self.start_supply_drop(),
+            Some(EventKind::Bounty(player)) => self.start_bounty(player),
+            Some(EventKind::Storm) => self.start_storm(),
+            None => {}
+        }
+    }
+
+    /// Pays the bounty, if any, on the boat at index to the player who sank it.
+    /// Called before the boat is removed.
+    pub fn claim_bounty(&mut self, index: EntityIndex, killer: &PlayerTuple<Server>) {
+        let entity_id = self.entities[index].id;
+        if self
+            .events
+            .bounty
+            .as_ref()
+            .map_or(true, |b| b.entity_id != entity_id)
+        {
+            return;
+        }
+        let bounty = self.events.bounty.take().unwrap();
+
+        let mut killer = killer.borrow_player_mut();
+        killer.score += bounty.reward;
+        self.events.announcements.push(format!(
+            "{} claimed the bounty of {} points on {}!",
+            killer.alias(),
+            bounty.reward,
+            bounty.alias
+        ));
+    }
+
+    /// Ends the supply drop once its loot is gone.
+    fn update_supply_drop(&mut self) {
+        if let Some(supply_drop) = &mut self.events.supply_drop {
+            let nearby: Vec<EntityId> = self
+                .entities
+                .iter_radius(supply_drop.position, Self::SUPPLY_DROP_RADIUS * 2.0)
+                .map(|(_, e)| e.id)
+                .collect();
+            // Forgotten as soon as they are gone, so recycled ids aren't mistaken for loot.
+            supply_drop.loot.retain(|id| nearby.contains(id));
+            if supply_drop.loot.is_empty() {
+                self.events.supply_drop = None;
+            }
+        }
+    }
+
+    /// Keeps the bounty up to date with its boat, ending it if the boat sank (to something
+    /// other than a player) or the time ran out.
+    fn update_bounty(&mut self, delta: Ticks) {
+        if let Some(bounty) = &mut self.events.bounty {
+            bounty.remaining = bounty.remaining.saturating_sub(delta);
+
+            let player = bounty.player.borrow_player();
+            let boat = if let Status::Alive { entity_index, .. } = player.data.status {
+                Some(&self.entities[entity_index]).filter(|e| e.id == bounty.entity_id)
+            } else {
+                None
+            };
+
+            if let Some(boat) = boat.filter(|_| bounty.remaining != Ticks::ZERO) {
+                bounty.alias = player.alias();
+                bounty.reward = Bounty::reward(player.score);
+
+                // Periodically report an approximate position, except while submerged.
+                bounty.report = bounty.report.saturating_sub(delta);
+                if boat.altitude.is_submerged() {
+                    bounty.position = None;
+                } else if bounty.report == Ticks::ZERO {
+                    bounty.position = Some(Bounty::report_position(boat.transform.position));
+                    bounty.report = Bounty::REPORT_PERIOD;
+                }
+            } else {
+                drop(player);
+                self.events
+                    .announcements
+                    .push(format!("The bounty on {} has lapsed.", bounty.alias));
+                self.events.bounty = None;
+            }
+        }
+    }
+
+    /// Moves the storm, turning it back from the world border.
+    fn update_storm(&mut self, delta: Ticks) {
+        if let Some(storm) = &mut self.events.storm {
+            storm.remaining = storm.remaining.saturating_sub(delta);
+            if storm.remaining == Ticks::ZERO {
+                self.events.storm = None;
+                self.events
+                    .announcements
+                    .push(String::from("The storm has passed."));
+                return;
+            }
+
+            storm.position += storm.velocity * delta.to_secs();
+            if storm.position.length_squared() > (self.radius * 0.8).powi(2) {
+                storm.velocity = -storm.position.normalize_or_zero() * Self::STORM_SPEED;
+            }
+        }
+    }
+
+    /// Scatters crates and coins around a random point in the water.
+    fn start_supply_drop(&mut self) {
+        let mut rng = thread_rng();
+        let position = (0..16)
+            .map(|_| gen_radius(&mut rng, self.radius * 0.8))
+            .find(|&p| {
+                p.y < ARCTIC
+                    && self.terrain.sample(p).unwrap_or(Altitude::MAX) < terrain::SAND_LEVEL
+            });
+
+        if let Some(position) = position {
+            let mut loot = Vec::with_capacity(Self::SUPPLY_DROP_AMOUNT * 2);
+            for entity_type in [EntityType::Crate, EntityType::Coin] {
+                for _ in 0..Self::SUPPLY_DROP_AMOUNT {
+                    loot.extend(self.spawn_static(
+                        entity_type,
+                        position + gen_radius(&mut rng, Self::SUPPLY_DROP_RADIUS),
+                        rng.gen(),
+                        Velocity::ZERO,
+                        Ticks::ZERO,
+                    ));
+                }
+            }
+
+            self.events.supply_drop = Some(SupplyDrop { position, loot });
+            self.events.announcements.push(String::from(
+                "A supply drop has been sighted! It is marked on your map.",
+            ));
+        }
+    }
+
+    /// Places a bounty on the player's boat.
+    fn start_bounty(&mut self, player_tuple: &Arc<PlayerTuple<Server>>) {
+        let player = player_tuple.borrow_player();
+        if let Status::Alive { entity_index, .. } = player.data.status {
+            let boat = &self.entities[entity_index];
+            let bounty = Bounty {
+                player: Arc::clone(player_tuple),
+                entity_id: boat.id,
+                alias: player.alias(),
+                reward: Bounty::reward(player.score),
+                position: (!boat.altitude.is_submerged())
+                    .then(|| Bounty::report_position(boat.transform.position)),
+                report: Bounty::REPORT_PERIOD,
+                remaining: Self::BOUNTY_DURATION,
+            };
+            self.events.announcements.push(format!(
+                "A bounty of {} points has been placed on {}!",
+                bounty.reward, bounty.alias
+            ));
+            self.events.bounty = Some(bounty);
+        }
+    }
+
+    /// Starts a storm somewhere in the world, roaming in a random direction.
+    fn start_storm(&mut self) {
+        let mut rng = thread_rng();
+        self.events.storm = Some(Storm {
+            position: gen_radius(&mut rng, self.radius * 0.7),
+            velocity: rng.gen::<Angle>().to_vec() * Self::STORM_SPEED,
+            radius: rng.gen_range(250.0..450.0),
+            remaining: Self::STORM_DURATION,
+        });
+        self.events.announcements.push(String::from(
+            "A storm is brewing! Boats caught in it are slowed, and aircraft are downed.",
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::Entity;
+    use crate::event::{Bounty, SupplyDrop};
+    use crate::world::World;
+    use common::altitude::Altitude;
+    use common::angle::Angle;
+    use common::death_reason::DeathReason;
+    use common::entity::EntityType;
+    use common::terrain::Terrain;
+    use common::ticks::Ticks;
+    use common::velocity::Velocity;
+    use core_protocol::id::PlayerId;
+    use game_server::player::{PlayerData, PlayerTuple};
+    use glam::{vec2, Vec2};
+    use std::sync::Arc;
+
+    fn world() -> World {
+        let mut world = World::new(10000.0);
+        world.terrain = Terrain::new();
+        world
+    }
+
+    #[test]
+    fn supply_drop() {
+        let mut world = world();
+        let position = vec2(100.0, 100.0);
+        let spawn_coin = |world: &mut World, position: Vec2| {
+            world
+                .spawn_static(
+                    EntityType::Coin,
+                    position,
+                    Angle::ZERO,
+                    Velocity::ZERO,
+                    Ticks::ZERO,
+                )
+                .unwrap()
+        };
+
+        let loot = spawn_coin(&mut world, position);
+        world.events.supply_drop = Some(SupplyDrop {
+            position,
+            loot: vec![loot],
+        });
+
+        // Other coins nearby don't prolong it.
+        spawn_coin(&mut world, position + vec2(20.0, 0.0));
+        world.update_supply_drop();
+        assert!(world.events.supply_drop.is_some());
+
+        let index = world
+            .entities
+            .iter_radius(position, 1.0)
+            .find(|(_, e)| e.id == loot)
+            .unwrap()
+            .0;
+        world.remove(index, DeathReason::Unknown);
+        world.update_supply_drop();
+        assert!(world.events.supply_drop.is_none());
+    }
+
+    #[test]
+    fn bounty() {
+        let mut world = world();
+        // A bot, since a real player would need client data (e.g. for an alias).
+        let player = Arc::new(PlayerTuple::new(PlayerData::new(
+            PlayerId::nth_bot(1).unwrap(),
+            None,
+        )));
+        let mut boat = Entity::new(EntityType::Akula, Some(Arc::clone(&player)));
+        let start = vec2(1234.0, 567.0);
+        boat.transform.position = start;
+        assert!(world.spawn_here_or_nearby(boat, 0.0, None).is_some());
+        let index = player
+            .borrow_player()
+            .data
+            .status
+            .get_entity_index()
+            .unwrap();
+
+        let position = |world: &World| world.events.bounty.as_ref().unwrap().position;
+        world.start_bounty(&player);
+        assert_eq!(position(&world), Some(Bounty::report_position(start)));
+        assert_ne!(position(&world), Some(start));
+
+        // Reported periodically.
+        let destination = vec2(3000.0, 3000.0);
+        world.entities[index].transform.position = destination;
+        world.update_bounty(Ticks::ONE);
+        assert_eq!(position(&world), Some(Bounty::report_position(start)));
+        world.update_bounty(Bounty::REPORT_PERIOD);
+        assert_eq!(position(&world), Some(Bounty::report_position(destination)));
+
+        // Hidden while submerged.
+        world.entities[index].altitude = Altitude::MIN;
+        world.update_bounty(Ticks::ONE);
+        assert_eq!(position(&world), None);
+        assert!(world.events.to_protocol().is_empty());
+    }
+}
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: AGPL-3.0-or-later
 
 use crate::entity::Entity;
+use crate::entity_extension::LaunchedAircraft;
 use crate::player::Status;
 use crate::protocol::*;
 use crate::server::Server;
@@ -46,6 +47,16 @@ impl CommandTrait for Spawn {
             return Err("cannot spawn as given entity type");
         }
 
+        if !self.entity_type.data().is_valid_loadout(self.loadout) {
+            return Err("invalid loadout");
+        }
+
+        if player.data.scouted.map_or(false, |t| {
+            t.elapsed() < SpectateTarget::SPAWN_DELAY.to_duration()
+        }) {
+            return Err("cannot spawn so soon after spectating other teams");
+        }
+
         // These initial positions may be overwritten later.
         let mut spawn_position = Vec2::ZERO;
         let mut spawn_radius = 0.8 * world.radius;
@@ -166,7 +177,18 @@ impl CommandTrait for Spawn {
         boat.transform.position = spawn_position;
         //#[cfg(debug_assertions)]
         //let begin = std::time::Instant::now();
-        if world.spawn_here_or_nearby(boat, spawn_radius, exclusion_zone) {
+        if world
+            .spawn_here_or_nearby(boat, spawn_radius, exclusion_zone)
+            .is_some()
+        {
+            let mut player = player_tuple.borrow_player_mut();
+            player.data.spectate = None;
+            // Only arm the boat with its loadout once it has been added.
+            let entity_index = player.data.status.get_entity_index().unwrap();
+            drop(player);
+            world.entities[entity_index]
+                .extension_mut()
+                .change_entity_type(self.entity_type, self.loadout);
             /*
             #[cfg(debug_assertions)]
             println!(
@@ -216,7 +238,7 @@ impl CommandTrait for Control {
                 None
             };
             let extension = entity.extension_mut();
-            extension.set_submerge(self.submerge);
+            extension.set_depth(self.depth);
             extension.set_active(self.active);
 
             drop(player);
@@ -262,9 +284,10 @@ impl CommandTrait for Fire {
             let entity = &mut world.entities[entity_index];
 
             let data = entity.data();
+            let armaments = entity.armaments();
 
             let index = self.armament_index as usize;
-            if index >= data.armaments.len() {
+            if index >= armaments.len() {
                 return Err("armament index out of bounds");
             }
 
@@ -272,7 +295,11 @@ impl CommandTrait for Fire {
                 return Err("armament not yet reloaded");
             }
 
-            let armament = &data.armaments[index];
+            if !entity.is_armament_ready(index) {
+                return Err("armament magazine empty");
+            }
+
+            let armament = &armaments[index];
             let armament_entity_data = armament.entity_type.data();
 
             // Can't fire if boat is a submerged former submarine.
@@ -298,8 +325,12 @@ impl CommandTrait for Fire {
                 }
             }
 
-            let armament_transform =
-                entity.transform + data.armament_transform(&entity.extension().turrets, index);
+            let armament_transform = entity.transform
+                + data.armament_transform(
+                    entity.extension().loadout(),
+                    &entity.extension().turrets,
+                    index,
+                );
 
             if armament_entity_data.sub_kind == EntitySubKind::Depositor {
                 if let Some(mut target) = aim_target {
@@ -346,14 +377,28 @@ impl CommandTrait for Fire {
                 };
                 armament_entity.transform.direction += thread_rng().gen::<Angle>() * deviation;
 
-                if !world.spawn_here_or_nearby(armament_entity, 0.0, None) {
-                    return Err("failed to fire from current location");
+                let id = world
+                    .spawn_here_or_nearby(armament_entity, 0.0, None)
+                    .ok_or("failed to fire from current location")?;
+
+                if armament_entity_data.kind == EntityKind::Aircraft {
+                    world.entities[entity_index]
+                        .extension_mut()
+                        .air_wing
+                        .push(LaunchedAircraft {
+                            id,
+                            entity_type: armament.entity_type,
+                            armament: index,
+                            status: SortieStatus::Airborne,
+                            fuel: armament_entity_data.lifespan,
+                        });
                 }
             }
 
             let entity = &mut world.entities[entity_index];
             entity.consume_armament(index);
             entity.extension_mut().clear_spawn_protection();
+            entity.extension_mut().clear_dock_immunity();
 
             Ok(())
         } else {
@@ -400,7 +445,7 @@ impl CommandTrait for Pay {
             payment.altitude = entity.altitude;
 
             // If payment successfully spawns, withdraw funds.
-            if world.spawn_here_or_nearby(payment, 1.0, None) {
+            if world.spawn_here_or_nearby(payment, 1.0, None).is_some() {
                 player.score -= withdraw;
             }
 
@@ -424,6 +469,40 @@ impl CommandTrait for Hint {
     }
 }
 
+impl CommandTrait for Spectate {
+    fn apply(
+        &self,
+        world: &mut World,
+        player_tuple: &Arc<PlayerTuple<Server>>,
+    ) -> Result<(), &'static str> {
+        let mut player = player_tuple.borrow_player_mut();
+
+        if player.data.status.is_alive() {
+            return Err("cannot spectate while alive");
+        }
+
+        player.data.spectate = match self.target {
+            Some(SpectateTarget::Player(player_id)) => {
+                if player_id == player.player_id {
+                    return Err("cannot spectate self");
+                }
+                Some(SpectateTarget::Player(player_id))
+            }
+            Some(SpectateTarget::Free(position)) => {
+                if !position.is_finite() {
+                    return Err("spectate position not finite");
+                }
+                Some(SpectateTarget::Free(
+                    position.clamp_length_max(world.radius),
+                ))
+            }
+            None => None,
+        };
+
+        Ok(())
+    }
+}
+
 impl CommandTrait for Upgrade {
     fn apply(
         &self,
@@ -434,10 +513,25 @@ impl CommandTrait for Upgrade {
         let status = &mut player.data.status;
 
         if let Status::Alive { entity_index, .. } = status {
+            if !self.entity_type.data().is_valid_loadout(self.loadout) {
+                return Err("invalid loadout");
+            }
+
             let entity = &mut world.entities[*entity_index];
-            if !entity
-                .entity_type
-                .can_upgrade_to(self.entity_type, player.score, player.is_bot())
+            // Docked boats may also refit to another boat of the same level, or another loadout
+            // of the same boat.
+            let refit = entity.extension().is_docked()
+                && (entity
+                    .entity_type
+                    .can_refit_to(self.entity_type, player.is_bot())
+                    || (entity.entity_type == self.entity_type
+                        && entity.extension().loadout() != self.loadout));
+            if !refit
+                && !entity.entity_type.can_upgrade_to(
+                    self.entity_type,
+                    player.score,
+                    player.is_bot(),
+                )
             {
                 return Err("cannot upgrade to provided entity type");
             }
@@ -452,7 +546,12 @@ impl CommandTrait for Upgrade {
 
             drop(player);
 
-            entity.change_entity_type(self.entity_type, &mut world.arena, below_full_potential);
+            entity.change_entity_type(
+                self.entity_type,
+                self.loadout,
+                &mut world.arena,
+                below_full_potential,
+            );
 
             Ok(())
         } else {
@@ -496,3 +595,154 @@ fn clamp_to_range(
         Ok(center + delta.clamp_length_max(range))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::protocol::CommandTrait;
+    use crate::world::World;
+    use crate::Server;
+    use common::entity::{EntityKind, EntityType};
+    use common::protocol::{Spawn, SpectateTarget, Upgrade};
+    use common::terrain::Terrain;
+    use common::util::level_to_score;
+    use core_protocol::id::PlayerId;
+    use game_server::player::{PlayerData, PlayerTuple};
+    use std::num::NonZeroU32;
+    use std::sync::Arc;
+    use std::time::{Duration, Instant};
+
+    fn player(i: u32, score: u32) -> Arc<PlayerTuple<Server>> {
+        let player = Arc::new(PlayerTuple::new(PlayerData::new(
+            PlayerId(NonZeroU32::new(i).unwrap()),
+            None,
+        )));
+        player.borrow_player_mut().score = score;
+        player
+    }
+
+    /// Returns the entity type and loadout of the player's boat, if alive.
+    fn boat(world: &World, player: &PlayerTuple<Server>) -> Option<(EntityType, u8)> {
+        let entity_index = player.borrow_player().data.status.get_entity_index()?;
+        let entity = &world.entities[entity_index];
+        Some((entity.entity_type, entity.extension().loadout()))
+    }
+
+    #[test]
+    fn spawn_loadout() {
+        let mut world = World::new(10000.0);
+        world.terrain = Terrain::new();
+
+        let entity_type = EntityType::ArleighBurke;
+        let data = entity_type.data();
+        let score = level_to_score(data.level);
+        let invalid = data.loadouts.len() as u8;
+
+        let rejected = player(1, score);
+        assert_eq!(
+            Spawn {
+                entity_type,
+                loadout: invalid
+            }
+            .apply(&mut world, &rejected),
+            Err("invalid loadout")
+        );
+        assert_eq!(boat(&world, &rejected), None);
+
+        for loadout in 0..invalid {
+            let player = player(loadout as u32 + 2, score);
+            assert_eq!(
+                Spawn {
+                    entity_type,
+                    loadout
+                }
+                .apply(&mut world, &player),
+                Ok(())
+            );
+            assert_eq!(boat(&world, &player), Some((entity_type, loadout)));
+
+            let entity_index = player.borrow_player().data.status.get_entity_index();
+            let entity = &world.entities[entity_index.unwrap()];
+            let magazines: Vec<u8> = data
+                .loadout_armaments(loadout)
+                .iter()
+                .map(|a| a.magazine.unwrap_or(0))
+                .collect();
+            assert_eq!(&*entity.extension().magazines, magazines.as_slice());
+        }
+    }
+
+    #[test]
+    fn upgrade_loadout() {
+        let mut world = World::new(10000.0);
+        world.terrain = Terrain::new();
+
+        let entity_type = EntityType::ArleighBurke;
+        let data = entity_type.data();
+        let score = level_to_score(data.level);
+        let invalid = data.loadouts.len() as u8;
+
+        // Any boat that may upgrade to the entity type.
+        let from = EntityType::iter()
+            .find(|t| {
+                t.data().kind == EntityKind::Boat
+                    && t.can_spawn_as(score, false)
+                    && t.can_upgrade_to(entity_type, score, false)
+            })
+            .unwrap();
+
+        let player = player(1, score);
+        assert_eq!(
+            Spawn {
+                entity_type: from,
+                loadout: 0
+            }
+            .apply(&mut world, &player),
+            Ok(())
+        );
+
+        assert_eq!(
+            Upgrade {
+                entity_type,
+                loadout: invalid
+            }
+            .apply(&mut world, &player),
+            Err("invalid loadout")
+        );
+        assert_eq!(boat(&world, &player), Some((from, 0)));
+
+        let loadout = invalid - 1;
+        assert_eq!(
+            Upgrade {
+                entity_type,
+                loadout
+            }
+            .apply(&mut world, &player),
+            Ok(())
+        );
+        assert_eq!(boat(&world, &player), Some((entity_type, loadout)));
+    }
+
+    #[test]
+    fn spawn_delayed_after_scouting() {
+        let mut world = World::new(10000.0);
+        world.terrain = Terrain::new();
+
+        let spawn = Spawn {
+            entity_type: EntityType::G5,
+            loadout: 0,
+        };
+        let player = player(1, 0);
+
+        player.borrow_player_mut().data.scouted = Some(Instant::now());
+        assert_eq!(
+            spawn.apply(&mut world, &player),
+            Err("cannot spawn so soon after spectating other teams")
+        );
+        assert_eq!(boat(&world, &player), None);
+
+        player.borrow_player_mut().data.scouted = Instant::now()
+            .checked_sub(SpectateTarget::SPAWN_DELAY.to_duration() + Duration::from_secs(1));
+        assert_eq!(spawn.apply(&mut world, &player), Ok(()));
+        assert_eq!(boat(&world, &player), Some((EntityType::G5, 0)));
+    }
+}
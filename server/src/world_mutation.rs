@@ -3,15 +3,18 @@
 
 use crate::entities::EntityIndex;
 use crate::entity::Entity;
+use crate::entity_extension::LaunchedAircraft;
+use crate::npc::Npcs;
 use crate::player::Status;
 use crate::server::Server;
-use crate::world::World;
+use crate::world::{Hit, World};
 use crate::world_physics_radius::MINE_SPEED;
 use common::altitude::Altitude;
 use common::angle::Angle;
 use common::death_reason::DeathReason;
 use common::entity::*;
 use common::guidance::Guidance;
+use common::protocol::{Kill, SortieStatus};
 use common::terrain::TerrainMutation;
 use common::ticks::Ticks;
 use common::util::*;
@@ -41,6 +44,7 @@ pub(crate) enum Mutation {
     Remove(DeathReason),
     Repair(Ticks),
     Reload(Ticks),
+    Resupply(Ticks),
     // For things that may only be collected once.
     CollectedBy(Arc<PlayerTuple<Server>>, u32),
     HitBy(Arc<PlayerTuple<Server>>, EntityType, Ticks),
@@ -121,7 +125,12 @@ impl Mutation {
                 return true;
             }
             Self::HitBy(other_player, weapon_type, damage) => {
-                let e = &mut entities[index];
+                let is_boat = world.entities[index].is_boat();
+                if is_boat {
+                    Self::record_hit(world, index, &other_player, weapon_type, damage);
+                }
+
+                let e = &mut world.entities[index];
                 if e.damage(damage) {
                     let killer_alias = {
                         let e_score = e.borrow_player().score;
@@ -132,6 +141,10 @@ impl Mutation {
                         alias
                     };
 
+                    if is_boat {
+                        Self::record_kill(world, index, &other_player, Some(weapon_type));
+                        world.claim_bounty(index, &other_player);
+                    }
                     world.remove(index, DeathReason::Weapon(killer_alias, weapon_type));
                     return true;
                 }
@@ -142,7 +155,11 @@ impl Mutation {
                 other_player,
                 ram,
             } => {
-                let entity = &mut entities[index];
+                if let Some(boat_type) = Self::boat_type(world, &other_player) {
+                    Self::record_hit(world, index, &other_player, boat_type, damage);
+                }
+
+                let entity = &mut world.entities[index];
                 if entity.damage(damage) {
                     let e_score = entity.borrow_player().score;
                     let killer_alias = {
@@ -153,6 +170,8 @@ impl Mutation {
                         alias
                     };
 
+                    Self::record_kill(world, index, &other_player, None);
+                    world.claim_bounty(index, &other_player);
                     world.remove(
                         index,
                         if ram {
@@ -181,7 +200,7 @@ impl Mutation {
             Self::ClearSpawnProtection => entities[index].extension_mut().clear_spawn_protection(),
             Self::UpgradeHq => {
                 let entity = &mut entities[index];
-                entity.change_entity_type(EntityType::Hq, &mut world.arena, false);
+                entity.change_entity_type(EntityType::Hq, 0, &mut world.arena, false);
                 entity.ticks = Ticks::ZERO;
             }
             Self::Repair(amount) => {
@@ -190,6 +209,9 @@ impl Mutation {
             Self::Reload(amount) => {
                 entities[index].reload(amount);
             }
+            Self::Resupply(amount) => {
+                entities[index].resupply(amount);
+            }
             Self::Score(score) => {
                 entities[index].borrow_player_mut().score += score;
             }
@@ -221,10 +243,25 @@ impl Mutation {
                 }
             }
             Self::FireAll(sub_kind) => {
+                // Aircraft in an air wing carry one load of weapons per sortie, then return to
+                // rearm. Their lifespan is their fuel, so it isn't reset by attacking.
+                let sortie = entities[index].data().kind == EntityKind::Aircraft
+                    && match Self::launched_aircraft(world, index) {
+                        Some(aircraft) if aircraft.status == SortieStatus::Airborne => {
+                            aircraft.status = SortieStatus::Returning;
+                            true
+                        }
+                        Some(_) => return false,
+                        None => false,
+                    };
+
+                let entities = &mut world.entities;
                 let entity = &mut entities[index];
 
-                // Reset entity lifespan (because it is actively engaging in battle.
-                entity.ticks = Ticks::ZERO;
+                if !sortie {
+                    // Reset entity lifespan (because it is actively engaging in battle.
+                    entity.ticks = Ticks::ZERO;
+                }
 
                 let data = entity.data();
                 let armament_entities: Vec<Entity> = data
@@ -244,7 +281,7 @@ impl Mutation {
                                     150.0 / armament_data.speed.to_mps().clamp(15.0, 50.0),
                                 ));
                             armament_entity.transform =
-                                entity.transform + data.armament_transform(&[], i);
+                                entity.transform + data.armament_transform(0, &[], i);
                             armament_entity.altitude = entity.altitude;
                             armament_entity.guidance = Guidance {
                                 direction_target: entity.transform.direction, // TODO: Randomize
@@ -273,6 +310,68 @@ impl Mutation {
         false
     }
 
+    /// Gets the type of a player's boat, if they have one.
+    fn boat_type(world: &World, player: &PlayerTuple<Server>) -> Option<EntityType> {
+        if let Status::Alive { entity_index, .. } = player.borrow_player().data.status {
+            Some(world.entities[entity_index].entity_type)
+        } else {
+            None
+        }
+    }
+
+    /// Gets the air wing entry of an aircraft, if the boat it was launched from still exists.
+    fn launched_aircraft(world: &mut World, index: EntityIndex) -> Option<&mut LaunchedAircraft> {
+        let aircraft = &world.entities[index];
+        let id = aircraft.id;
+        let boat_index = match aircraft.borrow_player().data.status {
+            Status::Alive { entity_index, .. } => entity_index,
+            _ => return None,
+        };
+        world.entities[boat_index]
+            .extension_mut()
+            .air_wing
+            .iter_mut()
+            .find(|a| a.id == id)
+    }
+
+    /// Records damage to a boat by another player, for combat logs.
+    fn record_hit(
+        world: &mut World,
+        index: EntityIndex,
+        attacker: &PlayerTuple<Server>,
+        entity_type: EntityType,
+        amount: Ticks,
+    ) {
+        let victim = world.entities[index].borrow_player();
+        let attacker = attacker.borrow_player();
+        world.hits.push(Hit {
+            attacker: attacker.player_id,
+            attacker_alias: attacker.alias(),
+            victim: victim.player_id,
+            victim_alias: victim.alias(),
+            entity_type,
+            amount,
+        });
+    }
+
+    /// Records a boat being sunk by another player, for the kill feed.
+    fn record_kill(
+        world: &mut World,
+        index: EntityIndex,
+        killer: &PlayerTuple<Server>,
+        weapon: Option<EntityType>,
+    ) {
+        let victim = &world.entities[index];
+        let kill = Kill {
+            killer: killer.borrow_player().alias(),
+            killer_entity_type: Self::boat_type(world, killer),
+            victim: victim.borrow_player().alias(),
+            victim_entity_type: victim.entity_type,
+            weapon,
+        };
+        world.kills.push(kill);
+    }
+
     /// Called by World::remove.
     pub fn on_world_remove(world: &mut World, index: EntityIndex, reason: &DeathReason) {
         let entity_type = world.entities[index].entity_type;
@@ -285,9 +384,11 @@ impl Mutation {
             let score_to_coins = matches!(
                 reason,
                 DeathReason::Border
+                    | DeathReason::Crush
                     | DeathReason::Terrain
                     | DeathReason::Unknown
                     | DeathReason::Obstacle(_)
+                    | DeathReason::Admin
             );
 
             Self::boat_died(world, index, score_to_coins);
@@ -309,13 +410,29 @@ impl Mutation {
                 };
 
                 if let Some(boat_index) = boat_index {
-                    // Reload landed aircraft instantly on the correct pad.
+                    // Rearm landed aircraft on the correct pad.
                     let landing_pad = if let DeathReason::Landing(pad) = reason {
                         Some(*pad)
                     } else {
                         None
                     };
 
+                    if data.kind == EntityKind::Aircraft {
+                        let id = world.entities[index].id;
+                        let air_wing = &mut world.entities[boat_index].extension_mut().air_wing;
+                        if let Some(i) = air_wing.iter().position(|a| a.id == id) {
+                            if let Some(pad) = landing_pad {
+                                let aircraft = &mut air_wing[i];
+                                aircraft.armament = pad;
+                                aircraft.status = SortieStatus::Rearming;
+                                aircraft.fuel = Ticks::ZERO;
+                            } else {
+                                // Lost.
+                                air_wing.swap_remove(i);
+                            }
+                        }
+                    }
+
                     Self::reload_limited_armament(world, boat_index, entity_type, landing_pad)
                 }
             }
@@ -330,6 +447,11 @@ impl Mutation {
         let mut player = entity.borrow_player_mut();
         let mut rng = thread_rng();
         let score = player.score;
+        let npc = Npcs::is_npc(player.player_id);
+        if npc && player.data.flags.left_game {
+            // NPCs that retire (e.g. convoys that reached their destination) leave nothing behind.
+            return;
+        }
         player.score = if player.is_bot() {
             // Make sure there are bots in the shallow area.
             respawn_score(player.score).min(level_to_score(rng.gen_range(1..=2)))
@@ -348,7 +470,7 @@ impl Mutation {
         let tangent = Vec2::new(-normal.y, normal.x);
         let altitude = entity.altitude;
 
-        for loot_type in entity.entity_type.loot(score, score_to_coins) {
+        for loot_type in entity.entity_type.loot(score, score_to_coins, npc) {
             let mut loot_entity = Entity::new(loot_type, None);
 
             // Make loot roughly conform to rectangle of ship.
@@ -428,7 +550,7 @@ impl Mutation {
             debug_assert_eq!(a.entity_type, entity_type);
             if *c == Ticks::MAX {
                 *c = if landing_pad.is_some() {
-                    Ticks::ZERO
+                    LaunchedAircraft::DECK_CYCLE
                 } else {
                     a.reload()
                 };
@@ -439,7 +561,7 @@ impl Mutation {
         };
 
         let boat = &mut world.entities[boat_index];
-        let armaments = &*boat.data().armaments;
+        let armaments = boat.armaments();
         let consumption = boat.extension_mut().reloads_mut();
 
         if let Some(i) = landing_pad {
@@ -472,3 +594,97 @@ impl Mutation {
          */
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::entities::EntityIndex;
+    use crate::entity::Entity;
+    use crate::world::World;
+    use crate::world_mutation::Mutation;
+    use crate::Server;
+    use common::entity::EntityType;
+    use common::terrain::Terrain;
+    use common::ticks::Ticks;
+    use common::velocity::Velocity;
+    use core_protocol::id::PlayerId;
+    use game_server::player::{PlayerData, PlayerTuple};
+    use std::sync::Arc;
+
+    fn spawn(world: &mut World, id: usize) -> (Arc<PlayerTuple<Server>>, EntityIndex) {
+        // Bots, since real players would need client data (e.g. for aliases).
+        let player = Arc::new(PlayerTuple::new(PlayerData::new(
+            PlayerId::nth_bot(id).unwrap(),
+            None,
+        )));
+        let entity = Entity::new(EntityType::G5, Some(Arc::clone(&player)));
+        assert!(world.spawn_here_or_nearby(entity, 10000.0, None).is_some());
+        let index = player
+            .borrow_player()
+            .data
+            .status
+            .get_entity_index()
+            .unwrap();
+        (player, index)
+    }
+
+    #[test]
+    fn record_hit_and_kill() {
+        let mut world = World::new(10000.0);
+        world.terrain = Terrain::new();
+        let (attacker, _) = spawn(&mut world, 1);
+        let (victim, victim_index) = spawn(&mut world, 2);
+
+        let removed = Mutation::HitBy(Arc::clone(&attacker), EntityType::Set65, Ticks::ONE).apply(
+            &mut world,
+            victim_index,
+            Ticks::ONE,
+            true,
+        );
+        assert!(!removed);
+        assert_eq!(world.hits.len(), 1);
+        let hit = &world.hits[0];
+        assert_eq!(hit.attacker, attacker.borrow_player().player_id);
+        assert_eq!(hit.victim, victim.borrow_player().player_id);
+        assert_eq!(hit.entity_type, EntityType::Set65);
+        assert_eq!(hit.amount, Ticks::ONE);
+        assert!(world.kills.is_empty());
+
+        let lethal = EntityType::G5.data().max_health();
+        let removed = Mutation::HitBy(Arc::clone(&attacker), EntityType::Set65, lethal).apply(
+            &mut world,
+            victim_index,
+            Ticks::ONE,
+            true,
+        );
+        assert!(removed);
+        assert_eq!(world.hits.len(), 2);
+        assert_eq!(world.kills.len(), 1);
+        let kill = &world.kills[0];
+        assert_eq!(kill.killer, attacker.borrow_player().alias());
+        assert_eq!(kill.killer_entity_type, Some(EntityType::G5));
+        assert_eq!(kill.victim, victim.borrow_player().alias());
+        assert_eq!(kill.victim_entity_type, EntityType::G5);
+        assert_eq!(kill.weapon, Some(EntityType::Set65));
+    }
+
+    #[test]
+    fn record_collision() {
+        let mut world = World::new(10000.0);
+        world.terrain = Terrain::new();
+        let (attacker, _) = spawn(&mut world, 1);
+        let (_, victim_index) = spawn(&mut world, 2);
+
+        let removed = Mutation::CollidedWithBoat {
+            other_player: Arc::clone(&attacker),
+            damage: EntityType::G5.data().max_health(),
+            impulse: Velocity::ZERO,
+            ram: true,
+        }
+        .apply(&mut world, victim_index, Ticks::ONE, true);
+        assert!(removed);
+        assert_eq!(world.hits.len(), 1);
+        assert_eq!(world.hits[0].entity_type, EntityType::G5);
+        assert_eq!(world.kills.len(), 1);
+        assert_eq!(world.kills[0].weapon, None);
+    }
+}
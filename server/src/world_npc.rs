@@ -0,0 +1,632 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::entities::EntityIndex;
+use crate::entity::Entity;
+use crate::npc::{NpcGroup, NpcGroupKind, Npcs};
+use crate::player::Status;
+use crate::protocol::CommandTrait;
+use crate::server::Server;
+use crate::world::World;
+use common::altitude::Altitude;
+use common::angle::Angle;
+use common::entity::*;
+use common::guidance::Guidance;
+use common::protocol::{Control, Depth, Fire};
+use common::terrain;
+use common::ticks::Ticks;
+use common::util::level_to_score;
+use common_util::range::gen_radius;
+use game_server::player::{PlayerData, PlayerTuple};
+use glam::Vec2;
+use log::debug;
+use rand::seq::IteratorRandom;
+use rand::{thread_rng, Rng};
+use std::sync::Arc;
+
+/// What an NPC boat should do this tick.
+struct NpcOrder {
+    /// Where to sail.
+    destination: Vec2,
+    /// Fraction of maximum speed.
+    throttle: f32,
+    /// Boat to attack, if any.
+    target: Option<EntityIndex>,
+}
+
+impl World {
+    /// Target density of convoys (per square meter).
+    const CONVOY_DENSITY: f32 = 1.0 / 25000000.0;
+    /// Target density of pirate patrols (per square meter).
+    const PATROL_DENSITY: f32 = 1.0 / 20000000.0;
+    /// How often a new NPC group may spawn.
+    const NPC_SPAWN_PERIOD: Ticks = Ticks::from_whole_secs(10);
+    /// Convoys that take longer than this to reach their destination give up.
+    const CONVOY_MAX_AGE: Ticks = Ticks::from_whole_secs(15 * 60);
+    /// Minimum distance between the ports at either end of a convoy's route.
+    const CONVOY_MIN_DISTANCE: f32 = 1500.0;
+    /// How close attackers may approach a convoy's leader before its escorts engage them.
+    const ESCORT_RANGE: f32 = 1000.0;
+    /// How far pirates wander from home while not hunting.
+    const PATROL_RANGE: f32 = 1500.0;
+    /// Chance, per tick, that an NPC boat fires a ready armament at its target.
+    const NPC_FIRE_CHANCE: f64 = 0.2;
+
+    /// Spawns NPC groups to maintain their densities, and issues orders to their boats.
+    pub fn update_npcs(&mut self, delta: Ticks) {
+        // Taken, since carrying out orders requires mutable access to the world.
+        let mut npcs = std::mem::take(&mut self.npcs);
+
+        npcs.spawn_cooldown = npcs.spawn_cooldown.saturating_sub(delta);
+        if npcs.spawn_cooldown == Ticks::ZERO {
+            npcs.spawn_cooldown = Self::NPC_SPAWN_PERIOD;
+            if npcs.count(|k| matches!(k, NpcGroupKind::Convoy { .. }))
+                < self.target_count(Self::CONVOY_DENSITY)
+            {
+                self.spawn_convoy(&mut npcs);
+            } else if npcs.count(|k| matches!(k, NpcGroupKind::Patrol { .. }))
+                < self.target_count(Self::PATROL_DENSITY)
+            {
+                self.spawn_patrol(&mut npcs);
+            }
+        }
+
+        for group in npcs.groups.iter_mut() {
+            group.age = group.age.saturating_add(delta);
+            if !group.retain_alive() {
+                continue;
+            }
+            group.record_attackers(&self.hits);
+
+            let orders = self.npc_orders(group);
+            if orders.is_empty() {
+                group.retire();
+                continue;
+            }
+
+            for (member, order) in group.members.iter().zip(orders) {
+                self.carry_out_npc_order(member, order);
+            }
+        }
+        npcs.groups.retain(|g| !g.members.is_empty());
+
+        self.npcs = npcs;
+    }
+
+    /// Decides what each member of a group should do, or returns no orders if the group should
+    /// retire.
+    fn npc_orders(&self, group: &mut NpcGroup) -> Vec<NpcOrder> {
+        let members = &group.members;
+        let leader = &self.entities[Self::npc_entity_index(&members[0]).unwrap()];
+        let leader_data = leader.data();
+        let leader_position = leader.transform.position;
+        let forward = leader.transform.direction.to_vec();
+        let side = forward.perp();
+
+        // Followers keep station on either side of, and slightly behind, the leader.
+        let station = |i: usize| -> Vec2 {
+            let rank = ((i + 1) / 2) as f32;
+            let file = if i % 2 == 0 { 1.0 } else { -1.0 };
+            leader_position
+                + forward * (leader_data.length * 0.5 - rank * 150.0)
+                + side * (file * (leader_data.width + 100.0))
+        };
+
+        let follower_order = |i: usize, target: Option<EntityIndex>| -> NpcOrder {
+            if let Some(target) = target {
+                NpcOrder {
+                    destination: self.entities[target].transform.position,
+                    throttle: 1.0,
+                    target: Some(target),
+                }
+            } else {
+                let destination = station(i);
+                let behind = self.npc_distance_squared(&members[i], destination) > 100f32.powi(2);
+                NpcOrder {
+                    destination,
+                    throttle: if behind { 1.0 } else { 0.6 },
+                    target: None,
+                }
+            }
+        };
+
+        match &mut group.kind {
+            NpcGroupKind::Convoy { destination } => {
+                if leader_position.distance_squared(*destination) < 250f32.powi(2)
+                    || group.age > Self::CONVOY_MAX_AGE
+                {
+                    // Delivered (or gave up).
+                    return Vec::new();
+                }
+
+                // Escorts leave players alone unless they attack the convoy.
+                let attackers = &group.attackers;
+                let threat = self.closest_npc_target(leader_position, Self::ESCORT_RANGE, |e| {
+                    attackers.contains(&e.borrow_player().player_id)
+                });
+                (0..members.len())
+                    .map(|i| {
+                        if i == 0 {
+                            NpcOrder {
+                                destination: *destination,
+                                throttle: 0.6,
+                                // Only matters if the tanker sank, and an escort took the lead.
+                                target: threat,
+                            }
+                        } else {
+                            follower_order(i, threat)
+                        }
+                    })
+                    .collect()
+            }
+            NpcGroupKind::Patrol { home, waypoint } => {
+                // Weak players are those that aren't new, but are out-leveled by the pirates.
+                let prey = self.closest_npc_target(
+                    leader_position,
+                    leader_data.sensors.visual.range,
+                    |e| {
+                        let data = e.data();
+                        data.level > 1 && data.level <= leader_data.level
+                    },
+                );
+
+                if leader_position.distance_squared(*waypoint) < 200f32.powi(2) {
+                    *waypoint = *home + gen_radius(&mut thread_rng(), Self::PATROL_RANGE);
+                }
+
+                (0..members.len())
+                    .map(|i| {
+                        if i == 0 {
+                            if let Some(prey) = prey {
+                                NpcOrder {
+                                    destination: self.entities[prey].transform.position,
+                                    throttle: 1.0,
+                                    target: Some(prey),
+                                }
+                            } else {
+                                NpcOrder {
+                                    destination: *waypoint,
+                                    throttle: 0.5,
+                                    target: None,
+                                }
+                            }
+                        } else {
+                            follower_order(i, prey)
+                        }
+                    })
+                    .collect()
+            }
+        }
+    }
+
+    /// Steers, aims, and fires an NPC's boat, using the same control path as players.
+    fn carry_out_npc_order(&mut self, member: &Arc<PlayerTuple<Server>>, order: NpcOrder) {
+        let entity_index = if let Some(entity_index) = Self::npc_entity_index(member) {
+            entity_index
+        } else {
+            return;
+        };
+        let entity = &self.entities[entity_index];
+        let target = order.target.map(|t| &self.entities[t]);
+
+        let fire = target
+            .filter(|_| thread_rng().gen_bool(Self::NPC_FIRE_CHANCE))
+            .and_then(|target| Self::npc_armament(entity, target))
+            .map(|armament_index| Fire { armament_index });
+
+        let control = Control {
+            guidance: Some(Guidance {
+                direction_target: self.npc_steer(entity, order.destination),
+                velocity_target: entity.data().speed * order.throttle,
+            }),
+            depth: Depth::Surface,
+            aim_target: target.map(|t| t.transform.position),
+            active: true,
+            fire,
+            pay: None,
+            hint: None,
+        };
+
+        if let Err(e) = control.apply(self, member) {
+            debug!("NPC control resulted in {}", e);
+        }
+    }
+
+    /// Returns the direction towards the destination, deflected away from land and the border.
+    fn npc_steer(&self, entity: &Entity, destination: Vec2) -> Angle {
+        let data = entity.data();
+        let position = entity.transform.position;
+        let mut movement = (destination - position).normalize_or_zero();
+
+        const SAMPLES: u32 = 10;
+        for i in 0..SAMPLES {
+            let angle =
+                Angle::from_radians(i as f32 * (2.0 * std::f32::consts::PI / SAMPLES as f32));
+            let delta_position = angle.to_vec() * data.length * 2.0;
+            let sample = position + delta_position;
+            if sample.length_squared() > (self.radius * 0.95).powi(2)
+                || self.terrain.sample(sample).unwrap_or(Altitude::MIN) >= terrain::SAND_LEVEL
+            {
+                movement -= angle.to_vec() * (4.0 / SAMPLES as f32);
+            }
+        }
+
+        Angle::from(movement)
+    }
+
+    /// Returns the closest surfaced, non-NPC boat within range that satisfies a filter.
+    fn closest_npc_target(
+        &self,
+        position: Vec2,
+        range: f32,
+        filter: impl Fn(&Entity) -> bool,
+    ) -> Option<EntityIndex> {
+        self.entities
+            .iter_radius(position, range)
+            .filter(|(_, entity)| {
+                entity.is_boat()
+                    && !entity.altitude.is_submerged()
+                    && !Npcs::is_npc(entity.borrow_player().player_id)
+                    && filter(entity)
+            })
+            .min_by(|(_, a), (_, b)| {
+                let a = a.transform.position.distance_squared(position);
+                let b = b.transform.position.distance_squared(position);
+                a.partial_cmp(&b).unwrap()
+            })
+            .map(|(index, _)| index)
+    }
+
+    /// Returns the index of a ready armament that could hit the target, if any.
+    fn npc_armament(entity: &Entity, target: &Entity) -> Option<u8> {
+        let data = entity.data();
+        let loadout = entity.extension().loadout();
+        let turrets = &entity.extension().turrets;
+        let distance = entity
+            .transform
+            .position
+            .distance(target.transform.position);
+
+        entity
+            .armaments()
+            .iter()
+            .enumerate()
+            .find(|&(i, armament)| {
+                let armament_data = armament.entity_type.data();
+                if !entity.is_armament_ready(i)
+                    || armament_data.kind != EntityKind::Weapon
+                    || !matches!(
+                        armament_data.sub_kind,
+                        EntitySubKind::Missile
+                            | EntitySubKind::Rocket
+                            | EntitySubKind::Shell
+                            | EntitySubKind::Torpedo
+                    )
+                    || distance > armament_data.range
+                {
+                    return false;
+                }
+
+                if let Some(turret_index) = armament.turret {
+                    if !data.turrets[turret_index].within_azimuth(turrets[turret_index]) {
+                        return false;
+                    }
+                }
+
+                let transform = entity.transform + data.armament_transform(loadout, turrets, i);
+                armament.vertical
+                    || (Angle::from(target.transform.position - transform.position)
+                        - transform.direction)
+                        .abs()
+                        < Angle::from_degrees(60.0)
+            })
+            .map(|(i, _)| i as u8)
+    }
+
+    /// Spawns a tanker, with escorts, at one port bound for another.
+    fn spawn_convoy(&mut self, npcs: &mut Npcs) {
+        let mut rng = thread_rng();
+        let max_distance_squared = (self.radius * 0.9).powi(2);
+        let ports = || {
+            self.ports
+                .iter()
+                .map(|p| p.position)
+                .filter(move |p| p.length_squared() < max_distance_squared)
+        };
+
+        let origin = if let Some(origin) = ports().choose(&mut rng) {
+            origin
+        } else {
+            return;
+        };
+        let destination = ports()
+            .filter(|d| d.distance_squared(origin) > Self::CONVOY_MIN_DISTANCE.powi(2))
+            .choose(&mut rng);
+        if let Some(destination) = destination {
+            self.spawn_convoy_between(npcs, origin, destination);
+        }
+    }
+
+    /// Spawns a tanker, with escorts, at the origin bound for the destination.
+    fn spawn_convoy_between(&mut self, npcs: &mut Npcs, origin: Vec2, destination: Vec2) {
+        let mut rng = thread_rng();
+        let tanker = if let Some(tanker) = self.spawn_npc(npcs, EntityType::Tanker, origin) {
+            tanker
+        } else {
+            return;
+        };
+        let mut members = vec![tanker];
+
+        for _ in 0..rng.gen_range(1..=3) {
+            if let Some(escort) = EntityType::iter()
+                .filter(|t| {
+                    let data = t.data();
+                    data.kind == EntityKind::Boat
+                        && !data.npc
+                        && matches!(
+                            data.sub_kind,
+                            EntitySubKind::Corvette | EntitySubKind::Destroyer
+                        )
+                        && (2..=4).contains(&data.level)
+                })
+                .choose(&mut rng)
+                .and_then(|escort| self.spawn_npc(npcs, escort, origin))
+            {
+                members.push(escort);
+            }
+        }
+
+        npcs.groups.push(NpcGroup {
+            kind: NpcGroupKind::Convoy { destination },
+            members,
+            age: Ticks::ZERO,
+            attackers: Vec::new(),
+        });
+    }
+
+    /// Spawns a pirate patrol in the waters frequented by low level players.
+    fn spawn_patrol(&mut self, npcs: &mut Npcs) {
+        let mut rng = thread_rng();
+        let home = Vec2::new(
+            (rng.gen::<f32>() - 0.5) * self.radius,
+            -rng.gen::<f32>() * 0.5 * self.radius,
+        );
+
+        let members: Vec<_> = (0..rng.gen_range(1..=3))
+            .filter_map(|_| {
+                let pirate = EntityType::iter()
+                    .filter(|t| {
+                        t.data().kind == EntityKind::Boat
+                            && t.data().sub_kind == EntitySubKind::Pirate
+                    })
+                    .choose(&mut rng)?;
+                self.spawn_npc(npcs, pirate, home)
+            })
+            .collect();
+
+        if !members.is_empty() {
+            npcs.groups.push(NpcGroup {
+                kind: NpcGroupKind::Patrol {
+                    home,
+                    waypoint: home,
+                },
+                members,
+                age: Ticks::ZERO,
+                attackers: Vec::new(),
+            });
+        }
+    }
+
+    /// Spawns an NPC boat near a position, returning its player if successful.
+    fn spawn_npc(
+        &mut self,
+        npcs: &mut Npcs,
+        entity_type: EntityType,
+        position: Vec2,
+    ) -> Option<Arc<PlayerTuple<Server>>> {
+        let mut player = PlayerData::new(npcs.next_player_id(), None);
+        // Worth sinking.
+        player.score = level_to_score(entity_type.data().level);
+        let player = Arc::new(PlayerTuple::new(player));
+
+        let mut boat = Entity::new(entity_type, Some(Arc::clone(&player)));
+        boat.transform.position = position;
+        self.spawn_here_or_nearby(boat, entity_type.data().radius * 2.0, None)
+            .map(|_| player)
+    }
+
+    /// Returns the index of an NPC's boat, if it is alive.
+    fn npc_entity_index(member: &Arc<PlayerTuple<Server>>) -> Option<EntityIndex> {
+        if let Status::Alive { entity_index, .. } = member.borrow_player().data.status {
+            Some(entity_index)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the squared distance from an NPC's boat to a position, or infinity if it sank.
+    fn npc_distance_squared(&self, member: &Arc<PlayerTuple<Server>>, position: Vec2) -> f32 {
+        Self::npc_entity_index(member).map_or(f32::INFINITY, |i| {
+            self.entities[i]
+                .transform
+                .position
+                .distance_squared(position)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::Entity;
+    use crate::npc::{NpcGroupKind, Npcs};
+    use crate::world::{Hit, World};
+    use common::angle::Angle;
+    use common::entity::{EntityKind, EntitySubKind, EntityType};
+    use common::protocol::Port;
+    use common::terrain::Terrain;
+    use common::ticks::Ticks;
+    use core_protocol::id::PlayerId;
+    use core_protocol::name::PlayerAlias;
+    use game_server::player::{PlayerData, PlayerTuple};
+    use glam::{vec2, Vec2};
+    use std::num::NonZeroU32;
+    use std::sync::Arc;
+
+    fn destination() -> Vec2 {
+        vec2(3000.0, 0.0)
+    }
+
+    /// Returns a world containing only a convoy, at the origin and bound for `destination()`.
+    fn convoy() -> World {
+        let mut world = World::new(10000.0);
+        world.terrain = Terrain::new();
+
+        let mut npcs = Npcs::default();
+        // No other groups.
+        npcs.spawn_cooldown = Ticks::MAX;
+        world.spawn_convoy_between(&mut npcs, Vec2::ZERO, destination());
+        assert_eq!(npcs.groups.len(), 1);
+        world.npcs = npcs;
+        world
+    }
+
+    fn leader_position(world: &World) -> Vec2 {
+        let leader = World::npc_entity_index(&world.npcs.groups[0].members[0]).unwrap();
+        world.entities[leader].transform.position
+    }
+
+    #[test]
+    fn spawn_convoy() {
+        let world = convoy();
+        let group = &world.npcs.groups[0];
+        assert!(
+            matches!(group.kind, NpcGroupKind::Convoy { destination: d } if d == destination())
+        );
+        assert!((2..=4).contains(&group.members.len()));
+
+        for (i, member) in group.members.iter().enumerate() {
+            assert!(Npcs::is_npc(member.borrow_player().player_id));
+            let data = world.entities[World::npc_entity_index(member).unwrap()].data();
+            assert_eq!(data.kind, EntityKind::Boat);
+            if i == 0 {
+                assert_eq!(data.sub_kind, EntitySubKind::Tanker);
+            } else {
+                assert!(matches!(
+                    data.sub_kind,
+                    EntitySubKind::Corvette | EntitySubKind::Destroyer
+                ));
+            }
+        }
+    }
+
+    #[test]
+    fn convoy_route() {
+        let mut world = World::new(10000.0);
+        world.terrain = Terrain::new();
+        let mut npcs = Npcs::default();
+
+        // Nowhere to go.
+        world.spawn_convoy(&mut npcs);
+        assert!(npcs.groups.is_empty());
+
+        let ports = [vec2(0.0, 0.0), vec2(500.0, 0.0), vec2(3000.0, 0.0)];
+        world.ports = ports
+            .iter()
+            .map(|&position| Port {
+                position,
+                direction: Angle::ZERO,
+            })
+            .collect();
+        world.spawn_convoy(&mut npcs);
+
+        let group = &npcs.groups[0];
+        let leader = World::npc_entity_index(&group.members[0]).unwrap();
+        let origin = world.entities[leader].transform.position;
+        if let NpcGroupKind::Convoy { destination } = group.kind {
+            assert!(ports.contains(&destination));
+            assert!(destination.distance(origin) > World::CONVOY_MIN_DISTANCE * 0.9);
+        } else {
+            panic!("not a convoy");
+        }
+    }
+
+    #[test]
+    fn escorts_engage_attackers() {
+        let mut world = convoy();
+
+        let player = Arc::new(PlayerTuple::new(PlayerData::new(
+            PlayerId(NonZeroU32::new(1).unwrap()),
+            None,
+        )));
+        let mut boat = Entity::new(EntityType::G5, Some(Arc::clone(&player)));
+        boat.transform.position = leader_position(&world) + vec2(0.0, 500.0);
+        assert!(world.spawn_here_or_nearby(boat, 0.0, None).is_some());
+        let boat_index = player
+            .borrow_player()
+            .data
+            .status
+            .get_entity_index()
+            .unwrap();
+
+        let escort_targets = |world: &mut World| {
+            let mut npcs = std::mem::take(&mut world.npcs);
+            let orders = world.npc_orders(&mut npcs.groups[0]);
+            world.npcs = npcs;
+            orders
+                .into_iter()
+                .skip(1)
+                .map(|o| o.target)
+                .collect::<Vec<_>>()
+        };
+
+        // Sailing nearby is not a threat.
+        world.update_npcs(Ticks::ONE);
+        assert!(escort_targets(&mut world).iter().all(Option::is_none));
+
+        // Attacking the convoy is.
+        let tanker = world.npcs.groups[0].members[0].borrow_player().player_id;
+        world.hits.push(Hit {
+            attacker: player.borrow_player().player_id,
+            attacker_alias: PlayerAlias::default(),
+            victim: tanker,
+            victim_alias: PlayerAlias::default(),
+            entity_type: EntityType::Mark18,
+            amount: Ticks::ONE,
+        });
+        world.update_npcs(Ticks::ONE);
+        assert!(escort_targets(&mut world)
+            .iter()
+            .all(|&t| t == Some(boat_index)));
+    }
+
+    #[test]
+    fn retire_convoy() {
+        for delivered in [true, false] {
+            let mut world = convoy();
+            let leader_position = leader_position(&world);
+            let group = &mut world.npcs.groups[0];
+            if delivered {
+                group.kind = NpcGroupKind::Convoy {
+                    destination: leader_position,
+                };
+            } else {
+                group.age = World::CONVOY_MAX_AGE;
+            }
+            let members = group.members.clone();
+
+            world.update_npcs(Ticks::ONE);
+            assert!(world.npcs.groups.is_empty());
+            for member in &members {
+                assert!(member.borrow_player().data.flags.left_game);
+            }
+
+            // Their boats are removed, without leaving anything behind.
+            world.physics(Ticks::ONE);
+            assert!(world
+                .entities
+                .iter_radius(leader_position, 1000.0)
+                .next()
+                .is_none());
+        }
+    }
+}
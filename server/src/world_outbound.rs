@@ -7,21 +7,36 @@ use crate::entity::Entity;
 use crate::player::Status;
 use crate::server::Server;
 use crate::world::World;
+use common::altitude::Altitude;
 use common::entity::{EntityKind, EntitySubKind};
+use common::protocol::{Depth, SpectateTarget};
 use common::ticks::Ticks;
 use common_util::range::{map_ranges, map_ranges_fast};
-use game_server::player::PlayerTuple;
+use core_protocol::id::TeamId;
+use game_server::player::{PlayerRepo, PlayerTuple};
 use glam::{vec2, Vec2};
 
 impl World {
     /// get_player_complete gets the complete update for a player, corresponding to everything they
-    /// are able to see at the current moment.
+    /// are able to see at the current moment. Spectators see what the player they follow sees.
     pub fn get_player_complete<'a>(
         &'a self,
         tuple: &'a PlayerTuple<Server>,
+        players: &'a PlayerRepo<Server>,
     ) -> CompleteRef<'a, impl Iterator<Item = ContactRef>> {
         let player = tuple.borrow_player();
-        let player_entity = match &player.data.status {
+
+        // The player whose boat determines visibility.
+        let viewer: &PlayerTuple<Server> = match player.data.spectate {
+            Some(SpectateTarget::Player(player_id)) if !player.data.status.is_alive() => players
+                .get(player_id)
+                .map(|followed| &**followed)
+                .filter(|followed| followed.borrow_player().data.status.is_alive())
+                .unwrap_or(tuple),
+            _ => tuple,
+        };
+
+        let player_entity = match &viewer.borrow_player().data.status {
             Status::Alive { entity_index, .. } => {
                 let entity = &self.entities[*entity_index];
                 debug_assert!(entity.is_boat());
@@ -32,9 +47,12 @@ impl World {
 
         struct Camera {
             active: bool,
+            altitude: Altitude,
             inner: f32,
             position: Vec2,
             radar: f32,
+            /// Height above sea level, if terrain can block line of sight.
+            sight_height: Option<f32>,
             sonar: f32,
             speed: f32,
             view: f32,
@@ -62,12 +80,14 @@ impl World {
                 sensors.sonar.range
             };
 
-            if player.data.status.is_alive() {
+            if viewer.borrow_player().data.status.is_alive() {
                 Camera {
                     active: entity.extension().is_active(),
+                    altitude: entity.altitude,
                     inner: data.radii().start,
                     position: entity.transform.position,
                     radar,
+                    sight_height: Some(entity.sight_height()),
                     sonar,
                     speed: entity.transform.velocity.abs().to_mps(),
                     view: data.camera_range(),
@@ -76,6 +96,20 @@ impl World {
             } else {
                 panic!("player not alive in outbound");
             }
+        } else if let Some(SpectateTarget::Free(position)) = player.data.spectate {
+            let range = SpectateTarget::FREE_RANGE;
+            Camera {
+                active: true,
+                altitude: Altitude::ZERO,
+                inner: 0.0,
+                position,
+                radar: range,
+                sight_height: None,
+                sonar: range,
+                speed: 0.0,
+                view: range,
+                visual: range,
+            }
         } else if let Status::Dead {
             position,
             time,
@@ -88,9 +122,11 @@ impl World {
             let range = map_ranges(elapsed, 10.0..2.0, 0.0..visual_range, true).max(500.0);
             Camera {
                 active: true,
+                altitude: Altitude::ZERO,
                 inner: 0.0,
                 position,
                 radar: range,
+                sight_height: None,
                 sonar: range,
                 speed: 0.0,
                 view: range,
@@ -100,9 +136,11 @@ impl World {
             let range = 500.0;
             Camera {
                 active: true,
+                altitude: Altitude::ZERO,
                 inner: 0.0,
                 position: Vec2::ZERO,
                 radar: range,
+                sight_height: None,
                 sonar: range,
                 speed: 0.0,
                 view: range,
@@ -121,6 +159,17 @@ impl World {
         let camera_pos = camera.position;
         let camera_view = camera.view;
 
+        // Smoke and chaff clouds that may hide contacts from the camera.
+        let clouds: Vec<(Vec2, f32, EntitySubKind)> = self
+            .entities
+            .iter_radius(camera.position, max_range)
+            .filter_map(|(_, e)| {
+                let data = e.data();
+                let radius = data.cloud_radius();
+                (radius > 0.0).then_some((e.transform.position, radius, data.sub_kind))
+            })
+            .collect();
+
         let contacts = player_entity
             .into_iter()
             .chain(
@@ -137,8 +186,8 @@ impl World {
                 // Variables related to the relationship between the player and the contact.
                 let distance_squared = camera.position.distance_squared(entity.transform.position);
                 let same_player =
-                    entity.player.is_some() && tuple == &**entity.player.as_ref().unwrap();
-                let friendly = entity.is_friendly_to_player(Some(tuple));
+                    entity.player.is_some() && viewer == &**entity.player.as_ref().unwrap();
+                let friendly = entity.is_friendly_to_player(Some(viewer));
                 let known = same_player || (friendly && distance_squared < 800f32.powi(2));
 
                 // Variables related to detecting the contact.
@@ -147,18 +196,37 @@ impl World {
                 let altitude = entity.altitude;
 
                 if !known {
-                    let inv_size = data.inv_size;
+                    // Smoke and chaff clouds are much larger than the decoys that spread them.
+                    let inv_size = if data.cloud_radius() > 0.0 {
+                        1.0
+                    } else {
+                        data.inv_size
+                    };
                     let default_ratio = distance_squared * inv_size;
                     uncertainty = 1.0;
                     let entity_abs_vel = entity.transform.velocity.abs().to_mps();
+                    let position = entity.transform.position;
+
+                    // Radar and visual, which are blocked by terrain.
+                    let mut sight_uncertainty = 1f32;
+
+                    // Chaff clutters radar returns from anything inside it.
+                    let jamming = if clouds.iter().any(|&(center, radius, sub_kind)| {
+                        sub_kind == EntitySubKind::Chaff
+                            && center.distance_squared(position) < radius.powi(2)
+                    }) {
+                        4.0
+                    } else {
+                        1.0
+                    };
 
                     if radar_range_inv.is_finite() && !altitude.is_submerged() {
-                        let radar_ratio = default_ratio * radar_range_inv;
+                        let radar_ratio = default_ratio * radar_range_inv * jamming;
 
                         if camera.active {
                             // Active radar can see moving targets easier.
-                            uncertainty =
-                                uncertainty.min(radar_ratio * 15.0 / (15.0 + entity_abs_vel));
+                            sight_uncertainty =
+                                sight_uncertainty.min(radar_ratio * 15.0 / (15.0 + entity_abs_vel));
                         }
 
                         // Always-on passive radar:
@@ -186,11 +254,15 @@ impl World {
                         };
                         // let passive_radar_ratio = BASE_FACTOR / emission;
 
-                        uncertainty = uncertainty.min(passive_radar_ratio);
+                        sight_uncertainty = sight_uncertainty.min(passive_radar_ratio * jamming);
                     }
 
                     if sonar_range_inv.is_finite() && !altitude.is_airborne() {
                         let mut sonar_ratio = default_ratio * sonar_range_inv;
+                        if Depth::across_thermocline(camera.altitude, altitude) {
+                            // The thermocline bends sound away.
+                            sonar_ratio *= 3.0;
+                        }
                         if camera.active {
                             // Active sonar.
                             uncertainty = uncertainty.min(sonar_ratio);
@@ -226,7 +298,14 @@ impl World {
                         uncertainty = uncertainty.min(sonar_ratio);
                     }
 
-                    if visual_range_inv.is_finite() {
+                    // Smoke blocks visual line of sight through it.
+                    let obscured = clouds.iter().any(|&(center, radius, sub_kind)| {
+                        sub_kind == EntitySubKind::Smoke
+                            && center != position
+                            && segment_intersects_circle(camera.position, position, center, radius)
+                    });
+
+                    if visual_range_inv.is_finite() && !obscured {
                         let mut visual_ratio = default_ratio * visual_range_inv;
                         if altitude.is_submerged() {
                             let extra = if data.kind == EntityKind::Boat
@@ -247,7 +326,23 @@ impl World {
                             ) + extra;
                         }
                         visible = visual_ratio < 1.0;
-                        uncertainty = uncertainty.min(visual_ratio);
+                        sight_uncertainty = sight_uncertainty.min(visual_ratio);
+                    }
+
+                    // Only ray march through terrain if radar or visual would make a difference.
+                    if (visible || sight_uncertainty < uncertainty)
+                        && camera.sight_height.map_or(true, |height| {
+                            self.terrain.line_of_sight(
+                                camera.position,
+                                height,
+                                position,
+                                entity.sight_height(),
+                            )
+                        })
+                    {
+                        uncertainty = uncertainty.min(sight_uncertainty);
+                    } else {
+                        visible = false;
                     }
 
                     if player_entity.is_some()
@@ -292,3 +387,171 @@ impl World {
         CompleteRef::new(contacts, player, self, camera_pos, camera_dims)
     }
 }
+
+/// Returns true if a spectator watching `target` sees beyond their own team, in which case they
+/// can't spawn until `SpectateTarget::SPAWN_DELAY` after, so they can't scout for their team.
+pub(crate) fn watches_beyond_team(
+    target: SpectateTarget,
+    team_id: Option<TeamId>,
+    players: &PlayerRepo<Server>,
+) -> bool {
+    match target {
+        SpectateTarget::Player(player_id) => players.get(player_id).map_or(false, |followed| {
+            team_id.is_none() || followed.borrow_player().team_id() != team_id
+        }),
+        SpectateTarget::Free(_) => true,
+    }
+}
+
+/// Returns true if the line segment from `start` to `end` passes within `radius` of `center`.
+fn segment_intersects_circle(start: Vec2, end: Vec2, center: Vec2, radius: f32) -> bool {
+    let segment = end - start;
+    let t = ((center - start).dot(segment) / segment.length_squared().max(f32::EPSILON))
+        .clamp(0.0, 1.0);
+    (start + segment * t).distance_squared(center) < radius.powi(2)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::entity::Entity;
+    use crate::world::World;
+    use crate::world_outbound::{segment_intersects_circle, watches_beyond_team};
+    use crate::Server;
+    use common::contact::ContactTrait;
+    use common::entity::EntityType;
+    use common::protocol::SpectateTarget;
+    use common::terrain::{ChunkSet, Terrain};
+    use common::ticks::Ticks;
+    use core_protocol::id::{PlayerId, TeamId};
+    use game_server::player::{PlayerData, PlayerRepo, PlayerTuple};
+    use glam::vec2;
+    use std::num::NonZeroU32;
+    use std::sync::Arc;
+
+    fn player(id: u32) -> Arc<PlayerTuple<Server>> {
+        Arc::new(PlayerTuple::new(PlayerData::new(
+            PlayerId(NonZeroU32::new(id).unwrap()),
+            None,
+        )))
+    }
+
+    #[test]
+    fn smoke_obscures_line_of_sight() {
+        let center = vec2(0.0, 0.0);
+
+        // Straight through, grazing, and missing.
+        assert!(segment_intersects_circle(
+            vec2(-100.0, 0.0),
+            vec2(100.0, 0.0),
+            center,
+            10.0
+        ));
+        assert!(segment_intersects_circle(
+            vec2(-100.0, 9.0),
+            vec2(100.0, 9.0),
+            center,
+            10.0
+        ));
+        assert!(!segment_intersects_circle(
+            vec2(-100.0, 11.0),
+            vec2(100.0, 11.0),
+            center,
+            10.0
+        ));
+
+        // The cloud must be between the ends, not merely on the same line.
+        assert!(!segment_intersects_circle(
+            vec2(20.0, 0.0),
+            vec2(100.0, 0.0),
+            center,
+            10.0
+        ));
+        assert!(segment_intersects_circle(
+            vec2(5.0, 0.0),
+            vec2(100.0, 0.0),
+            center,
+            10.0
+        ));
+
+        // Degenerate segment.
+        assert!(segment_intersects_circle(
+            vec2(3.0, 4.0),
+            vec2(3.0, 4.0),
+            center,
+            10.0
+        ));
+        assert!(!segment_intersects_circle(
+            vec2(30.0, 40.0),
+            vec2(30.0, 40.0),
+            center,
+            10.0
+        ));
+    }
+
+    #[test]
+    fn watching_beyond_team() {
+        let followed = player(1);
+        let followed_id = followed.borrow_player().player_id;
+        let mut players = PlayerRepo::new();
+        players.insert(followed_id, followed);
+
+        assert!(watches_beyond_team(
+            SpectateTarget::Player(followed_id),
+            None,
+            &players
+        ));
+        assert!(watches_beyond_team(
+            SpectateTarget::Player(followed_id),
+            Some(TeamId(NonZeroU32::new(1).unwrap())),
+            &players
+        ));
+        assert!(watches_beyond_team(
+            SpectateTarget::Free(vec2(0.0, 0.0)),
+            None,
+            &players
+        ));
+
+        // Nothing to see.
+        let missing_id = PlayerId(NonZeroU32::new(2).unwrap());
+        assert!(!watches_beyond_team(
+            SpectateTarget::Player(missing_id),
+            None,
+            &players
+        ));
+    }
+
+    #[test]
+    fn spectator_sees_what_followed_sees() {
+        let mut world = World::new(10000.0);
+        world.terrain = Terrain::new();
+
+        let followed = player(1);
+        let mut boat = Entity::new(EntityType::G5, Some(Arc::clone(&followed)));
+        boat.transform.position = vec2(3000.0, 0.0);
+        assert!(world.spawn_here_or_nearby(boat, 0.0, None).is_some());
+        let followed_id = followed.borrow_player().player_id;
+
+        let spectator = player(2);
+        let mut players = PlayerRepo::new();
+        players.insert(followed_id, Arc::clone(&followed));
+        for target in [
+            None,
+            Some(SpectateTarget::Player(followed_id)),
+            Some(SpectateTarget::Free(vec2(3000.0, 0.0))),
+        ] {
+            spectator.borrow_player_mut().data.spectate = target;
+            let update = world
+                .get_player_complete(&spectator, &players)
+                .into_update(Ticks::ZERO, &mut ChunkSet::new());
+            assert_eq!(
+                update
+                    .contacts
+                    .iter()
+                    .any(|c| c.player_id() == Some(followed_id)),
+                target.is_some(),
+                "{:?}",
+                target
+            );
+        }
+    }
+}
@@ -8,6 +8,7 @@ use common::altitude::Altitude;
 use common::angle::Angle;
 use common::death_reason::DeathReason;
 use common::entity::*;
+use common::protocol::{Depth, SortieStatus};
 use common::terrain::TerrainMutation;
 use common::ticks::Ticks;
 use common::transform::Transform;
@@ -17,14 +18,14 @@ use common::world::{
 };
 use common_util::range::map_ranges;
 use glam::Vec2;
-use maybe_parallel_iterator::{IntoMaybeParallelIterator, MaybeParallelSort};
+use maybe_parallel_iterator::IntoMaybeParallelIterator;
 use rand::Rng;
 use std::sync::{Arc, Mutex};
 
 /// Fate terminates the physics for a particular entity with a single fate.
 enum Fate {
     Remove(DeathReason),
-    MoveSector,
+    Relocate,
     DowngradeHq,
 }
 
@@ -37,19 +38,28 @@ impl World {
         let border_radius = self.radius; // Avoids double borrow.
         let border_radius_squared = self.radius.powi(2);
         let terrain = &self.terrain;
+        let storm = self.events.storm;
+        let ports = &self.ports;
 
         // Collected updates (order doesn't matter).
         let terrain_mutations = Mutex::new(Vec::new());
         let barrel_spawns = Mutex::new(Vec::new());
         let reset_flags = Mutex::new(Vec::new());
+        let air_wing_updates = Mutex::new(Vec::new());
 
-        let mut fates: Vec<_> = self
+        // Other entities can't be accessed while updating aircraft, so they land where their boat
+        // was as of the last update.
+        let landing_pads = std::mem::take(&mut self.landing_pads);
+        let next_landing_pads = Mutex::new(Vec::new());
+
+        let fates: Vec<_> = self
             .entities
             .par_iter_mut()
             .into_maybe_parallel_iter()
             .filter_map(|(index, entity)| {
                 let index = index as EntityIndex;
                 let data = entity.data();
+                let initial_position = entity.transform.position;
 
                 if data.lifespan != Ticks::ZERO {
                     entity.ticks = entity.ticks.saturating_add(delta);
@@ -87,16 +97,38 @@ impl World {
 
                 match data.kind {
                     EntityKind::Aircraft => {
-                        let position_diff = if let Status::Alive {
-                            aim_target: Some(aim_target),
-                            ..
-                        } = entity.borrow_player().data.status
-                        {
-                            aim_target - entity.transform.position
-                        } else {
-                            // Hover when no target or player is dead.
-                            Vec2::ZERO
-                        };
+                        let landing_pad = landing_pads.get(&entity.id).copied();
+                        let returning = landing_pad.map_or(false, |(pad, returning)| {
+                            returning || entity.is_bingo_fuel(pad)
+                        });
+
+                        if landing_pad.is_some() {
+                            if let Status::Alive { entity_index, .. } =
+                                entity.borrow_player().data.status
+                            {
+                                let fuel = data.lifespan.saturating_sub(entity.ticks);
+                                air_wing_updates.lock().unwrap().push((
+                                    entity_index,
+                                    entity.id,
+                                    fuel,
+                                    returning,
+                                ));
+                            }
+                        }
+
+                        let position_diff =
+                            if let Some((pad, _)) = landing_pad.filter(|_| returning) {
+                                pad - entity.transform.position
+                            } else if let Status::Alive {
+                                aim_target: Some(aim_target),
+                                ..
+                            } = entity.borrow_player().data.status
+                            {
+                                aim_target - entity.transform.position
+                            } else {
+                                // Hover when no target or player is dead.
+                                Vec2::ZERO
+                            };
 
                         entity.guidance.direction_target = Angle::from(position_diff)
                             + Angle::from_radians(
@@ -132,6 +164,14 @@ impl World {
                         }
 
                         entity.apply_altitude_target(terrain, None, 4.0, delta);
+
+                        // Aircraft don't last long in a storm.
+                        if storm.map_or(false, |s| s.contains(entity.transform.position))
+                            && rand::thread_rng()
+                                .gen_bool((1.0 - 0.8f32.powf(delta_seconds)) as f64)
+                        {
+                            return Some((index, Fate::Remove(DeathReason::Unknown)));
+                        }
                     }
                     EntityKind::Collectible | EntityKind::Weapon | EntityKind::Decoy => {
                         let altitude_change =
@@ -172,6 +212,45 @@ impl World {
                             delta,
                         );
 
+                        if data.sub_kind == EntitySubKind::Submarine {
+                            // Diesel engines can only run (and recharge the battery) with air from
+                            // a snorkel.
+                            let snorkeling = entity.altitude >= Depth::Periscope.altitude();
+                            let speed =
+                                entity.transform.velocity.to_mps().abs() / data.speed.to_mps();
+                            entity
+                                .extension_mut()
+                                .update_battery(snorkeling, speed, delta);
+                        }
+
+                        // Boats may dock at ports within the border, but not while submerged.
+                        let docked = !entity.altitude.is_submerged()
+                            && entity.transform.position.length_squared() < border_radius_squared
+                            && ports.iter().any(|p| p.can_dock(&entity.transform));
+                        entity.extension_mut().update_dock(docked, delta);
+
+                        // Heavy seas slow boats on the surface.
+                        if !entity.altitude.is_submerged()
+                            && storm.map_or(false, |s| s.contains(entity.transform.position))
+                        {
+                            max_speed *= 0.6;
+                        }
+
+                        if data.sub_kind == EntitySubKind::Submarine
+                            && entity.altitude.is_submerged()
+                        {
+                            max_speed *= Depth::speed_factor(entity.altitude);
+
+                            // Damaged hulls risk being crushed at depth.
+                            let damage = entity.ticks.to_secs() / data.max_health().to_secs();
+                            if entity.altitude < Depth::crush_altitude(data.depth, damage) {
+                                repair_eligible = false;
+                                if entity.kill_in(delta, Ticks::from_secs(30.0)) {
+                                    return Some((index, Fate::Remove(DeathReason::Crush)));
+                                }
+                            }
+                        }
+
                         if entity.borrow_player().data.flags != Flags::default() {
                             reset_flags
                                 .lock()
@@ -335,6 +414,26 @@ impl World {
                     entity.update_turret_aim(delta_seconds);
                     entity.reload(delta);
                     entity.extension_mut().update_tickers(delta);
+                    entity.extension_mut().update_air_wing();
+
+                    let extension = entity.extension();
+                    if !extension.air_wing.is_empty() {
+                        let mut next_landing_pads = next_landing_pads.lock().unwrap();
+                        for aircraft in extension
+                            .air_wing
+                            .iter()
+                            .filter(|a| a.status != SortieStatus::Rearming)
+                        {
+                            let pad = entity.transform
+                                + data.armament_transform(
+                                    extension.loadout(),
+                                    &extension.turrets,
+                                    aircraft.armament,
+                                );
+                            let returning = aircraft.status == SortieStatus::Returning;
+                            next_landing_pads.push((aircraft.id, (pad.position, returning)));
+                        }
+                    }
 
                     if repair_eligible {
                         let repair_amount = if data.length > 200.0 {
@@ -344,7 +443,13 @@ impl World {
                         } else {
                             1.0
                         };
-                        entity.repair(delta * repair_amount);
+                        // Ports have the facilities to repair much faster.
+                        let dock_factor = if entity.extension().is_docked() {
+                            4.0
+                        } else {
+                            1.0
+                        };
+                        entity.repair(delta * (repair_amount * dock_factor));
                     }
 
                     if data.sub_kind == EntitySubKind::Dredger {
@@ -356,14 +461,35 @@ impl World {
                     }
                 }
 
-                if index.changed(entity) {
-                    Some((index, Fate::MoveSector))
+                if entity.transform.position != initial_position {
+                    Some((index, Fate::Relocate))
                 } else {
                     None
                 }
             })
             .collect();
 
+        self.landing_pads = next_landing_pads
+            .into_inner()
+            .unwrap()
+            .into_iter()
+            .collect();
+
+        // Apply before fates, which may remove boats.
+        for (boat_index, id, fuel, returning) in air_wing_updates.into_inner().unwrap() {
+            if let Some(aircraft) = self.entities[boat_index]
+                .extension_mut()
+                .air_wing
+                .iter_mut()
+                .find(|a| a.id == id)
+            {
+                aircraft.fuel = fuel;
+                if returning {
+                    aircraft.status = SortieStatus::Returning;
+                }
+            }
+        }
+
         for (mutation, award_entity_index) in terrain_mutations.into_inner().unwrap() {
             if self.terrain.modify(mutation).unwrap_or(false) {
                 if let Some(index) = award_entity_index {
@@ -390,21 +516,18 @@ impl World {
             );
         }
 
-        // Sorted in reverse to remove correctly.
-        fates.maybe_par_sort_unstable_by(|a, b| b.0.cmp(&a.0));
-
         for (index, fate) in fates {
             match fate {
                 Fate::Remove(reason) => {
                     self.remove(index, reason);
                 }
-                Fate::MoveSector => {
-                    self.entities.move_sector(index);
+                Fate::Relocate => {
+                    self.entities.relocate(index);
                 }
                 Fate::DowngradeHq => {
                     let entity = &mut self.entities[index];
                     entity.ticks = Ticks::ZERO;
-                    entity.change_entity_type(EntityType::OilPlatform, &mut self.arena, false);
+                    entity.change_entity_type(EntityType::OilPlatform, 0, &mut self.arena, false);
                 }
             }
         }
@@ -413,8 +536,8 @@ impl World {
         self.entities
             .par_iter()
             .into_maybe_parallel_iter()
-            .for_each(|(index, entity)| {
-                assert!(!index.changed(entity));
+            .for_each(|(index, _)| {
+                assert!(self.entities.is_indexed(index));
             });
 
         // Clear flags at end so they can be asserted in Mutation::reload_limited_armament.
@@ -427,9 +550,14 @@ impl World {
 #[cfg(test)]
 mod tests {
     use crate::entity::Entity;
+    use crate::entity_extension::LaunchedAircraft;
+    use crate::protocol::CommandTrait;
     use crate::world::World;
+    use crate::world_mutation::Mutation;
     use crate::Server;
-    use common::entity::{EntityKind, EntityType};
+    use common::death_reason::DeathReason;
+    use common::entity::{EntityKind, EntitySubKind, EntityType};
+    use common::protocol::{Depth, Fire, SortieStatus};
     use common::terrain::Terrain;
     use common::ticks::Ticks;
     use core_protocol::id::PlayerId;
@@ -459,11 +587,11 @@ mod tests {
             .collect();
 
         for (typ, player) in cases.iter().zip(players.iter()) {
-            let mut entity = Entity::new(*typ, Some(Arc::clone(&player)));
+            let mut entity = Entity::new(*typ, Some(Arc::clone(player)));
             entity.damage(entity.data().max_health() - Ticks::ONE);
             //entity.damage(Ticks::from_damage(1.0));
             assert!(
-                world.spawn_here_or_nearby(entity, 10000.0, None),
+                world.spawn_here_or_nearby(entity, 10000.0, None).is_some(),
                 "could not spawn {:?}",
                 typ
             );
@@ -488,13 +616,11 @@ mod tests {
                         *typ
                     );
 
-                    if entity.ticks == Ticks::ZERO {
-                        if timings[i].1.is_none() {
-                            timings[i].1 = Some(counter);
-                            done += 1;
-                            if done == cases.len() {
-                                break 'outer;
-                            }
+                    if entity.ticks == Ticks::ZERO && timings[i].1.is_none() {
+                        timings[i].1 = Some(counter);
+                        done += 1;
+                        if done == cases.len() {
+                            break 'outer;
                         }
                     }
                 } else {
@@ -514,4 +640,163 @@ mod tests {
             println!("{:?} {:?}", case, timing.unwrap());
         }
     }
+
+    /// Tests that a damaged submarine is crushed deep, but not shallower.
+    #[test]
+    fn crush_depth() {
+        for (depth, crushed) in [(Depth::Shallow, false), (Depth::Deep, true)] {
+            let mut world = World::new(10000.0);
+            world.terrain = Terrain::new();
+
+            let player = Arc::new(PlayerTuple::new(PlayerData::new(
+                PlayerId(NonZeroU32::new(1).unwrap()),
+                None,
+            )));
+            let mut submarine = Entity::new(EntityType::Akula, Some(Arc::clone(&player)));
+            submarine.damage(submarine.data().max_health() * 0.6);
+            assert!(world.spawn_here_or_nearby(submarine, 0.0, None).is_some());
+            let index = player
+                .borrow_player()
+                .data
+                .status
+                .get_entity_index()
+                .unwrap();
+
+            let submarine = &mut world.entities[index];
+            submarine.extension_mut().set_depth(depth);
+            submarine.altitude = depth.altitude().max(-submarine.data().depth);
+            let damage = submarine.ticks;
+
+            world.physics(Ticks::ONE);
+            assert_eq!(world.entities[index].ticks > damage, crushed, "{:?}", depth);
+        }
+    }
+
+    /// Tests launching, landing, and rearming an aircraft, and losing it on a second sortie.
+    #[test]
+    fn air_wing() {
+        let mut world = World::new(10000.0);
+        world.terrain = Terrain::new();
+
+        let player = Arc::new(PlayerTuple::new(PlayerData::new(
+            PlayerId(NonZeroU32::new(1).unwrap()),
+            None,
+        )));
+        let boat = Entity::new(EntityType::ArleighBurke, Some(Arc::clone(&player)));
+        assert!(world.spawn_here_or_nearby(boat, 0.0, None).is_some());
+        let boat_index = player
+            .borrow_player()
+            .data
+            .status
+            .get_entity_index()
+            .unwrap();
+        let armament = world.entities[boat_index]
+            .armaments()
+            .iter()
+            .position(|a| a.entity_type == EntityType::Seahawk)
+            .unwrap();
+
+        let launch = |world: &mut World| {
+            Fire {
+                armament_index: armament as u8,
+            }
+            .apply(world, &player)
+            .unwrap();
+
+            let extension = world.entities[boat_index].extension();
+            assert_eq!(extension.reloads[armament], Ticks::MAX);
+            assert_eq!(extension.air_wing.len(), 1);
+            let sortie = &extension.air_wing[0];
+            assert_eq!(sortie.status, SortieStatus::Airborne);
+            assert_eq!(sortie.armament, armament);
+            let id = sortie.id;
+
+            let boat_position = world.entities[boat_index].transform.position;
+            world
+                .entities
+                .iter_radius(boat_position, 1000.0)
+                .find(|(_, e)| e.id == id)
+                .unwrap()
+                .0
+        };
+
+        // Launch.
+        let aircraft_index = launch(&mut world);
+        let aircraft_id = world.entities[aircraft_index].id;
+
+        // Attacking uses up the aircraft's weapons, so it returns without resetting its fuel.
+        world.entities[aircraft_index].ticks = Ticks::from_whole_secs(10);
+        Mutation::FireAll(EntitySubKind::Torpedo).apply(
+            &mut world,
+            aircraft_index,
+            Ticks::ONE,
+            true,
+        );
+        let torpedoes = world.arena.count_sub_kind(EntitySubKind::Torpedo);
+        assert!(torpedoes > 0);
+        assert_eq!(
+            world.entities[aircraft_index].ticks,
+            Ticks::from_whole_secs(10)
+        );
+        assert_eq!(
+            world.entities[boat_index].extension().air_wing[0].status,
+            SortieStatus::Returning
+        );
+        Mutation::FireAll(EntitySubKind::Torpedo).apply(
+            &mut world,
+            aircraft_index,
+            Ticks::ONE,
+            true,
+        );
+        assert_eq!(
+            world.arena.count_sub_kind(EntitySubKind::Torpedo),
+            torpedoes
+        );
+
+        // Boats tell their aircraft where to land.
+        world.physics(Ticks::ONE);
+        assert!(world.landing_pads[&aircraft_id].1);
+
+        // Land.
+        let boat = &world.entities[boat_index];
+        let pad = boat.transform
+            + boat.data().armament_transform(
+                boat.extension().loadout(),
+                &boat.extension().turrets,
+                armament,
+            );
+        let aircraft = &mut world.entities[aircraft_index];
+        aircraft.transform.position = pad.position;
+        assert_eq!(
+            world.entities[aircraft_index].landing_pad(&world.entities[boat_index]),
+            Some(armament)
+        );
+        Mutation::Remove(DeathReason::Landing(armament)).apply(
+            &mut world,
+            aircraft_index,
+            Ticks::ONE,
+            true,
+        );
+        let extension = world.entities[boat_index].extension();
+        assert_eq!(extension.air_wing.len(), 1);
+        assert_eq!(extension.air_wing[0].status, SortieStatus::Rearming);
+        assert_eq!(extension.reloads[armament], LaunchedAircraft::DECK_CYCLE);
+
+        // Rearm.
+        let boat = &mut world.entities[boat_index];
+        boat.reload(LaunchedAircraft::DECK_CYCLE);
+        boat.extension_mut().update_air_wing();
+        assert!(boat.extension().air_wing.is_empty());
+        assert!(boat.is_armament_ready(armament));
+
+        // Lose the aircraft on a second sortie, which takes a full reload to replace.
+        let aircraft_index = launch(&mut world);
+        Mutation::Remove(DeathReason::Unknown).apply(&mut world, aircraft_index, Ticks::ONE, true);
+        let boat = &world.entities[boat_index];
+        assert!(boat.extension().air_wing.is_empty());
+        assert_eq!(
+            boat.extension().reloads[armament],
+            boat.armaments()[armament].reload()
+        );
+    }
 }
@@ -16,6 +16,7 @@ use common::util::hash_u32_to_f32;
 use common::velocity::Velocity;
 use maybe_parallel_iterator::{IntoMaybeParallelIterator, MaybeParallelSort};
 use rand::{thread_rng, Rng};
+use std::collections::HashSet;
 use std::sync::Arc;
 use std::sync::Mutex;
 
@@ -39,6 +40,11 @@ impl World {
             _ => {}
         }
 
+        if entity.is_supply() {
+            // Enough for resupplying.
+            radius = radius.max(Entity::RESUPPLY_RANGE);
+        }
+
         radius
     }
 
@@ -65,6 +71,8 @@ impl World {
 
         // TODO: look into lock free data structures.
         let mutations = Mutex::new(Vec::new());
+        // Boats that are already being resupplied this tick.
+        let resupplied = Mutex::new(HashSet::new());
 
         self.entities
             .par_iter()
@@ -143,6 +151,17 @@ impl World {
                         }
                     }
 
+                    // Supplies resupply boats near them, whether or not they collide. Being near
+                    // multiple supplies doesn't resupply any faster.
+                    for (supply, boat) in [(entity, other_entity), (other_entity, entity)] {
+                        if supply.is_supply()
+                            && supply.can_resupply(boat)
+                            && resupplied.lock().unwrap().insert(get_index(boat))
+                        {
+                            mutate(boat, Mutation::Resupply(delta));
+                        }
+                    }
+
                     if !entity.collides_with(other_entity, delta_seconds) || !altitude_overlap {
                         if collectibles.len() == 1 && altitude_overlap {
                             // Collectibles gravitate towards players (except if the player created them).
@@ -195,10 +214,12 @@ impl World {
                                                 target_data.kind == EntityKind::Aircraft || matches!(target_data.sub_kind, EntitySubKind::Missile | EntitySubKind::Rocket | EntitySubKind::RocketTorpedo)
                                             },
                                             EntitySubKind::Torpedo => {
-                                                target_data.kind == EntityKind::Boat || target_data.kind == EntityKind::Decoy
+                                                target_data.kind == EntityKind::Boat || (target_data.kind == EntityKind::Decoy && target_data.sub_kind == EntitySubKind::Sonar)
                                             },
                                             EntitySubKind::Missile => {
-                                                target_data.kind == EntityKind::Boat && weapon.altitude_overlapping(target)
+                                                // Chaff seduces radar-guided missiles.
+                                                let chaff = target_data.kind == EntityKind::Decoy && target_data.sub_kind == EntitySubKind::Chaff && weapon_data.sensors.radar.range > 0.0;
+                                                (target_data.kind == EntityKind::Boat || chaff) && weapon.altitude_overlapping(target)
                                             }
                                             _ => {
                                                 target_data.kind == EntityKind::Boat
@@ -524,6 +545,8 @@ impl World {
                         // Collectibles don't collide with obstacles.
                     } else if boats.len() == 1 && decoys.len() == 1 {
                         // No-op; boats don't collide with decoys.
+                    } else if weapons.len() == 1 && decoys.len() == 1 && decoys[0].data().sub_kind == EntitySubKind::Smoke {
+                        // No-op; weapons pass through smoke.
                     } else if weapons.len() == 1
                         && collectibles.len() == 1
                         && (collectibles[0].entity_type == EntityType::Coin || weapons[0].data().sub_kind != EntitySubKind::Torpedo)
@@ -595,9 +618,99 @@ fn collision_multiplier(d2: f32, r2: f32, is_sub: bool) -> f32 {
 #[cfg(test)]
 mod tests {
     use crate::entity::Entity;
+    use crate::entity_extension::EntityExtension;
     use crate::world::World;
+    use crate::Server;
+    use common::angle::Angle;
     use common::entity::EntityType;
+    use common::terrain::Terrain;
     use common::ticks::Ticks;
+    use core_protocol::id::PlayerId;
+    use game_server::player::{PlayerData, PlayerTuple};
+    use glam::{vec2, Vec2};
+    use std::num::NonZeroU32;
+    use std::sync::Arc;
+
+    /// Returns the direction a `weapon_type`, fired east from the origin, steers towards
+    /// after sensing a `decoy_type` to its north east.
+    fn seduction(weapon_type: EntityType, decoy_type: EntityType) -> Angle {
+        let mut world = World::new(10000.0);
+        world.terrain = Terrain::new();
+
+        let player = |id: u32| -> Arc<PlayerTuple<Server>> {
+            Arc::new(PlayerTuple::new(PlayerData::new(
+                PlayerId(NonZeroU32::new(id).unwrap()),
+                None,
+            )))
+        };
+
+        let mut weapon = Entity::new(weapon_type, Some(player(1)));
+        weapon.transform.velocity = weapon.data().speed;
+        // Sensors activate after a second.
+        weapon.ticks = Ticks::from_secs(2.0);
+        let weapon_id = world.spawn_here_or_nearby(weapon, 0.0, None).unwrap();
+
+        let mut decoy = Entity::new(decoy_type, Some(player(2)));
+        decoy.transform.position = vec2(300.0, 100.0);
+        world.spawn_here_or_nearby(decoy, 0.0, None).unwrap();
+
+        world.physics_radius(Ticks::ONE);
+
+        let direction_target = world
+            .entities
+            .iter_radius(Vec2::ZERO, 10.0)
+            .find(|(_, e)| e.id == weapon_id)
+            .unwrap()
+            .1
+            .guidance
+            .direction_target;
+        direction_target
+    }
+
+    #[test]
+    fn chaff_seduces_missiles() {
+        let towards_decoy = Angle::from(vec2(300.0, 100.0));
+        let tolerance = Angle::from_degrees(5.0);
+
+        // Radar guided missiles home on chaff.
+        assert!(
+            (seduction(EntityType::Harpoon, EntityType::Srboc) - towards_decoy).abs() < tolerance
+        );
+
+        // But not on smoke, and torpedoes ignore chaff.
+        assert!(seduction(EntityType::Harpoon, EntityType::SmokeFloat).abs() < tolerance);
+        assert!(seduction(EntityType::Mark48, EntityType::Srboc).abs() < tolerance);
+    }
+
+    #[test]
+    fn resupply_once_per_tick() {
+        let mut world = World::new(10000.0);
+        world.terrain = Terrain::new();
+
+        let player = Arc::new(PlayerTuple::new(PlayerData::new(
+            PlayerId(NonZeroU32::new(1).unwrap()),
+            None,
+        )));
+        let boat = Entity::new(EntityType::G5, Some(Arc::clone(&player)));
+        world.spawn_here_or_nearby(boat, 0.0, None).unwrap();
+        let index = player
+            .borrow_player()
+            .data
+            .status
+            .get_entity_index()
+            .unwrap();
+
+        // Within range of two supplies.
+        for x in [-200.0, 200.0] {
+            let mut platform = Entity::new(EntityType::OilPlatform, None);
+            platform.transform.position = vec2(x, 0.0);
+            world.spawn_here_or_nearby(platform, 0.0, None).unwrap();
+        }
+
+        world.entities[index].extension_mut().magazines[0] = 0;
+        world.physics_radius(EntityExtension::RESUPPLY_PERIOD);
+        assert_eq!(world.entities[index].extension().magazines[0], 1);
+    }
 
     #[test]
     fn test_minimum_scan_radius() {
@@ -0,0 +1,116 @@
+// SPDX-FileCopyrightText: 2021 Softbear, Inc.
+// SPDX-License-Identifier: AGPL-3.0-or-later
+
+use crate::world::World;
+use common::altitude::Altitude;
+use common::angle::Angle;
+use common::protocol::Port;
+use common::terrain;
+use common::world::ARCTIC;
+use glam::Vec2;
+
+impl World {
+    /// Ports are placed at most one per square cell of this size.
+    const PORT_SPACING: f32 = 2000.0;
+    /// Candidate positions per side of each cell.
+    const PORT_CANDIDATES: usize = 8;
+    /// Distance from a port at which land is sought, to tell if it is along the coast.
+    const PORT_SHELTER_DISTANCE: f32 = 150.0;
+    /// Distance from a port at which the open sea is sought, to avoid ports on lakes.
+    const PORT_APPROACH_DISTANCE: f32 = 600.0;
+    /// Ports must be at least this deep, so that any boat may dock.
+    const PORT_MIN_DEPTH: Altitude = Altitude::from_whole_meters(-10);
+
+    /// Places ports along coastlines, in the most sheltered suitable position (if any) of each cell
+    /// of a grid. Depends only on the terrain generator, so may be called before any chunks are
+    /// generated.
+    pub fn generate_ports(&mut self) {
+        let max_radius = Self::max_radius();
+        let cells = (max_radius / Self::PORT_SPACING).ceil() as i32;
+        let step = Self::PORT_SPACING / Self::PORT_CANDIDATES as f32;
+
+        self.ports = (-cells..cells)
+            .flat_map(|y| (-cells..cells).map(move |x| (x, y)))
+            .filter_map(|(x, y)| {
+                let corner = Vec2::new(x as f32, y as f32) * Self::PORT_SPACING;
+                (0..Self::PORT_CANDIDATES.pow(2))
+                    .filter_map(|i| {
+                        let offset = Vec2::new(
+                            (i % Self::PORT_CANDIDATES) as f32,
+                            (i / Self::PORT_CANDIDATES) as f32,
+                        ) + 0.5;
+                        self.port_at(corner + offset * step, max_radius)
+                    })
+                    .max_by_key(|&(_, shelter)| shelter)
+                    .map(|(port, _)| port)
+            })
+            .collect();
+    }
+
+    /// Returns a port at the position, and how sheltered it is, if the position is suitable.
+    fn port_at(&self, position: Vec2, max_radius: f32) -> Option<(Port, usize)> {
+        if position.y >= ARCTIC || position.length_squared() > (max_radius * 0.9).powi(2) {
+            return None;
+        }
+
+        let sample = |p: Vec2| self.terrain.sample_generated(p).unwrap_or(Altitude::MAX);
+        if sample(position) > Self::PORT_MIN_DEPTH {
+            return None;
+        }
+
+        let mut shelter = 0;
+        let mut open = Vec2::ZERO;
+        for i in 0..8 {
+            let direction = Angle::from_radians(i as f32 * (std::f32::consts::PI / 4.0)).to_vec();
+            if sample(position + direction * Self::PORT_SHELTER_DISTANCE) >= terrain::SAND_LEVEL {
+                shelter += 1;
+            } else {
+                open += direction;
+            }
+        }
+
+        // Along the coast, but open to the sea.
+        let open = open.normalize_or_zero();
+        let approach = position + open * Self::PORT_APPROACH_DISTANCE;
+        ((2..=5).contains(&shelter) && sample(approach) < terrain::SAND_LEVEL).then(|| {
+            (
+                Port {
+                    position,
+                    direction: Angle::from(open),
+                },
+                shelter,
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::world::World;
+    use common::altitude::Altitude;
+    use common::protocol::Port;
+    use common::terrain;
+
+    #[test]
+    fn generate_ports() {
+        crate::noise::init();
+
+        let mut world = World::new(1000.0);
+        world.generate_ports();
+        assert!(!world.ports.is_empty());
+
+        for port in &world.ports {
+            let altitude = world.terrain.sample_generated(port.position).unwrap();
+            assert!(altitude < terrain::SAND_LEVEL, "{:?} is on land", port);
+            assert!(altitude <= Altitude::from_whole_meters(-10));
+            for other in &world.ports {
+                assert!(
+                    port == other || port.position.distance(other.position) > Port::RADIUS * 2.0,
+                    "{:?} overlaps {:?}",
+                    port,
+                    other
+                );
+            }
+        }
+    }
+}
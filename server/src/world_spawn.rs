@@ -33,15 +33,16 @@ impl World {
     ///
     /// An optional exclusion zone can block spawning.
     ///
-    /// Returns true if spawning successful, false if failed.
+    /// Returns the id of the spawned entity if spawning successful, None if failed.
     ///
-    /// INVARIANT: Will not affect any entity indices except adding a new one at the end.
+    /// INVARIANT: Will not affect the indices of existing entities. The new entity may reuse the
+    /// index of a previously removed one.
     pub fn spawn_here_or_nearby(
         &mut self,
         mut entity: Entity,
         initial_radius: f32,
         exclusion_zone: Option<Vec2>,
-    ) -> bool {
+    ) -> Option<EntityId> {
         let retry = initial_radius > 0.0;
         if retry {
             let start_time = Instant::now();
@@ -130,19 +131,19 @@ impl World {
 
         let t = entity.entity_type;
         let spawned = self.try_spawn(entity);
-        if !spawned {
+        if spawned.is_none() {
             warn!("couldn't spawn {:?}", t);
         }
         spawned
     }
 
-    /// try_spawn attempts to spawn an entity at a position and returns if the entity was spawned.
-    pub fn try_spawn(&mut self, entity: Entity) -> bool {
+    /// try_spawn attempts to spawn an entity at a position and returns its id if the entity was
+    /// spawned.
+    pub fn try_spawn(&mut self, entity: Entity) -> Option<EntityId> {
         if self.can_spawn(&entity, 1.0, self.radius) {
-            self.add(entity);
-            true
+            Some(self.add(entity))
         } else {
-            false
+            None
         }
     }
 
@@ -315,7 +316,7 @@ impl World {
         }
     }
 
-    /// Spawns one basic entity.
+    /// Spawns one basic entity, returning its id if there was room.
     pub fn spawn_static(
         &mut self,
         entity_type: EntityType,
@@ -323,7 +324,7 @@ impl World {
         direction: Angle,
         velocity: Velocity,
         ticks: Ticks,
-    ) {
+    ) -> Option<EntityId> {
         self.try_spawn(Entity {
             player: None,
             transform: Transform {
@@ -339,6 +340,6 @@ impl World {
             ticks,
             id: unset_entity_id(),
             altitude: Altitude::ZERO,
-        });
+        })
     }
 }
@@ -32,12 +32,10 @@ impl World {
                     Rgba::from([0, 0, 0, 255])
                 } else if altitude < Altitude::ZERO {
                     Rgba::from([0, 0, 255, 255])
+                } else if position_y > ARCTIC {
+                    Rgba::from([0, 230, 255, 255])
                 } else {
-                    if position_y > ARCTIC {
-                        Rgba::from([0, 230, 255, 255])
-                    } else {
-                        Rgba::from([0, 255, 0, 255])
-                    }
+                    Rgba::from([0, 255, 0, 255])
                 };
             }
         }
@@ -153,12 +151,17 @@ mod tests {
                 .filter(|t| t.can_spawn_as(score, bot) && t.data().level == level)
                 .choose(&mut rng)
                 .unwrap();
-            let spawn = Command::Spawn(Spawn { entity_type });
+            let spawn = Command::Spawn(Spawn {
+                entity_type,
+                loadout: 0,
+            });
             const SPAWN_ATTEMPTS: usize = 25;
-            for i in 0..=SPAWN_ATTEMPTS {
+            // Like in game, bots with big boats may not find room in a crowded world (they would
+            // try again later).
+            for _ in 0..=SPAWN_ATTEMPTS {
                 match spawn.as_command().apply(&mut world, player) {
                     Err(e) => {
-                        if i == SPAWN_ATTEMPTS || !bot {
+                        if !bot {
                             panic!(
                                 "spawn {:?} command by player={} resulted in {}",
                                 entity_type, !bot, e